@@ -17,7 +17,11 @@ use anchor_client::{
     },
     Client, ClientError,
 };
-use governance::{Governance, Proposal, TreasuryStats, PROPOSAL_PREFIX, VOTE_MARKER_PREFIX};
+use governance::{
+    Governance, GovernanceConfig, Proposal, ProposalAccountMeta, ProposalInstruction, ProposalKind,
+    ProposalState, TreasuryStats, PROPOSAL_PREFIX, VOTE_MARKER_PREFIX,
+};
+use serde::Deserialize;
 use spl_associated_token_account::{create_associated_token_account, get_associated_token_address};
 use spl_token::{
     state::{Account, Mint},
@@ -25,6 +29,51 @@ use spl_token::{
 };
 use spl_token_metadata::state::Metadata;
 use staking::{StakingData, StakingUserData};
+use std::{fs::File, str::FromStr};
+
+/// Instruction of executable proposal in JSON file
+#[derive(Deserialize, Debug)]
+struct InstructionConfig {
+    program_id: String,
+    accounts: Vec<AccountMetaConfig>,
+    data: Vec<u8>,
+}
+
+#[derive(Deserialize, Debug)]
+struct AccountMetaConfig {
+    pubkey: String,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+/// Loading proposal instructions from JSON file
+pub fn load_proposal_instructions(path: &str) -> Result<Vec<ProposalInstruction>, std::io::Error> {
+    let to_io_error = |err: String| std::io::Error::new(std::io::ErrorKind::Other, err);
+    let file = File::open(path)?;
+    let instructions: Vec<InstructionConfig> =
+        serde_json::from_reader(file).map_err(|err| to_io_error(err.to_string()))?;
+
+    let mut proposal_instructions = vec![];
+    for instruction in instructions {
+        let mut accounts = vec![];
+        for meta in instruction.accounts {
+            accounts.push(ProposalAccountMeta {
+                pubkey: Pubkey::from_str(&meta.pubkey)
+                    .map_err(|err| to_io_error(err.to_string()))?,
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            });
+        }
+        proposal_instructions.push(ProposalInstruction {
+            program_id: Pubkey::from_str(&instruction.program_id)
+                .map_err(|err| to_io_error(err.to_string()))?,
+            accounts: accounts,
+            data: instruction.data,
+        });
+    }
+
+    Ok(proposal_instructions)
+}
 
 pub fn initialize_governance(
     client: &Client,
@@ -36,6 +85,7 @@ pub fn initialize_governance(
     min_stake_to_propose: f64,
     min_vote_participation_percent: u8,
     payment_period_sec: i64,
+    execution_delay_seconds: i64,
     sponsors: Vec<Pubkey>,
 ) -> Result<(), ClientError> {
     let gov_program = client.program(*gov_id);
@@ -81,6 +131,7 @@ pub fn initialize_governance(
             min_stake_to_propose: ui_amount_to_amount(min_stake_to_propose, mint_data.decimals),
             min_vote_participation_percent: min_vote_participation_percent,
             payment_period_sec: payment_period_sec,
+            execution_delay_seconds: execution_delay_seconds,
             treasury_owner_bump: treasury_owner_bump,
             sponsors: sponsors,
         })
@@ -204,6 +255,10 @@ pub fn governance_info(
         governance_data.payment_period_start
     );
     println!("payment_period_sec: {}", governance_data.payment_period_sec);
+    println!(
+        "execution_delay_seconds: {}",
+        governance_data.execution_delay_seconds
+    );
     println!("sponsors: {:?}", governance_data.sponsors);
     println!(
         "Treasure owner bump: {}",
@@ -547,6 +602,173 @@ pub fn make_proposal(
     Ok(())
 }
 
+pub fn make_executable_proposal(
+    client: &Client,
+    gov_id: &Pubkey,
+    staking_id: &Pubkey,
+    governance_key: &Pubkey,
+    staking_user_data_key: &Pubkey,
+    user_token_wallet_owner: &Keypair,
+    treasury_key: &Pubkey,
+    ipfs_hash: &String,
+    instructions: Vec<ProposalInstruction>,
+) -> Result<(), ClientError> {
+    let gov_program = client.program(*gov_id);
+    let staking_program = client.program(*staking_id);
+
+    let governance_data: Governance = gov_program.account(*governance_key)?;
+    let staking_user_data: StakingUserData = staking_program.account(*staking_user_data_key)?;
+
+    let treasury_owner_pda = Pubkey::create_program_address(
+        &[
+            governance::TREASURY_PREFIX.as_bytes(),
+            gov_id.as_ref(),
+            governance_key.as_ref(),
+            &[governance_data.treasury_owner_bump],
+        ],
+        &gov_id,
+    )
+    .expect("Creating PDA Error");
+    println!(
+        "Treasury owner (instructions signer): {}",
+        treasury_owner_pda
+    );
+
+    // Treasury transfers of the instructions are counted in the treasury stats
+    let (treasury_stats_pda, _) = Pubkey::find_program_address(
+        &[
+            governance::TREASURY_STATS_PREFIX.as_bytes(),
+            gov_id.as_ref(),
+            governance_key.as_ref(),
+            treasury_key.as_ref(),
+        ],
+        &gov_id,
+    );
+
+    // pda of ['proposal', governance_program_id, governance, ipfs_hash[0..32]]
+    let (proposal_key, bump) = Pubkey::find_program_address(
+        &[
+            PROPOSAL_PREFIX.as_bytes(),
+            gov_id.as_ref(),
+            governance_key.as_ref(),
+            ipfs_hash[..MAX_SEED_LEN].as_bytes(),
+        ],
+        &gov_id,
+    );
+    println!("Unique pubkey for proposal: {}", proposal_key);
+
+    gov_program
+        .request()
+        .accounts(governance::accounts::MakeExecutableProposal {
+            governance: *governance_key,
+            treasury: *treasury_key,
+            treasury_stats: treasury_stats_pda,
+            treasury_owner: treasury_owner_pda,
+            staking_user_data: *staking_user_data_key,
+            user_token_wallet: staking_user_data.user_token_wallet,
+            user_token_wallet_owner: user_token_wallet_owner.pubkey(),
+            proposal: proposal_key,
+            payer: gov_program.payer(),
+            system_program: system_program::id(),
+            rent: Rent::id(),
+            clock: Clock::id(),
+        })
+        .args(governance::instruction::MakeExecutableProposal {
+            _proposal_bump: bump,
+            ipfs_hash: ipfs_hash.clone(),
+            instructions: instructions,
+        })
+        .signer(user_token_wallet_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn propose_config_change(
+    client: &Client,
+    gov_id: &Pubkey,
+    staking_id: &Pubkey,
+    governance_key: &Pubkey,
+    staking_user_data_key: &Pubkey,
+    user_token_wallet_owner: &Keypair,
+    treasury_key: &Pubkey,
+    ipfs_hash: &String,
+    approval_fixed_period_in_seconds: i64,
+    min_approval_percent: u8,
+    min_stake_to_propose: f64,
+    min_vote_participation_percent: u8,
+    payment_period_sec: i64,
+    execution_delay_seconds: i64,
+) -> Result<(), ClientError> {
+    let gov_program = client.program(*gov_id);
+    let staking_program = client.program(*staking_id);
+
+    let governance_data: Governance = gov_program.account(*governance_key)?;
+    let staking_user_data: StakingUserData = staking_program.account(*staking_user_data_key)?;
+    let staking_data: StakingData = staking_program.account(governance_data.staking_data)?;
+    let metadata_account = gov_program
+        .rpc()
+        .get_account(&staking_data.stake_token_metadata)?;
+    let metadata_data: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
+    let mint_acc = gov_program.rpc().get_account(&metadata_data.mint)?;
+    let mint_data = Mint::unpack(&mint_acc.data).unwrap();
+
+    let treasury_owner_pda = Pubkey::create_program_address(
+        &[
+            governance::TREASURY_PREFIX.as_bytes(),
+            gov_id.as_ref(),
+            governance_key.as_ref(),
+            &[governance_data.treasury_owner_bump],
+        ],
+        &gov_id,
+    )
+    .expect("Creating PDA Error");
+
+    // pda of ['proposal', governance_program_id, governance, ipfs_hash[0..32]]
+    let (proposal_key, bump) = Pubkey::find_program_address(
+        &[
+            PROPOSAL_PREFIX.as_bytes(),
+            gov_id.as_ref(),
+            governance_key.as_ref(),
+            ipfs_hash[..MAX_SEED_LEN].as_bytes(),
+        ],
+        &gov_id,
+    );
+    println!("Unique pubkey for proposal: {}", proposal_key);
+
+    gov_program
+        .request()
+        .accounts(governance::accounts::MakeConfigProposal {
+            governance: *governance_key,
+            treasury: *treasury_key,
+            treasury_owner: treasury_owner_pda,
+            staking_user_data: *staking_user_data_key,
+            user_token_wallet: staking_user_data.user_token_wallet,
+            user_token_wallet_owner: user_token_wallet_owner.pubkey(),
+            proposal: proposal_key,
+            payer: gov_program.payer(),
+            system_program: system_program::id(),
+            rent: Rent::id(),
+            clock: Clock::id(),
+        })
+        .args(governance::instruction::MakeConfigProposal {
+            _proposal_bump: bump,
+            ipfs_hash: ipfs_hash.clone(),
+            config: GovernanceConfig {
+                approval_fixed_period_in_seconds: approval_fixed_period_in_seconds,
+                min_approval_percent: min_approval_percent,
+                min_stake_to_propose: ui_amount_to_amount(min_stake_to_propose, mint_data.decimals),
+                min_vote_participation_percent: min_vote_participation_percent,
+                payment_period_sec: payment_period_sec,
+                execution_delay_seconds: execution_delay_seconds,
+            },
+        })
+        .signer(user_token_wallet_owner)
+        .send()?;
+
+    Ok(())
+}
+
 pub fn approve_proposal(
     client: &Client,
     gov_id: &Pubkey,
@@ -572,6 +794,9 @@ pub fn approve_proposal(
         ],
         &gov_id,
     );
+    // pda of ['locker_authority']
+    let (locker_authority, locker_bump) =
+        Pubkey::find_program_address(&[staking::LOCKER_AUTHORITY_PREFIX.as_bytes()], &gov_id);
 
     // Approve
     gov_program
@@ -584,6 +809,8 @@ pub fn approve_proposal(
             staking_data: staking_user_data.staking_data,
             proposal: *proposal_key,
             vote_marker: vote_marker_pda,
+            locker_authority: locker_authority,
+            governance_program: *gov_id,
             payer: gov_program.payer(),
             staking_program: *staking_id,
             system_program: system_program::id(),
@@ -592,6 +819,7 @@ pub fn approve_proposal(
         .args(governance::instruction::ApproveProposal {
             vote: vote,
             marker_bump: bump,
+            locker_bump: locker_bump,
         })
         .signer(user_token_wallet_owner)
         .send()?;
@@ -609,6 +837,30 @@ pub fn finalize_proposal(
     let proposal_data: Proposal = gov_program.account(*proposal_key)?;
     let governance_data: Governance = gov_program.account(proposal_data.governance)?;
 
+    gov_program
+        .request()
+        .accounts(governance::accounts::FinalizeProposal {
+            governance: proposal_data.governance,
+            staking_data: governance_data.staking_data,
+            proposal: *proposal_key,
+            clock: Clock::id(),
+        })
+        .args(governance::instruction::FinalizeProposal)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn execute_proposal(
+    client: &Client,
+    gov_id: &Pubkey,
+    proposal_key: &Pubkey,
+) -> Result<(), ClientError> {
+    let gov_program = client.program(*gov_id);
+
+    let proposal_data: Proposal = gov_program.account(*proposal_key)?;
+    let governance_data: Governance = gov_program.account(proposal_data.governance)?;
+
     let treasury_owner_pda = Pubkey::create_program_address(
         &[
             governance::TREASURY_PREFIX.as_bytes(),
@@ -620,9 +872,14 @@ pub fn finalize_proposal(
     )
     .expect("Creating PDA Error");
 
+    // Not payment proposals have the treasury as recipient
     let recipient_acc = gov_program.rpc().get_account(&proposal_data.recipient)?;
     let mint_key = Account::unpack(&recipient_acc.data)?.mint;
-    let treasury = get_associated_token_address(&treasury_owner_pda, &mint_key);
+    let treasury = if proposal_data.kind == ProposalKind::Payment {
+        get_associated_token_address(&treasury_owner_pda, &mint_key)
+    } else {
+        proposal_data.recipient
+    };
     println!("Treasury: {}", treasury);
 
     // Check treasury is created
@@ -632,23 +889,111 @@ pub fn finalize_proposal(
         .value
         .expect("There is none treasury for this SPL Token");
 
-    let treasury_account = gov_program.rpc().get_account(&treasury)?;
-    let treasury_account_data = Account::unpack(&treasury_account.data)?;
+    // Pass programs and accounts of every instruction, the treasury owner signs by program
+    let mut remaining_accounts = vec![];
+    for instruction in &proposal_data.instructions {
+        remaining_accounts.push(AccountMeta::new_readonly(instruction.program_id, false));
+        for meta in &instruction.accounts {
+            if meta.pubkey == treasury_owner_pda {
+                continue;
+            }
+            if meta.is_writable {
+                remaining_accounts.push(AccountMeta::new(meta.pubkey, false));
+            } else {
+                remaining_accounts.push(AccountMeta::new_readonly(meta.pubkey, false));
+            }
+        }
+    }
 
     gov_program
         .request()
-        .accounts(governance::accounts::FinalizeProposal {
+        .accounts(governance::accounts::ExecuteProposal {
             governance: proposal_data.governance,
-            staking_data: governance_data.staking_data,
             proposal: *proposal_key,
             treasury: treasury,
             treasury_owner: treasury_owner_pda,
-            treasury_mint: treasury_account_data.mint,
+            treasury_mint: mint_key,
             recipient: proposal_data.recipient,
             token_program: spl_token::id(),
             clock: Clock::id(),
         })
-        .args(governance::instruction::FinalizeProposal)
+        .accounts(remaining_accounts)
+        .args(governance::instruction::ExecuteProposal)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn apply_config_proposal(
+    client: &Client,
+    gov_id: &Pubkey,
+    proposal_key: &Pubkey,
+) -> Result<(), ClientError> {
+    let gov_program = client.program(*gov_id);
+
+    let proposal_data: Proposal = gov_program.account(*proposal_key)?;
+
+    gov_program
+        .request()
+        .accounts(governance::accounts::ApplyConfigProposal {
+            governance: proposal_data.governance,
+            proposal: *proposal_key,
+            clock: Clock::id(),
+        })
+        .args(governance::instruction::ApplyConfigProposal)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn cancel_queued_proposal(
+    client: &Client,
+    gov_id: &Pubkey,
+    proposal_key: &Pubkey,
+    signers: &Vec<Keypair>,
+) -> Result<(), ClientError> {
+    let gov_program = client.program(*gov_id);
+
+    let proposal_data: Proposal = gov_program.account(*proposal_key)?;
+
+    // Sponsors signatures are passed as remaining accounts
+    let sponsors_accounts = signers
+        .iter()
+        .map(|signer| AccountMeta::new_readonly(signer.pubkey(), true))
+        .collect::<Vec<AccountMeta>>();
+
+    let mut request = gov_program
+        .request()
+        .accounts(governance::accounts::CancelQueuedProposal {
+            governance: proposal_data.governance,
+            proposal: *proposal_key,
+        })
+        .accounts(sponsors_accounts)
+        .args(governance::instruction::CancelQueuedProposal);
+    for signer in signers {
+        request = request.signer(signer);
+    }
+    request.send()?;
+
+    Ok(())
+}
+
+pub fn cancel_proposal(
+    client: &Client,
+    gov_id: &Pubkey,
+    proposal_key: &Pubkey,
+    author: &Keypair,
+) -> Result<(), ClientError> {
+    let gov_program = client.program(*gov_id);
+
+    gov_program
+        .request()
+        .accounts(governance::accounts::CancelProposal {
+            proposal: *proposal_key,
+            author: author.pubkey(),
+        })
+        .args(governance::instruction::CancelProposal)
+        .signer(author)
         .send()?;
 
     Ok(())
@@ -662,14 +1007,53 @@ pub fn proposal_info(
     let program = client.program(*program_id);
     let proposal_data: Proposal = program.account(*proposal)?;
     println!("governance: {}", proposal_data.governance);
+    println!("author: {}", proposal_data.author);
     println!("starting_timestamp: {}", proposal_data.starting_timestamp);
-    println!("is_closed: {}", proposal_data.is_closed);
+    println!("voting_ends_at: {}", proposal_data.voting_ends_at);
+    println!("state: {:?}", proposal_data.state);
+    if proposal_data.state == ProposalState::Defeated {
+        println!("defeat_reason: {:?}", proposal_data.defeat_reason);
+    }
     println!("recipient: {}", proposal_data.recipient);
     println!("payment_amount: {}", proposal_data.payment_amount);
     println!("ipfs_hash: {}", proposal_data.ipfs_hash);
     println!("pros_weight: {}", proposal_data.pros_weight);
     println!("cons_weight: {}", proposal_data.cons_weight);
     println!("is_sponsored: {}", proposal_data.is_sponsored);
+    println!("snapshot_slot: {}", proposal_data.snapshot_slot);
+    println!("snapshot_timestamp: {}", proposal_data.snapshot_timestamp);
+    println!("kind: {:?}", proposal_data.kind);
+    println!("eta: {}", proposal_data.eta);
+    for (i, instruction) in proposal_data.instructions.iter().enumerate() {
+        println!("instructions[{}].program_id: {}", i, instruction.program_id);
+        for meta in &instruction.accounts {
+            println!(
+                "instructions[{}].account: {} (signer: {}, writable: {})",
+                i, meta.pubkey, meta.is_signer, meta.is_writable
+            );
+        }
+        println!("instructions[{}].data: {:?}", i, instruction.data);
+    }
+    if proposal_data.kind == ProposalKind::ConfigChange {
+        let config = proposal_data.config;
+        println!(
+            "config.approval_fixed_period_in_seconds: {}",
+            config.approval_fixed_period_in_seconds
+        );
+        println!(
+            "config.min_approval_percent: {}",
+            config.min_approval_percent
+        );
+        println!(
+            "config.min_stake_to_propose: {}",
+            config.min_stake_to_propose
+        );
+        println!(
+            "config.min_vote_participation_percent: {}",
+            config.min_vote_participation_percent
+        );
+        println!("config.payment_period_sec: {}", config.payment_period_sec);
+    }
 
     Ok(())
 }
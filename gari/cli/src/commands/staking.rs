@@ -1,9 +1,10 @@
 use anchor_client::{
     solana_sdk::{
-        borsh::try_from_slice_unchecked, clock::Clock, program_pack::Pack, pubkey::Pubkey,
-        rent::Rent, signature::Keypair, signer::Signer, system_program, sysvar::SysvarId,
+        borsh::try_from_slice_unchecked, clock::Clock, instruction::AccountMeta,
+        program_pack::Pack, pubkey::Pubkey, rent::Rent, signature::Keypair, signer::Signer,
+        system_instruction, system_program, sysvar::SysvarId,
     },
-    Client, ClientError,
+    Client, ClientError, Program,
 };
 use bigint::U256;
 use spl_associated_token_account::{create_associated_token_account, get_associated_token_address};
@@ -12,7 +13,10 @@ use spl_token::{
     ui_amount_to_amount,
 };
 use spl_token_metadata::state::Metadata;
-use staking::{StakingData, StakingUserData};
+use staking::{
+    InterestMode, LockTier, LockedStake, PauseLevel, PendingWithdrawal, RateModel, RatePoint,
+    RewardMode, RewardStream, StakingData, StakingUserData, UserReward,
+};
 ///! Staking commands handlers
 use std::{
     ops::{Div, Mul},
@@ -29,6 +33,7 @@ pub fn initialize_staking(
     starting_interest_rate: u64,
     cap: f64,
     mint_authority: &Keypair,
+    reward_reserve: bool,
 ) -> Result<(), ClientError> {
     let program = client.program(*program_id);
 
@@ -80,286 +85,2113 @@ pub fn initialize_staking(
             .send()?;
     }
 
-    // Update mint authority to pda of [staking_program_id, staking_data_key, token_metadata_id]
-    let (pda_mint_authority_pk, mint_auth_bump) = Pubkey::find_program_address(
+    // Update mint authority to pda of [staking_program_id, staking_data_key, token_metadata_id]
+    let (pda_mint_authority_pk, mint_auth_bump) = Pubkey::find_program_address(
+        &[
+            &program_id.to_bytes(),
+            &staking_data.pubkey().to_bytes(),
+            &token_metadata.to_bytes(),
+        ],
+        program_id,
+    );
+    println!("New token mint authority: {}", pda_mint_authority_pk);
+
+    // Initialize Staking Data
+    let mut request = program.request();
+    if !reward_reserve {
+        request = request
+            .instruction(spl_token::instruction::set_authority(
+                &spl_token::id(),
+                &metadata.mint,
+                Some(&pda_mint_authority_pk),
+                spl_token::instruction::AuthorityType::MintTokens,
+                &mint_authority.pubkey(),
+                &[],
+            )?)
+            .signer(mint_authority);
+    }
+    request
+        .accounts(staking::accounts::InitializeStaking {
+            staking_data: staking_data.pubkey(),
+            staking_owner: program.payer(),
+            holding_wallet: holding_wallet,
+            holding_wallet_owner: holding_wallet_owner_pk,
+            staking_token_metadata: *token_metadata,
+            staking_token_mint: metadata.mint,
+            rent: Rent::id(),
+            clock: Clock::id(),
+            system_program: system_program::id(),
+        })
+        .args(staking::instruction::InitializeStaking {
+            token: *token_metadata,
+            starting_interest_rate: starting_interest_rate,
+            max_interest_rate: max_interest_rate,
+            starting_timestamp: starting_timestamp,
+            cap: ui_amount_to_amount(cap, mint_data.decimals),
+            holding_bump: holding_bump,
+            mint_auth_bump: mint_auth_bump,
+        })
+        .signer(staking_data)
+        .send()?;
+
+    // Mint authority stays untouched, interest is paid from the reward reserve
+    if reward_reserve {
+        program
+            .request()
+            .accounts(staking::accounts::SetRewardMode {
+                staking_data: staking_data.pubkey(),
+                staking_owner: program.payer(),
+            })
+            .args(staking::instruction::SetRewardMode {
+                reward_mode: RewardMode::Reserve,
+            })
+            .send()?;
+    }
+
+    Ok(())
+}
+
+pub fn set_interest_rate(
+    client: &Client,
+    program_id: &Pubkey,
+    new_interest_rate: u64,
+    staking_data_key: &Pubkey,
+    staking_data_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    let staking_data: StakingData = program.account(*staking_data_key)?;
+    let metadata_account = program
+        .rpc()
+        .get_account(&staking_data.stake_token_metadata)?;
+    let metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
+
+    // Mint authority is pda of [staking_program_id, staking_data_key, token_metadata_id]
+    let token_mint_authority = Pubkey::create_program_address(
+        &[
+            &program_id.to_bytes(),
+            &staking_data_key.to_bytes(),
+            &staking_data.stake_token_metadata.to_bytes(),
+            &[staking_data.mint_auth_bump],
+        ],
+        program_id,
+    )
+    .expect("PDA Creating Error");
+
+    // Set up new interest rate
+    program
+        .request()
+        .accounts(staking::accounts::SetInterestRate {
+            staking_data: *staking_data_key,
+            staking_owner: staking_data_owner.pubkey(),
+            holding_wallet: staking_data.holding_wallet,
+            staking_token_metadata: staking_data.stake_token_metadata,
+            mint: metadata.mint,
+            mint_authority: token_mint_authority,
+            token_program: spl_token::id(),
+            clock: Clock::id(),
+        })
+        .args(staking::instruction::SetInterestRate {
+            new_interest_rate: new_interest_rate,
+        })
+        .signer(staking_data_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn set_rate_schedule(
+    client: &Client,
+    program_id: &Pubkey,
+    rate_schedule: Vec<RatePoint>,
+    staking_data_key: &Pubkey,
+    staking_data_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    let staking_data: StakingData = program.account(*staking_data_key)?;
+    let metadata_account = program
+        .rpc()
+        .get_account(&staking_data.stake_token_metadata)?;
+    let metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
+
+    // Mint authority is pda of [staking_program_id, staking_data_key, token_metadata_id]
+    let token_mint_authority = Pubkey::create_program_address(
+        &[
+            &program_id.to_bytes(),
+            &staking_data_key.to_bytes(),
+            &staking_data.stake_token_metadata.to_bytes(),
+            &[staking_data.mint_auth_bump],
+        ],
+        program_id,
+    )
+    .expect("PDA Creating Error");
+
+    program
+        .request()
+        .accounts(staking::accounts::SetInterestRate {
+            staking_data: *staking_data_key,
+            staking_owner: staking_data_owner.pubkey(),
+            holding_wallet: staking_data.holding_wallet,
+            staking_token_metadata: staking_data.stake_token_metadata,
+            mint: metadata.mint,
+            mint_authority: token_mint_authority,
+            token_program: spl_token::id(),
+            clock: Clock::id(),
+        })
+        .args(staking::instruction::SetRateSchedule {
+            rate_schedule: rate_schedule,
+        })
+        .signer(staking_data_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn set_rate_model(
+    client: &Client,
+    program_id: &Pubkey,
+    rate_model: Option<RateModel>,
+    staking_data_key: &Pubkey,
+    staking_data_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    let staking_data: StakingData = program.account(*staking_data_key)?;
+    let metadata_account = program
+        .rpc()
+        .get_account(&staking_data.stake_token_metadata)?;
+    let metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
+
+    // Mint authority is pda of [staking_program_id, staking_data_key, token_metadata_id]
+    let token_mint_authority = Pubkey::create_program_address(
+        &[
+            &program_id.to_bytes(),
+            &staking_data_key.to_bytes(),
+            &staking_data.stake_token_metadata.to_bytes(),
+            &[staking_data.mint_auth_bump],
+        ],
+        program_id,
+    )
+    .expect("PDA Creating Error");
+
+    program
+        .request()
+        .accounts(staking::accounts::SetInterestRate {
+            staking_data: *staking_data_key,
+            staking_owner: staking_data_owner.pubkey(),
+            holding_wallet: staking_data.holding_wallet,
+            staking_token_metadata: staking_data.stake_token_metadata,
+            mint: metadata.mint,
+            mint_authority: token_mint_authority,
+            token_program: spl_token::id(),
+            clock: Clock::id(),
+        })
+        .args(staking::instruction::SetRateModel {
+            rate_model: rate_model,
+        })
+        .signer(staking_data_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn set_reward_mode(
+    client: &Client,
+    program_id: &Pubkey,
+    reward_mode: RewardMode,
+    staking_data_key: &Pubkey,
+    staking_data_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    program
+        .request()
+        .accounts(staking::accounts::SetRewardMode {
+            staking_data: *staking_data_key,
+            staking_owner: staking_data_owner.pubkey(),
+        })
+        .args(staking::instruction::SetRewardMode {
+            reward_mode: reward_mode,
+        })
+        .signer(staking_data_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn fund_reward_reserve(
+    client: &Client,
+    program_id: &Pubkey,
+    ui_amount: f64,
+    staking_data_key: &Pubkey,
+    funder: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    let staking_data: StakingData = program.account(*staking_data_key)?;
+    let holding_wallet_acc = program.rpc().get_account(&staking_data.holding_wallet)?;
+    let holding_wallet_data = Account::unpack(&holding_wallet_acc.data).unwrap();
+    let mint_acc = program.rpc().get_account(&holding_wallet_data.mint)?;
+    let mint_data = Mint::unpack(&mint_acc.data).unwrap();
+    let funder_wallet = get_associated_token_address(&funder.pubkey(), &holding_wallet_data.mint);
+
+    program
+        .request()
+        .accounts(staking::accounts::FundRewardReserve {
+            staking_data: *staking_data_key,
+            holding_wallet: staking_data.holding_wallet,
+            funder_wallet: funder_wallet,
+            funder: funder.pubkey(),
+            token_program: spl_token::id(),
+        })
+        .args(staking::instruction::FundRewardReserve {
+            amount: ui_amount_to_amount(ui_amount, mint_data.decimals),
+        })
+        .signer(funder)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn withdraw_reward_reserve(
+    client: &Client,
+    program_id: &Pubkey,
+    ui_amount: f64,
+    staking_data_key: &Pubkey,
+    staking_data_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    let staking_data: StakingData = program.account(*staking_data_key)?;
+    let holding_wallet_acc = program.rpc().get_account(&staking_data.holding_wallet)?;
+    let holding_wallet_data = Account::unpack(&holding_wallet_acc.data).unwrap();
+    let mint_acc = program.rpc().get_account(&holding_wallet_data.mint)?;
+    let mint_data = Mint::unpack(&mint_acc.data).unwrap();
+    let recipient_wallet =
+        get_associated_token_address(&staking_data_owner.pubkey(), &holding_wallet_data.mint);
+
+    // holding wallet owner is pda [staking_program_id, staking_data_id]
+    let holding_wallet_owner_pk = Pubkey::create_program_address(
+        &[
+            &program_id.to_bytes(),
+            &staking_data_key.to_bytes(),
+            &[staking_data.holding_bump],
+        ],
+        program_id,
+    )
+    .expect("PDA Creating Error");
+
+    program
+        .request()
+        .accounts(staking::accounts::WithdrawRewardReserve {
+            staking_data: *staking_data_key,
+            staking_owner: staking_data_owner.pubkey(),
+            holding_wallet: staking_data.holding_wallet,
+            holding_wallet_owner: holding_wallet_owner_pk,
+            recipient_wallet: recipient_wallet,
+            token_program: spl_token::id(),
+        })
+        .args(staking::instruction::WithdrawRewardReserve {
+            amount: ui_amount_to_amount(ui_amount, mint_data.decimals),
+        })
+        .signer(staking_data_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn set_lock_tiers(
+    client: &Client,
+    program_id: &Pubkey,
+    lock_tiers: Vec<LockTier>,
+    staking_data_key: &Pubkey,
+    staking_data_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    program
+        .request()
+        .accounts(staking::accounts::SetLockTiers {
+            staking_data: *staking_data_key,
+            staking_owner: staking_data_owner.pubkey(),
+        })
+        .args(staking::instruction::SetLockTiers {
+            lock_tiers: lock_tiers,
+        })
+        .signer(staking_data_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn set_authorized_lockers(
+    client: &Client,
+    program_id: &Pubkey,
+    authorized_lockers: Vec<Pubkey>,
+    staking_data_key: &Pubkey,
+    staking_data_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    program
+        .request()
+        .accounts(staking::accounts::SetAuthorizedLockers {
+            staking_data: *staking_data_key,
+            staking_owner: staking_data_owner.pubkey(),
+        })
+        .args(staking::instruction::SetAuthorizedLockers {
+            authorized_lockers: authorized_lockers,
+        })
+        .signer(staking_data_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn set_cooldown(
+    client: &Client,
+    program_id: &Pubkey,
+    cooldown_seconds: i64,
+    staking_data_key: &Pubkey,
+    staking_data_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    program
+        .request()
+        .accounts(staking::accounts::SetCooldown {
+            staking_data: *staking_data_key,
+            staking_owner: staking_data_owner.pubkey(),
+        })
+        .args(staking::instruction::SetCooldown {
+            cooldown_seconds: cooldown_seconds,
+        })
+        .signer(staking_data_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn enable_receipts(
+    client: &Client,
+    program_id: &Pubkey,
+    metadata_program_id: &Pubkey,
+    staking_data_key: &Pubkey,
+    name: &String,
+    symbol: &String,
+    staking_data_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+    let staking_data: StakingData = program.account(*staking_data_key)?;
+    let metadata_account = program
+        .rpc()
+        .get_account(&staking_data.stake_token_metadata)?;
+    let token_metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
+    let mint_acc = program.rpc().get_account(&token_metadata.mint)?;
+    let mint_data = Mint::unpack(&mint_acc.data).unwrap();
+
+    let (receipt_mint_authority, receipt_mint_auth_bump) =
+        find_receipt_mint_authority_address(program_id, staking_data_key);
+
+    // Receipts have the decimals of the staking token, so the shares are shown as the tokens
+    let receipt_mint = Keypair::new();
+    println!("Generated receipt Mint pubkey: {}", receipt_mint.pubkey());
+    program
+        .request()
+        .instruction(system_instruction::create_account(
+            &program.payer(),
+            &receipt_mint.pubkey(),
+            program
+                .rpc()
+                .get_minimum_balance_for_rent_exemption(Mint::LEN)?,
+            Mint::LEN as u64,
+            &spl_token::id(),
+        ))
+        .instruction(spl_token::instruction::initialize_mint(
+            &spl_token::id(),
+            &receipt_mint.pubkey(),
+            &receipt_mint_authority,
+            None,
+            mint_data.decimals,
+        )?)
+        .signer(&receipt_mint)
+        .send()?;
+
+    let metadata_rent = program
+        .rpc()
+        .get_minimum_balance_for_rent_exemption(spl_token_metadata::state::MAX_METADATA_LEN)?;
+    let (receipt_metadata, _) = Pubkey::find_program_address(
+        &[
+            spl_token_metadata::state::PREFIX.as_bytes(),
+            spl_token_metadata::id().as_ref(),
+            receipt_mint.pubkey().as_ref(),
+        ],
+        &spl_token_metadata::id(),
+    );
+    println!("Receipt Metadata Pubkey: {}", receipt_metadata);
+
+    program
+        .request()
+        .instruction(system_instruction::transfer(
+            &staking_data_owner.pubkey(),
+            &receipt_metadata,
+            metadata_rent,
+        ))
+        .accounts(staking::accounts::EnableReceipts {
+            staking_data: *staking_data_key,
+            staking_owner: staking_data_owner.pubkey(),
+            receipt_mint: receipt_mint.pubkey(),
+            receipt_mint_authority: receipt_mint_authority,
+            receipt_metadata: receipt_metadata,
+            metadata_program: *metadata_program_id,
+            token_metadata_program: spl_token_metadata::id(),
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+            rent: Rent::id(),
+        })
+        .args(staking::instruction::EnableReceipts {
+            receipt_mint_auth_bump: receipt_mint_auth_bump,
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+        })
+        .signer(staking_data_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn mint_receipts(
+    client: &Client,
+    program_id: &Pubkey,
+    shares: u64,
+    staking_user_data_key: &Pubkey,
+    user_token_wallet_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    let staking_user_data: StakingUserData = program.account(*staking_user_data_key)?;
+    let staking_data: StakingData = program.account(staking_user_data.staking_data)?;
+    let (receipt_mint_authority, _) =
+        find_receipt_mint_authority_address(program_id, &staking_user_data.staking_data);
+
+    create_receipt_wallet(&program, &staking_data, &user_token_wallet_owner.pubkey())?;
+
+    program
+        .request()
+        .accounts(staking::accounts::MintReceipts {
+            staking_user_data: *staking_user_data_key,
+            user_token_wallet: staking_user_data.user_token_wallet,
+            user_token_wallet_owner: user_token_wallet_owner.pubkey(),
+            staking_data: staking_user_data.staking_data,
+            receipt_mint: staking_data.receipt_mint,
+            receipt_mint_authority: receipt_mint_authority,
+            receipt_wallet: get_associated_token_address(
+                &user_token_wallet_owner.pubkey(),
+                &staking_data.receipt_mint,
+            ),
+            token_program: spl_token::id(),
+            clock: Clock::id(),
+        })
+        .accounts(reward_stream_accounts(
+            program_id,
+            &staking_data,
+            staking_user_data_key,
+        ))
+        .args(staking::instruction::MintReceipts { shares: shares })
+        .signer(user_token_wallet_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn redeem_receipts(
+    client: &Client,
+    program_id: &Pubkey,
+    shares: u64,
+    staking_user_data_key: &Pubkey,
+    user_token_wallet_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    let staking_user_data: StakingUserData = program.account(*staking_user_data_key)?;
+    let staking_data: StakingData = program.account(staking_user_data.staking_data)?;
+
+    program
+        .request()
+        .accounts(staking::accounts::RedeemReceipts {
+            staking_user_data: *staking_user_data_key,
+            user_token_wallet: staking_user_data.user_token_wallet,
+            user_token_wallet_owner: user_token_wallet_owner.pubkey(),
+            staking_data: staking_user_data.staking_data,
+            allow_list_entry: find_allow_list_entry_address(
+                program_id,
+                &staking_user_data.staking_data,
+                &user_token_wallet_owner.pubkey(),
+            )
+            .0,
+            receipt_mint: staking_data.receipt_mint,
+            receipt_wallet: get_associated_token_address(
+                &user_token_wallet_owner.pubkey(),
+                &staking_data.receipt_mint,
+            ),
+            token_program: spl_token::id(),
+            clock: Clock::id(),
+        })
+        .accounts(reward_stream_accounts(
+            program_id,
+            &staking_data,
+            staking_user_data_key,
+        ))
+        .args(staking::instruction::RedeemReceipts { shares: shares })
+        .signer(user_token_wallet_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn set_deposit_limits(
+    client: &Client,
+    program_id: &Pubkey,
+    allow_list_enabled: bool,
+    user_deposit_cap: f64,
+    total_deposit_cap: f64,
+    staking_data_key: &Pubkey,
+    staking_data_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+    let staking_data: StakingData = program.account(*staking_data_key)?;
+    let metadata_account = program
+        .rpc()
+        .get_account(&staking_data.stake_token_metadata)?;
+    let token_metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
+    let mint_acc = program.rpc().get_account(&token_metadata.mint)?;
+    let mint_data = Mint::unpack(&mint_acc.data).unwrap();
+
+    program
+        .request()
+        .accounts(staking::accounts::SetDepositLimits {
+            staking_data: *staking_data_key,
+            staking_owner: staking_data_owner.pubkey(),
+        })
+        .args(staking::instruction::SetDepositLimits {
+            allow_list_enabled: allow_list_enabled,
+            user_deposit_cap: ui_amount_to_amount(user_deposit_cap, mint_data.decimals),
+            total_deposit_cap: ui_amount_to_amount(total_deposit_cap, mint_data.decimals),
+        })
+        .signer(staking_data_owner)
+        .send()?;
+
+    Ok(())
+}
+
+/// Allow-list entry is pda of ["allow_list", staking_program_id, staking_data, wallet]
+fn find_allow_list_entry_address(
+    program_id: &Pubkey,
+    staking_data_key: &Pubkey,
+    wallet: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            staking::ALLOW_LIST_PREFIX.as_bytes(),
+            &program_id.to_bytes(),
+            &staking_data_key.to_bytes(),
+            &wallet.to_bytes(),
+        ],
+        program_id,
+    )
+}
+
+pub fn add_to_allow_list(
+    client: &Client,
+    program_id: &Pubkey,
+    wallet: &Pubkey,
+    staking_data_key: &Pubkey,
+    staking_data_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    let (allow_list_entry_key, allow_list_bump) =
+        find_allow_list_entry_address(program_id, staking_data_key, wallet);
+    println!(
+        "Pubkey for AllowListEntry account: {}",
+        allow_list_entry_key
+    );
+
+    program
+        .request()
+        .accounts(staking::accounts::AddToAllowList {
+            allow_list_entry: allow_list_entry_key,
+            staking_data: *staking_data_key,
+            staking_owner: staking_data_owner.pubkey(),
+            wallet: *wallet,
+            system_program: system_program::id(),
+            rent: Rent::id(),
+        })
+        .args(staking::instruction::AddToAllowList {
+            _allow_list_bump: allow_list_bump,
+        })
+        .signer(staking_data_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn remove_from_allow_list(
+    client: &Client,
+    program_id: &Pubkey,
+    wallet: &Pubkey,
+    staking_data_key: &Pubkey,
+    staking_data_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    let (allow_list_entry_key, _) =
+        find_allow_list_entry_address(program_id, staking_data_key, wallet);
+
+    program
+        .request()
+        .accounts(staking::accounts::RemoveFromAllowList {
+            allow_list_entry: allow_list_entry_key,
+            staking_data: *staking_data_key,
+            staking_owner: staking_data_owner.pubkey(),
+        })
+        .args(staking::instruction::RemoveFromAllowList)
+        .signer(staking_data_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn set_fees(
+    client: &Client,
+    program_id: &Pubkey,
+    deposit_fee_bps: u64,
+    withdraw_fee_bps: u64,
+    staking_data_key: &Pubkey,
+    fee_collector: &Pubkey,
+    staking_data_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+    let staking_data: StakingData = program.account(*staking_data_key)?;
+
+    program
+        .request()
+        .accounts(staking::accounts::SetFees {
+            staking_data: *staking_data_key,
+            staking_owner: staking_data_owner.pubkey(),
+            holding_wallet: staking_data.holding_wallet,
+            fee_collector: *fee_collector,
+        })
+        .args(staking::instruction::SetFees {
+            deposit_fee_bps: deposit_fee_bps,
+            withdraw_fee_bps: withdraw_fee_bps,
+        })
+        .signer(staking_data_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn set_early_unstake_penalty(
+    client: &Client,
+    program_id: &Pubkey,
+    penalty: u64,
+    window_seconds: i64,
+    staking_data_key: &Pubkey,
+    penalty_wallet: Option<Pubkey>,
+    staking_data_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+    let staking_data: StakingData = program.account(*staking_data_key)?;
+
+    program
+        .request()
+        .accounts(staking::accounts::SetEarlyUnstakePenalty {
+            staking_data: *staking_data_key,
+            staking_owner: staking_data_owner.pubkey(),
+            holding_wallet: staking_data.holding_wallet,
+            penalty_wallet: penalty_wallet.unwrap_or(staking_data.holding_wallet),
+        })
+        .args(staking::instruction::SetEarlyUnstakePenalty {
+            penalty: penalty,
+            window_seconds: window_seconds,
+        })
+        .signer(staking_data_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn set_guardian(
+    client: &Client,
+    program_id: &Pubkey,
+    guardian: &Pubkey,
+    staking_data_key: &Pubkey,
+    staking_data_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    program
+        .request()
+        .accounts(staking::accounts::SetGuardian {
+            staking_data: *staking_data_key,
+            staking_owner: staking_data_owner.pubkey(),
+        })
+        .args(staking::instruction::SetGuardian {
+            guardian: *guardian,
+        })
+        .signer(staking_data_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn set_slasher(
+    client: &Client,
+    program_id: &Pubkey,
+    slasher: &Pubkey,
+    staking_data_key: &Pubkey,
+    staking_data_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    program
+        .request()
+        .accounts(staking::accounts::SetSlasher {
+            staking_data: *staking_data_key,
+            staking_owner: staking_data_owner.pubkey(),
+        })
+        .args(staking::instruction::SetSlasher { slasher: *slasher })
+        .signer(staking_data_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn slash(
+    client: &Client,
+    program_id: &Pubkey,
+    slash_bps: u64,
+    reason: u16,
+    staking_user_data_key: &Pubkey,
+    slash_wallet: Option<Pubkey>,
+    slasher: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    let staking_user_data: StakingUserData = program.account(*staking_user_data_key)?;
+    let staking_data: StakingData = program.account(staking_user_data.staking_data)?;
+    let metadata_account = program
+        .rpc()
+        .get_account(&staking_data.stake_token_metadata)?;
+    let token_metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
+
+    // holding wallet owner is pda [staking_program_id, staking_data_id]
+    let holding_wallet_owner_pk = Pubkey::create_program_address(
+        &[
+            &program_id.to_bytes(),
+            &staking_user_data.staking_data.to_bytes(),
+            &[staking_data.holding_bump],
+        ],
+        program_id,
+    )
+    .expect("PDA Creating Error");
+
+    // Mint authority is pda of [staking_program_id, staking_data_key, token_metadata_id]
+    let token_mint_authority = Pubkey::create_program_address(
+        &[
+            &program_id.to_bytes(),
+            &staking_user_data.staking_data.to_bytes(),
+            &staking_data.stake_token_metadata.to_bytes(),
+            &[staking_data.mint_auth_bump],
+        ],
+        program_id,
+    )
+    .expect("PDA Creating Error");
+
+    // Tokens of the slashed shares are burned when the slash wallet is the holding wallet
+    let slash_wallet = slash_wallet.unwrap_or(staking_data.holding_wallet);
+
+    program
+        .request()
+        .accounts(staking::accounts::Slash {
+            staking_data: staking_user_data.staking_data,
+            slasher: slasher.pubkey(),
+            staking_user_data: *staking_user_data_key,
+            holding_wallet: staking_data.holding_wallet,
+            holding_wallet_owner: holding_wallet_owner_pk,
+            slash_wallet: slash_wallet,
+            staking_token_metadata: staking_data.stake_token_metadata,
+            mint: token_metadata.mint,
+            mint_authority: token_mint_authority,
+            token_program: spl_token::id(),
+            clock: Clock::id(),
+        })
+        .accounts(reward_stream_accounts(
+            program_id,
+            &staking_data,
+            staking_user_data_key,
+        ))
+        .args(staking::instruction::Slash {
+            slash_bps: slash_bps,
+            reason: reason,
+        })
+        .signer(slasher)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn pause(
+    client: &Client,
+    program_id: &Pubkey,
+    level: PauseLevel,
+    staking_data_key: &Pubkey,
+    authority: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    program
+        .request()
+        .accounts(staking::accounts::SetPause {
+            staking_data: *staking_data_key,
+            authority: authority.pubkey(),
+        })
+        .args(staking::instruction::Pause { level: level })
+        .signer(authority)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn unpause(
+    client: &Client,
+    program_id: &Pubkey,
+    staking_data_key: &Pubkey,
+    authority: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    program
+        .request()
+        .accounts(staking::accounts::SetPause {
+            staking_data: *staking_data_key,
+            authority: authority.pubkey(),
+        })
+        .args(staking::instruction::Unpause)
+        .signer(authority)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn propose_owner(
+    client: &Client,
+    program_id: &Pubkey,
+    new_owner: &Pubkey,
+    staking_data_key: &Pubkey,
+    staking_data_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    program
+        .request()
+        .accounts(staking::accounts::ProposeOwner {
+            staking_data: *staking_data_key,
+            staking_owner: staking_data_owner.pubkey(),
+        })
+        .args(staking::instruction::ProposeOwner {
+            new_owner: *new_owner,
+        })
+        .signer(staking_data_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn accept_owner(
+    client: &Client,
+    program_id: &Pubkey,
+    staking_data_key: &Pubkey,
+    pending_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    program
+        .request()
+        .accounts(staking::accounts::AcceptOwner {
+            staking_data: *staking_data_key,
+            pending_owner: pending_owner.pubkey(),
+        })
+        .args(staking::instruction::AcceptOwner)
+        .signer(pending_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn initialize_user_staking(
+    client: &Client,
+    program_id: &Pubkey,
+    staking_data_key: &Pubkey,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    let staking_data: StakingData = program.account(*staking_data_key)?;
+    let metadata_account = program
+        .rpc()
+        .get_account(&staking_data.stake_token_metadata)?;
+    let metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
+
+    let staking_user_data = Keypair::new();
+    print!("Pubkey for UserStakingData account: ");
+    println!("{}", staking_user_data.pubkey());
+
+    // Find or initialize user SPL Token Account
+    let user_token_wallet = get_associated_token_address(&program.payer(), &metadata.mint);
+    println!("Pubkey for user token wallet: {}", user_token_wallet);
+    if let Some(account) = program
+        .rpc()
+        .get_account_with_commitment(&user_token_wallet, program.rpc().commitment())?
+        .value
+    {
+        let account_data = Account::unpack(&account.data).unwrap();
+        if account.owner == spl_token::id()
+            && account_data.owner == program.payer()
+            && account_data.mint == metadata.mint
+        {
+            println!("User token wallet already exists");
+        } else {
+            panic!("Account {} was incorrectly initialized", user_token_wallet);
+        }
+    } else {
+        println!("Initializing user token wallet");
+        program
+            .request()
+            .instruction(create_associated_token_account(
+                &program.payer(),
+                &program.payer(),
+                &metadata.mint,
+            ))
+            .send()?;
+    }
+
+    println!("Initialing user staking data");
+    // Initialize user staking data account
+    program
+        .request()
+        .accounts(staking::accounts::InitializeStakingUser {
+            staking_user_data: staking_user_data.pubkey(),
+            user_token_wallet: user_token_wallet,
+            user_token_wallet_owner: program.payer(),
+            staking_data: *staking_data_key,
+            allow_list_entry: find_allow_list_entry_address(
+                program_id,
+                staking_data_key,
+                &program.payer(),
+            )
+            .0,
+            staking_token_metadata: staking_data.stake_token_metadata,
+            system_program: system_program::id(),
+            rent: Rent::id(),
+        })
+        .args(staking::instruction::InitializeStakingUser)
+        .signer(&staking_user_data)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn stake(
+    client: &Client,
+    program_id: &Pubkey,
+    ui_amount: f64,
+    staking_user_data_key: &Pubkey,
+    user_token_wallet_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    // Get the user data
+    let staking_user_data: StakingUserData = program.account(*staking_user_data_key)?;
+    let staking_data: StakingData = program.account(staking_user_data.staking_data)?;
+    let metadata_account = program
+        .rpc()
+        .get_account(&staking_data.stake_token_metadata)?;
+    let token_metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
+    let mint_acc = program.rpc().get_account(&token_metadata.mint)?;
+    let mint_data = Mint::unpack(&mint_acc.data).unwrap();
+
+    // Mint authority is pda of [staking_program_id, staking_data_key, token_metadata_id]
+    let token_mint_authority = Pubkey::create_program_address(
+        &[
+            &program_id.to_bytes(),
+            &staking_user_data.staking_data.to_bytes(),
+            &staking_data.stake_token_metadata.to_bytes(),
+            &[staking_data.mint_auth_bump],
+        ],
+        program_id,
+    )
+    .expect("PDA Creating Error");
+
+    create_receipt_wallet(&program, &staking_data, &user_token_wallet_owner.pubkey())?;
+
+    // Stake
+    program
+        .request()
+        .accounts(staking::accounts::Stake {
+            staking_user_data: *staking_user_data_key,
+            user_token_wallet: staking_user_data.user_token_wallet,
+            user_token_wallet_owner: user_token_wallet_owner.pubkey(),
+            staking_data: staking_user_data.staking_data,
+            allow_list_entry: find_allow_list_entry_address(
+                program_id,
+                &staking_user_data.staking_data,
+                &user_token_wallet_owner.pubkey(),
+            )
+            .0,
+            holding_wallet: staking_data.holding_wallet,
+            fee_collector: staking_data.fee_collector,
+            staking_token_metadata: staking_data.stake_token_metadata,
+            mint: token_metadata.mint,
+            mint_authority: token_mint_authority,
+            token_program: spl_token::id(),
+            clock: Clock::id(),
+        })
+        .accounts(receipt_accounts(
+            program_id,
+            &staking_user_data.staking_data,
+            &staking_data,
+            &user_token_wallet_owner.pubkey(),
+            true,
+        ))
+        .accounts(reward_stream_accounts(
+            program_id,
+            &staking_data,
+            staking_user_data_key,
+        ))
+        .args(staking::instruction::Stake {
+            amount: ui_amount_to_amount(ui_amount, mint_data.decimals),
+        })
+        .signer(user_token_wallet_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn stake_locked(
+    client: &Client,
+    program_id: &Pubkey,
+    ui_amount: f64,
+    tier: u8,
+    staking_user_data_key: &Pubkey,
+    user_token_wallet_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    // Get the user data
+    let staking_user_data: StakingUserData = program.account(*staking_user_data_key)?;
+    let staking_data: StakingData = program.account(staking_user_data.staking_data)?;
+    let metadata_account = program
+        .rpc()
+        .get_account(&staking_data.stake_token_metadata)?;
+    let token_metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
+    let mint_acc = program.rpc().get_account(&token_metadata.mint)?;
+    let mint_data = Mint::unpack(&mint_acc.data).unwrap();
+
+    // Mint authority is pda of [staking_program_id, staking_data_key, token_metadata_id]
+    let token_mint_authority = Pubkey::create_program_address(
+        &[
+            &program_id.to_bytes(),
+            &staking_user_data.staking_data.to_bytes(),
+            &staking_data.stake_token_metadata.to_bytes(),
+            &[staking_data.mint_auth_bump],
+        ],
+        program_id,
+    )
+    .expect("PDA Creating Error");
+
+    let locked_stake = Keypair::new();
+    println!("Pubkey for LockedStake account: {}", locked_stake.pubkey());
+
+    // Stake locked
+    program
+        .request()
+        .accounts(staking::accounts::StakeLocked {
+            staking_user_data: *staking_user_data_key,
+            locked_stake: locked_stake.pubkey(),
+            user_token_wallet: staking_user_data.user_token_wallet,
+            user_token_wallet_owner: user_token_wallet_owner.pubkey(),
+            staking_data: staking_user_data.staking_data,
+            allow_list_entry: find_allow_list_entry_address(
+                program_id,
+                &staking_user_data.staking_data,
+                &user_token_wallet_owner.pubkey(),
+            )
+            .0,
+            holding_wallet: staking_data.holding_wallet,
+            fee_collector: staking_data.fee_collector,
+            staking_token_metadata: staking_data.stake_token_metadata,
+            mint: token_metadata.mint,
+            mint_authority: token_mint_authority,
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+            rent: Rent::id(),
+            clock: Clock::id(),
+        })
+        .accounts(reward_stream_accounts(
+            program_id,
+            &staking_data,
+            staking_user_data_key,
+        ))
+        .args(staking::instruction::StakeLocked {
+            amount: ui_amount_to_amount(ui_amount, mint_data.decimals),
+            tier: tier,
+        })
+        .signer(user_token_wallet_owner)
+        .signer(&locked_stake)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn release_locked_stake(
+    client: &Client,
+    program_id: &Pubkey,
+    locked_stake_key: &Pubkey,
+    user_token_wallet_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    let locked_stake: LockedStake = program.account(*locked_stake_key)?;
+    let staking_user_data: StakingUserData = program.account(locked_stake.staking_user_data)?;
+    let staking_data: StakingData = program.account(staking_user_data.staking_data)?;
+
+    program
+        .request()
+        .accounts(staking::accounts::ReleaseLockedStake {
+            staking_user_data: locked_stake.staking_user_data,
+            locked_stake: *locked_stake_key,
+            user_token_wallet: staking_user_data.user_token_wallet,
+            user_token_wallet_owner: user_token_wallet_owner.pubkey(),
+            staking_data: staking_user_data.staking_data,
+            clock: Clock::id(),
+        })
+        .accounts(reward_stream_accounts(
+            program_id,
+            &staking_data,
+            &locked_stake.staking_user_data,
+        ))
+        .args(staking::instruction::ReleaseLockedStake)
+        .signer(user_token_wallet_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn unstake(
+    client: &Client,
+    program_id: &Pubkey,
+    ui_amount: f64,
+    staking_user_data_key: &Pubkey,
+    user_token_wallet_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    // Get the user data
+    let staking_user_data: StakingUserData = program.account(*staking_user_data_key)?;
+    let staking_data: StakingData = program.account(staking_user_data.staking_data)?;
+    let metadata_account = program
+        .rpc()
+        .get_account(&staking_data.stake_token_metadata)?;
+    let token_metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
+    let mint_acc = program.rpc().get_account(&token_metadata.mint)?;
+    let mint_data = Mint::unpack(&mint_acc.data).unwrap();
+
+    // holding wallet owner is pda [staking_program_id, staking_data_id]
+    let holding_wallet_owner_pk = Pubkey::create_program_address(
+        &[
+            &program_id.to_bytes(),
+            &staking_user_data.staking_data.to_bytes(),
+            &[staking_data.holding_bump],
+        ],
+        program_id,
+    )
+    .expect("PDA Creating Error");
+
+    // Mint authority is pda of [staking_program_id, staking_data_key, token_metadata_id]
+    let token_mint_authority = Pubkey::create_program_address(
+        &[
+            &program_id.to_bytes(),
+            &staking_user_data.staking_data.to_bytes(),
+            &staking_data.stake_token_metadata.to_bytes(),
+            &[staking_data.mint_auth_bump],
+        ],
+        program_id,
+    )
+    .expect("PDA Creating Error");
+
+    // Unstake
+    program
+        .request()
+        .accounts(staking::accounts::Unstake {
+            staking_user_data: *staking_user_data_key,
+            user_token_wallet: staking_user_data.user_token_wallet,
+            user_token_wallet_owner: user_token_wallet_owner.pubkey(),
+            staking_data: staking_user_data.staking_data,
+            holding_wallet: staking_data.holding_wallet,
+            holding_wallet_owner: holding_wallet_owner_pk,
+            penalty_wallet: staking_data.penalty_wallet,
+            fee_collector: staking_data.fee_collector,
+            staking_token_metadata: staking_data.stake_token_metadata,
+            mint: token_metadata.mint,
+            mint_authority: token_mint_authority,
+            token_program: spl_token::id(),
+            clock: Clock::id(),
+        })
+        .accounts(receipt_accounts(
+            program_id,
+            &staking_user_data.staking_data,
+            &staking_data,
+            &user_token_wallet_owner.pubkey(),
+            false,
+        ))
+        .accounts(reward_stream_accounts(
+            program_id,
+            &staking_data,
+            staking_user_data_key,
+        ))
+        .args(staking::instruction::Unstake {
+            amount: ui_amount_to_amount(ui_amount, mint_data.decimals),
+        })
+        .signer(user_token_wallet_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn set_interest_mode(
+    client: &Client,
+    program_id: &Pubkey,
+    interest_mode: InterestMode,
+    staking_user_data_key: &Pubkey,
+    user_token_wallet_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    let staking_user_data: StakingUserData = program.account(*staking_user_data_key)?;
+
+    // Interest to date is paid in the current mode
+    accrue_interest(client, program_id, &staking_user_data.staking_data)?;
+
+    program
+        .request()
+        .accounts(staking::accounts::SetInterestMode {
+            staking_user_data: *staking_user_data_key,
+            user_token_wallet: staking_user_data.user_token_wallet,
+            user_token_wallet_owner: user_token_wallet_owner.pubkey(),
+            staking_data: staking_user_data.staking_data,
+        })
+        .args(staking::instruction::SetInterestMode {
+            interest_mode: interest_mode,
+        })
+        .signer(user_token_wallet_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn claim_interest(
+    client: &Client,
+    program_id: &Pubkey,
+    staking_user_data_key: &Pubkey,
+    user_token_wallet_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    // Get the user data
+    let staking_user_data: StakingUserData = program.account(*staking_user_data_key)?;
+    let staking_data: StakingData = program.account(staking_user_data.staking_data)?;
+    let metadata_account = program
+        .rpc()
+        .get_account(&staking_data.stake_token_metadata)?;
+    let token_metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
+
+    // holding wallet owner is pda [staking_program_id, staking_data_id]
+    let holding_wallet_owner_pk = Pubkey::create_program_address(
+        &[
+            &program_id.to_bytes(),
+            &staking_user_data.staking_data.to_bytes(),
+            &[staking_data.holding_bump],
+        ],
+        program_id,
+    )
+    .expect("PDA Creating Error");
+
+    // Mint authority is pda of [staking_program_id, staking_data_key, token_metadata_id]
+    let token_mint_authority = Pubkey::create_program_address(
+        &[
+            &program_id.to_bytes(),
+            &staking_user_data.staking_data.to_bytes(),
+            &staking_data.stake_token_metadata.to_bytes(),
+            &[staking_data.mint_auth_bump],
+        ],
+        program_id,
+    )
+    .expect("PDA Creating Error");
+
+    program
+        .request()
+        .accounts(staking::accounts::ClaimInterest {
+            staking_user_data: *staking_user_data_key,
+            user_token_wallet: staking_user_data.user_token_wallet,
+            user_token_wallet_owner: user_token_wallet_owner.pubkey(),
+            staking_data: staking_user_data.staking_data,
+            holding_wallet: staking_data.holding_wallet,
+            holding_wallet_owner: holding_wallet_owner_pk,
+            staking_token_metadata: staking_data.stake_token_metadata,
+            mint: token_metadata.mint,
+            mint_authority: token_mint_authority,
+            token_program: spl_token::id(),
+            clock: Clock::id(),
+        })
+        .accounts(reward_stream_accounts(
+            program_id,
+            &staking_data,
+            staking_user_data_key,
+        ))
+        .args(staking::instruction::ClaimInterest)
+        .signer(user_token_wallet_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn request_unstake(
+    client: &Client,
+    program_id: &Pubkey,
+    ui_amount: f64,
+    staking_user_data_key: &Pubkey,
+    user_token_wallet_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    // Get the user data
+    let staking_user_data: StakingUserData = program.account(*staking_user_data_key)?;
+    let staking_data: StakingData = program.account(staking_user_data.staking_data)?;
+    let metadata_account = program
+        .rpc()
+        .get_account(&staking_data.stake_token_metadata)?;
+    let token_metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
+    let mint_acc = program.rpc().get_account(&token_metadata.mint)?;
+    let mint_data = Mint::unpack(&mint_acc.data).unwrap();
+
+    // holding wallet owner is pda [staking_program_id, staking_data_id]
+    let holding_wallet_owner_pk = Pubkey::create_program_address(
+        &[
+            &program_id.to_bytes(),
+            &staking_user_data.staking_data.to_bytes(),
+            &[staking_data.holding_bump],
+        ],
+        program_id,
+    )
+    .expect("PDA Creating Error");
+
+    // Mint authority is pda of [staking_program_id, staking_data_key, token_metadata_id]
+    let token_mint_authority = Pubkey::create_program_address(
+        &[
+            &program_id.to_bytes(),
+            &staking_user_data.staking_data.to_bytes(),
+            &staking_data.stake_token_metadata.to_bytes(),
+            &[staking_data.mint_auth_bump],
+        ],
+        program_id,
+    )
+    .expect("PDA Creating Error");
+
+    let pending_withdrawal = Keypair::new();
+    println!(
+        "Pubkey for PendingWithdrawal account: {}",
+        pending_withdrawal.pubkey()
+    );
+
+    // Request unstake
+    program
+        .request()
+        .accounts(staking::accounts::RequestUnstake {
+            staking_user_data: *staking_user_data_key,
+            pending_withdrawal: pending_withdrawal.pubkey(),
+            user_token_wallet: staking_user_data.user_token_wallet,
+            user_token_wallet_owner: user_token_wallet_owner.pubkey(),
+            staking_data: staking_user_data.staking_data,
+            holding_wallet: staking_data.holding_wallet,
+            holding_wallet_owner: holding_wallet_owner_pk,
+            penalty_wallet: staking_data.penalty_wallet,
+            fee_collector: staking_data.fee_collector,
+            staking_token_metadata: staking_data.stake_token_metadata,
+            mint: token_metadata.mint,
+            mint_authority: token_mint_authority,
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+            rent: Rent::id(),
+            clock: Clock::id(),
+        })
+        .accounts(receipt_accounts(
+            program_id,
+            &staking_user_data.staking_data,
+            &staking_data,
+            &user_token_wallet_owner.pubkey(),
+            false,
+        ))
+        .accounts(reward_stream_accounts(
+            program_id,
+            &staking_data,
+            staking_user_data_key,
+        ))
+        .args(staking::instruction::RequestUnstake {
+            amount: ui_amount_to_amount(ui_amount, mint_data.decimals),
+        })
+        .signer(user_token_wallet_owner)
+        .signer(&pending_withdrawal)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn withdraw(
+    client: &Client,
+    program_id: &Pubkey,
+    pending_withdrawal_key: &Pubkey,
+    user_token_wallet_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    let pending_withdrawal: PendingWithdrawal = program.account(*pending_withdrawal_key)?;
+    let staking_user_data: StakingUserData =
+        program.account(pending_withdrawal.staking_user_data)?;
+    let staking_data: StakingData = program.account(staking_user_data.staking_data)?;
+
+    // holding wallet owner is pda [staking_program_id, staking_data_id]
+    let holding_wallet_owner_pk = Pubkey::create_program_address(
+        &[
+            &program_id.to_bytes(),
+            &staking_user_data.staking_data.to_bytes(),
+            &[staking_data.holding_bump],
+        ],
+        program_id,
+    )
+    .expect("PDA Creating Error");
+
+    program
+        .request()
+        .accounts(staking::accounts::Withdraw {
+            staking_user_data: pending_withdrawal.staking_user_data,
+            pending_withdrawal: *pending_withdrawal_key,
+            user_token_wallet: staking_user_data.user_token_wallet,
+            user_token_wallet_owner: user_token_wallet_owner.pubkey(),
+            staking_data: staking_user_data.staking_data,
+            holding_wallet: staking_data.holding_wallet,
+            holding_wallet_owner: holding_wallet_owner_pk,
+            token_program: spl_token::id(),
+            clock: Clock::id(),
+        })
+        .args(staking::instruction::Withdraw)
+        .signer(user_token_wallet_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn cancel_unstake(
+    client: &Client,
+    program_id: &Pubkey,
+    pending_withdrawal_key: &Pubkey,
+    user_token_wallet_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    let pending_withdrawal: PendingWithdrawal = program.account(*pending_withdrawal_key)?;
+    let staking_user_data: StakingUserData =
+        program.account(pending_withdrawal.staking_user_data)?;
+    let staking_data: StakingData = program.account(staking_user_data.staking_data)?;
+    let metadata_account = program
+        .rpc()
+        .get_account(&staking_data.stake_token_metadata)?;
+    let token_metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
+
+    // Mint authority is pda of [staking_program_id, staking_data_key, token_metadata_id]
+    let token_mint_authority = Pubkey::create_program_address(
+        &[
+            &program_id.to_bytes(),
+            &staking_user_data.staking_data.to_bytes(),
+            &staking_data.stake_token_metadata.to_bytes(),
+            &[staking_data.mint_auth_bump],
+        ],
+        program_id,
+    )
+    .expect("PDA Creating Error");
+
+    program
+        .request()
+        .accounts(staking::accounts::CancelUnstake {
+            staking_user_data: pending_withdrawal.staking_user_data,
+            pending_withdrawal: *pending_withdrawal_key,
+            user_token_wallet: staking_user_data.user_token_wallet,
+            user_token_wallet_owner: user_token_wallet_owner.pubkey(),
+            staking_data: staking_user_data.staking_data,
+            allow_list_entry: find_allow_list_entry_address(
+                program_id,
+                &staking_user_data.staking_data,
+                &user_token_wallet_owner.pubkey(),
+            )
+            .0,
+            holding_wallet: staking_data.holding_wallet,
+            staking_token_metadata: staking_data.stake_token_metadata,
+            mint: token_metadata.mint,
+            mint_authority: token_mint_authority,
+            token_program: spl_token::id(),
+            clock: Clock::id(),
+        })
+        .accounts(reward_stream_accounts(
+            program_id,
+            &staking_data,
+            &pending_withdrawal.staking_user_data,
+        ))
+        .args(staking::instruction::CancelUnstake)
+        .signer(user_token_wallet_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn transfer_shares(
+    client: &Client,
+    program_id: &Pubkey,
+    shares: u64,
+    staking_user_data_key: &Pubkey,
+    recipient_staking_user_data_key: &Pubkey,
+    user_token_wallet_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    let staking_user_data: StakingUserData = program.account(*staking_user_data_key)?;
+    let staking_data: StakingData = program.account(staking_user_data.staking_data)?;
+    let (recipient_token_wallet, recipient_allow_list_entry) =
+        recipient_accounts(&program, program_id, recipient_staking_user_data_key)?;
+
+    program
+        .request()
+        .accounts(staking::accounts::TransferShares {
+            staking_user_data: *staking_user_data_key,
+            user_token_wallet: staking_user_data.user_token_wallet,
+            user_token_wallet_owner: user_token_wallet_owner.pubkey(),
+            recipient_staking_user_data: *recipient_staking_user_data_key,
+            recipient_token_wallet: recipient_token_wallet,
+            recipient_allow_list_entry: recipient_allow_list_entry,
+            staking_data: staking_user_data.staking_data,
+            clock: Clock::id(),
+        })
+        .accounts(reward_stream_accounts(
+            program_id,
+            &staking_data,
+            staking_user_data_key,
+        ))
+        .accounts(reward_stream_accounts(
+            program_id,
+            &staking_data,
+            recipient_staking_user_data_key,
+        ))
+        .args(staking::instruction::TransferShares { shares: shares })
+        .signer(user_token_wallet_owner)
+        .send()?;
+
+    Ok(())
+}
+
+pub fn transfer_locked_stake(
+    client: &Client,
+    program_id: &Pubkey,
+    locked_stake_key: &Pubkey,
+    recipient_staking_user_data_key: &Pubkey,
+    user_token_wallet_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    let locked_stake: LockedStake = program.account(*locked_stake_key)?;
+    let staking_user_data: StakingUserData = program.account(locked_stake.staking_user_data)?;
+    let staking_data: StakingData = program.account(staking_user_data.staking_data)?;
+    let (recipient_token_wallet, recipient_allow_list_entry) =
+        recipient_accounts(&program, program_id, recipient_staking_user_data_key)?;
+
+    program
+        .request()
+        .accounts(staking::accounts::TransferLockedStake {
+            staking_user_data: locked_stake.staking_user_data,
+            locked_stake: *locked_stake_key,
+            user_token_wallet: staking_user_data.user_token_wallet,
+            user_token_wallet_owner: user_token_wallet_owner.pubkey(),
+            recipient_staking_user_data: *recipient_staking_user_data_key,
+            recipient_token_wallet: recipient_token_wallet,
+            recipient_allow_list_entry: recipient_allow_list_entry,
+            staking_data: staking_user_data.staking_data,
+            clock: Clock::id(),
+        })
+        .accounts(reward_stream_accounts(
+            program_id,
+            &staking_data,
+            &locked_stake.staking_user_data,
+        ))
+        .accounts(reward_stream_accounts(
+            program_id,
+            &staking_data,
+            recipient_staking_user_data_key,
+        ))
+        .args(staking::instruction::TransferLockedStake)
+        .signer(user_token_wallet_owner)
+        .send()?;
+
+    Ok(())
+}
+
+/// Token wallet and allow-list entry of the shares recipient
+fn recipient_accounts(
+    program: &Program,
+    program_id: &Pubkey,
+    recipient_staking_user_data_key: &Pubkey,
+) -> Result<(Pubkey, Pubkey), ClientError> {
+    let recipient_staking_user_data: StakingUserData =
+        program.account(*recipient_staking_user_data_key)?;
+    let recipient_token_wallet = program
+        .rpc()
+        .get_account(&recipient_staking_user_data.user_token_wallet)?;
+    let recipient_token_wallet_data = Account::unpack(&recipient_token_wallet.data).unwrap();
+    let (recipient_allow_list_entry, _) = find_allow_list_entry_address(
+        program_id,
+        &recipient_staking_user_data.staking_data,
+        &recipient_token_wallet_data.owner,
+    );
+
+    Ok((
+        recipient_staking_user_data.user_token_wallet,
+        recipient_allow_list_entry,
+    ))
+}
+
+pub fn close_staking_user(
+    client: &Client,
+    program_id: &Pubkey,
+    staking_user_data_key: &Pubkey,
+    user_token_wallet_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    let staking_user_data: StakingUserData = program.account(*staking_user_data_key)?;
+    let staking_data: StakingData = program.account(staking_user_data.staking_data)?;
+
+    program
+        .request()
+        .accounts(staking::accounts::CloseStakingUser {
+            staking_user_data: *staking_user_data_key,
+            user_token_wallet: staking_user_data.user_token_wallet,
+            user_token_wallet_owner: user_token_wallet_owner.pubkey(),
+            staking_data: staking_user_data.staking_data,
+            clock: Clock::id(),
+        })
+        .accounts(reward_stream_accounts(
+            program_id,
+            &staking_data,
+            staking_user_data_key,
+        ))
+        .args(staking::instruction::CloseStakingUser)
+        .signer(user_token_wallet_owner)
+        .send()?;
+
+    Ok(())
+}
+
+/// RewardStream and UserReward account pairs for every reward stream of the staking pool
+fn reward_stream_accounts(
+    program_id: &Pubkey,
+    staking_data: &StakingData,
+    staking_user_data_key: &Pubkey,
+) -> Vec<AccountMeta> {
+    let mut accounts = vec![];
+    for reward_stream_key in staking_data.reward_streams.iter() {
+        let (user_reward_key, _) =
+            find_user_reward_address(program_id, reward_stream_key, staking_user_data_key);
+        accounts.push(AccountMeta {
+            pubkey: *reward_stream_key,
+            is_signer: false,
+            is_writable: true,
+        });
+        accounts.push(AccountMeta {
+            pubkey: user_reward_key,
+            is_signer: false,
+            is_writable: true,
+        });
+    }
+    accounts
+}
+
+/// Receipt mint, receipt mint authority (only for minting) and receipt wallet of the owner
+/// in receipt mode
+fn receipt_accounts(
+    program_id: &Pubkey,
+    staking_data_key: &Pubkey,
+    staking_data: &StakingData,
+    owner: &Pubkey,
+    mint_receipts: bool,
+) -> Vec<AccountMeta> {
+    let mut accounts = vec![];
+    if !staking_data.receipts_enabled() {
+        return accounts;
+    }
+
+    accounts.push(AccountMeta {
+        pubkey: staking_data.receipt_mint,
+        is_signer: false,
+        is_writable: true,
+    });
+    if mint_receipts {
+        let (receipt_mint_authority, _) =
+            find_receipt_mint_authority_address(program_id, staking_data_key);
+        accounts.push(AccountMeta {
+            pubkey: receipt_mint_authority,
+            is_signer: false,
+            is_writable: false,
+        });
+    }
+    accounts.push(AccountMeta {
+        pubkey: get_associated_token_address(owner, &staking_data.receipt_mint),
+        is_signer: false,
+        is_writable: true,
+    });
+    accounts
+}
+
+/// Receipt mint authority is pda of ["receipt_mint", staking_program_id, staking_data]
+fn find_receipt_mint_authority_address(
+    program_id: &Pubkey,
+    staking_data_key: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            staking::RECEIPT_MINT_PREFIX.as_bytes(),
+            &program_id.to_bytes(),
+            &staking_data_key.to_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// Creates the associated receipt wallet of the owner in receipt mode
+fn create_receipt_wallet(
+    program: &Program,
+    staking_data: &StakingData,
+    owner: &Pubkey,
+) -> Result<(), ClientError> {
+    if !staking_data.receipts_enabled() {
+        return Ok(());
+    }
+
+    let receipt_wallet = get_associated_token_address(owner, &staking_data.receipt_mint);
+    if program
+        .rpc()
+        .get_account_with_commitment(&receipt_wallet, program.rpc().commitment())?
+        .value
+        .is_none()
+    {
+        println!("Initializing receipt wallet: {}", receipt_wallet);
+        program
+            .request()
+            .instruction(create_associated_token_account(
+                &program.payer(),
+                owner,
+                &staking_data.receipt_mint,
+            ))
+            .send()?;
+    }
+
+    Ok(())
+}
+
+/// User reward is pda of ["user_reward", staking_program_id, reward_stream, staking_user_data]
+fn find_user_reward_address(
+    program_id: &Pubkey,
+    reward_stream_key: &Pubkey,
+    staking_user_data_key: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
         &[
+            staking::USER_REWARD_PREFIX.as_bytes(),
             &program_id.to_bytes(),
-            &staking_data.pubkey().to_bytes(),
-            &token_metadata.to_bytes(),
+            &reward_stream_key.to_bytes(),
+            &staking_user_data_key.to_bytes(),
         ],
         program_id,
+    )
+}
+
+pub fn add_reward_stream(
+    client: &Client,
+    program_id: &Pubkey,
+    staking_data_key: &Pubkey,
+    reward_mint: &Pubkey,
+    ui_emission_per_second: f64,
+    start_timestamp: Option<i64>,
+    end_timestamp: i64,
+    staking_data_owner: &Keypair,
+) -> Result<(), ClientError> {
+    let program = client.program(*program_id);
+
+    let start_timestamp = start_timestamp.unwrap_or(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64,
+    );
+    let mint_acc = program.rpc().get_account(reward_mint)?;
+    let mint_data = Mint::unpack(&mint_acc.data).unwrap();
+
+    let reward_stream = Keypair::new();
+    println!(
+        "Pubkey for RewardStream account: {}",
+        reward_stream.pubkey()
+    );
+
+    // vault owner is the pda of [staking_program_id, reward_stream]
+    let (vault_owner_pk, vault_bump) = Pubkey::find_program_address(
+        &[&program_id.to_bytes(), &reward_stream.pubkey().to_bytes()],
+        program_id,
     );
-    println!("New token mint authority: {}", pda_mint_authority_pk);
 
-    // Initialize Staking Data
+    // Create new Token Account
+    let vault = get_associated_token_address(&vault_owner_pk, reward_mint);
+    println!("Pubkey for reward vault: {}", vault);
     program
         .request()
-        .instruction(spl_token::instruction::set_authority(
-            &spl_token::id(),
-            &metadata.mint,
-            Some(&pda_mint_authority_pk),
-            spl_token::instruction::AuthorityType::MintTokens,
-            &mint_authority.pubkey(),
-            &[],
-        )?)
-        .signer(mint_authority)
-        .accounts(staking::accounts::InitializeStaking {
-            staking_data: staking_data.pubkey(),
-            staking_owner: program.payer(),
-            holding_wallet: holding_wallet,
-            holding_wallet_owner: holding_wallet_owner_pk,
-            staking_token_metadata: *token_metadata,
-            staking_token_mint: metadata.mint,
+        .instruction(create_associated_token_account(
+            &program.payer(),
+            &vault_owner_pk,
+            reward_mint,
+        ))
+        .send()?;
+
+    program
+        .request()
+        .accounts(staking::accounts::AddRewardStream {
+            staking_data: *staking_data_key,
+            staking_owner: staking_data_owner.pubkey(),
+            reward_stream: reward_stream.pubkey(),
+            sponsor: program.payer(),
+            vault: vault,
+            vault_owner: vault_owner_pk,
+            system_program: system_program::id(),
             rent: Rent::id(),
             clock: Clock::id(),
-            system_program: system_program::id(),
         })
-        .args(staking::instruction::InitializeStaking {
-            token: *token_metadata,
-            starting_interest_rate: starting_interest_rate,
-            max_interest_rate: max_interest_rate,
-            starting_timestamp: starting_timestamp,
-            cap: ui_amount_to_amount(cap, mint_data.decimals),
-            holding_bump: holding_bump,
-            mint_auth_bump: mint_auth_bump,
+        .args(staking::instruction::AddRewardStream {
+            emission_per_second: ui_amount_to_amount(ui_emission_per_second, mint_data.decimals),
+            start_timestamp: start_timestamp,
+            end_timestamp: end_timestamp,
+            vault_bump: vault_bump,
         })
-        .signer(staking_data)
+        .signer(staking_data_owner)
+        .signer(&reward_stream)
         .send()?;
 
     Ok(())
 }
 
-pub fn set_interest_rate(
+pub fn remove_reward_stream(
     client: &Client,
     program_id: &Pubkey,
-    new_interest_rate: u64,
-    staking_data_key: &Pubkey,
+    reward_stream_key: &Pubkey,
     staking_data_owner: &Keypair,
 ) -> Result<(), ClientError> {
     let program = client.program(*program_id);
 
-    let staking_data: StakingData = program.account(*staking_data_key)?;
-    let metadata_account = program
-        .rpc()
-        .get_account(&staking_data.stake_token_metadata)?;
-    let metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
+    let reward_stream: RewardStream = program.account(*reward_stream_key)?;
 
-    // Mint authority is pda of [staking_program_id, staking_data_key, token_metadata_id]
-    let token_mint_authority = Pubkey::create_program_address(
+    // vault owner is the pda of [staking_program_id, reward_stream]
+    let vault_owner_pk = Pubkey::create_program_address(
         &[
             &program_id.to_bytes(),
-            &staking_data_key.to_bytes(),
-            &staking_data.stake_token_metadata.to_bytes(),
-            &[staking_data.mint_auth_bump],
+            &reward_stream_key.to_bytes(),
+            &[reward_stream.vault_bump],
         ],
         program_id,
     )
     .expect("PDA Creating Error");
 
-    // Set up new interest rate
+    // Find or initialize sponsor reward token account
+    let sponsor_reward_wallet =
+        get_associated_token_address(&reward_stream.sponsor, &reward_stream.reward_mint);
+    println!(
+        "Pubkey for sponsor reward wallet: {}",
+        sponsor_reward_wallet
+    );
+    if program
+        .rpc()
+        .get_account_with_commitment(&sponsor_reward_wallet, program.rpc().commitment())?
+        .value
+        .is_none()
+    {
+        println!("Initializing sponsor reward wallet");
+        program
+            .request()
+            .instruction(create_associated_token_account(
+                &program.payer(),
+                &reward_stream.sponsor,
+                &reward_stream.reward_mint,
+            ))
+            .send()?;
+    }
+
     program
         .request()
-        .accounts(staking::accounts::SetInterestRate {
-            staking_data: *staking_data_key,
+        .accounts(staking::accounts::RemoveRewardStream {
+            staking_data: reward_stream.staking_data,
             staking_owner: staking_data_owner.pubkey(),
-            holding_wallet: staking_data.holding_wallet,
-            staking_token_metadata: staking_data.stake_token_metadata,
-            mint: metadata.mint,
-            mint_authority: token_mint_authority,
+            reward_stream: *reward_stream_key,
+            sponsor: reward_stream.sponsor,
+            vault: reward_stream.vault,
+            vault_owner: vault_owner_pk,
+            sponsor_reward_wallet: sponsor_reward_wallet,
             token_program: spl_token::id(),
             clock: Clock::id(),
         })
-        .args(staking::instruction::SetInterestRate {
-            new_interest_rate: new_interest_rate,
-        })
+        .args(staking::instruction::RemoveRewardStream {})
         .signer(staking_data_owner)
         .send()?;
 
     Ok(())
 }
 
-pub fn initialize_user_staking(
+pub fn init_user_reward(
     client: &Client,
     program_id: &Pubkey,
-    staking_data_key: &Pubkey,
+    reward_stream_key: &Pubkey,
+    staking_user_data_key: &Pubkey,
 ) -> Result<(), ClientError> {
     let program = client.program(*program_id);
 
-    let staking_data: StakingData = program.account(*staking_data_key)?;
-    let metadata_account = program
-        .rpc()
-        .get_account(&staking_data.stake_token_metadata)?;
-    let metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
-
-    let staking_user_data = Keypair::new();
-    print!("Pubkey for UserStakingData account: ");
-    println!("{}", staking_user_data.pubkey());
-
-    // Find or initialize user SPL Token Account
-    let user_token_wallet = get_associated_token_address(&program.payer(), &metadata.mint);
-    println!("Pubkey for user token wallet: {}", user_token_wallet);
-    if let Some(account) = program
-        .rpc()
-        .get_account_with_commitment(&user_token_wallet, program.rpc().commitment())?
-        .value
-    {
-        let account_data = Account::unpack(&account.data).unwrap();
-        if account.owner == spl_token::id()
-            && account_data.owner == program.payer()
-            && account_data.mint == metadata.mint
-        {
-            println!("User token wallet already exists");
-        } else {
-            panic!("Account {} was incorrectly initialized", user_token_wallet);
-        }
-    } else {
-        println!("Initializing user token wallet");
-        program
-            .request()
-            .instruction(create_associated_token_account(
-                &program.payer(),
-                &program.payer(),
-                &metadata.mint,
-            ))
-            .send()?;
-    }
+    let (user_reward_key, user_reward_bump) =
+        find_user_reward_address(program_id, reward_stream_key, staking_user_data_key);
+    println!("Pubkey for UserReward account: {}", user_reward_key);
 
-    println!("Initialing user staking data");
-    // Initialize user staking data account
     program
         .request()
-        .accounts(staking::accounts::InitializeStakingUser {
-            staking_user_data: staking_user_data.pubkey(),
-            user_token_wallet: user_token_wallet,
-            user_token_wallet_owner: program.payer(),
-            staking_data: *staking_data_key,
-            staking_token_metadata: staking_data.stake_token_metadata,
+        .accounts(staking::accounts::InitUserReward {
+            user_reward: user_reward_key,
+            reward_stream: *reward_stream_key,
+            staking_user_data: *staking_user_data_key,
+            payer: program.payer(),
             system_program: system_program::id(),
             rent: Rent::id(),
         })
-        .args(staking::instruction::InitializeStakingUser)
-        .signer(&staking_user_data)
+        .args(staking::instruction::InitUserReward {
+            _user_reward_bump: user_reward_bump,
+        })
         .send()?;
 
     Ok(())
 }
 
-pub fn stake(
+pub fn claim_rewards(
     client: &Client,
     program_id: &Pubkey,
-    ui_amount: f64,
+    reward_stream_key: &Pubkey,
     staking_user_data_key: &Pubkey,
     user_token_wallet_owner: &Keypair,
 ) -> Result<(), ClientError> {
     let program = client.program(*program_id);
 
-    // Get the user data
+    let reward_stream: RewardStream = program.account(*reward_stream_key)?;
     let staking_user_data: StakingUserData = program.account(*staking_user_data_key)?;
-    let staking_data: StakingData = program.account(staking_user_data.staking_data)?;
-    let metadata_account = program
-        .rpc()
-        .get_account(&staking_data.stake_token_metadata)?;
-    let token_metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
-    let mint_acc = program.rpc().get_account(&token_metadata.mint)?;
-    let mint_data = Mint::unpack(&mint_acc.data).unwrap();
+    let (user_reward_key, _) =
+        find_user_reward_address(program_id, reward_stream_key, staking_user_data_key);
 
-    // Mint authority is pda of [staking_program_id, staking_data_key, token_metadata_id]
-    let token_mint_authority = Pubkey::create_program_address(
+    // vault owner is the pda of [staking_program_id, reward_stream]
+    let vault_owner_pk = Pubkey::create_program_address(
         &[
             &program_id.to_bytes(),
-            &staking_user_data.staking_data.to_bytes(),
-            &staking_data.stake_token_metadata.to_bytes(),
-            &[staking_data.mint_auth_bump],
+            &reward_stream_key.to_bytes(),
+            &[reward_stream.vault_bump],
         ],
         program_id,
     )
     .expect("PDA Creating Error");
 
-    // Stake
+    // Find or initialize user reward token account
+    let user_reward_wallet = get_associated_token_address(
+        &user_token_wallet_owner.pubkey(),
+        &reward_stream.reward_mint,
+    );
+    println!("Pubkey for user reward wallet: {}", user_reward_wallet);
+    if program
+        .rpc()
+        .get_account_with_commitment(&user_reward_wallet, program.rpc().commitment())?
+        .value
+        .is_none()
+    {
+        println!("Initializing user reward wallet");
+        program
+            .request()
+            .instruction(create_associated_token_account(
+                &program.payer(),
+                &user_token_wallet_owner.pubkey(),
+                &reward_stream.reward_mint,
+            ))
+            .send()?;
+    }
+
     program
         .request()
-        .accounts(staking::accounts::Stake {
+        .accounts(staking::accounts::ClaimRewards {
+            staking_data: staking_user_data.staking_data,
             staking_user_data: *staking_user_data_key,
             user_token_wallet: staking_user_data.user_token_wallet,
             user_token_wallet_owner: user_token_wallet_owner.pubkey(),
-            staking_data: staking_user_data.staking_data,
-            holding_wallet: staking_data.holding_wallet,
-            staking_token_metadata: staking_data.stake_token_metadata,
-            mint: token_metadata.mint,
-            mint_authority: token_mint_authority,
+            reward_stream: *reward_stream_key,
+            user_reward: user_reward_key,
+            vault: reward_stream.vault,
+            vault_owner: vault_owner_pk,
+            user_reward_wallet: user_reward_wallet,
             token_program: spl_token::id(),
             clock: Clock::id(),
         })
-        .args(staking::instruction::Stake {
-            amount: ui_amount_to_amount(ui_amount, mint_data.decimals),
-        })
+        .args(staking::instruction::ClaimRewards)
         .signer(user_token_wallet_owner)
         .send()?;
 
     Ok(())
 }
 
-pub fn unstake(
+pub fn close_user_reward(
     client: &Client,
     program_id: &Pubkey,
-    ui_amount: f64,
+    reward_stream_key: &Pubkey,
     staking_user_data_key: &Pubkey,
     user_token_wallet_owner: &Keypair,
 ) -> Result<(), ClientError> {
     let program = client.program(*program_id);
 
-    // Get the user data
     let staking_user_data: StakingUserData = program.account(*staking_user_data_key)?;
-    let staking_data: StakingData = program.account(staking_user_data.staking_data)?;
-    let metadata_account = program
-        .rpc()
-        .get_account(&staking_data.stake_token_metadata)?;
-    let token_metadata: Metadata = try_from_slice_unchecked(&metadata_account.data).unwrap();
-    let mint_acc = program.rpc().get_account(&token_metadata.mint)?;
-    let mint_data = Mint::unpack(&mint_acc.data).unwrap();
-
-    // holding wallet owner is pda [staking_program_id, staking_data_id]
-    let holding_wallet_owner_pk = Pubkey::create_program_address(
-        &[
-            &program_id.to_bytes(),
-            &staking_user_data.staking_data.to_bytes(),
-            &[staking_data.holding_bump],
-        ],
-        program_id,
-    )
-    .expect("PDA Creating Error");
-
-    // Mint authority is pda of [staking_program_id, staking_data_key, token_metadata_id]
-    let token_mint_authority = Pubkey::create_program_address(
-        &[
-            &program_id.to_bytes(),
-            &staking_user_data.staking_data.to_bytes(),
-            &staking_data.stake_token_metadata.to_bytes(),
-            &[staking_data.mint_auth_bump],
-        ],
-        program_id,
-    )
-    .expect("PDA Creating Error");
+    let (user_reward_key, _) =
+        find_user_reward_address(program_id, reward_stream_key, staking_user_data_key);
 
-    // Unstake
     program
         .request()
-        .accounts(staking::accounts::Unstake {
+        .accounts(staking::accounts::CloseUserReward {
+            staking_data: staking_user_data.staking_data,
             staking_user_data: *staking_user_data_key,
             user_token_wallet: staking_user_data.user_token_wallet,
             user_token_wallet_owner: user_token_wallet_owner.pubkey(),
-            staking_data: staking_user_data.staking_data,
-            holding_wallet: staking_data.holding_wallet,
-            holding_wallet_owner: holding_wallet_owner_pk,
-            staking_token_metadata: staking_data.stake_token_metadata,
-            mint: token_metadata.mint,
-            mint_authority: token_mint_authority,
-            token_program: spl_token::id(),
-            clock: Clock::id(),
-        })
-        .args(staking::instruction::Unstake {
-            amount: ui_amount_to_amount(ui_amount, mint_data.decimals),
+            reward_stream: *reward_stream_key,
+            user_reward: user_reward_key,
         })
+        .args(staking::instruction::CloseUserReward)
         .signer(user_token_wallet_owner)
         .send()?;
 
@@ -418,6 +2250,7 @@ pub fn staking_info(
     let program = client.program(*program_id);
     let staking_data: StakingData = program.account(*staking_key)?;
     println!("owner: {}", staking_data.owner);
+    println!("pending_owner: {}", staking_data.pending_owner);
     println!(
         "stake_token_metadata: {}",
         staking_data.stake_token_metadata
@@ -426,6 +2259,31 @@ pub fn staking_info(
     println!("total_staked: {}", staking_data.total_staked);
     println!("total_shares: {}", staking_data.total_shares);
     println!("interest_rate_daily: {}", staking_data.interest_rate_daily);
+    println!(
+        "current_interest_rate_daily: {}",
+        staking::utils::effective_interest_rate(
+            staking_data.interest_rate_daily,
+            &staking_data.rate_schedule,
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64,
+        )
+    );
+    match staking_data.rate_model {
+        Some(rate_model) => println!(
+            "rate_model: base rate: {}, slope low: {}, slope high: {}, kink: {}",
+            rate_model.base_rate, rate_model.slope_low, rate_model.slope_high, rate_model.kink
+        ),
+        None => println!("rate_model: fixed rate"),
+    }
+    println!("rate_schedule:");
+    for point in staking_data.rate_schedule.iter() {
+        println!(
+            "  since {}: daily rate: {}",
+            point.effective_timestamp, point.daily_rate
+        );
+    }
     println!(
         "max_interest_rate_daily: {}",
         staking_data.max_interest_rate_daily
@@ -435,6 +2293,55 @@ pub fn staking_info(
         staking_data.last_interest_accrued_timestamp
     );
     println!("cap: {}", staking_data.cap);
+    println!("lock_tiers:");
+    for (index, tier) in staking_data.lock_tiers.iter().enumerate() {
+        println!(
+            "  {}: duration: {} sec, boost: {}",
+            index, tier.duration, tier.boost
+        );
+    }
+    println!("authorized_lockers:");
+    for locker in staking_data.authorized_lockers.iter() {
+        println!("  {}", locker);
+    }
+    println!("cooldown_seconds: {}", staking_data.cooldown_seconds);
+    println!("pending_withdrawals: {}", staking_data.pending_withdrawals);
+    println!("guardian: {}", staking_data.guardian);
+    println!("slasher: {}", staking_data.slasher);
+    println!("pause_level: {:?}", staking_data.pause_level);
+    println!("reward_mode: {:?}", staking_data.reward_mode);
+    println!("reward_reserve: {}", staking_data.reward_reserve);
+    println!("reserve_exhausted: {}", staking_data.reserve_exhausted);
+    println!(
+        "early_unstake_penalty: {}",
+        staking_data.early_unstake_penalty
+    );
+    println!(
+        "penalty_window_seconds: {}",
+        staking_data.penalty_window_seconds
+    );
+    println!("penalty_wallet: {}", staking_data.penalty_wallet);
+    println!("deposit_fee_bps: {}", staking_data.deposit_fee_bps);
+    println!("withdraw_fee_bps: {}", staking_data.withdraw_fee_bps);
+    println!("fee_collector: {}", staking_data.fee_collector);
+    println!("allow_list_enabled: {}", staking_data.allow_list_enabled);
+    println!("user_deposit_cap: {}", staking_data.user_deposit_cap);
+    println!("total_deposit_cap: {}", staking_data.total_deposit_cap);
+    println!("receipt_mint: {}", staking_data.receipt_mint);
+    println!("receipt_shares: {}", staking_data.receipt_shares);
+    println!("reward_streams:");
+    for reward_stream_key in staking_data.reward_streams.iter() {
+        let reward_stream: RewardStream = program.account(*reward_stream_key)?;
+        println!(
+            "  {}: mint: {}, vault: {}, emission per second: {}, from {} to {}",
+            reward_stream_key,
+            reward_stream.reward_mint,
+            reward_stream.vault,
+            reward_stream.emission_per_second,
+            reward_stream.start_timestamp,
+            reward_stream.end_timestamp
+        );
+    }
     Ok(())
 }
 
@@ -451,7 +2358,7 @@ pub fn total_staked_for(
         return Ok(0);
     }
 
-    let (unminted_interest, _) = staking::utils::calculate_accrued_interest(
+    let (unminted_interest, _) = staking::utils::calculate_scheduled_accrued_interest(
         staking_data.last_interest_accrued_timestamp,
         SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -459,7 +2366,9 @@ pub fn total_staked_for(
             .as_secs() as i64,
         staking_data.total_staked,
         staking_data.interest_rate_daily,
+        &staking_data.rate_schedule,
     );
+    let unminted_interest = staking_data.payable_interest(unminted_interest);
     let total_staked_with_interest = staking_data
         .total_staked
         .checked_add(unminted_interest)
@@ -484,7 +2393,7 @@ pub fn total_staked(
     let program = client.program(*program_id);
     let staking_data: StakingData = program.account(*staking_key)?;
 
-    let (unminted_interest, _) = staking::utils::calculate_accrued_interest(
+    let (unminted_interest, _) = staking::utils::calculate_scheduled_accrued_interest(
         staking_data.last_interest_accrued_timestamp,
         SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -492,7 +2401,9 @@ pub fn total_staked(
             .as_secs() as i64,
         staking_data.total_staked,
         staking_data.interest_rate_daily,
+        &staking_data.rate_schedule,
     );
+    let unminted_interest = staking_data.payable_interest(unminted_interest);
     let total_staked_with_interest = staking_data
         .total_staked
         .checked_add(unminted_interest)
@@ -509,7 +2420,7 @@ pub fn total_unminted_interest(
     let program = client.program(*program_id);
     let staking_data: StakingData = program.account(*staking_key)?;
 
-    let (unminted_interest, _) = staking::utils::calculate_accrued_interest(
+    let (unminted_interest, _) = staking::utils::calculate_scheduled_accrued_interest(
         staking_data.last_interest_accrued_timestamp,
         SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -517,7 +2428,9 @@ pub fn total_unminted_interest(
             .as_secs() as i64,
         staking_data.total_staked,
         staking_data.interest_rate_daily,
+        &staking_data.rate_schedule,
     );
+    let unminted_interest = staking_data.payable_interest(unminted_interest);
 
     Ok(unminted_interest)
 }
@@ -532,8 +2445,66 @@ pub fn staking_user_info(
     println!("user_token_wallet: {}", staking_user_data.user_token_wallet);
     println!("staking_data: {}", staking_user_data.staking_data);
     println!("ownership_share: {}", staking_user_data.ownership_share);
-    println!("locked amount: {}", staking_user_data.locked_amount);
-    println!("locked until: {}", staking_user_data.locked_until);
+    println!("locks:");
+    for lock in staking_user_data.locks.iter() {
+        println!(
+            "  locker program: {}, locker: {}, amount: {}, until: {}",
+            lock.locker_program, lock.locker, lock.amount, lock.until
+        );
+    }
+    println!(
+        "tier locked shares: {}",
+        staking_user_data.tier_locked_shares
+    );
+    println!("interest mode: {:?}", staking_user_data.interest_mode);
+    println!("principal: {}", staking_user_data.principal);
+    println!("claimed interest: {}", staking_user_data.claimed_interest);
+    println!(
+        "last stake timestamp: {}",
+        staking_user_data.last_stake_timestamp
+    );
+    println!(
+        "pending withdrawals: {}",
+        staking_user_data.pending_withdrawals
+    );
+    println!("share checkpoints:");
+    for checkpoint in staking_user_data.share_checkpoints.iter() {
+        println!("  slot: {}, shares: {}", checkpoint.slot, checkpoint.shares);
+    }
+
+    let staking_data: StakingData = program.account(staking_user_data.staking_data)?;
+    for reward_stream_key in staking_data.reward_streams.iter() {
+        let (user_reward_key, _) =
+            find_user_reward_address(program_id, reward_stream_key, staking_user_key);
+        let mut reward_stream: RewardStream = program.account(*reward_stream_key)?;
+        match program.account::<UserReward>(user_reward_key) {
+            Ok(mut user_reward) => {
+                // Estimate rewards to date
+                staking::utils::update_reward_stream(
+                    &mut reward_stream,
+                    staking_data.total_shares,
+                    SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs() as i64,
+                );
+                staking::utils::settle_user_reward(
+                    &reward_stream,
+                    &mut user_reward,
+                    staking_user_data.ownership_share,
+                    staking_user_data.ownership_share,
+                );
+                println!(
+                    "reward stream {}: claimable rewards: {}",
+                    reward_stream_key, user_reward.pending_rewards
+                );
+            }
+            Err(_) => println!(
+                "reward stream {}: user reward is not initialized",
+                reward_stream_key
+            ),
+        }
+    }
 
     Ok(())
 }
@@ -301,6 +301,15 @@ fn get_clap_app<'a, 'b>(name: &'a str, desc: &'a str, version: &'a str) -> App<'
                              Defaults to the client keypair address."
                         ),
                 )
+                .arg(
+                    Arg::with_name("reward_reserve")
+                        .long("reward-reserve")
+                        .takes_value(false)
+                        .help(
+                            "Pay the interest from the reward reserve instead of minting. \
+                             The mint authority is not transferred."
+                        ),
+                )
                 .arg(
                     Arg::with_name("staking_data_owner")
                         .long("staking-data-owner")
@@ -372,10 +381,10 @@ fn get_clap_app<'a, 'b>(name: &'a str, desc: &'a str, version: &'a str) -> App<'
                 ),
         )
         .subcommand(
-            SubCommand::with_name("initialize-user-staking")
+            SubCommand::with_name("set-rate-schedule")
                 .about(
-                    "Initialize UserStakingData Account with provided StakingDataAccount pubkey. \
-                    Pubkeys for UserTokenWallet and UserStakingData are generated randomly."
+                    "Replace the interest rate schedule for provided StakingData Account. \
+                     Accrues interest first."
                 )
                 .arg(
                     Arg::with_name("staking_data_account")
@@ -386,729 +395,3366 @@ fn get_clap_app<'a, 'b>(name: &'a str, desc: &'a str, version: &'a str) -> App<'
                         .help("The StakingData Account Pubkey."),
                 )
                 .arg(
-                    Arg::with_name("user_authority")
-                        .long("user-authority")
+                    Arg::with_name("rate_point")
+                        .long("point")
+                        .short("p")
+                        .value_name("TIMESTAMP:DAILY_RATE")
+                        .validator(is_valid_rate_point)
+                        .takes_value(true)
+                        .multiple(true)
+                        .help(
+                            "The list of rate breakpoints sorted by timestamp. Daily rate is in 1e-6 (1/100 of a basis point). \
+                            Specify every breakpoint like -p 1640995200:261 -p 1672531200:159 ..."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("staking_data_owner")
+                        .long("staking-data-owner")
                         .value_name("KEYPAIR")
                         .validator(is_valid_signer)
                         .takes_value(true)
                         .help(
-                            "Specify the user token wallet owner keypair. \
+                            "Specify the staking data owner keypair. \
                              Defaults to the client keypair address."
                         ),
-                )
+                ),
         )
         .subcommand(
-            SubCommand::with_name("stake")
+            SubCommand::with_name("set-rate-model")
                 .about(
-                    "Stakes tokens for the user. \
-                     Accrues interest first. \
-                     StakingData Account must be created with command initialize-staking first. \
-                     UserStakingData Account will must be created with command initialize-user-staking first."
+                    "Set the utilization based rate model for provided StakingData Account. \
+                     The daily rate falls from base rate by slope low below the kink and by slope high above it. \
+                     Accrues interest first."
                 )
                 .arg(
-                    Arg::with_name("ui_amount")
-                        .value_name("f64")
-                        .validator(is_valid_ui_amount)
+                    Arg::with_name("staking_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
                         .required(true)
                         .takes_value(true)
-                        .help("Amount of tokens to stake (float value)."),
+                        .help("The StakingData Account Pubkey."),
                 )
                 .arg(
-                    Arg::with_name("staking_user_data_account")
-                        .value_name("PUBKEY")
-                        .validator(is_valid_pubkey)
+                    Arg::with_name("base_rate")
+                        .value_name("u64")
+                        .validator(is_valid_uint)
+                        .required_unless("fixed_rate")
                         .takes_value(true)
-                        .help("The UserStakingData Account pubkey."),
+                        .help("Daily interest rate in 1e-6 (1/100 of a basis point) with nothing staked."),
                 )
                 .arg(
-                    Arg::with_name("user_authority")
-                        .long("user-authority")
+                    Arg::with_name("slope_low")
+                        .value_name("u64")
+                        .validator(is_valid_uint)
+                        .required_unless("fixed_rate")
+                        .takes_value(true)
+                        .help("Rate decrease in 1e-6 for the full utilization below the kink."),
+                )
+                .arg(
+                    Arg::with_name("slope_high")
+                        .value_name("u64")
+                        .validator(is_valid_uint)
+                        .required_unless("fixed_rate")
+                        .takes_value(true)
+                        .help("Rate decrease in 1e-6 for the full utilization above the kink."),
+                )
+                .arg(
+                    Arg::with_name("kink")
+                        .value_name("u64")
+                        .validator(is_valid_uint)
+                        .required_unless("fixed_rate")
+                        .takes_value(true)
+                        .help("Utilization in 1e-6 where the slope changes."),
+                )
+                .arg(
+                    Arg::with_name("fixed_rate")
+                        .long("fixed-rate")
+                        .takes_value(false)
+                        .help("Remove the rate model and return to the fixed interest rate."),
+                )
+                .arg(
+                    Arg::with_name("staking_data_owner")
+                        .long("staking-data-owner")
                         .value_name("KEYPAIR")
                         .validator(is_valid_signer)
                         .takes_value(true)
                         .help(
-                            "Specify the user token wallet owner keypair. \
+                            "Specify the staking data owner keypair. \
                              Defaults to the client keypair address."
                         ),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("unstake")
+            SubCommand::with_name("set-reward-mode")
                 .about(
-                    "Unstakes tokens of the caller and pushes them. \
-                     Accrues interest first. \
-                     StakingData Account must be created with command initialize-staking first. \
-                     UserStakingData Account will must be created with command initialize-user-staking first."
+                    "Set the source of the interest for provided StakingData Account. \
+                     Mode mint mints the interest, mode reserve pays it from the reward reserve."
                 )
                 .arg(
-                    Arg::with_name("ui_amount")
-                        .value_name("f64")
-                        .validator(is_valid_ui_amount)
+                    Arg::with_name("mode")
+                        .value_name("MODE")
+                        .possible_values(&["mint", "reserve"])
                         .required(true)
                         .takes_value(true)
-                        .help("Amount of tokens to unstake (float value)."),
+                        .help("Reward mode."),
                 )
                 .arg(
-                    Arg::with_name("staking_user_data_account")
+                    Arg::with_name("staking_data_account")
                         .value_name("PUBKEY")
                         .validator(is_valid_pubkey)
+                        .required(true)
                         .takes_value(true)
-                        .help("The UserStakingData Account pubkey."),
+                        .help("The StakingData Account Pubkey."),
                 )
                 .arg(
-                    Arg::with_name("user_authority")
-                        .long("user-authority")
+                    Arg::with_name("staking_data_owner")
+                        .long("staking-data-owner")
                         .value_name("KEYPAIR")
                         .validator(is_valid_signer)
                         .takes_value(true)
                         .help(
-                            "Specify the user token wallet owner keypair. \
+                            "Specify the staking data owner keypair. \
                              Defaults to the client keypair address."
                         ),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("accrue-interest")
-                .about("Accrues interest to date. It is also accrued by most operations.")
+            SubCommand::with_name("fund-reward-reserve")
+                .about("Transfer tokens to the reward reserve of provided StakingData Account.")
+                .arg(
+                    Arg::with_name("ui_amount")
+                        .value_name("f64")
+                        .validator(is_valid_ui_amount)
+                        .required(true)
+                        .takes_value(true)
+                        .help("Amount of tokens to add to the reserve (float value)."),
+                )
                 .arg(
                     Arg::with_name("staking_data_account")
                         .value_name("PUBKEY")
                         .validator(is_valid_pubkey)
+                        .required(true)
                         .takes_value(true)
-                        .help("The StakingData Account pubkey."),
-                )
-        )
-        .subcommand(
-            SubCommand::with_name("total-staked-for")
-                .about(
-                    "Viewer for the total stake of the provided staking user data address, including tokens not yet minted, \
-                     but virtually accrued to date. Doesn't change state."
+                        .help("The StakingData Account Pubkey."),
                 )
                 .arg(
-                    Arg::with_name("staking_user_data_account")
-                        .value_name("PUBKEY")
-                        .validator(is_valid_pubkey)
+                    Arg::with_name("funder")
+                        .long("funder")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
                         .takes_value(true)
-                        .help("The StakingUserData Account pubkey."),
+                        .help(
+                            "Specify the funder token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("total-staked")
+            SubCommand::with_name("withdraw-reward-reserve")
                 .about(
-                    "Viewer for the total amount of all stakes, including interest to date even if it's still virtual."
+                    "Transfer unused tokens of the reward reserve of provided StakingData Account \
+                     to the owner token wallet."
+                )
+                .arg(
+                    Arg::with_name("ui_amount")
+                        .value_name("f64")
+                        .validator(is_valid_ui_amount)
+                        .required(true)
+                        .takes_value(true)
+                        .help("Amount of tokens to withdraw from the reserve (float value)."),
                 )
                 .arg(
                     Arg::with_name("staking_data_account")
                         .value_name("PUBKEY")
                         .validator(is_valid_pubkey)
+                        .required(true)
                         .takes_value(true)
-                        .help("The StakingData Account pubkey."),
+                        .help("The StakingData Account Pubkey."),
+                )
+                .arg(
+                    Arg::with_name("staking_data_owner")
+                        .long("staking-data-owner")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the staking data owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("total-unminted-interest")
+            SubCommand::with_name("set-lock-tiers")
                 .about(
-                    "Viewer for the virtual interest to date that hasn't been minted yet."
+                    "Replace the lock tiers available for stake-locked command \
+                     for provided StakingData Account."
                 )
                 .arg(
                     Arg::with_name("staking_data_account")
                         .value_name("PUBKEY")
                         .validator(is_valid_pubkey)
+                        .required(true)
                         .takes_value(true)
-                        .help("The StakingData Account pubkey."),
-                ),
-        )
-        .subcommand(
-            SubCommand::with_name("initialize-governance")
-                .about(
-                    "Initialize Governance account. You need to do this before using the gov instructions. \
-                    The StakingData pubkey must be provided. \
-                    Pubkey for gov account is generated randomly."
+                        .help("The StakingData Account Pubkey."),
                 )
                 .arg(
-                    Arg::with_name("staking_data_key")
-                        .value_name("PUBKEY")
-                        .validator(is_valid_pubkey)
+                    Arg::with_name("lock_tier")
+                        .long("tier")
+                        .short("t")
+                        .value_name("DURATION_SEC:BOOST")
+                        .validator(is_valid_lock_tier)
                         .takes_value(true)
-                        .help("The StakingData pubkey."),
+                        .multiple(true)
+                        .help(
+                            "The list of lock tiers. Boost is in 1e-4 (basis point), 10000 is no boost. \
+                            Specify every tier like -t 2592000:11000 -t 7776000:13000 ..."
+                        ),
                 )
                 .arg(
-                    Arg::with_name("approval_fixed_period_in_seconds")
-                        .value_name("i64_SECONDS")
-                        .validator(is_valid_int)
+                    Arg::with_name("staking_data_owner")
+                        .long("staking-data-owner")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
                         .takes_value(true)
-                        .help("The approval period in seconds for every proposal."),
+                        .help(
+                            "Specify the staking data owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("set-authorized-lockers")
+                .about(
+                    "Replace the programs allowed to lock the user shares \
+                     for provided StakingData Account."
                 )
                 .arg(
-                    Arg::with_name("min_approval_percent")
-                        .value_name("u8_PERCENT")
-                        .validator(is_valid_uint)
+                    Arg::with_name("staking_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
                         .takes_value(true)
-                        .help("The minimum approval percent for every proposal."),
+                        .help("The StakingData Account Pubkey."),
                 )
                 .arg(
-                    Arg::with_name("min_stake_to_propose")
-                        .value_name("f64_AMOUNT")
-                        .validator(is_valid_ui_amount)
+                    Arg::with_name("locker")
+                        .long("locker")
+                        .short("l")
+                        .value_name("PROGRAM_ID")
+                        .validator(is_valid_pubkey)
                         .takes_value(true)
-                        .help("The minimum stake amount to make new proposal."),
+                        .multiple(true)
+                        .help(
+                            "The list of locker program ids. \
+                            Specify every program like -l <GOVERNANCE_PROGRAM_ID> -l ..."
+                        ),
                 )
                 .arg(
-                    Arg::with_name("min_vote_participation_percent")
-                        .value_name("u8_PERCENT")
-                        .validator(is_valid_uint)
+                    Arg::with_name("staking_data_owner")
+                        .long("staking-data-owner")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
                         .takes_value(true)
-                        .help("The minimum vote participation percent for every proposal."),
+                        .help(
+                            "Specify the staking data owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("set-cooldown")
+                .about(
+                    "Update unstake cooldown for provided StakingData Account. \
+                     With non-zero cooldown tokens are unstaked by request-unstake and withdraw commands."
                 )
                 .arg(
-                    Arg::with_name("payment_period_sec")
+                    Arg::with_name("cooldown_seconds")
                         .value_name("i64")
                         .validator(is_valid_int)
+                        .required(true)
                         .takes_value(true)
-                        .help("Payment period duration in seconds."),
+                        .help("New unstake cooldown in seconds."),
                 )
                 .arg(
-                    Arg::with_name("sponsor")
-                        .long("sponsor")
-                        .short("s")
+                    Arg::with_name("staking_data_account")
                         .value_name("PUBKEY")
                         .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The StakingData Account Pubkey."),
+                )
+                .arg(
+                    Arg::with_name("staking_data_owner")
+                        .long("staking-data-owner")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
                         .takes_value(true)
-                        .multiple(true)
                         .help(
-                            "The list of sponsors. Specify every sponsor like \
-                            -s <SPONSOR_1_PUBKEY> -s <SPONSOR_2_PUBKEY> ..."
+                            "Specify the staking data owner keypair. \
+                             Defaults to the client keypair address."
                         ),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("initialize-treasury-stats")
-                .about("Initialize Governance treasury stats account for specific SPL Token.")
+            SubCommand::with_name("enable-receipts")
+                .about(
+                    "Switch provided StakingData Account to receipt mode. \
+                     Stake mints receipt tokens and unstake burns them, \
+                     users can move their shares into receipt tokens and any receipt holder \
+                     can redeem them back into the shares."
+                )
                 .arg(
-                    Arg::with_name("governance")
+                    Arg::with_name("staking_data_account")
                         .value_name("PUBKEY")
                         .validator(is_valid_pubkey)
+                        .required(true)
                         .takes_value(true)
-                        .help("The Governance pubkey."),
+                        .help("The StakingData Account Pubkey."),
                 )
                 .arg(
-                    Arg::with_name("mint")
-                        .value_name("PUBKEY")
-                        .validator(is_valid_pubkey)
+                    Arg::with_name("name")
+                        .validator(is_valid_name)
+                        .value_name("STRING")
+                        .required(true)
                         .takes_value(true)
-                        .help("The SPL Token mint pubkey."),
+                        .help("Receipt Token Name"),
                 )
                 .arg(
-                    Arg::with_name("max_proposal_payment_percent")
-                        .value_name("u8_PERCENT")
-                        .validator(is_valid_percentage)
+                    Arg::with_name("symbol")
+                        .validator(is_valid_symbol)
+                        .value_name("STRING")
+                        .required(true)
                         .takes_value(true)
-                        .help("The maximum payment amount (percent of highest balance) for every proposal."),
+                        .help("Receipt Token Symbol"),
                 )
                 .arg(
-                    Arg::with_name("payment_amount_in_period_limit_percent")
-                        .value_name("u8_PERCENT")
-                        .validator(is_valid_percentage)
-                        .takes_value(true)
-                        .help("The hard cap for proposal payment amount in period (percent of highest balance)."),
-                )
+                    Arg::with_name("staking_data_owner")
+                        .long("staking-data-owner")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the staking data owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
         )
-        .subcommand(SubCommand::with_name("governance-info")
-                .about("Information about provided governance.")
+        .subcommand(
+            SubCommand::with_name("mint-receipts")
+                .about(
+                    "Moves the shares available to unstake into receipt tokens of the user."
+                )
                 .arg(
-                    Arg::with_name("governance")
-                        .value_name("PUBKEY")
-                        .validator(is_valid_pubkey)
+                    Arg::with_name("shares")
+                        .value_name("u64")
+                        .validator(is_valid_uint)
+                        .required(true)
                         .takes_value(true)
-                        .help("The pubkey of governance."),
+                        .help("Amount of shares to move."),
                 )
-        )
-        .subcommand(SubCommand::with_name("treasury-stats-info")
-                .about("Information about provided governance treasury.")
                 .arg(
-                    Arg::with_name("governance")
+                    Arg::with_name("staking_user_data_account")
                         .value_name("PUBKEY")
                         .validator(is_valid_pubkey)
+                        .required(true)
                         .takes_value(true)
-                        .help("The pubkey of governance."),
+                        .help("The UserStakingData Account pubkey."),
                 )
                 .arg(
-                    Arg::with_name("treasury")
-                        .value_name("PUBKEY")
-                        .validator(is_valid_pubkey)
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
                         .takes_value(true)
-                        .help("The pubkey of treasury."),
-                )
+                        .help(
+                            "Specify the user token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
         )
-        .subcommand(SubCommand::with_name("add-sponsor")
+        .subcommand(
+            SubCommand::with_name("redeem-receipts")
                 .about(
-                    "Add the new sponsor Pubkey into sponsors list. \
-                    Required the 60% signatures of the current sponsors list."
+                    "Redeems the receipt tokens of the user back into the shares of UserStakingData Account."
                 )
                 .arg(
-                    Arg::with_name("governance")
-                        .value_name("PUBKEY")
-                        .validator(is_valid_pubkey)
+                    Arg::with_name("shares")
+                        .value_name("u64")
+                        .validator(is_valid_uint)
+                        .required(true)
                         .takes_value(true)
-                        .help("The pubkey of governance."),
+                        .help("Amount of receipts to redeem."),
                 )
                 .arg(
-                    Arg::with_name("sponsor")
+                    Arg::with_name("staking_user_data_account")
                         .value_name("PUBKEY")
                         .validator(is_valid_pubkey)
+                        .required(true)
                         .takes_value(true)
-                        .help("The pubkey of new sponsor."),
+                        .help("The UserStakingData Account pubkey."),
                 )
                 .arg(
-                    Arg::with_name("signers")
-                        .long("signer")
-                        .short("s")
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
                         .value_name("KEYPAIR")
                         .validator(is_valid_signer)
                         .takes_value(true)
-                        .multiple(true)
                         .help(
-                            "The list of signers. Specify every signer like \
-                            -s <SIGNER_1_KEYPAIR> -s <SIGNER_2_KEYPAIR> ..."
+                            "Specify the user token wallet owner and receipts owner keypair. \
+                             Defaults to the client keypair address."
                         ),
                 ),
         )
-        .subcommand(SubCommand::with_name("remove-sponsor")
+        .subcommand(
+            SubCommand::with_name("set-deposit-limits")
                 .about(
-                    "Remove the sponsor Pubkey from sponsors list. \
-                    Required the 60% signatures of the current sponsors list."
+                    "Update allow-list and deposit caps for provided StakingData Account. \
+                     With enabled allow-list only wallets added by add-to-allow-list command can stake."
                 )
                 .arg(
-                    Arg::with_name("governance")
-                        .value_name("PUBKEY")
-                        .validator(is_valid_pubkey)
+                    Arg::with_name("allow_list_enabled")
+                        .value_name("BOOL")
+                        .validator(is_valid_bool)
+                        .required(true)
                         .takes_value(true)
-                        .help("The pubkey of governance."),
+                        .help("true to enable the allow-list."),
                 )
                 .arg(
-                    Arg::with_name("sponsor")
+                    Arg::with_name("user_deposit_cap")
+                        .value_name("f64_AMOUNT")
+                        .validator(is_valid_ui_amount)
+                        .required(true)
+                        .takes_value(true)
+                        .help("Maximum value of the user stake, 0 for no cap."),
+                )
+                .arg(
+                    Arg::with_name("total_deposit_cap")
+                        .value_name("f64_AMOUNT")
+                        .validator(is_valid_ui_amount)
+                        .required(true)
+                        .takes_value(true)
+                        .help("Maximum total staked tokens, 0 for no cap."),
+                )
+                .arg(
+                    Arg::with_name("staking_data_account")
                         .value_name("PUBKEY")
                         .validator(is_valid_pubkey)
+                        .required(true)
                         .takes_value(true)
-                        .help("The pubkey of sponsor to be removed."),
+                        .help("The StakingData Account Pubkey."),
                 )
                 .arg(
-                    Arg::with_name("signers")
-                        .long("signer")
-                        .short("s")
+                    Arg::with_name("staking_data_owner")
+                        .long("staking-data-owner")
                         .value_name("KEYPAIR")
                         .validator(is_valid_signer)
                         .takes_value(true)
-                        .multiple(true)
                         .help(
-                            "The list of signers. Specify every signer like \
-                            -s <SIGNER_1_KEYPAIR> -s <SIGNER_2_KEYPAIR> ..."
+                            "Specify the staking data owner keypair. \
+                             Defaults to the client keypair address."
                         ),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("treasury-balance")
-                .about("Treasury balance for provided treasury pubkey.")
+            SubCommand::with_name("add-to-allow-list")
+                .about("Allow the wallet to stake in permissioned staking pool.")
                 .arg(
-                    Arg::with_name("treasury")
+                    Arg::with_name("wallet")
                         .value_name("PUBKEY")
                         .validator(is_valid_pubkey)
+                        .required(true)
                         .takes_value(true)
-                        .help("The pubkey of governance treasury."),
-                ),
-        )
-        .subcommand(
-            SubCommand::with_name("fund-treasury")
-                .about("Funding of the governance treasury. Treasury must be initialized first.")
-                .arg(
-                    Arg::with_name("amount")
-                        .value_name("TOKEN_AMOUNT")
-                        .takes_value(true)
-                        .validator(is_valid_ui_amount)
-                        .help("Amount of tokens to fund."),
+                        .help("The user wallet Pubkey (owner of the user token wallet)."),
                 )
                 .arg(
-                    Arg::with_name("governance")
+                    Arg::with_name("staking_data_account")
                         .value_name("PUBKEY")
                         .validator(is_valid_pubkey)
+                        .required(true)
                         .takes_value(true)
-                        .help("The pubkey of initialized Governance."),
+                        .help("The StakingData Account Pubkey."),
                 )
                 .arg(
-                    Arg::with_name("treasury")
+                    Arg::with_name("staking_data_owner")
+                        .long("staking-data-owner")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the staking data owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("remove-from-allow-list")
+                .about(
+                    "Remove the wallet from allow-list of permissioned staking pool. \
+                     Tokens already staked can be unstaked."
+                )
+                .arg(
+                    Arg::with_name("wallet")
                         .value_name("PUBKEY")
                         .validator(is_valid_pubkey)
+                        .required(true)
                         .takes_value(true)
-                        .help("The pubkey of governance treasury."),
+                        .help("The user wallet Pubkey (owner of the user token wallet)."),
                 )
                 .arg(
-                    Arg::with_name("user_token_wallet")
-                        .long("from")
+                    Arg::with_name("staking_data_account")
                         .value_name("PUBKEY")
                         .validator(is_valid_pubkey)
+                        .required(true)
                         .takes_value(true)
-                        .help(
-                            "Specify the user token wallet pubkey. \
-                             Defaults to the token associated address."
-                        ),
+                        .help("The StakingData Account Pubkey."),
                 )
                 .arg(
-                    Arg::with_name("user_authority")
-                        .long("user-authority")
+                    Arg::with_name("staking_data_owner")
+                        .long("staking-data-owner")
                         .value_name("KEYPAIR")
                         .validator(is_valid_signer)
                         .takes_value(true)
                         .help(
-                            "Specify the user token wallet owner keypair. \
+                            "Specify the staking data owner keypair. \
                              Defaults to the client keypair address."
                         ),
                 ),
         )
-        .subcommand(SubCommand::with_name("proposal-info")
-                .about("Information about provided proposal.")
-                .arg(
-                    Arg::with_name("proposal")
-                        .value_name("PUBKEY")
-                        .validator(is_valid_pubkey)
-                        .takes_value(true)
-                        .help("The pubkey of proposal."),
-                )
-        )
         .subcommand(
-            SubCommand::with_name("make-proposal")
+            SubCommand::with_name("set-fees")
                 .about(
-                    "Create the new proposal with provided recipient address, payment amount and description IPFS Hash. \
-                    Governance pubkey and StakingUserData also must be provided. \
-                    Proposal maker must has staked tokens."
-                )
-                .arg(
-                    Arg::with_name("governance")
-                        .value_name("PUBKEY")
-                        .validator(is_valid_pubkey)
-                        .takes_value(true)
-                        .help("The pubkey of initialized Governance."),
+                    "Update deposit and withdraw fees for provided StakingData Account. \
+                     Fees are transferred to the fee collector on stake and unstake."
                 )
                 .arg(
-                    Arg::with_name("staking_user_data")
-                        .value_name("PUBKEY")
-                        .validator(is_valid_pubkey)
+                    Arg::with_name("deposit_fee_bps")
+                        .value_name("u64")
+                        .validator(is_valid_uint)
+                        .required(true)
                         .takes_value(true)
-                        .help("The pubkey of StakingUserData."),
+                        .help("New deposit fee in basis points."),
                 )
                 .arg(
-                    Arg::with_name("payment_amount")
-                        .value_name("TOKEN_AMOUNT")
+                    Arg::with_name("withdraw_fee_bps")
+                        .value_name("u64")
+                        .validator(is_valid_uint)
+                        .required(true)
                         .takes_value(true)
-                        .validator(is_valid_ui_amount)
-                        .help("Amount of payment tokens."),
+                        .help("New withdraw fee in basis points."),
                 )
                 .arg(
-                    Arg::with_name("recipient")
+                    Arg::with_name("staking_data_account")
                         .value_name("PUBKEY")
                         .validator(is_valid_pubkey)
+                        .required(true)
                         .takes_value(true)
-                        .help("The pubkey of the recipient."),
+                        .help("The StakingData Account Pubkey."),
                 )
                 .arg(
-                    Arg::with_name("ipfs_hash")
-                        .value_name("HAS_STRING")
+                    Arg::with_name("fee_collector")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
                         .takes_value(true)
-                        .validator(is_valid_hash_len)
-                        .help("The IPFS hash of proposal description."),
+                        .help("The token account receiving the fees."),
                 )
                 .arg(
-                    Arg::with_name("user_authority")
-                        .long("user-authority")
+                    Arg::with_name("staking_data_owner")
+                        .long("staking-data-owner")
                         .value_name("KEYPAIR")
                         .validator(is_valid_signer)
                         .takes_value(true)
                         .help(
-                            "Specify the user token wallet owner keypair. \
+                            "Specify the staking data owner keypair. \
                              Defaults to the client keypair address."
                         ),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("approve-proposal")
+            SubCommand::with_name("set-early-unstake-penalty")
                 .about(
-                    "Vote for existent proposal. \
-                    Proposal pubkey and StakingUserData must be provided. \
-                    User must has staked tokens to vote."
+                    "Update early unstake penalty for provided StakingData Account. \
+                     The penalty decays linearly to zero over the window since the user last stake."
                 )
                 .arg(
-                    Arg::with_name("staking_user_data")
-                        .value_name("PUBKEY")
-                        .validator(is_valid_pubkey)
+                    Arg::with_name("penalty")
+                        .value_name("u64")
+                        .validator(is_valid_uint)
+                        .required(true)
                         .takes_value(true)
-                        .help("The pubkey of StakingUserData."),
+                        .help("New early unstake penalty in basis points."),
                 )
                 .arg(
-                    Arg::with_name("vote")
-                        .value_name("BOOL")
-                        .validator(is_valid_bool)
+                    Arg::with_name("window_seconds")
+                        .value_name("i64")
+                        .validator(is_valid_int)
+                        .required(true)
                         .takes_value(true)
-                        .help("true or false for pros or cons."),
+                        .help("New penalty window in seconds."),
                 )
                 .arg(
-                    Arg::with_name("proposal")
+                    Arg::with_name("staking_data_account")
                         .value_name("PUBKEY")
                         .validator(is_valid_pubkey)
+                        .required(true)
                         .takes_value(true)
-                        .help("The pubkey of proposal to vote."),
+                        .help("The StakingData Account Pubkey."),
                 )
                 .arg(
-                    Arg::with_name("user_authority")
-                        .long("user-authority")
+                    Arg::with_name("penalty_wallet")
+                        .long("penalty-wallet")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help(
+                            "Specify the token account receiving the penalty, e.g. governance treasury. \
+                             Defaults to the holding wallet, leaving the penalty to the remaining stakers."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("staking_data_owner")
+                        .long("staking-data-owner")
                         .value_name("KEYPAIR")
                         .validator(is_valid_signer)
                         .takes_value(true)
                         .help(
-                            "Specify the user token wallet owner keypair. \
+                            "Specify the staking data owner keypair. \
                              Defaults to the client keypair address."
                         ),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("finalize-proposal")
-                .about(
-                    "Trying to finalize the proposal. \
-                    No authority is required to invoke this instruction."
+            SubCommand::with_name("set-guardian")
+                .about("Set the guardian who can pause and unpause provided StakingData Account.")
+                .arg(
+                    Arg::with_name("guardian")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The guardian pubkey."),
                 )
                 .arg(
-                    Arg::with_name("proposal")
+                    Arg::with_name("staking_data_account")
                         .value_name("PUBKEY")
                         .validator(is_valid_pubkey)
+                        .required(true)
                         .takes_value(true)
-                        .help("The pubkey of proposal to finalize."),
+                        .help("The StakingData Account Pubkey."),
                 )
+                .arg(
+                    Arg::with_name("staking_data_owner")
+                        .long("staking-data-owner")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the staking data owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
         )
-}
+        .subcommand(
+            SubCommand::with_name("set-slasher")
+                .about(
+                    "Set the slasher who can slash the users of provided StakingData Account. \
+                     Default pubkey 11111111111111111111111111111111 disables slashing."
+                )
+                .arg(
+                    Arg::with_name("slasher")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The slasher pubkey."),
+                )
+                .arg(
+                    Arg::with_name("staking_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The StakingData Account Pubkey."),
+                )
+                .arg(
+                    Arg::with_name("staking_data_owner")
+                        .long("staking-data-owner")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the staking data owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("slash")
+                .about(
+                    "Slash the part of the user shares. Tokens of the slashed shares are burned \
+                     or redirected to the slash wallet."
+                )
+                .arg(
+                    Arg::with_name("slash_bps")
+                        .value_name("NUMBER")
+                        .validator(is_valid_uint)
+                        .required(true)
+                        .takes_value(true)
+                        .help("Part of the user shares to slash in 1e-4 (basis point), 10000 is all the shares."),
+                )
+                .arg(
+                    Arg::with_name("reason")
+                        .value_name("NUMBER")
+                        .validator(is_valid_u16)
+                        .required(true)
+                        .takes_value(true)
+                        .help("Reason code emitted with the slash event."),
+                )
+                .arg(
+                    Arg::with_name("staking_user_data")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The StakingUserData Account Pubkey to slash."),
+                )
+                .arg(
+                    Arg::with_name("slash_wallet")
+                        .long("slash-wallet")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help(
+                            "Token account receiving the slashed tokens. \
+                             Defaults to the holding wallet, which burns the tokens."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("slasher")
+                        .long("slasher")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the slasher keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("pause")
+                .about(
+                    "Pause provided StakingData Account. Level unstake-only blocks deposits only, \
+                     level full blocks all staking operations."
+                )
+                .arg(
+                    Arg::with_name("level")
+                        .value_name("LEVEL")
+                        .possible_values(&["unstake-only", "full"])
+                        .required(true)
+                        .takes_value(true)
+                        .help("Pause level."),
+                )
+                .arg(
+                    Arg::with_name("staking_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The StakingData Account Pubkey."),
+                )
+                .arg(
+                    Arg::with_name("authority")
+                        .long("authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the staking data owner or guardian keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("unpause")
+                .about("Unpause provided StakingData Account.")
+                .arg(
+                    Arg::with_name("staking_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The StakingData Account Pubkey."),
+                )
+                .arg(
+                    Arg::with_name("authority")
+                        .long("authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the staking data owner or guardian keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("transfer-staking-ownership")
+                .about(
+                    "Propose the new owner for provided StakingData Account. \
+                     The new owner should accept the ownership with command accept-staking-ownership."
+                )
+                .arg(
+                    Arg::with_name("new_owner")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The new owner pubkey."),
+                )
+                .arg(
+                    Arg::with_name("staking_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The StakingData Account Pubkey."),
+                )
+                .arg(
+                    Arg::with_name("staking_data_owner")
+                        .long("staking-data-owner")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the staking data owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("accept-staking-ownership")
+                .about("Accept the ownership of provided StakingData Account by the proposed owner.")
+                .arg(
+                    Arg::with_name("staking_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The StakingData Account Pubkey."),
+                )
+                .arg(
+                    Arg::with_name("new_owner")
+                        .long("new-owner")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the proposed owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("initialize-user-staking")
+                .about(
+                    "Initialize UserStakingData Account with provided StakingDataAccount pubkey. \
+                    Pubkeys for UserTokenWallet and UserStakingData are generated randomly."
+                )
+                .arg(
+                    Arg::with_name("staking_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The StakingData Account Pubkey."),
+                )
+                .arg(
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the user token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("stake")
+                .about(
+                    "Stakes tokens for the user. \
+                     Accrues interest first. \
+                     StakingData Account must be created with command initialize-staking first. \
+                     UserStakingData Account will must be created with command initialize-user-staking first."
+                )
+                .arg(
+                    Arg::with_name("ui_amount")
+                        .value_name("f64")
+                        .validator(is_valid_ui_amount)
+                        .required(true)
+                        .takes_value(true)
+                        .help("Amount of tokens to stake (float value)."),
+                )
+                .arg(
+                    Arg::with_name("staking_user_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The UserStakingData Account pubkey."),
+                )
+                .arg(
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the user token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("stake-locked")
+                .about(
+                    "Stakes tokens for the user locked for the duration of provided lock tier. \
+                     New shares are boosted by the tier multiplier. \
+                     Accrues interest first. \
+                     Pubkey for LockedStake account is generated randomly."
+                )
+                .arg(
+                    Arg::with_name("ui_amount")
+                        .value_name("f64")
+                        .validator(is_valid_ui_amount)
+                        .required(true)
+                        .takes_value(true)
+                        .help("Amount of tokens to stake (float value)."),
+                )
+                .arg(
+                    Arg::with_name("tier")
+                        .value_name("u8")
+                        .validator(is_valid_decimals)
+                        .required(true)
+                        .takes_value(true)
+                        .help("Index of the lock tier."),
+                )
+                .arg(
+                    Arg::with_name("staking_user_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The UserStakingData Account pubkey."),
+                )
+                .arg(
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the user token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("release-locked-stake")
+                .about(
+                    "Releases the expired locked stake, so its shares can be unstaked. \
+                     LockedStake Account is closed."
+                )
+                .arg(
+                    Arg::with_name("locked_stake_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The LockedStake Account pubkey."),
+                )
+                .arg(
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the user token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("unstake")
+                .about(
+                    "Unstakes tokens of the caller and pushes them. \
+                     Accrues interest first. \
+                     StakingData Account must be created with command initialize-staking first. \
+                     UserStakingData Account will must be created with command initialize-user-staking first."
+                )
+                .arg(
+                    Arg::with_name("ui_amount")
+                        .value_name("f64")
+                        .validator(is_valid_ui_amount)
+                        .required(true)
+                        .takes_value(true)
+                        .help("Amount of tokens to unstake (float value)."),
+                )
+                .arg(
+                    Arg::with_name("staking_user_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The UserStakingData Account pubkey."),
+                )
+                .arg(
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the user token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("set-interest-mode")
+                .about(
+                    "Set the interest payout of the user. Mode compound keeps the interest staked, \
+                     mode claim pays the interest by claim-interest command. \
+                     Accrues interest first."
+                )
+                .arg(
+                    Arg::with_name("mode")
+                        .value_name("MODE")
+                        .possible_values(&["compound", "claim"])
+                        .required(true)
+                        .takes_value(true)
+                        .help("Interest mode."),
+                )
+                .arg(
+                    Arg::with_name("staking_user_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The UserStakingData Account pubkey."),
+                )
+                .arg(
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the user token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("claim-interest")
+                .about(
+                    "Transfers the interest of claim mode user to the user token wallet. \
+                     Accrues interest first."
+                )
+                .arg(
+                    Arg::with_name("staking_user_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The UserStakingData Account pubkey."),
+                )
+                .arg(
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the user token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("request-unstake")
+                .about(
+                    "Requests to unstake tokens of the caller. The tokens stop earning interest \
+                     and can be withdrawn with command withdraw after the cooldown. \
+                     Accrues interest first. Pubkey for PendingWithdrawal is generated randomly."
+                )
+                .arg(
+                    Arg::with_name("ui_amount")
+                        .value_name("f64")
+                        .validator(is_valid_ui_amount)
+                        .required(true)
+                        .takes_value(true)
+                        .help("Amount of tokens to unstake (float value)."),
+                )
+                .arg(
+                    Arg::with_name("staking_user_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The UserStakingData Account pubkey."),
+                )
+                .arg(
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the user token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("withdraw")
+                .about(
+                    "Pushes the tokens of PendingWithdrawal to the caller after the cooldown. \
+                     PendingWithdrawal Account is closed."
+                )
+                .arg(
+                    Arg::with_name("pending_withdrawal_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The PendingWithdrawal Account pubkey."),
+                )
+                .arg(
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the user token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("transfer-shares")
+                .about(
+                    "Moves the shares available to unstake to UserStakingData Account of another user \
+                     in the same staking pool without unstaking."
+                )
+                .arg(
+                    Arg::with_name("shares")
+                        .value_name("u64")
+                        .validator(is_valid_uint)
+                        .required(true)
+                        .takes_value(true)
+                        .help("Amount of shares to transfer."),
+                )
+                .arg(
+                    Arg::with_name("staking_user_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The UserStakingData Account pubkey of the sender."),
+                )
+                .arg(
+                    Arg::with_name("recipient_staking_user_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The UserStakingData Account pubkey of the recipient."),
+                )
+                .arg(
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the sender user token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("transfer-locked-stake")
+                .about(
+                    "Moves the locked stake position with its shares to UserStakingData Account of \
+                     another user in the same staking pool. The shares stay locked for the recipient."
+                )
+                .arg(
+                    Arg::with_name("locked_stake_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The LockedStake Account pubkey."),
+                )
+                .arg(
+                    Arg::with_name("recipient_staking_user_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The UserStakingData Account pubkey of the recipient."),
+                )
+                .arg(
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the sender user token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("close-user-staking")
+                .about(
+                    "Closes UserStakingData and UserReward Accounts of the user who fully unstaked \
+                     and returns their rent. \
+                     Time-locked stakes have to be released, \
+                     pending withdrawals and rewards have to be withdrawn and claimed first, \
+                     UserReward Accounts of removed reward streams closed by close-user-reward."
+                )
+                .arg(
+                    Arg::with_name("staking_user_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The UserStakingData Account pubkey."),
+                )
+                .arg(
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the user token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("cancel-unstake")
+                .about(
+                    "Stakes the tokens of PendingWithdrawal back. \
+                     Accrues interest first. PendingWithdrawal Account is closed."
+                )
+                .arg(
+                    Arg::with_name("pending_withdrawal_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The PendingWithdrawal Account pubkey."),
+                )
+                .arg(
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the user token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("add-reward-stream")
+                .about(
+                    "Adds the external reward stream to provided StakingData Account. \
+                     Reward tokens are emitted per second and distributed pro rata to the user shares. \
+                     The client keypair is the sponsor who funds the reward vault. \
+                     Pubkey for RewardStream account is generated randomly."
+                )
+                .arg(
+                    Arg::with_name("reward_mint")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The reward token Mint Pubkey."),
+                )
+                .arg(
+                    Arg::with_name("emission_per_second")
+                        .value_name("f64")
+                        .validator(is_valid_ui_amount)
+                        .required(true)
+                        .takes_value(true)
+                        .help("Amount of reward tokens distributed every second (float value)."),
+                )
+                .arg(
+                    Arg::with_name("end_timestamp")
+                        .value_name("UNIXTIMESTAMP")
+                        .validator(is_valid_int)
+                        .required(true)
+                        .takes_value(true)
+                        .help("End of the emissions."),
+                )
+                .arg(
+                    Arg::with_name("staking_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The StakingData Account Pubkey."),
+                )
+                .arg(
+                    Arg::with_name("start_timestamp")
+                        .long("start-timestamp")
+                        .short("t")
+                        .value_name("UNIXTIMESTAMP")
+                        .validator(is_valid_int)
+                        .takes_value(true)
+                        .help(
+                            "Start of the emissions. \
+                            Defaults to the current timestamp."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("staking_data_owner")
+                        .long("staking-data-owner")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the staking data owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("remove-reward-stream")
+                .about(
+                    "Removes the finished reward stream from its StakingData Account. \
+                     Reward tokens not owed to the users are returned to the sponsor."
+                )
+                .arg(
+                    Arg::with_name("reward_stream_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The RewardStream Account pubkey."),
+                )
+                .arg(
+                    Arg::with_name("staking_data_owner")
+                        .long("staking-data-owner")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the staking data owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("init-user-reward")
+                .about(
+                    "Initializes UserReward Account of the user for provided reward stream. \
+                     It is required for every reward stream before the user stake changes."
+                )
+                .arg(
+                    Arg::with_name("reward_stream_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The RewardStream Account pubkey."),
+                )
+                .arg(
+                    Arg::with_name("staking_user_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The UserStakingData Account pubkey."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("claim-rewards")
+                .about(
+                    "Transfers the rewards of provided reward stream earned by the user. \
+                     User reward wallet is created if needed."
+                )
+                .arg(
+                    Arg::with_name("reward_stream_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The RewardStream Account pubkey."),
+                )
+                .arg(
+                    Arg::with_name("staking_user_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The UserStakingData Account pubkey."),
+                )
+                .arg(
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the user token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("close-user-reward")
+                .about(
+                    "Closes UserReward Account of the removed reward stream and returns its rent. \
+                     Rewards have to be claimed first."
+                )
+                .arg(
+                    Arg::with_name("reward_stream_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The RewardStream Account pubkey."),
+                )
+                .arg(
+                    Arg::with_name("staking_user_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .required(true)
+                        .takes_value(true)
+                        .help("The UserStakingData Account pubkey."),
+                )
+                .arg(
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the user token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("accrue-interest")
+                .about("Accrues interest to date. It is also accrued by most operations.")
+                .arg(
+                    Arg::with_name("staking_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The StakingData Account pubkey."),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("total-staked-for")
+                .about(
+                    "Viewer for the total stake of the provided staking user data address, including tokens not yet minted, \
+                     but virtually accrued to date. Doesn't change state."
+                )
+                .arg(
+                    Arg::with_name("staking_user_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The StakingUserData Account pubkey."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("total-staked")
+                .about(
+                    "Viewer for the total amount of all stakes, including interest to date even if it's still virtual."
+                )
+                .arg(
+                    Arg::with_name("staking_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The StakingData Account pubkey."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("total-unminted-interest")
+                .about(
+                    "Viewer for the virtual interest to date that hasn't been minted yet."
+                )
+                .arg(
+                    Arg::with_name("staking_data_account")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The StakingData Account pubkey."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("initialize-governance")
+                .about(
+                    "Initialize Governance account. You need to do this before using the gov instructions. \
+                    The StakingData pubkey must be provided. \
+                    Pubkey for gov account is generated randomly."
+                )
+                .arg(
+                    Arg::with_name("staking_data_key")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The StakingData pubkey."),
+                )
+                .arg(
+                    Arg::with_name("approval_fixed_period_in_seconds")
+                        .value_name("i64_SECONDS")
+                        .validator(is_valid_int)
+                        .takes_value(true)
+                        .help("The approval period in seconds for every proposal."),
+                )
+                .arg(
+                    Arg::with_name("min_approval_percent")
+                        .value_name("u8_PERCENT")
+                        .validator(is_valid_uint)
+                        .takes_value(true)
+                        .help("The minimum approval percent for every proposal."),
+                )
+                .arg(
+                    Arg::with_name("min_stake_to_propose")
+                        .value_name("f64_AMOUNT")
+                        .validator(is_valid_ui_amount)
+                        .takes_value(true)
+                        .help("The minimum stake amount to make new proposal."),
+                )
+                .arg(
+                    Arg::with_name("min_vote_participation_percent")
+                        .value_name("u8_PERCENT")
+                        .validator(is_valid_uint)
+                        .takes_value(true)
+                        .help("The minimum vote participation percent for every proposal."),
+                )
+                .arg(
+                    Arg::with_name("payment_period_sec")
+                        .value_name("i64")
+                        .validator(is_valid_int)
+                        .takes_value(true)
+                        .help("Payment period duration in seconds."),
+                )
+                .arg(
+                    Arg::with_name("execution_delay_seconds")
+                        .value_name("i64_SECONDS")
+                        .validator(is_valid_int)
+                        .takes_value(true)
+                        .help("The timelock between proposal approval and execution in seconds."),
+                )
+                .arg(
+                    Arg::with_name("sponsor")
+                        .long("sponsor")
+                        .short("s")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .multiple(true)
+                        .help(
+                            "The list of sponsors. Specify every sponsor like \
+                            -s <SPONSOR_1_PUBKEY> -s <SPONSOR_2_PUBKEY> ..."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("initialize-treasury-stats")
+                .about("Initialize Governance treasury stats account for specific SPL Token.")
+                .arg(
+                    Arg::with_name("governance")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The Governance pubkey."),
+                )
+                .arg(
+                    Arg::with_name("mint")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The SPL Token mint pubkey."),
+                )
+                .arg(
+                    Arg::with_name("max_proposal_payment_percent")
+                        .value_name("u8_PERCENT")
+                        .validator(is_valid_percentage)
+                        .takes_value(true)
+                        .help("The maximum payment amount (percent of highest balance) for every proposal."),
+                )
+                .arg(
+                    Arg::with_name("payment_amount_in_period_limit_percent")
+                        .value_name("u8_PERCENT")
+                        .validator(is_valid_percentage)
+                        .takes_value(true)
+                        .help("The hard cap for proposal payment amount in period (percent of highest balance)."),
+                )
+        )
+        .subcommand(SubCommand::with_name("governance-info")
+                .about("Information about provided governance.")
+                .arg(
+                    Arg::with_name("governance")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of governance."),
+                )
+        )
+        .subcommand(SubCommand::with_name("treasury-stats-info")
+                .about("Information about provided governance treasury.")
+                .arg(
+                    Arg::with_name("governance")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of governance."),
+                )
+                .arg(
+                    Arg::with_name("treasury")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of treasury."),
+                )
+        )
+        .subcommand(SubCommand::with_name("add-sponsor")
+                .about(
+                    "Add the new sponsor Pubkey into sponsors list. \
+                    Required the 60% signatures of the current sponsors list."
+                )
+                .arg(
+                    Arg::with_name("governance")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of governance."),
+                )
+                .arg(
+                    Arg::with_name("sponsor")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of new sponsor."),
+                )
+                .arg(
+                    Arg::with_name("signers")
+                        .long("signer")
+                        .short("s")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .multiple(true)
+                        .help(
+                            "The list of signers. Specify every signer like \
+                            -s <SIGNER_1_KEYPAIR> -s <SIGNER_2_KEYPAIR> ..."
+                        ),
+                ),
+        )
+        .subcommand(SubCommand::with_name("remove-sponsor")
+                .about(
+                    "Remove the sponsor Pubkey from sponsors list. \
+                    Required the 60% signatures of the current sponsors list."
+                )
+                .arg(
+                    Arg::with_name("governance")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of governance."),
+                )
+                .arg(
+                    Arg::with_name("sponsor")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of sponsor to be removed."),
+                )
+                .arg(
+                    Arg::with_name("signers")
+                        .long("signer")
+                        .short("s")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .multiple(true)
+                        .help(
+                            "The list of signers. Specify every signer like \
+                            -s <SIGNER_1_KEYPAIR> -s <SIGNER_2_KEYPAIR> ..."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("treasury-balance")
+                .about("Treasury balance for provided treasury pubkey.")
+                .arg(
+                    Arg::with_name("treasury")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of governance treasury."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("fund-treasury")
+                .about("Funding of the governance treasury. Treasury must be initialized first.")
+                .arg(
+                    Arg::with_name("amount")
+                        .value_name("TOKEN_AMOUNT")
+                        .takes_value(true)
+                        .validator(is_valid_ui_amount)
+                        .help("Amount of tokens to fund."),
+                )
+                .arg(
+                    Arg::with_name("governance")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of initialized Governance."),
+                )
+                .arg(
+                    Arg::with_name("treasury")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of governance treasury."),
+                )
+                .arg(
+                    Arg::with_name("user_token_wallet")
+                        .long("from")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help(
+                            "Specify the user token wallet pubkey. \
+                             Defaults to the token associated address."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the user token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(SubCommand::with_name("proposal-info")
+                .about("Information about provided proposal.")
+                .arg(
+                    Arg::with_name("proposal")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of proposal."),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("make-proposal")
+                .about(
+                    "Create the new proposal with provided recipient address, payment amount and description IPFS Hash. \
+                    Governance pubkey and StakingUserData also must be provided. \
+                    Proposal maker must has staked tokens."
+                )
+                .arg(
+                    Arg::with_name("governance")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of initialized Governance."),
+                )
+                .arg(
+                    Arg::with_name("staking_user_data")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of StakingUserData."),
+                )
+                .arg(
+                    Arg::with_name("payment_amount")
+                        .value_name("TOKEN_AMOUNT")
+                        .takes_value(true)
+                        .validator(is_valid_ui_amount)
+                        .help("Amount of payment tokens."),
+                )
+                .arg(
+                    Arg::with_name("recipient")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of the recipient."),
+                )
+                .arg(
+                    Arg::with_name("ipfs_hash")
+                        .value_name("HAS_STRING")
+                        .takes_value(true)
+                        .validator(is_valid_hash_len)
+                        .help("The IPFS hash of proposal description."),
+                )
+                .arg(
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the user token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("make-executable-proposal")
+                .about(
+                    "Create the new proposal which executes the instructions from JSON file \
+                    after approval. Instructions are signed by the governance treasury owner. \
+                    Proposal maker must has staked tokens."
+                )
+                .arg(
+                    Arg::with_name("governance")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of initialized Governance."),
+                )
+                .arg(
+                    Arg::with_name("staking_user_data")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of StakingUserData."),
+                )
+                .arg(
+                    Arg::with_name("treasury")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of governance treasury."),
+                )
+                .arg(
+                    Arg::with_name("ipfs_hash")
+                        .value_name("HAS_STRING")
+                        .takes_value(true)
+                        .validator(is_valid_hash_len)
+                        .help("The IPFS hash of proposal description."),
+                )
+                .arg(
+                    Arg::with_name("instructions")
+                        .value_name("JSON_FILE")
+                        .takes_value(true)
+                        .help(
+                            "The path to JSON file with list of instructions: \
+                            [{\"program_id\", \"accounts\": [{\"pubkey\", \"is_signer\", \"is_writable\"}], \"data\": [u8]}]"
+                        ),
+                )
+                .arg(
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the user token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("propose-config-change")
+                .about(
+                    "Create the new proposal which changes the governance parameters after approval. \
+                    Proposal maker must has staked tokens."
+                )
+                .arg(
+                    Arg::with_name("governance")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of initialized Governance."),
+                )
+                .arg(
+                    Arg::with_name("staking_user_data")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of StakingUserData."),
+                )
+                .arg(
+                    Arg::with_name("treasury")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of governance treasury."),
+                )
+                .arg(
+                    Arg::with_name("ipfs_hash")
+                        .value_name("HAS_STRING")
+                        .takes_value(true)
+                        .validator(is_valid_hash_len)
+                        .help("The IPFS hash of proposal description."),
+                )
+                .arg(
+                    Arg::with_name("approval_fixed_period_in_seconds")
+                        .value_name("SECONDS")
+                        .takes_value(true)
+                        .validator(is_valid_int)
+                        .help("New approval fixed period in seconds."),
+                )
+                .arg(
+                    Arg::with_name("min_approval_percent")
+                        .value_name("PERCENT")
+                        .takes_value(true)
+                        .validator(is_valid_uint)
+                        .help("New minimum percent of pros vote to approve proposal."),
+                )
+                .arg(
+                    Arg::with_name("min_stake_to_propose")
+                        .value_name("TOKEN_AMOUNT")
+                        .takes_value(true)
+                        .validator(is_valid_ui_amount)
+                        .help("New minimum amount of staked tokens to make proposal."),
+                )
+                .arg(
+                    Arg::with_name("min_vote_participation_percent")
+                        .value_name("PERCENT")
+                        .takes_value(true)
+                        .validator(is_valid_uint)
+                        .help("New minimum percent of participation to approve proposal."),
+                )
+                .arg(
+                    Arg::with_name("payment_period_sec")
+                        .value_name("SECONDS")
+                        .takes_value(true)
+                        .validator(is_valid_int)
+                        .help("New payment period in seconds."),
+                )
+                .arg(
+                    Arg::with_name("execution_delay_seconds")
+                        .value_name("SECONDS")
+                        .takes_value(true)
+                        .validator(is_valid_int)
+                        .help("New timelock between proposal approval and execution in seconds."),
+                )
+                .arg(
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the user token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("approve-proposal")
+                .about(
+                    "Vote for existent proposal. \
+                    Proposal pubkey and StakingUserData must be provided. \
+                    User must has staked tokens to vote."
+                )
+                .arg(
+                    Arg::with_name("staking_user_data")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of StakingUserData."),
+                )
+                .arg(
+                    Arg::with_name("vote")
+                        .value_name("BOOL")
+                        .validator(is_valid_bool)
+                        .takes_value(true)
+                        .help("true or false for pros or cons."),
+                )
+                .arg(
+                    Arg::with_name("proposal")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of proposal to vote."),
+                )
+                .arg(
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the user token wallet owner keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("finalize-proposal")
+                .about(
+                    "Trying to finalize the proposal. \
+                    No authority is required to invoke this instruction."
+                )
+                .arg(
+                    Arg::with_name("proposal")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of proposal to finalize."),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("execute-proposal")
+                .about(
+                    "Execute the queued payment or executable proposal after the execution delay. \
+                    No authority is required to invoke this instruction."
+                )
+                .arg(
+                    Arg::with_name("proposal")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of proposal to execute."),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("apply-config-proposal")
+                .about(
+                    "Apply the governance parameters of approved config change proposal. \
+                    No authority is required to invoke this instruction."
+                )
+                .arg(
+                    Arg::with_name("proposal")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of proposal to apply."),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("cancel-queued-proposal")
+                .about(
+                    "Cancel the queued proposal before its execution. \
+                    Required the 60% signatures of the current sponsors list."
+                )
+                .arg(
+                    Arg::with_name("proposal")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of proposal to cancel."),
+                )
+                .arg(
+                    Arg::with_name("signers")
+                        .long("signer")
+                        .short("s")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .multiple(true)
+                        .help(
+                            "The list of signers. Specify every signer like \
+                            -s <SIGNER_1_KEYPAIR> -s <SIGNER_2_KEYPAIR> ..."
+                        ),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("cancel-proposal")
+                .about(
+                    "Cancel the proposal by its author. \
+                    Proposal can be cancelled only before any votes."
+                )
+                .arg(
+                    Arg::with_name("proposal")
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("The pubkey of proposal to cancel."),
+                )
+                .arg(
+                    Arg::with_name("user_authority")
+                        .long("user-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the proposal author keypair. \
+                             Defaults to the client keypair address."
+                        ),
+                ),
+        )
+}
+
+fn main() {
+    let app_matches =
+        get_clap_app(crate_name!(), crate_description!(), crate_version!()).get_matches();
+
+    let config = if let Some(config_path) = app_matches.value_of("config") {
+        config::CLIConfig::load(config_path).expect("Config loading error")
+    } else {
+        config::CLIConfig::default()
+    };
+
+    let cluster = Cluster::from_str(&config.network).expect("Cluster error");
+    let payer = read_keypair_file(&config.fee_payer_path).expect("Reading payer keypair error");
+    println!("RPC Client URL: {}", cluster.url());
+
+    // Convert program ids into Pubkey
+    let metadata_id = Pubkey::from_str(config.program_ids.metadata_id.as_ref())
+        .expect("Metadata PK convert error");
+    let staking_id =
+        Pubkey::from_str(config.program_ids.staking_id.as_ref()).expect("Staking PK convert error");
+    let governance_id = Pubkey::from_str(config.program_ids.governance_id.as_ref())
+        .expect("Governance PK convert error");
+
+    let (sub_command, sub_matches) = app_matches.subcommand();
+    match (sub_command, sub_matches) {
+        // TokenMetadata commands
+        ("create-token", Some(arg_matches)) => {
+            let decimals = value_t!(arg_matches, "decimals", u8).unwrap_or(9);
+            let mint_authority = read_keypair_file(
+                arg_matches
+                    .value_of("mint_authority")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read mint authority keypair");
+
+            println!("Creating new token with");
+            println!("Decimals: {}", decimals);
+            println!("Mint authority: {}", mint_authority.pubkey());
+
+            let client =
+                Client::new_with_options(cluster, mint_authority, CommitmentConfig::processed());
+            commands::metadata::create_token(&client, &metadata_id, decimals)
+                .expect("Creating token error");
+            println!("Creating successfully completed");
+        }
+
+        ("initialize-metadata", Some(arg_matches)) => {
+            let name = value_t_or_exit!(arg_matches, "name", String);
+            let symbol = value_t_or_exit!(arg_matches, "symbol", String);
+            let mint_key = value_t_or_exit!(arg_matches, "mint", Pubkey);
+
+            let mint_authority = read_keypair_file(
+                arg_matches
+                    .value_of("mint_authority")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read mint authority keypair");
+
+            print!("Creating new token metadata with ");
+            println!("name: {} and symbol: {}", name, symbol);
+            println!("For Mint: {}", mint_key);
+
+            let client =
+                Client::new_with_options(cluster, mint_authority, CommitmentConfig::processed());
+            commands::metadata::initialize_metadata(
+                &client,
+                &metadata_id,
+                &mint_key,
+                &name,
+                &symbol,
+            )
+            .expect("Initialize metadata error");
+            println!("Metadata account successfully created");
+        }
+
+        ("initialize-token-wallet", Some(arg_matches)) => {
+            let token_key = value_t_or_exit!(arg_matches, "token_account", Pubkey);
+            let owner = value_t_or_exit!(arg_matches, "owner", Pubkey);
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::metadata::initialize_token_wallet(&client, &metadata_id, &token_key, &owner)
+                .expect("Initialize token wallet error");
+        }
+
+        ("token-wallet-balance", Some(arg_matches)) => {
+            let token_wallet = value_t_or_exit!(arg_matches, "token_wallet", Pubkey);
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            let balance =
+                commands::metadata::token_wallet_balance(&client, &metadata_id, &token_wallet)
+                    .expect("Getting balance error");
+            println!("Balance: {}", balance);
+        }
+
+        ("mint-to", Some(arg_matches)) => {
+            let token_key = value_t_or_exit!(arg_matches, "token_account", Pubkey);
+            let amount = value_t_or_exit!(arg_matches, "amount", f64);
+            let destination = value_t_or_exit!(arg_matches, "destination", Pubkey);
+
+            let mint_authority = read_keypair_file(
+                arg_matches
+                    .value_of("mint_authority")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read mint authority keypair");
+
+            println!("Mint {} tokens ({}) to {}", amount, token_key, destination);
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::metadata::mint_to(
+                &client,
+                &metadata_id,
+                &token_key,
+                &destination,
+                &mint_authority,
+                amount,
+            )
+            .expect("Mint to error");
+
+            println!("Tokens successfully minted");
+        }
+
+        ("transfer", Some(arg_matches)) => {
+            let token_key = value_t_or_exit!(arg_matches, "token_account", Pubkey);
+            let amount = value_t_or_exit!(arg_matches, "amount", f64);
+            let source = value_t_or_exit!(arg_matches, "source", Pubkey);
+            let destination = value_t_or_exit!(arg_matches, "destination", Pubkey);
+
+            let payer = read_keypair_file(
+                arg_matches
+                    .value_of("transfer_authority")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read owner keypair");
+
+            println!(
+                "Transfer {} tokens ({}) to {}",
+                amount, token_key, destination
+            );
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::metadata::transfer(
+                &client,
+                &metadata_id,
+                &token_key,
+                &source,
+                &destination,
+                amount,
+            )
+            .expect("Transfer error");
+
+            println!("Tokens successfully transferred");
+        }
+
+        ("metadata-info", Some(arg_matches)) => {
+            let metadata_key = value_t_or_exit!(arg_matches, "metadata_account", Pubkey);
+            println!("Information of MetadataAccount: {}", metadata_key);
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::metadata::metadata_info(&client, &metadata_id, &metadata_key)
+                .expect("Getting info error");
+        }
+
+        // Staking commands
+        ("initialize-staking", Some(arg_matches)) => {
+            let starting_interest_rate =
+                value_t_or_exit!(arg_matches, "starting_interest_rate", u64);
+            let max_interest_rate = value_t_or_exit!(arg_matches, "max_interest_rate", u64);
+            let token_metadata = value_t_or_exit!(arg_matches, "token_metadata", Pubkey);
+            let cap = value_t_or_exit!(arg_matches, "cap", f64);
+            let starting_timestamp = match value_t!(arg_matches, "starting_timestamp", i64) {
+                Ok(val) => Some(val),
+                Err(_) => None,
+            };
+
+            let payer = read_keypair_file(
+                arg_matches
+                    .value_of("staking_data_owner")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read owner keypair");
+
+            let mint_authority = read_keypair_file(
+                arg_matches
+                    .value_of("mint_authority")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read mint authority keypair");
+
+            let staking_data = Keypair::new();
+            print!("Initializing new StakingData Account: ");
+            println!("{}", staking_data.pubkey());
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::initialize_staking(
+                &client,
+                &staking_id,
+                &staking_data,
+                &token_metadata,
+                starting_timestamp,
+                max_interest_rate,
+                starting_interest_rate,
+                cap,
+                &mint_authority,
+                arg_matches.is_present("reward_reserve"),
+            )
+            .expect("Initialize staking error");
+            println!("StakingData account successfully created");
+        }
+
+        ("staking-info", Some(arg_matches)) => {
+            let staking_key = value_t_or_exit!(arg_matches, "staking_account", Pubkey);
+            println!("Information of StakingData account: {}", staking_key);
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::staking_info(&client, &staking_id, &staking_key)
+                .expect("Getting info error");
+        }
+
+        ("staking-user-info", Some(arg_matches)) => {
+            let staking_user_key = value_t_or_exit!(arg_matches, "staking_user_account", Pubkey);
+            print!("Information of StakingUserData account: ");
+            println!("{}", staking_user_key);
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::staking_user_info(&client, &staking_id, &staking_user_key)
+                .expect("Getting info error");
+        }
+
+        ("set-interest-rate", Some(arg_matches)) => {
+            let new_interest_rate = value_t_or_exit!(arg_matches, "new_interest_rate", u64);
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+
+            let staking_data_owner = read_keypair_file(
+                arg_matches
+                    .value_of("staking_data_owner")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read owner keypair");
+
+            println!("Updating staking data: {}", staking_data_key);
+            println!("New interest rate: {}", new_interest_rate);
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::set_interest_rate(
+                &client,
+                &staking_id,
+                new_interest_rate,
+                &staking_data_key,
+                &staking_data_owner,
+            )
+            .expect("Set interest error");
+
+            println!("Updating successfully completed");
+        }
+
+        ("set-rate-schedule", Some(arg_matches)) => {
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+            let rate_schedule: Vec<staking::RatePoint> = arg_matches
+                .values_of("rate_point")
+                .map(|values| values.collect())
+                .unwrap_or_else(Vec::new)
+                .iter()
+                .map(|value| {
+                    let parts: Vec<&str> = value.split(':').collect();
+                    staking::RatePoint {
+                        effective_timestamp: parts[0].parse().unwrap(),
+                        daily_rate: parts[1].parse().unwrap(),
+                    }
+                })
+                .collect();
+
+            let staking_data_owner = read_keypair_file(
+                arg_matches
+                    .value_of("staking_data_owner")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read owner keypair");
+
+            println!("Updating staking data: {}", staking_data_key);
+            println!("New rate schedule points count: {}", rate_schedule.len());
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::set_rate_schedule(
+                &client,
+                &staking_id,
+                rate_schedule,
+                &staking_data_key,
+                &staking_data_owner,
+            )
+            .expect("Set rate schedule error");
+
+            println!("Updating successfully completed");
+        }
+
+        ("set-rate-model", Some(arg_matches)) => {
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+            let rate_model = if arg_matches.is_present("fixed_rate") {
+                None
+            } else {
+                Some(staking::RateModel {
+                    base_rate: value_t_or_exit!(arg_matches, "base_rate", u64),
+                    slope_low: value_t_or_exit!(arg_matches, "slope_low", u64),
+                    slope_high: value_t_or_exit!(arg_matches, "slope_high", u64),
+                    kink: value_t_or_exit!(arg_matches, "kink", u64),
+                })
+            };
+
+            let staking_data_owner = read_keypair_file(
+                arg_matches
+                    .value_of("staking_data_owner")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read owner keypair");
+
+            println!("Updating staking data: {}", staking_data_key);
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::set_rate_model(
+                &client,
+                &staking_id,
+                rate_model,
+                &staking_data_key,
+                &staking_data_owner,
+            )
+            .expect("Set rate model error");
+
+            println!("Updating successfully completed");
+        }
+
+        ("set-reward-mode", Some(arg_matches)) => {
+            let reward_mode = match arg_matches.value_of("mode").unwrap() {
+                "reserve" => staking::RewardMode::Reserve,
+                _ => staking::RewardMode::Mint,
+            };
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+
+            let staking_data_owner = read_keypair_file(
+                arg_matches
+                    .value_of("staking_data_owner")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read owner keypair");
+
+            println!("Updating staking data: {}", staking_data_key);
+            println!("New reward mode: {:?}", reward_mode);
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::set_reward_mode(
+                &client,
+                &staking_id,
+                reward_mode,
+                &staking_data_key,
+                &staking_data_owner,
+            )
+            .expect("Set reward mode error");
+
+            println!("Updating successfully completed");
+        }
+        ("fund-reward-reserve", Some(arg_matches)) => {
+            let ui_amount = value_t_or_exit!(arg_matches, "ui_amount", f64);
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+
+            let funder = read_keypair_file(
+                arg_matches
+                    .value_of("funder")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read funder keypair");
+
+            println!("Funding reward reserve with {} tokens", ui_amount);
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::fund_reward_reserve(
+                &client,
+                &staking_id,
+                ui_amount,
+                &staking_data_key,
+                &funder,
+            )
+            .expect("Fund reward reserve error");
+
+            println!("Funding successfully completed");
+        }
+        ("withdraw-reward-reserve", Some(arg_matches)) => {
+            let ui_amount = value_t_or_exit!(arg_matches, "ui_amount", f64);
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+
+            let staking_data_owner = read_keypair_file(
+                arg_matches
+                    .value_of("staking_data_owner")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read owner keypair");
+
+            println!("Withdrawing {} tokens from reward reserve", ui_amount);
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::withdraw_reward_reserve(
+                &client,
+                &staking_id,
+                ui_amount,
+                &staking_data_key,
+                &staking_data_owner,
+            )
+            .expect("Withdraw reward reserve error");
+
+            println!("Withdrawal successfully completed");
+        }
+        ("set-lock-tiers", Some(arg_matches)) => {
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+            let lock_tiers: Vec<staking::LockTier> = arg_matches
+                .values_of("lock_tier")
+                .map(|values| values.collect())
+                .unwrap_or_else(Vec::new)
+                .iter()
+                .map(|value| {
+                    let parts: Vec<&str> = value.split(':').collect();
+                    staking::LockTier {
+                        duration: parts[0].parse().unwrap(),
+                        boost: parts[1].parse().unwrap(),
+                    }
+                })
+                .collect();
+
+            let staking_data_owner = read_keypair_file(
+                arg_matches
+                    .value_of("staking_data_owner")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read owner keypair");
+
+            println!("Updating staking data: {}", staking_data_key);
+            println!("New lock tiers count: {}", lock_tiers.len());
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::set_lock_tiers(
+                &client,
+                &staking_id,
+                lock_tiers,
+                &staking_data_key,
+                &staking_data_owner,
+            )
+            .expect("Set lock tiers error");
+
+            println!("Updating successfully completed");
+        }
+
+        ("set-authorized-lockers", Some(arg_matches)) => {
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+            let authorized_lockers: Vec<Pubkey> = arg_matches
+                .values_of("locker")
+                .map(|values| values.collect())
+                .unwrap_or_else(Vec::new)
+                .iter()
+                .map(|value| Pubkey::from_str(value).unwrap())
+                .collect();
+
+            let staking_data_owner = read_keypair_file(
+                arg_matches
+                    .value_of("staking_data_owner")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read owner keypair");
+
+            println!("Updating staking data: {}", staking_data_key);
+            println!("New authorized lockers count: {}", authorized_lockers.len());
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::set_authorized_lockers(
+                &client,
+                &staking_id,
+                authorized_lockers,
+                &staking_data_key,
+                &staking_data_owner,
+            )
+            .expect("Set authorized lockers error");
+
+            println!("Updating successfully completed");
+        }
+
+        ("set-cooldown", Some(arg_matches)) => {
+            let cooldown_seconds = value_t_or_exit!(arg_matches, "cooldown_seconds", i64);
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+
+            let staking_data_owner = read_keypair_file(
+                arg_matches
+                    .value_of("staking_data_owner")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read owner keypair");
+
+            println!("Updating staking data: {}", staking_data_key);
+            println!("New cooldown: {} sec", cooldown_seconds);
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::set_cooldown(
+                &client,
+                &staking_id,
+                cooldown_seconds,
+                &staking_data_key,
+                &staking_data_owner,
+            )
+            .expect("Set cooldown error");
+
+            println!("Updating successfully completed");
+        }
+
+        ("enable-receipts", Some(arg_matches)) => {
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+            let name = value_t_or_exit!(arg_matches, "name", String);
+            let symbol = value_t_or_exit!(arg_matches, "symbol", String);
+
+            let staking_data_owner = read_keypair_file(
+                arg_matches
+                    .value_of("staking_data_owner")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read owner keypair");
+
+            println!("Updating staking data: {}", staking_data_key);
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::enable_receipts(
+                &client,
+                &staking_id,
+                &metadata_id,
+                &staking_data_key,
+                &name,
+                &symbol,
+                &staking_data_owner,
+            )
+            .expect("Enable receipts error");
+
+            println!("Updating successfully completed");
+        }
+
+        ("mint-receipts", Some(arg_matches)) => {
+            let shares = value_t_or_exit!(arg_matches, "shares", u64);
+            let staking_user_data_key =
+                value_t_or_exit!(arg_matches, "staking_user_data_account", Pubkey);
+            let user_token_wallet_owner = read_keypair_file(
+                arg_matches
+                    .value_of("user_authority")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read user authority keypair");
+
+            println!(
+                "Mint receipts for {} shares of {}",
+                shares, staking_user_data_key
+            );
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::mint_receipts(
+                &client,
+                &staking_id,
+                shares,
+                &staking_user_data_key,
+                &user_token_wallet_owner,
+            )
+            .expect("Mint receipts error");
+
+            println!("Mint receipts successfully completed");
+        }
+
+        ("redeem-receipts", Some(arg_matches)) => {
+            let shares = value_t_or_exit!(arg_matches, "shares", u64);
+            let staking_user_data_key =
+                value_t_or_exit!(arg_matches, "staking_user_data_account", Pubkey);
+            let user_token_wallet_owner = read_keypair_file(
+                arg_matches
+                    .value_of("user_authority")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read user authority keypair");
+
+            println!(
+                "Redeem receipts for {} shares of {}",
+                shares, staking_user_data_key
+            );
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::redeem_receipts(
+                &client,
+                &staking_id,
+                shares,
+                &staking_user_data_key,
+                &user_token_wallet_owner,
+            )
+            .expect("Redeem receipts error");
+
+            println!("Redeem receipts successfully completed");
+        }
+
+        ("set-deposit-limits", Some(arg_matches)) => {
+            let allow_list_enabled = value_t_or_exit!(arg_matches, "allow_list_enabled", bool);
+            let user_deposit_cap = value_t_or_exit!(arg_matches, "user_deposit_cap", f64);
+            let total_deposit_cap = value_t_or_exit!(arg_matches, "total_deposit_cap", f64);
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+
+            let staking_data_owner = read_keypair_file(
+                arg_matches
+                    .value_of("staking_data_owner")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read owner keypair");
+
+            println!("Updating staking data: {}", staking_data_key);
+            println!("Allow-list enabled: {}", allow_list_enabled);
+            println!("New user deposit cap: {}", user_deposit_cap);
+            println!("New total deposit cap: {}", total_deposit_cap);
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::set_deposit_limits(
+                &client,
+                &staking_id,
+                allow_list_enabled,
+                user_deposit_cap,
+                total_deposit_cap,
+                &staking_data_key,
+                &staking_data_owner,
+            )
+            .expect("Set deposit limits error");
+
+            println!("Updating successfully completed");
+        }
+
+        ("add-to-allow-list", Some(arg_matches)) => {
+            let wallet = value_t_or_exit!(arg_matches, "wallet", Pubkey);
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+
+            let staking_data_owner = read_keypair_file(
+                arg_matches
+                    .value_of("staking_data_owner")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read owner keypair");
+
+            println!(
+                "Adding {} to allow-list of staking data: {}",
+                wallet, staking_data_key
+            );
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::add_to_allow_list(
+                &client,
+                &staking_id,
+                &wallet,
+                &staking_data_key,
+                &staking_data_owner,
+            )
+            .expect("Add to allow-list error");
+
+            println!("Updating successfully completed");
+        }
+
+        ("remove-from-allow-list", Some(arg_matches)) => {
+            let wallet = value_t_or_exit!(arg_matches, "wallet", Pubkey);
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+
+            let staking_data_owner = read_keypair_file(
+                arg_matches
+                    .value_of("staking_data_owner")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read owner keypair");
+
+            println!(
+                "Removing {} from allow-list of staking data: {}",
+                wallet, staking_data_key
+            );
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::remove_from_allow_list(
+                &client,
+                &staking_id,
+                &wallet,
+                &staking_data_key,
+                &staking_data_owner,
+            )
+            .expect("Remove from allow-list error");
+
+            println!("Updating successfully completed");
+        }
+
+        ("set-fees", Some(arg_matches)) => {
+            let deposit_fee_bps = value_t_or_exit!(arg_matches, "deposit_fee_bps", u64);
+            let withdraw_fee_bps = value_t_or_exit!(arg_matches, "withdraw_fee_bps", u64);
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+            let fee_collector = value_t_or_exit!(arg_matches, "fee_collector", Pubkey);
+
+            let staking_data_owner = read_keypair_file(
+                arg_matches
+                    .value_of("staking_data_owner")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read owner keypair");
+
+            println!("Updating staking data: {}", staking_data_key);
+            println!("New deposit fee: {} bps", deposit_fee_bps);
+            println!("New withdraw fee: {} bps", withdraw_fee_bps);
+            println!("New fee collector: {}", fee_collector);
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::set_fees(
+                &client,
+                &staking_id,
+                deposit_fee_bps,
+                withdraw_fee_bps,
+                &staking_data_key,
+                &fee_collector,
+                &staking_data_owner,
+            )
+            .expect("Set fees error");
+
+            println!("Updating successfully completed");
+        }
+
+        ("set-early-unstake-penalty", Some(arg_matches)) => {
+            let penalty = value_t_or_exit!(arg_matches, "penalty", u64);
+            let window_seconds = value_t_or_exit!(arg_matches, "window_seconds", i64);
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+            let penalty_wallet = match value_t!(arg_matches, "penalty_wallet", Pubkey) {
+                Ok(key) => Some(key),
+                Err(_) => None,
+            };
+
+            let staking_data_owner = read_keypair_file(
+                arg_matches
+                    .value_of("staking_data_owner")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read owner keypair");
+
+            println!("Updating staking data: {}", staking_data_key);
+            println!("New early unstake penalty: {} bps", penalty);
+            println!("New penalty window: {} sec", window_seconds);
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::set_early_unstake_penalty(
+                &client,
+                &staking_id,
+                penalty,
+                window_seconds,
+                &staking_data_key,
+                penalty_wallet,
+                &staking_data_owner,
+            )
+            .expect("Set early unstake penalty error");
+
+            println!("Updating successfully completed");
+        }
+
+        ("set-guardian", Some(arg_matches)) => {
+            let guardian = value_t_or_exit!(arg_matches, "guardian", Pubkey);
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+
+            let staking_data_owner = read_keypair_file(
+                arg_matches
+                    .value_of("staking_data_owner")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read owner keypair");
+
+            println!("Updating staking data: {}", staking_data_key);
+            println!("New guardian: {}", guardian);
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::set_guardian(
+                &client,
+                &staking_id,
+                &guardian,
+                &staking_data_key,
+                &staking_data_owner,
+            )
+            .expect("Set guardian error");
+
+            println!("Updating successfully completed");
+        }
+
+        ("set-slasher", Some(arg_matches)) => {
+            let slasher = value_t_or_exit!(arg_matches, "slasher", Pubkey);
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+
+            let staking_data_owner = read_keypair_file(
+                arg_matches
+                    .value_of("staking_data_owner")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read owner keypair");
+
+            println!("Updating staking data: {}", staking_data_key);
+            println!("New slasher: {}", slasher);
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::set_slasher(
+                &client,
+                &staking_id,
+                &slasher,
+                &staking_data_key,
+                &staking_data_owner,
+            )
+            .expect("Set slasher error");
+
+            println!("Updating successfully completed");
+        }
+
+        ("slash", Some(arg_matches)) => {
+            let slash_bps = value_t_or_exit!(arg_matches, "slash_bps", u64);
+            let reason = value_t_or_exit!(arg_matches, "reason", u16);
+            let staking_user_data_key = value_t_or_exit!(arg_matches, "staking_user_data", Pubkey);
+            let slash_wallet = value_t!(arg_matches, "slash_wallet", Pubkey).ok();
+
+            let slasher = read_keypair_file(
+                arg_matches
+                    .value_of("slasher")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read slasher keypair");
+
+            println!("Slashing staking user data: {}", staking_user_data_key);
+            println!("Slash: {} bps, reason: {}", slash_bps, reason);
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::slash(
+                &client,
+                &staking_id,
+                slash_bps,
+                reason,
+                &staking_user_data_key,
+                slash_wallet,
+                &slasher,
+            )
+            .expect("Slash error");
+
+            println!("Slashing successfully completed");
+        }
+
+        ("pause", Some(arg_matches)) => {
+            let level = match arg_matches.value_of("level").unwrap() {
+                "full" => staking::PauseLevel::Full,
+                _ => staking::PauseLevel::UnstakeOnly,
+            };
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+
+            let authority = read_keypair_file(
+                arg_matches
+                    .value_of("authority")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read authority keypair");
+
+            println!("Pausing staking data: {}", staking_data_key);
+            println!("Pause level: {:?}", level);
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::pause(&client, &staking_id, level, &staking_data_key, &authority)
+                .expect("Pause error");
+
+            println!("Pausing successfully completed");
+        }
+
+        ("unpause", Some(arg_matches)) => {
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+
+            let authority = read_keypair_file(
+                arg_matches
+                    .value_of("authority")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read authority keypair");
+
+            println!("Unpausing staking data: {}", staking_data_key);
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::unpause(&client, &staking_id, &staking_data_key, &authority)
+                .expect("Unpause error");
+
+            println!("Unpausing successfully completed");
+        }
+
+        ("transfer-staking-ownership", Some(arg_matches)) => {
+            let new_owner = value_t_or_exit!(arg_matches, "new_owner", Pubkey);
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+
+            let staking_data_owner = read_keypair_file(
+                arg_matches
+                    .value_of("staking_data_owner")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read owner keypair");
+
+            println!("Updating staking data: {}", staking_data_key);
+            println!("Proposed owner: {}", new_owner);
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::propose_owner(
+                &client,
+                &staking_id,
+                &new_owner,
+                &staking_data_key,
+                &staking_data_owner,
+            )
+            .expect("Transfer ownership error");
+
+            println!("Updating successfully completed");
+        }
+
+        ("accept-staking-ownership", Some(arg_matches)) => {
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+
+            let new_owner = read_keypair_file(
+                arg_matches
+                    .value_of("new_owner")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read new owner keypair");
+
+            println!("Updating staking data: {}", staking_data_key);
+            println!("New owner: {}", new_owner.pubkey());
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::accept_owner(&client, &staking_id, &staking_data_key, &new_owner)
+                .expect("Accept ownership error");
+
+            println!("Updating successfully completed");
+        }
 
-fn main() {
-    let app_matches =
-        get_clap_app(crate_name!(), crate_description!(), crate_version!()).get_matches();
+        ("initialize-user-staking", Some(arg_matches)) => {
+            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+            let payer = read_keypair_file(
+                arg_matches
+                    .value_of("user_authority")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read user authority keypair");
 
-    let config = if let Some(config_path) = app_matches.value_of("config") {
-        config::CLIConfig::load(config_path).expect("Config loading error")
-    } else {
-        config::CLIConfig::default()
-    };
+            println!("Creating new UserStakingData account");
+            print!("User authority: ");
+            println!("{}", payer.pubkey());
 
-    let cluster = Cluster::from_str(&config.network).expect("Cluster error");
-    let payer = read_keypair_file(&config.fee_payer_path).expect("Reading payer keypair error");
-    println!("RPC Client URL: {}", cluster.url());
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::initialize_user_staking(&client, &staking_id, &staking_data_key)
+                .expect("Initializing error");
 
-    // Convert program ids into Pubkey
-    let metadata_id = Pubkey::from_str(config.program_ids.metadata_id.as_ref())
-        .expect("Metadata PK convert error");
-    let staking_id =
-        Pubkey::from_str(config.program_ids.staking_id.as_ref()).expect("Staking PK convert error");
-    let governance_id = Pubkey::from_str(config.program_ids.governance_id.as_ref())
-        .expect("Governance PK convert error");
+            println!("Initializing successfully completed");
+        }
 
-    let (sub_command, sub_matches) = app_matches.subcommand();
-    match (sub_command, sub_matches) {
-        // TokenMetadata commands
-        ("create-token", Some(arg_matches)) => {
-            let decimals = value_t!(arg_matches, "decimals", u8).unwrap_or(9);
-            let mint_authority = read_keypair_file(
+        ("stake", Some(arg_matches)) => {
+            let ui_amount = value_t_or_exit!(arg_matches, "ui_amount", f64);
+            let staking_user_data_key =
+                value_t_or_exit!(arg_matches, "staking_user_data_account", Pubkey);
+            let user_token_wallet_owner = read_keypair_file(
                 arg_matches
-                    .value_of("mint_authority")
+                    .value_of("user_authority")
                     .unwrap_or(&config.fee_payer_path),
             )
-            .expect("Cannot read mint authority keypair");
+            .expect("Cannot read user authority keypair");
 
-            println!("Creating new token with");
-            println!("Decimals: {}", decimals);
-            println!("Mint authority: {}", mint_authority.pubkey());
+            println!("Staking {} tokens", ui_amount);
+            println!("User authority: {}", user_token_wallet_owner.pubkey());
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::stake(
+                &client,
+                &staking_id,
+                ui_amount,
+                &staking_user_data_key,
+                &user_token_wallet_owner,
+            )
+            .expect("Stake error");
 
-            let client =
-                Client::new_with_options(cluster, mint_authority, CommitmentConfig::processed());
-            commands::metadata::create_token(&client, &metadata_id, decimals)
-                .expect("Creating token error");
-            println!("Creating successfully completed");
+            println!("Staking successfully completed");
         }
 
-        ("initialize-metadata", Some(arg_matches)) => {
-            let name = value_t_or_exit!(arg_matches, "name", String);
-            let symbol = value_t_or_exit!(arg_matches, "symbol", String);
-            let mint_key = value_t_or_exit!(arg_matches, "mint", Pubkey);
-
-            let mint_authority = read_keypair_file(
+        ("stake-locked", Some(arg_matches)) => {
+            let ui_amount = value_t_or_exit!(arg_matches, "ui_amount", f64);
+            let tier = value_t_or_exit!(arg_matches, "tier", u8);
+            let staking_user_data_key =
+                value_t_or_exit!(arg_matches, "staking_user_data_account", Pubkey);
+            let user_token_wallet_owner = read_keypair_file(
                 arg_matches
-                    .value_of("mint_authority")
+                    .value_of("user_authority")
                     .unwrap_or(&config.fee_payer_path),
             )
-            .expect("Cannot read mint authority keypair");
+            .expect("Cannot read user authority keypair");
 
-            print!("Creating new token metadata with ");
-            println!("name: {} and symbol: {}", name, symbol);
-            println!("For Mint: {}", mint_key);
+            println!("Staking {} tokens locked with tier {}", ui_amount, tier);
+            println!("User authority: {}", user_token_wallet_owner.pubkey());
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::stake_locked(
+                &client,
+                &staking_id,
+                ui_amount,
+                tier,
+                &staking_user_data_key,
+                &user_token_wallet_owner,
+            )
+            .expect("Stake locked error");
 
-            let client =
-                Client::new_with_options(cluster, mint_authority, CommitmentConfig::processed());
-            commands::metadata::initialize_metadata(
+            println!("Staking successfully completed");
+        }
+
+        ("release-locked-stake", Some(arg_matches)) => {
+            let locked_stake_key = value_t_or_exit!(arg_matches, "locked_stake_account", Pubkey);
+            let user_token_wallet_owner = read_keypair_file(
+                arg_matches
+                    .value_of("user_authority")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read user authority keypair");
+
+            println!("Releasing locked stake: {}", locked_stake_key);
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::release_locked_stake(
                 &client,
-                &metadata_id,
-                &mint_key,
-                &name,
-                &symbol,
+                &staking_id,
+                &locked_stake_key,
+                &user_token_wallet_owner,
             )
-            .expect("Initialize metadata error");
-            println!("Metadata account successfully created");
+            .expect("Release locked stake error");
+
+            println!("Releasing successfully completed");
         }
 
-        ("initialize-token-wallet", Some(arg_matches)) => {
-            let token_key = value_t_or_exit!(arg_matches, "token_account", Pubkey);
-            let owner = value_t_or_exit!(arg_matches, "owner", Pubkey);
+        ("unstake", Some(arg_matches)) => {
+            let ui_amount = value_t_or_exit!(arg_matches, "ui_amount", f64);
+            let staking_user_data_key =
+                value_t_or_exit!(arg_matches, "staking_user_data_account", Pubkey);
+            let user_token_wallet_owner = read_keypair_file(
+                arg_matches
+                    .value_of("user_authority")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read user authority keypair");
+
+            println!("Unstake {} tokens", ui_amount);
+            println!("User authority: {}", user_token_wallet_owner.pubkey());
             let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
-            commands::metadata::initialize_token_wallet(&client, &metadata_id, &token_key, &owner)
-                .expect("Initialize token wallet error");
+            commands::staking::unstake(
+                &client,
+                &staking_id,
+                ui_amount,
+                &staking_user_data_key,
+                &user_token_wallet_owner,
+            )
+            .expect("Unstake error");
+
+            println!("Unstake successfully completed");
         }
 
-        ("token-wallet-balance", Some(arg_matches)) => {
-            let token_wallet = value_t_or_exit!(arg_matches, "token_wallet", Pubkey);
+        ("set-interest-mode", Some(arg_matches)) => {
+            let interest_mode = match arg_matches.value_of("mode").unwrap() {
+                "claim" => staking::InterestMode::Claim,
+                _ => staking::InterestMode::Compound,
+            };
+            let staking_user_data_key =
+                value_t_or_exit!(arg_matches, "staking_user_data_account", Pubkey);
+            let user_token_wallet_owner = read_keypair_file(
+                arg_matches
+                    .value_of("user_authority")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read user authority keypair");
+
+            println!("New interest mode: {:?}", interest_mode);
             let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
-            let balance =
-                commands::metadata::token_wallet_balance(&client, &metadata_id, &token_wallet)
-                    .expect("Getting balance error");
-            println!("Balance: {}", balance);
+            commands::staking::set_interest_mode(
+                &client,
+                &staking_id,
+                interest_mode,
+                &staking_user_data_key,
+                &user_token_wallet_owner,
+            )
+            .expect("Set interest mode error");
+
+            println!("Updating successfully completed");
         }
+        ("claim-interest", Some(arg_matches)) => {
+            let staking_user_data_key =
+                value_t_or_exit!(arg_matches, "staking_user_data_account", Pubkey);
+            let user_token_wallet_owner = read_keypair_file(
+                arg_matches
+                    .value_of("user_authority")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read user authority keypair");
 
-        ("mint-to", Some(arg_matches)) => {
-            let token_key = value_t_or_exit!(arg_matches, "token_account", Pubkey);
-            let amount = value_t_or_exit!(arg_matches, "amount", f64);
-            let destination = value_t_or_exit!(arg_matches, "destination", Pubkey);
+            println!("User authority: {}", user_token_wallet_owner.pubkey());
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::claim_interest(
+                &client,
+                &staking_id,
+                &staking_user_data_key,
+                &user_token_wallet_owner,
+            )
+            .expect("Claim interest error");
 
-            let mint_authority = read_keypair_file(
+            println!("Claim interest successfully completed");
+        }
+        ("request-unstake", Some(arg_matches)) => {
+            let ui_amount = value_t_or_exit!(arg_matches, "ui_amount", f64);
+            let staking_user_data_key =
+                value_t_or_exit!(arg_matches, "staking_user_data_account", Pubkey);
+            let user_token_wallet_owner = read_keypair_file(
                 arg_matches
-                    .value_of("mint_authority")
+                    .value_of("user_authority")
                     .unwrap_or(&config.fee_payer_path),
             )
-            .expect("Cannot read mint authority keypair");
+            .expect("Cannot read user authority keypair");
 
-            println!("Mint {} tokens ({}) to {}", amount, token_key, destination);
+            println!("Request unstake {} tokens", ui_amount);
+            println!("User authority: {}", user_token_wallet_owner.pubkey());
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::request_unstake(
+                &client,
+                &staking_id,
+                ui_amount,
+                &staking_user_data_key,
+                &user_token_wallet_owner,
+            )
+            .expect("Request unstake error");
+
+            println!("Request unstake successfully completed");
+        }
+
+        ("withdraw", Some(arg_matches)) => {
+            let pending_withdrawal_key =
+                value_t_or_exit!(arg_matches, "pending_withdrawal_account", Pubkey);
+            let user_token_wallet_owner = read_keypair_file(
+                arg_matches
+                    .value_of("user_authority")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read user authority keypair");
 
+            println!("Withdraw pending withdrawal: {}", pending_withdrawal_key);
             let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
-            commands::metadata::mint_to(
+            commands::staking::withdraw(
                 &client,
-                &metadata_id,
-                &token_key,
-                &destination,
-                &mint_authority,
-                amount,
+                &staking_id,
+                &pending_withdrawal_key,
+                &user_token_wallet_owner,
             )
-            .expect("Mint to error");
+            .expect("Withdraw error");
 
-            println!("Tokens successfully minted");
+            println!("Withdraw successfully completed");
         }
 
-        ("transfer", Some(arg_matches)) => {
-            let token_key = value_t_or_exit!(arg_matches, "token_account", Pubkey);
-            let amount = value_t_or_exit!(arg_matches, "amount", f64);
-            let source = value_t_or_exit!(arg_matches, "source", Pubkey);
-            let destination = value_t_or_exit!(arg_matches, "destination", Pubkey);
+        ("transfer-shares", Some(arg_matches)) => {
+            let shares = value_t_or_exit!(arg_matches, "shares", u64);
+            let staking_user_data_key =
+                value_t_or_exit!(arg_matches, "staking_user_data_account", Pubkey);
+            let recipient_staking_user_data_key =
+                value_t_or_exit!(arg_matches, "recipient_staking_user_data_account", Pubkey);
+            let user_token_wallet_owner = read_keypair_file(
+                arg_matches
+                    .value_of("user_authority")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read user authority keypair");
 
-            let payer = read_keypair_file(
+            println!(
+                "Transfer {} shares from {} to {}",
+                shares, staking_user_data_key, recipient_staking_user_data_key
+            );
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::transfer_shares(
+                &client,
+                &staking_id,
+                shares,
+                &staking_user_data_key,
+                &recipient_staking_user_data_key,
+                &user_token_wallet_owner,
+            )
+            .expect("Transfer shares error");
+
+            println!("Transfer shares successfully completed");
+        }
+
+        ("transfer-locked-stake", Some(arg_matches)) => {
+            let locked_stake_key = value_t_or_exit!(arg_matches, "locked_stake_account", Pubkey);
+            let recipient_staking_user_data_key =
+                value_t_or_exit!(arg_matches, "recipient_staking_user_data_account", Pubkey);
+            let user_token_wallet_owner = read_keypair_file(
                 arg_matches
-                    .value_of("transfer_authority")
+                    .value_of("user_authority")
                     .unwrap_or(&config.fee_payer_path),
             )
-            .expect("Cannot read owner keypair");
+            .expect("Cannot read user authority keypair");
 
             println!(
-                "Transfer {} tokens ({}) to {}",
-                amount, token_key, destination
+                "Transfer locked stake {} to {}",
+                locked_stake_key, recipient_staking_user_data_key
             );
-
             let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
-            commands::metadata::transfer(
+            commands::staking::transfer_locked_stake(
                 &client,
-                &metadata_id,
-                &token_key,
-                &source,
-                &destination,
-                amount,
+                &staking_id,
+                &locked_stake_key,
+                &recipient_staking_user_data_key,
+                &user_token_wallet_owner,
             )
-            .expect("Transfer error");
+            .expect("Transfer locked stake error");
 
-            println!("Tokens successfully transferred");
-        }
-
-        ("metadata-info", Some(arg_matches)) => {
-            let metadata_key = value_t_or_exit!(arg_matches, "metadata_account", Pubkey);
-            println!("Information of MetadataAccount: {}", metadata_key);
-            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
-            commands::metadata::metadata_info(&client, &metadata_id, &metadata_key)
-                .expect("Getting info error");
+            println!("Transfer locked stake successfully completed");
         }
 
-        // Staking commands
-        ("initialize-staking", Some(arg_matches)) => {
-            let starting_interest_rate =
-                value_t_or_exit!(arg_matches, "starting_interest_rate", u64);
-            let max_interest_rate = value_t_or_exit!(arg_matches, "max_interest_rate", u64);
-            let token_metadata = value_t_or_exit!(arg_matches, "token_metadata", Pubkey);
-            let cap = value_t_or_exit!(arg_matches, "cap", f64);
-            let starting_timestamp = match value_t!(arg_matches, "starting_timestamp", i64) {
-                Ok(val) => Some(val),
-                Err(_) => None,
-            };
-
-            let payer = read_keypair_file(
+        ("close-user-staking", Some(arg_matches)) => {
+            let staking_user_data_key =
+                value_t_or_exit!(arg_matches, "staking_user_data_account", Pubkey);
+            let user_token_wallet_owner = read_keypair_file(
                 arg_matches
-                    .value_of("staking_data_owner")
+                    .value_of("user_authority")
                     .unwrap_or(&config.fee_payer_path),
             )
-            .expect("Cannot read owner keypair");
+            .expect("Cannot read user authority keypair");
 
-            let mint_authority = read_keypair_file(
+            println!("Closing user staking data: {}", staking_user_data_key);
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::close_staking_user(
+                &client,
+                &staking_id,
+                &staking_user_data_key,
+                &user_token_wallet_owner,
+            )
+            .expect("Close user staking error");
+
+            println!("Close user staking successfully completed");
+        }
+
+        ("cancel-unstake", Some(arg_matches)) => {
+            let pending_withdrawal_key =
+                value_t_or_exit!(arg_matches, "pending_withdrawal_account", Pubkey);
+            let user_token_wallet_owner = read_keypair_file(
                 arg_matches
-                    .value_of("mint_authority")
+                    .value_of("user_authority")
                     .unwrap_or(&config.fee_payer_path),
             )
-            .expect("Cannot read mint authority keypair");
-
-            let staking_data = Keypair::new();
-            print!("Initializing new StakingData Account: ");
-            println!("{}", staking_data.pubkey());
+            .expect("Cannot read user authority keypair");
 
+            println!("Cancel pending withdrawal: {}", pending_withdrawal_key);
             let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
-            commands::staking::initialize_staking(
+            commands::staking::cancel_unstake(
                 &client,
                 &staking_id,
-                &staking_data,
-                &token_metadata,
-                starting_timestamp,
-                max_interest_rate,
-                starting_interest_rate,
-                cap,
-                &mint_authority,
+                &pending_withdrawal_key,
+                &user_token_wallet_owner,
             )
-            .expect("Initialize staking error");
-            println!("StakingData account successfully created");
-        }
-
-        ("staking-info", Some(arg_matches)) => {
-            let staking_key = value_t_or_exit!(arg_matches, "staking_account", Pubkey);
-            println!("Information of StakingData account: {}", staking_key);
-            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
-            commands::staking::staking_info(&client, &staking_id, &staking_key)
-                .expect("Getting info error");
-        }
+            .expect("Cancel unstake error");
 
-        ("staking-user-info", Some(arg_matches)) => {
-            let staking_user_key = value_t_or_exit!(arg_matches, "staking_user_account", Pubkey);
-            print!("Information of StakingUserData account: ");
-            println!("{}", staking_user_key);
-            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
-            commands::staking::staking_user_info(&client, &staking_id, &staking_user_key)
-                .expect("Getting info error");
+            println!("Cancel unstake successfully completed");
         }
 
-        ("set-interest-rate", Some(arg_matches)) => {
-            let new_interest_rate = value_t_or_exit!(arg_matches, "new_interest_rate", u64);
+        ("add-reward-stream", Some(arg_matches)) => {
+            let reward_mint = value_t_or_exit!(arg_matches, "reward_mint", Pubkey);
+            let ui_emission_per_second = value_t_or_exit!(arg_matches, "emission_per_second", f64);
+            let end_timestamp = value_t_or_exit!(arg_matches, "end_timestamp", i64);
             let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
+            let start_timestamp = match value_t!(arg_matches, "start_timestamp", i64) {
+                Ok(val) => Some(val),
+                Err(_) => None,
+            };
 
             let staking_data_owner = read_keypair_file(
                 arg_matches
@@ -1117,44 +3763,61 @@ fn main() {
             )
             .expect("Cannot read owner keypair");
 
-            println!("Updating staking data: {}", staking_data_key);
-            println!("New interest rate: {}", new_interest_rate);
-
+            println!("Adding reward stream to staking data: {}", staking_data_key);
             let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
-            commands::staking::set_interest_rate(
+            commands::staking::add_reward_stream(
                 &client,
                 &staking_id,
-                new_interest_rate,
                 &staking_data_key,
+                &reward_mint,
+                ui_emission_per_second,
+                start_timestamp,
+                end_timestamp,
                 &staking_data_owner,
             )
-            .expect("Set interest error");
+            .expect("Add reward stream error");
 
-            println!("Updating successfully completed");
+            println!("Reward stream successfully added, fund the reward vault to start emissions");
         }
-
-        ("initialize-user-staking", Some(arg_matches)) => {
-            let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
-            let payer = read_keypair_file(
+        ("remove-reward-stream", Some(arg_matches)) => {
+            let reward_stream_key = value_t_or_exit!(arg_matches, "reward_stream_account", Pubkey);
+            let staking_data_owner = read_keypair_file(
                 arg_matches
-                    .value_of("user_authority")
+                    .value_of("staking_data_owner")
                     .unwrap_or(&config.fee_payer_path),
             )
-            .expect("Cannot read user authority keypair");
-
-            println!("Creating new UserStakingData account");
-            print!("User authority: ");
-            println!("{}", payer.pubkey());
+            .expect("Cannot read owner keypair");
 
+            println!("Removing reward stream: {}", reward_stream_key);
             let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
-            commands::staking::initialize_user_staking(&client, &staking_id, &staking_data_key)
-                .expect("Initializing error");
+            commands::staking::remove_reward_stream(
+                &client,
+                &staking_id,
+                &reward_stream_key,
+                &staking_data_owner,
+            )
+            .expect("Remove reward stream error");
 
-            println!("Initializing successfully completed");
+            println!("Reward stream successfully removed");
         }
+        ("init-user-reward", Some(arg_matches)) => {
+            let reward_stream_key = value_t_or_exit!(arg_matches, "reward_stream_account", Pubkey);
+            let staking_user_data_key =
+                value_t_or_exit!(arg_matches, "staking_user_data_account", Pubkey);
 
-        ("stake", Some(arg_matches)) => {
-            let ui_amount = value_t_or_exit!(arg_matches, "ui_amount", f64);
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::staking::init_user_reward(
+                &client,
+                &staking_id,
+                &reward_stream_key,
+                &staking_user_data_key,
+            )
+            .expect("Init user reward error");
+
+            println!("UserReward account successfully created");
+        }
+        ("claim-rewards", Some(arg_matches)) => {
+            let reward_stream_key = value_t_or_exit!(arg_matches, "reward_stream_account", Pubkey);
             let staking_user_data_key =
                 value_t_or_exit!(arg_matches, "staking_user_data_account", Pubkey);
             let user_token_wallet_owner = read_keypair_file(
@@ -1164,23 +3827,21 @@ fn main() {
             )
             .expect("Cannot read user authority keypair");
 
-            println!("Staking {} tokens", ui_amount);
-            println!("User authority: {}", user_token_wallet_owner.pubkey());
+            println!("Claim rewards of reward stream: {}", reward_stream_key);
             let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
-            commands::staking::stake(
+            commands::staking::claim_rewards(
                 &client,
                 &staking_id,
-                ui_amount,
+                &reward_stream_key,
                 &staking_user_data_key,
                 &user_token_wallet_owner,
             )
-            .expect("Stake error");
+            .expect("Claim rewards error");
 
-            println!("Staking successfully completed");
+            println!("Claim rewards successfully completed");
         }
-
-        ("unstake", Some(arg_matches)) => {
-            let ui_amount = value_t_or_exit!(arg_matches, "ui_amount", f64);
+        ("close-user-reward", Some(arg_matches)) => {
+            let reward_stream_key = value_t_or_exit!(arg_matches, "reward_stream_account", Pubkey);
             let staking_user_data_key =
                 value_t_or_exit!(arg_matches, "staking_user_data_account", Pubkey);
             let user_token_wallet_owner = read_keypair_file(
@@ -1190,21 +3851,22 @@ fn main() {
             )
             .expect("Cannot read user authority keypair");
 
-            println!("Unstake {} tokens", ui_amount);
-            println!("User authority: {}", user_token_wallet_owner.pubkey());
+            println!(
+                "Closing user reward of reward stream: {}",
+                reward_stream_key
+            );
             let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
-            commands::staking::unstake(
+            commands::staking::close_user_reward(
                 &client,
                 &staking_id,
-                ui_amount,
+                &reward_stream_key,
                 &staking_user_data_key,
                 &user_token_wallet_owner,
             )
-            .expect("Unstake error");
+            .expect("Close user reward error");
 
-            println!("Unstake successfully completed");
+            println!("Close user reward successfully completed");
         }
-
         ("accrue-interest", Some(arg_matches)) => {
             let staking_data_key = value_t_or_exit!(arg_matches, "staking_data_account", Pubkey);
 
@@ -1257,6 +3919,8 @@ fn main() {
             let min_vote_participation_percent =
                 value_t_or_exit!(arg_matches, "min_vote_participation_percent", u8);
             let payment_period_sec = value_t_or_exit!(arg_matches, "payment_period_sec", i64);
+            let execution_delay_seconds =
+                value_t_or_exit!(arg_matches, "execution_delay_seconds", i64);
             let sponsors = values_t_or_exit!(arg_matches, "sponsor", Pubkey);
 
             println!("Initialize new Governance");
@@ -1271,6 +3935,7 @@ fn main() {
                 min_stake_to_propose,
                 min_vote_participation_percent,
                 payment_period_sec,
+                execution_delay_seconds,
                 sponsors,
             )
             .expect("Initialize error");
@@ -1453,6 +4118,84 @@ fn main() {
             println!("Successfully completed");
         }
 
+        ("make-executable-proposal", Some(arg_matches)) => {
+            let governance_key = value_t_or_exit!(arg_matches, "governance", Pubkey);
+            let staking_user_data = value_t_or_exit!(arg_matches, "staking_user_data", Pubkey);
+            let treasury_key = value_t_or_exit!(arg_matches, "treasury", Pubkey);
+            let ipfs_hash = value_t_or_exit!(arg_matches, "ipfs_hash", String);
+            let instructions_path = value_t_or_exit!(arg_matches, "instructions", String);
+            let user_token_wallet_owner = read_keypair_file(
+                arg_matches
+                    .value_of("user_authority")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read user authority keypair");
+            let instructions = commands::governance::load_proposal_instructions(&instructions_path)
+                .expect("Instructions loading error");
+
+            println!("Creating new executable proposal with");
+            println!("Governance: {}", governance_key);
+            println!("Instructions count: {}", instructions.len());
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::governance::make_executable_proposal(
+                &client,
+                &governance_id,
+                &staking_id,
+                &governance_key,
+                &staking_user_data,
+                &user_token_wallet_owner,
+                &treasury_key,
+                &ipfs_hash,
+                instructions,
+            )
+            .expect("Making proposal error");
+            println!("Successfully completed");
+        }
+
+        ("propose-config-change", Some(arg_matches)) => {
+            let governance_key = value_t_or_exit!(arg_matches, "governance", Pubkey);
+            let staking_user_data = value_t_or_exit!(arg_matches, "staking_user_data", Pubkey);
+            let treasury_key = value_t_or_exit!(arg_matches, "treasury", Pubkey);
+            let ipfs_hash = value_t_or_exit!(arg_matches, "ipfs_hash", String);
+            let approval_fixed_period_in_seconds =
+                value_t_or_exit!(arg_matches, "approval_fixed_period_in_seconds", i64);
+            let min_approval_percent = value_t_or_exit!(arg_matches, "min_approval_percent", u8);
+            let min_stake_to_propose = value_t_or_exit!(arg_matches, "min_stake_to_propose", f64);
+            let min_vote_participation_percent =
+                value_t_or_exit!(arg_matches, "min_vote_participation_percent", u8);
+            let payment_period_sec = value_t_or_exit!(arg_matches, "payment_period_sec", i64);
+            let execution_delay_seconds =
+                value_t_or_exit!(arg_matches, "execution_delay_seconds", i64);
+            let user_token_wallet_owner = read_keypair_file(
+                arg_matches
+                    .value_of("user_authority")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read user authority keypair");
+
+            println!("Creating new config change proposal with");
+            println!("Governance: {}", governance_key);
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::governance::propose_config_change(
+                &client,
+                &governance_id,
+                &staking_id,
+                &governance_key,
+                &staking_user_data,
+                &user_token_wallet_owner,
+                &treasury_key,
+                &ipfs_hash,
+                approval_fixed_period_in_seconds,
+                min_approval_percent,
+                min_stake_to_propose,
+                min_vote_participation_percent,
+                payment_period_sec,
+                execution_delay_seconds,
+            )
+            .expect("Making proposal error");
+            println!("Successfully completed");
+        }
+
         ("approve-proposal", Some(arg_matches)) => {
             let staking_user_data_key = value_t_or_exit!(arg_matches, "staking_user_data", Pubkey);
             let proposal_key = value_t_or_exit!(arg_matches, "proposal", Pubkey);
@@ -1492,6 +4235,67 @@ fn main() {
                 .expect("Getting data error");
         }
 
+        ("execute-proposal", Some(arg_matches)) => {
+            let proposal_key = value_t_or_exit!(arg_matches, "proposal", Pubkey);
+
+            println!("Executing the proposal: {}", proposal_key);
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::governance::execute_proposal(&client, &governance_id, &proposal_key)
+                .expect("Executing error");
+            println!("Successfully completed");
+        }
+
+        ("apply-config-proposal", Some(arg_matches)) => {
+            let proposal_key = value_t_or_exit!(arg_matches, "proposal", Pubkey);
+
+            println!("Applying the proposal config: {}", proposal_key);
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::governance::apply_config_proposal(&client, &governance_id, &proposal_key)
+                .expect("Applying error");
+            println!("Successfully completed");
+        }
+
+        ("cancel-queued-proposal", Some(arg_matches)) => {
+            let proposal_key = value_t_or_exit!(arg_matches, "proposal", Pubkey);
+            let signers = values_t_or_exit!(arg_matches, "signers", String);
+            let signers: Vec<Keypair> = signers
+                .iter()
+                .map(|p| read_keypair_file(p).expect("Cannot read the signer keypair"))
+                .collect();
+
+            println!("Cancelling the proposal: {}", proposal_key);
+            println!("Signers:");
+            for signer in &signers {
+                println!("{}", signer.pubkey());
+            }
+
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::governance::cancel_queued_proposal(
+                &client,
+                &governance_id,
+                &proposal_key,
+                &signers,
+            )
+            .expect("Cancelling error");
+            println!("Successfully completed");
+        }
+
+        ("cancel-proposal", Some(arg_matches)) => {
+            let proposal_key = value_t_or_exit!(arg_matches, "proposal", Pubkey);
+            let author = read_keypair_file(
+                arg_matches
+                    .value_of("user_authority")
+                    .unwrap_or(&config.fee_payer_path),
+            )
+            .expect("Cannot read user authority keypair");
+
+            println!("Cancelling the proposal: {}", proposal_key);
+            let client = Client::new_with_options(cluster, payer, CommitmentConfig::processed());
+            commands::governance::cancel_proposal(&client, &governance_id, &proposal_key, &author)
+                .expect("Cancelling error");
+            println!("Successfully completed");
+        }
+
         ("proposal-info", Some(arg_matches)) => {
             let proposal_key = value_t_or_exit!(arg_matches, "proposal", Pubkey);
             println!("Information about proposal: {}", proposal_key);
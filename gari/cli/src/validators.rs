@@ -34,6 +34,13 @@ pub fn is_valid_uint(string: String) -> Result<(), String> {
     }
 }
 
+pub fn is_valid_u16(string: String) -> Result<(), String> {
+    match string.parse::<u16>() {
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!("Invalid input {}", string)),
+    }
+}
+
 pub fn is_valid_int(string: String) -> Result<(), String> {
     match string.parse::<i64>() {
         Ok(_) => Ok(()),
@@ -54,3 +61,27 @@ pub fn is_valid_ui_amount(string: String) -> Result<(), String> {
         Err(_) => Err(format!("Invalid input amount {}", string)),
     }
 }
+
+pub fn is_valid_lock_tier(string: String) -> Result<(), String> {
+    let parts: Vec<&str> = string.split(':').collect();
+    if parts.len() != 2 || parts[0].parse::<i64>().is_err() || parts[1].parse::<u64>().is_err() {
+        return Err(format!(
+            "Invalid lock tier {}, expected DURATION_SEC:BOOST",
+            string
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn is_valid_rate_point(string: String) -> Result<(), String> {
+    let parts: Vec<&str> = string.split(':').collect();
+    if parts.len() != 2 || parts[0].parse::<i64>().is_err() || parts[1].parse::<u64>().is_err() {
+        return Err(format!(
+            "Invalid rate point {}, expected TIMESTAMP:DAILY_RATE",
+            string
+        ));
+    }
+
+    Ok(())
+}
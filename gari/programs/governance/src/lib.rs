@@ -1,8 +1,11 @@
 //! Governance program for making and voting for proposals
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
 use anchor_lang::solana_program::pubkey::MAX_SEED_LEN;
 use anchor_spl::token::{self, Mint, TokenAccount, Transfer};
+use spl_token::instruction::TokenInstruction;
 use staking::{LockAmount, StakingData, StakingUserData};
 
 #[program]
@@ -25,6 +28,7 @@ pub mod governance {
     /// \param min_stake_to_propose: minimum amount of staked tokens to proposal creating
     /// \param min_vote_participation_percent: minimum percent of participation to approve proposal
     /// \param payment_period_sec: fixed payment period
+    /// \param execution_delay_seconds: timelock between proposal approval and execution
     /// \param treasury_owner_bump: PDA bump for treasury owner (needs for signatures)
     /// \param sponsors: initial list of sponsors (system accounts addresses)
     pub fn initialize_governance(
@@ -35,6 +39,7 @@ pub mod governance {
         min_stake_to_propose: u64,
         min_vote_participation_percent: u8,
         payment_period_sec: i64,
+        execution_delay_seconds: i64,
         treasury_owner_bump: u8,
         sponsors: Vec<Pubkey>,
     ) -> ProgramResult {
@@ -54,6 +59,11 @@ pub mod governance {
             return Err(GovernanceError::InvalidMinVoteParticipationPercent.into());
         }
 
+        if !(0..=MAX_EXECUTION_DELAY_SECONDS).contains(&execution_delay_seconds) {
+            msg!("Invalid execution delay");
+            return Err(GovernanceError::InvalidExecutionDelay.into());
+        }
+
         let governance_data = &mut ctx.accounts.governance;
         governance_data.staking_data = staking_data_key;
         governance_data.approval_fixed_period_in_seconds = approval_fixed_period_in_seconds;
@@ -64,6 +74,7 @@ pub mod governance {
         governance_data.sponsors = sponsors;
         governance_data.payment_period_start = ctx.accounts.clock.unix_timestamp;
         governance_data.payment_period_sec = payment_period_sec;
+        governance_data.execution_delay_seconds = execution_delay_seconds;
 
         Ok(())
     }
@@ -125,34 +136,9 @@ pub mod governance {
             return Err(GovernanceError::SponsorsLimitExceeded.into());
         }
 
-        if ctx.remaining_accounts.len() == 0 {
-            msg!("Not enough signers to change sponsors list");
-            return Err(GovernanceError::MissingSponsorsSignatures.into());
-        }
-
-        // There is no access to HashSet
-        let mut sponsors_keys_set = vec![];
-        let mut signature_count = 0;
-        for account in ctx.remaining_accounts {
-            if sponsors_keys_set.contains(account.key) {
-                msg!("Skip sponsor key duplicate");
-                continue;
-            }
-            sponsors_keys_set.push(account.key());
-
-            if ctx.accounts.governance.sponsors.contains(account.key) && account.is_signer {
-                signature_count += 1;
-            }
-        }
-
-        let signature_count = signature_count as f64;
-        let sponsors_len = ctx.accounts.governance.sponsors.len() as f64;
-        let signers_percent = ((signature_count / sponsors_len) * 100.0) as u8;
-        msg!("Signers percent: {}", signers_percent);
-        if signers_percent < SPONSORS_LIST_APPROVAL_PERCENT {
-            msg!("Not enough signers to change sponsors list");
-            return Err(GovernanceError::MissingSponsorsSignatures.into());
-        }
+        ctx.accounts
+            .governance
+            .check_sponsors_signatures(ctx.remaining_accounts)?;
 
         msg!("Sponsors list changing approved");
         let governance_data = &mut ctx.accounts.governance;
@@ -176,34 +162,9 @@ pub mod governance {
             return Err(GovernanceError::SponsorsLimitExceeded.into());
         }
 
-        if ctx.remaining_accounts.len() == 0 {
-            msg!("Not enough signers to change sponsors list");
-            return Err(GovernanceError::MissingSponsorsSignatures.into());
-        }
-
-        // There is no access to HashSet
-        let mut sponsors_keys_set = vec![];
-        let mut signature_count = 0;
-        for account in ctx.remaining_accounts {
-            if sponsors_keys_set.contains(account.key) {
-                msg!("Skip sponsor key duplicate");
-                continue;
-            }
-            sponsors_keys_set.push(account.key());
-
-            if ctx.accounts.governance.sponsors.contains(account.key) && account.is_signer {
-                signature_count += 1;
-            }
-        }
-
-        let signature_count = signature_count as f64;
-        let sponsors_len = ctx.accounts.governance.sponsors.len() as f64;
-        let signers_percent = ((signature_count / sponsors_len) * 100.0) as u8;
-        msg!("Signers percent: {}", signers_percent);
-        if signers_percent < SPONSORS_LIST_APPROVAL_PERCENT {
-            msg!("Not enough signers to change sponsors list");
-            return Err(GovernanceError::MissingSponsorsSignatures.into());
-        }
+        ctx.accounts
+            .governance
+            .check_sponsors_signatures(ctx.remaining_accounts)?;
 
         // Change sponsors list
         msg!("Sponsors list changing approved");
@@ -286,83 +247,193 @@ pub mod governance {
             return Err(GovernanceError::InvalidIPFSHashSize.into());
         }
 
-        // Check sender has staked tokens
-        if ctx.accounts.staking_user_data.ownership_share == 0 {
-            msg!("Only Staking token holders can make proposals");
-            return Err(GovernanceError::InvalidStakingHolder.into());
-        }
+        ctx.accounts
+            .governance
+            .check_proposer(&ctx.accounts.staking_user_data)?;
 
-        // Check min_stake_to_propose
-        if ctx.accounts.staking_user_data.ownership_share
-            < ctx.accounts.governance.min_stake_to_propose
-        {
-            msg!("There is not enough staked tokens to make proposal");
-            return Err(GovernanceError::InsufficientStakedTokens.into());
-        }
+        ctx.accounts.treasury_stats.count_payment(
+            &mut ctx.accounts.governance,
+            ctx.accounts.treasury.amount,
+            payment_amount,
+            ctx.accounts.clock.unix_timestamp,
+        )?;
 
-        // Check payment amount
-        if payment_amount > ctx.accounts.treasury.amount {
-            msg!("There is not enough tokens in governance to make proposal");
-            return Err(GovernanceError::InsufficientFunds.into());
-        }
+        // Initialize new proposal
+        let proposal_data = &mut ctx.accounts.proposal;
+        proposal_data.init(
+            &ctx.accounts.governance,
+            ctx.accounts.user_token_wallet_owner.key(),
+            ipfs_hash,
+            ProposalKind::Payment,
+            &ctx.accounts.clock,
+        );
+        proposal_data.recipient = ctx.accounts.recipient.key();
+        proposal_data.payment_amount = payment_amount;
 
-        // Check max proposal amount limit (percent)
-        let highest_balance = ctx.accounts.treasury_stats.highest_balance as f64;
-        let max_proposal_payment_percent =
-            ctx.accounts.treasury_stats.max_proposal_payment_percent as f64 / 100.0;
-        let max_proposal_payment_amount = (highest_balance * max_proposal_payment_percent) as u64;
-        if payment_amount > max_proposal_payment_amount {
-            msg!("Max proposal payment amount limit exceeded");
-            return Err(GovernanceError::MaxProposalAmountLimit.into());
-        }
+        Ok(())
+    }
 
-        // Check ending of the period
-        let period_ending_time = ctx.accounts.governance.payment_period_start
-            + ctx.accounts.governance.payment_period_sec;
-        if ctx.accounts.clock.unix_timestamp >= period_ending_time {
-            // Reset payment counter if period ends
-            let governance_data = &mut ctx.accounts.governance;
-            governance_data.payment_period_start = ctx.accounts.clock.unix_timestamp;
-            let treasury_stats_data = &mut ctx.accounts.treasury_stats;
-            treasury_stats_data.payment_amount_in_period = 0;
+    /// Create new proposal which executes the list of instructions instead of the payment.
+    /// Approved instructions are invoked by `execute_proposal` with the treasury owner as signer.
+    /// Instructions signed by the treasury owner can move the treasury tokens only by SPL Token
+    /// transfers from the treasury, the transferred amount is limited as the payment proposal amount.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` Governance account
+    /// 1. `[]` Governance treasury account
+    /// 2. `[writable]` Governance treasury stats account
+    /// 3. `[]` Governance treasury owner
+    /// 4. `[]` Staking user data account of proposal author
+    /// 5. `[]` Proposal author token wallet
+    /// 6. `[signer]` Proposal author token wallet owner (authority to making proposal)
+    /// 7. `[writable]` The proposal data account (pda of ['proposal', governance_program_id, governance, ipfs_hash[0..32]])
+    /// 8. `[writable, signer]` Fee payer
+    /// 9. `[]` System program
+    /// 10. `[]` Rent sysvar
+    /// 11. `[]` Clock sysvar
+    ///
+    /// \param _proposal_bump: PDA bump for proposal account (needs for creating account, never saved)
+    /// \param ipfs_hash: ipfs hash for proposal details
+    /// \param instructions: list of instructions to execute after approval
+    pub fn make_executable_proposal(
+        ctx: Context<MakeExecutableProposal>,
+        _proposal_bump: u8,
+        ipfs_hash: String,
+        instructions: Vec<ProposalInstruction>,
+    ) -> ProgramResult {
+        msg!("Instruction: make executable proposal");
+
+        if ipfs_hash.len() > MAX_IPFS_HASH_LEN {
+            return Err(GovernanceError::InvalidIPFSHashSize.into());
         }
 
-        // Check payment amount in period limit (percent)
-        let payment_amount_in_period_limit_percent =
-            ctx.accounts
-                .treasury_stats
-                .payment_amount_in_period_limit_percent as f64
-                / 100.0;
-        let payment_amount_in_period_limit =
-            (highest_balance * payment_amount_in_period_limit_percent) as u64;
-        if (payment_amount + ctx.accounts.treasury_stats.payment_amount_in_period)
-            > payment_amount_in_period_limit
-        {
-            msg!("This proposal exceeds the maximum amount that can be paid for current period");
-            return Err(GovernanceError::PaymentAmountLimitExceeded.into());
+        ctx.accounts
+            .governance
+            .check_proposer(&ctx.accounts.staking_user_data)?;
+
+        if instructions.is_empty() || instructions.len() > MAX_PROPOSAL_INSTRUCTIONS {
+            msg!("Invalid proposal instructions count");
+            return Err(GovernanceError::InvalidProposalInstructions.into());
         }
+        let treasury_owner = ctx.accounts.treasury_owner.key();
+        let treasury = ctx.accounts.treasury.key();
+        let mut payment_amount: u64 = 0;
+        for instruction in &instructions {
+            if instruction.accounts.len() > MAX_PROPOSAL_INSTRUCTION_ACCOUNTS
+                || instruction.data.len() > MAX_PROPOSAL_INSTRUCTION_DATA_LEN
+            {
+                msg!("Proposal instruction is too large");
+                return Err(GovernanceError::InvalidProposalInstructions.into());
+            }
+            // Treasury owner is the only signer available on execution
+            if instruction
+                .accounts
+                .iter()
+                .any(|meta| meta.is_signer && meta.pubkey != treasury_owner)
+            {
+                msg!("Only treasury owner can sign proposal instructions");
+                return Err(GovernanceError::InvalidProposalInstructions.into());
+            }
+            if !instruction.accounts.iter().any(|meta| meta.is_signer) {
+                continue;
+            }
+            // Treasury tokens can be moved only by the transfers counted in treasury stats
+            if instruction.program_id == token::ID {
+                let amount = instruction.treasury_transfer_amount(&treasury)?;
+                payment_amount = payment_amount.checked_add(amount).unwrap();
+            } else if instruction
+                .accounts
+                .iter()
+                .any(|meta| meta.pubkey == treasury)
+            {
+                msg!("Treasury can be used only by SPL Token transfer instructions");
+                return Err(GovernanceError::InvalidProposalInstructions.into());
+            }
+        }
+        msg!("Treasury payment amount: {}", payment_amount);
 
-        // update treasury stats
-        let treasury_stats_data = &mut ctx.accounts.treasury_stats;
-        treasury_stats_data.payment_amount_in_period += payment_amount;
+        ctx.accounts.treasury_stats.count_payment(
+            &mut ctx.accounts.governance,
+            ctx.accounts.treasury.amount,
+            payment_amount,
+            ctx.accounts.clock.unix_timestamp,
+        )?;
 
         // Initialize new proposal
         let proposal_data = &mut ctx.accounts.proposal;
-        proposal_data.governance = ctx.accounts.governance.key();
-        proposal_data.is_closed = false;
-        proposal_data.starting_timestamp = ctx.accounts.clock.unix_timestamp;
-        proposal_data.recipient = ctx.accounts.recipient.key();
+        proposal_data.init(
+            &ctx.accounts.governance,
+            ctx.accounts.user_token_wallet_owner.key(),
+            ipfs_hash,
+            ProposalKind::Executable,
+            &ctx.accounts.clock,
+        );
+        // The treasury is set as recipient to keep finalizing accounts valid
+        proposal_data.recipient = treasury;
         proposal_data.payment_amount = payment_amount;
-        proposal_data.ipfs_hash = ipfs_hash;
-        proposal_data.pros_weight = 0;
-        proposal_data.cons_weight = 0;
-        proposal_data.is_sponsored = false;
+        proposal_data.instructions = instructions;
+
+        Ok(())
+    }
+
+    /// Create new proposal which changes the governance config instead of the payment.
+    /// Approved config is applied by `apply_config_proposal`.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[]` Governance account
+    /// 1. `[]` Governance treasury account
+    /// 2. `[]` Governance treasury owner
+    /// 3. `[]` Staking user data account of proposal author
+    /// 4. `[]` Proposal author token wallet
+    /// 5. `[signer]` Proposal author token wallet owner (authority to making proposal)
+    /// 6. `[writable]` The proposal data account (pda of ['proposal', governance_program_id, governance, ipfs_hash[0..32]])
+    /// 7. `[writable, signer]` Fee payer
+    /// 8. `[]` System program
+    /// 9. `[]` Rent sysvar
+    /// 10. `[]` Clock sysvar
+    ///
+    /// \param _proposal_bump: PDA bump for proposal account (needs for creating account, never saved)
+    /// \param ipfs_hash: ipfs hash for proposal details
+    /// \param config: new governance config to apply after approval
+    pub fn make_config_proposal(
+        ctx: Context<MakeConfigProposal>,
+        _proposal_bump: u8,
+        ipfs_hash: String,
+        config: GovernanceConfig,
+    ) -> ProgramResult {
+        msg!("Instruction: make config proposal");
+
+        if ipfs_hash.len() > MAX_IPFS_HASH_LEN {
+            return Err(GovernanceError::InvalidIPFSHashSize.into());
+        }
+
+        ctx.accounts
+            .governance
+            .check_proposer(&ctx.accounts.staking_user_data)?;
+
+        config.validate()?;
+
+        // Initialize new proposal
+        let proposal_data = &mut ctx.accounts.proposal;
+        proposal_data.init(
+            &ctx.accounts.governance,
+            ctx.accounts.user_token_wallet_owner.key(),
+            ipfs_hash,
+            ProposalKind::ConfigChange,
+            &ctx.accounts.clock,
+        );
+        // No payment, the treasury is set as recipient to keep finalizing accounts valid
+        proposal_data.recipient = ctx.accounts.treasury.key();
+        proposal_data.config = config;
 
         Ok(())
     }
 
-    /// Vote for/against the proposal. Vote weight equals the staking shares.
-    /// User shares locked after voting for all period of proposal voting.
+    /// Vote for/against the proposal. Vote weight equals the staking shares as of the proposal
+    /// snapshot, so the shares staked or received after the proposal is made can't vote.
+    /// Shares moved out after the snapshot can't vote, the current shares must cover the snapshot.
+    /// User shares locked after voting for all period of proposal voting, the shares already
+    /// locked by other votes stay under their own locks.
     ///
     /// Accounts expected by this instruction:
     /// 0. `[]` Governance account
@@ -372,66 +443,98 @@ pub mod governance {
     /// 4. `[]` Staking data account
     /// 5. `[writable]` Proposal account
     /// 6. `[]` Vote marker account (needs to avoid duplicate votes)
-    /// 7. `[]` Fee payer
-    /// 8. `[]` Staking program
-    /// 9. `[]` System program
-    /// 10. `[]` Clock sysvar
+    /// 7. `[]` Locker authority pda of ["locker_authority"] (signs the staking lock)
+    /// 8. `[]` Governance program
+    /// 9. `[]` Fee payer
+    /// 10. `[]` Staking program
+    /// 11. `[]` System program
+    /// 12. `[]` Clock sysvar
     ///
     /// \param vote: bool true for pros/false for cons
     /// \param marker_bump: PDA bump for vote marker account
+    /// \param locker_bump: PDA bump for locker authority
     pub fn approve_proposal(
         ctx: Context<ApproveProposal>,
         vote: bool,
         marker_bump: u8,
+        locker_bump: u8,
     ) -> ProgramResult {
         msg!("Instruction: approve proposal");
 
         // Check proposal is still open
-        let approval_period_ending = ctx.accounts.proposal.starting_timestamp
-            + ctx.accounts.governance.approval_fixed_period_in_seconds;
+        if ctx.accounts.proposal.state != ProposalState::Voting {
+            msg!("Proposal is already closed");
+            return Err(GovernanceError::ClosedProposal.into());
+        }
+        let approval_period_ending = ctx.accounts.proposal.voting_ends_at;
         if ctx.accounts.clock.unix_timestamp >= approval_period_ending {
             msg!("Propose is already closed");
             return Err(GovernanceError::ClosedProposal.into());
         }
 
-        // Check user has staked tokens
-        if ctx.accounts.staking_user_data.ownership_share == 0 {
+        // Check user had staked tokens at the snapshot
+        let snapshot_shares = ctx
+            .accounts
+            .staking_user_data
+            .shares_at(ctx.accounts.proposal.snapshot_slot);
+        msg!("Snapshot shares: {}", snapshot_shares);
+        if snapshot_shares == 0 {
             msg!("Only Staking token holders can approve proposals");
             return Err(GovernanceError::InvalidStakingHolder.into());
         }
-
-        // Lock the tokens on StakingUserData
-        let remaining_approval_period = approval_period_ending - ctx.accounts.clock.unix_timestamp;
-        let mut locked_until = ctx.accounts.clock.unix_timestamp + remaining_approval_period;
-        // Amount always locked into recent proposal
-        if locked_until < ctx.accounts.staking_user_data.locked_until {
-            locked_until = ctx.accounts.staking_user_data.locked_until;
+        if ctx.accounts.staking_user_data.ownership_share < snapshot_shares {
+            msg!("Staked shares are below the snapshot shares");
+            return Err(GovernanceError::SharesBelowSnapshot.into());
         }
 
+        // Lock the tokens on StakingUserData until the proposal voting ends
+        let locked_until = approval_period_ending;
+
         // Calculate and lock vote weight
-        let (possible_interest, _) = staking::utils::calculate_accrued_interest(
+        let (possible_interest, _) = staking::utils::calculate_scheduled_accrued_interest(
             ctx.accounts.staking_data.last_interest_accrued_timestamp,
             approval_period_ending,
             ctx.accounts.staking_data.total_staked,
             ctx.accounts.staking_data.interest_rate_daily,
+            &ctx.accounts.staking_data.rate_schedule,
         );
+        let possible_interest = ctx
+            .accounts
+            .staking_data
+            .payable_interest(possible_interest);
         msg!("Possible interest: {}", possible_interest);
-        let vote_weight = ctx.accounts.staking_user_data.ownership_share + possible_interest;
+        let vote_weight = snapshot_shares + possible_interest;
 
-        staking::cpi::lock_amount(
-            CpiContext::new(
-                ctx.accounts.staking_program.clone(),
-                LockAmount {
-                    staking_data: ctx.accounts.staking_data.clone(),
-                    staking_user_data: ctx.accounts.staking_user_data.clone().into(),
-                    user_token_wallet: ctx.accounts.user_token_wallet.clone(),
-                    user_token_wallet_owner: ctx.accounts.user_token_wallet_owner.clone(),
-                    clock: ctx.accounts.clock.clone(),
-                },
-            ),
-            locked_until,
-            vote_weight,
-        )?;
+        let available_shares = ctx
+            .accounts
+            .staking_user_data
+            .available_shares(ctx.accounts.clock.unix_timestamp);
+        let lock_amount = vote_weight.min(available_shares);
+        msg!("Lock amount: {}", lock_amount);
+
+        let seeds = &[staking::LOCKER_AUTHORITY_PREFIX.as_bytes(), &[locker_bump]];
+        let signer = &[&seeds[..]];
+        if lock_amount > 0 {
+            staking::cpi::lock_amount(
+                CpiContext::new_with_signer(
+                    ctx.accounts.staking_program.clone(),
+                    LockAmount {
+                        staking_data: ctx.accounts.staking_data.clone(),
+                        staking_user_data: ctx.accounts.staking_user_data.clone().into(),
+                        user_token_wallet: ctx.accounts.user_token_wallet.clone(),
+                        user_token_wallet_owner: ctx.accounts.user_token_wallet_owner.clone(),
+                        locker_program: ctx.accounts.governance_program.clone(),
+                        locker_authority: ctx.accounts.locker_authority.clone(),
+                        clock: ctx.accounts.clock.clone(),
+                    },
+                    signer,
+                ),
+                locked_until,
+                lock_amount,
+                ctx.accounts.proposal.key(),
+                locker_bump,
+            )?;
+        }
 
         // Update proposal weight
         let proposal_data = &mut ctx.accounts.proposal;
@@ -455,37 +558,31 @@ pub mod governance {
     }
 
     /// Trying finalize the proposal. Check all conditions and make decision: do nothing or
-    /// approve proposal and transfer amount or close proposal and don't transfer amount.
+    /// approve and queue proposal (`Succeeded` state) or close proposal without approval
+    /// (`Defeated` state with the defeat reason). Queued proposal is executed by
+    /// `execute_proposal` (or `apply_config_proposal`) after the execution delay.
     ///
     /// Accounts expected by this instruction:
     /// 0. `[]` Governance account
     /// 1. `[]` Staking data account
     /// 2. `[writable]` Proposal account
-    /// 3. `[writable]` Governance treasury account
-    /// 4. `[signer]` Governance treasury owner
-    /// 5. `[]` Governance treasury mint
-    /// 6. `[writable]` Recipient SPL Token account
-    /// 7. `[]` SPL Token program
-    /// 8. `[]` Clock sysvar
+    /// 3. `[]` Clock sysvar
     pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> ProgramResult {
         msg!("Instruction: finalize proposal");
 
         // Check proposal is still open
-        if ctx.accounts.proposal.is_closed {
+        if ctx.accounts.proposal.state != ProposalState::Voting {
             msg!("Proposal is already closed");
             return Err(GovernanceError::ClosedProposal.into());
         }
 
         // Check the vote is still in progress
-        let approval_period_ending = ctx.accounts.proposal.starting_timestamp
-            + ctx.accounts.governance.approval_fixed_period_in_seconds;
+        let approval_period_ending = ctx.accounts.proposal.voting_ends_at;
         if ctx.accounts.clock.unix_timestamp < approval_period_ending {
             msg!("The vote in progress");
             return Err(GovernanceError::VoteInProgress.into());
         }
 
-        ctx.accounts.proposal.is_closed = true;
-
         // Count the votes
         let pros_weight = ctx.accounts.proposal.pros_weight as f64;
         let cons_weight = ctx.accounts.proposal.cons_weight as f64;
@@ -500,6 +597,7 @@ pub mod governance {
         if participation_percent < ctx.accounts.governance.min_vote_participation_percent {
             msg!("The minimum participation percent not reached");
             msg!("Amount will not be sent");
+            ctx.accounts.proposal.defeat(ProposalDefeatReason::Quorum);
             return Ok(());
         }
 
@@ -509,6 +607,7 @@ pub mod governance {
         if approval_percent < ctx.accounts.governance.min_approval_percent {
             msg!("The minimum approval percent not reached");
             msg!("Amount will not be sent");
+            ctx.accounts.proposal.defeat(ProposalDefeatReason::Approval);
             return Ok(());
         }
 
@@ -516,10 +615,64 @@ pub mod governance {
         if !ctx.accounts.proposal.is_sponsored {
             msg!("The proposal is not sponsored");
             msg!("Amount will not be sent");
+            ctx.accounts
+                .proposal
+                .defeat(ProposalDefeatReason::NotSponsored);
             return Ok(());
         }
 
-        msg!("Sending amount to recipient");
+        // Queue the proposal, holders who disagree can exit until the eta
+        let eta =
+            ctx.accounts.clock.unix_timestamp + ctx.accounts.governance.execution_delay_seconds;
+        msg!("Proposal is queued until: {}", eta);
+        let proposal_data = &mut ctx.accounts.proposal;
+        proposal_data.state = ProposalState::Succeeded;
+        proposal_data.eta = eta;
+
+        Ok(())
+    }
+
+    /// Execute queued payment or executable proposal: transfer the payment amount to recipient
+    /// or invoke every proposal instruction with the treasury owner as signer.
+    /// Proposal can be executed only once, after the eta and before the grace period expires.
+    /// Proposal is marked as `Expired` if the grace period is passed.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[]` Governance account
+    /// 1. `[writable]` Proposal account
+    /// 2. `[writable]` Governance treasury account
+    /// 3. `[]` Governance treasury owner
+    /// 4. `[]` Governance treasury mint
+    /// 5. `[writable]` Recipient SPL Token account
+    /// 6. `[]` SPL Token program
+    /// 7. `[]` Clock sysvar
+    /// 8+N. `[]` The N accounts and programs used by the proposal instructions
+    pub fn execute_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteProposal<'info>>,
+    ) -> ProgramResult {
+        msg!("Instruction: execute proposal");
+
+        if ctx.accounts.proposal.kind == ProposalKind::ConfigChange {
+            msg!("Config change proposal must be applied");
+            return Err(GovernanceError::NotExecutableProposal.into());
+        }
+        if ctx
+            .accounts
+            .proposal
+            .is_expired(ctx.accounts.clock.unix_timestamp)
+        {
+            msg!("Proposal execution grace period is expired");
+            ctx.accounts.proposal.state = ProposalState::Expired;
+            return Ok(());
+        }
+        ctx.accounts
+            .proposal
+            .check_execution(ctx.accounts.clock.unix_timestamp)?;
+
+        // Save the state before invoking, so the instructions can't execute proposal again
+        ctx.accounts.proposal.state = ProposalState::Executed;
+        ctx.accounts.proposal.exit(ctx.program_id)?;
+
         let seeds = &[
             TREASURY_PREFIX.as_bytes(),
             ctx.program_id.as_ref(),
@@ -527,18 +680,129 @@ pub mod governance {
             &[ctx.accounts.governance.treasury_owner_bump],
         ];
         let signer = &[&seeds[..]];
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.clone(),
-                Transfer {
-                    from: ctx.accounts.treasury.to_account_info().clone(),
-                    to: ctx.accounts.recipient.to_account_info().clone(),
-                    authority: ctx.accounts.treasury_owner.clone(),
-                },
-                signer,
-            ),
-            ctx.accounts.proposal.payment_amount,
-        )?;
+        if ctx.accounts.proposal.kind == ProposalKind::Payment {
+            msg!("Sending amount to recipient");
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.clone(),
+                    Transfer {
+                        from: ctx.accounts.treasury.to_account_info().clone(),
+                        to: ctx.accounts.recipient.to_account_info().clone(),
+                        authority: ctx.accounts.treasury_owner.clone(),
+                    },
+                    signer,
+                ),
+                ctx.accounts.proposal.payment_amount,
+            )?;
+        } else {
+            let mut account_infos = ctx.remaining_accounts.to_vec();
+            account_infos.push(ctx.accounts.treasury_owner.clone());
+            for instruction in &ctx.accounts.proposal.instructions {
+                invoke_signed(&instruction.to_instruction(), &account_infos, signer)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply the config of queued config change proposal to the governance.
+    /// Proposal can be applied only once, after the eta and before the grace period expires.
+    /// Proposal is marked as `Expired` if the grace period is passed. The new approval period
+    /// doesn't change the voting end of the proposals already made.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` Governance account
+    /// 1. `[writable]` Proposal account
+    /// 2. `[]` Clock sysvar
+    pub fn apply_config_proposal(ctx: Context<ApplyConfigProposal>) -> ProgramResult {
+        msg!("Instruction: apply config proposal");
+
+        if ctx.accounts.proposal.kind != ProposalKind::ConfigChange {
+            msg!("Proposal has no config to apply");
+            return Err(GovernanceError::NotConfigProposal.into());
+        }
+        if ctx
+            .accounts
+            .proposal
+            .is_expired(ctx.accounts.clock.unix_timestamp)
+        {
+            msg!("Proposal execution grace period is expired");
+            ctx.accounts.proposal.state = ProposalState::Expired;
+            return Ok(());
+        }
+        ctx.accounts
+            .proposal
+            .check_execution(ctx.accounts.clock.unix_timestamp)?;
+
+        let config = ctx.accounts.proposal.config;
+        config.validate()?;
+
+        let governance_data = &mut ctx.accounts.governance;
+        governance_data.approval_fixed_period_in_seconds = config.approval_fixed_period_in_seconds;
+        governance_data.min_approval_percent = config.min_approval_percent;
+        governance_data.min_stake_to_propose = config.min_stake_to_propose;
+        governance_data.min_vote_participation_percent = config.min_vote_participation_percent;
+        governance_data.payment_period_sec = config.payment_period_sec;
+        governance_data.execution_delay_seconds = config.execution_delay_seconds;
+
+        ctx.accounts.proposal.state = ProposalState::Executed;
+
+        Ok(())
+    }
+
+    /// Cancel the queued proposal before its execution.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[]` Governance account
+    /// 1. `[writable]` Proposal account
+    /// 2+N. `[singer]` The N signer accounts of current sponsors. Needs the 60% of sponsors to approve this instruction.
+    pub fn cancel_queued_proposal(ctx: Context<CancelQueuedProposal>) -> ProgramResult {
+        msg!("Instruction: cancel queued proposal");
+
+        match ctx.accounts.proposal.state {
+            ProposalState::Succeeded => {}
+            ProposalState::Executed => {
+                msg!("Proposal is already executed");
+                return Err(GovernanceError::ProposalAlreadyExecuted.into());
+            }
+            ProposalState::Cancelled => {
+                msg!("Proposal is already cancelled");
+                return Err(GovernanceError::CancelledProposal.into());
+            }
+            _ => {
+                msg!("Proposal is not queued");
+                return Err(GovernanceError::ProposalNotApproved.into());
+            }
+        }
+
+        ctx.accounts
+            .governance
+            .check_sponsors_signatures(ctx.remaining_accounts)?;
+
+        msg!("Proposal cancelling approved");
+        ctx.accounts.proposal.state = ProposalState::Cancelled;
+
+        Ok(())
+    }
+
+    /// Cancel the proposal by its author before any votes.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` Proposal account
+    /// 1. `[signer]` Proposal author (token wallet owner who made the proposal)
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> ProgramResult {
+        msg!("Instruction: cancel proposal");
+
+        if ctx.accounts.proposal.state != ProposalState::Voting {
+            msg!("Proposal is already closed");
+            return Err(GovernanceError::ClosedProposal.into());
+        }
+        if ctx.accounts.proposal.pros_weight > 0 || ctx.accounts.proposal.cons_weight > 0 {
+            msg!("Proposal already has votes");
+            return Err(GovernanceError::ProposalHasVotes.into());
+        }
+
+        ctx.accounts.proposal.state = ProposalState::Cancelled;
 
         Ok(())
     }
@@ -552,6 +816,7 @@ pub mod governance {
     min_stake_to_propose: u64,
     min_vote_participation_percent: u8,
     payment_period_sec: i64,
+    execution_delay_seconds: i64,
     treasury_owner_bump: u8,
     sponsors: Vec<Pubkey>
 )]
@@ -695,7 +960,98 @@ pub struct MakeProposal<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(vote: bool, marker_bump: u8)]
+#[instruction(_proposal_bump: u8, ipfs_hash: String)]
+pub struct MakeExecutableProposal<'info> {
+    #[account(mut)]
+    governance: ProgramAccount<'info, Governance>,
+    #[account(constraint = treasury.owner == treasury_owner.key())]
+    treasury: CpiAccount<'info, TokenAccount>,
+    #[account(mut,
+        constraint = treasury_stats.treasury == treasury.key()
+    )]
+    treasury_stats: ProgramAccount<'info, TreasuryStats>,
+    // pda of ["treasury", governance_program_id, governance_data]
+    #[account(seeds = [
+            TREASURY_PREFIX.as_bytes(),
+            program_id.as_ref(),
+            governance.key().as_ref()
+        ],
+        bump = governance.treasury_owner_bump
+    )]
+    treasury_owner: AccountInfo<'info>,
+    #[account(
+        constraint = staking_user_data.staking_data == governance.staking_data,
+        constraint = staking_user_data.user_token_wallet == user_token_wallet.key()
+    )]
+    staking_user_data: CpiAccount<'info, StakingUserData>,
+    #[account(
+        constraint = user_token_wallet.owner == *user_token_wallet_owner.key,
+    )]
+    user_token_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(signer)]
+    user_token_wallet_owner: AccountInfo<'info>,
+    // pda of ['proposal', governance_program_id, governance, ipfs_hash[0..32]]
+    #[account(init, payer = payer, space = Proposal::EXECUTABLE_LEN,
+        seeds = [
+            PROPOSAL_PREFIX.as_bytes(),
+            program_id.as_ref(),
+            governance.key().as_ref(),
+            ipfs_hash[..MAX_SEED_LEN].as_bytes()
+        ],
+        bump = _proposal_bump,
+    )]
+    proposal: ProgramAccount<'info, Proposal>,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    rent: Sysvar<'info, Rent>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+#[instruction(_proposal_bump: u8, ipfs_hash: String)]
+pub struct MakeConfigProposal<'info> {
+    governance: ProgramAccount<'info, Governance>,
+    #[account(constraint = treasury.owner == treasury_owner.key())]
+    treasury: CpiAccount<'info, TokenAccount>,
+    // pda of ["treasury", governance_program_id, governance_data]
+    #[account(seeds = [
+            TREASURY_PREFIX.as_bytes(),
+            program_id.as_ref(),
+            governance.key().as_ref()
+        ],
+        bump = governance.treasury_owner_bump
+    )]
+    treasury_owner: AccountInfo<'info>,
+    #[account(
+        constraint = staking_user_data.staking_data == governance.staking_data,
+        constraint = staking_user_data.user_token_wallet == user_token_wallet.key()
+    )]
+    staking_user_data: CpiAccount<'info, StakingUserData>,
+    #[account(
+        constraint = user_token_wallet.owner == *user_token_wallet_owner.key,
+    )]
+    user_token_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(signer)]
+    user_token_wallet_owner: AccountInfo<'info>,
+    // pda of ['proposal', governance_program_id, governance, ipfs_hash[0..32]]
+    #[account(init, payer = payer, space = Proposal::LEN,
+        seeds = [
+            PROPOSAL_PREFIX.as_bytes(),
+            program_id.as_ref(),
+            governance.key().as_ref(),
+            ipfs_hash[..MAX_SEED_LEN].as_bytes()
+        ],
+        bump = _proposal_bump,
+    )]
+    proposal: ProgramAccount<'info, Proposal>,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    rent: Sysvar<'info, Rent>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+#[instruction(vote: bool, marker_bump: u8, locker_bump: u8)]
 pub struct ApproveProposal<'info> {
     governance: ProgramAccount<'info, Governance>,
     #[account(mut,
@@ -727,6 +1083,14 @@ pub struct ApproveProposal<'info> {
         bump = marker_bump,
     )]
     vote_marker: ProgramAccount<'info, VoteMarker>,
+    // Locker authority: pda of ['locker_authority'], proves the lock is placed by governance
+    #[account(
+        seeds = [staking::LOCKER_AUTHORITY_PREFIX.as_bytes()],
+        bump = locker_bump,
+    )]
+    locker_authority: AccountInfo<'info>,
+    #[account(constraint = governance_program.key == program_id)]
+    governance_program: AccountInfo<'info>,
     payer: AccountInfo<'info>,
     staking_program: AccountInfo<'info>,
     system_program: AccountInfo<'info>,
@@ -740,7 +1104,19 @@ pub struct FinalizeProposal<'info> {
         constraint = governance.staking_data == staking_data.key()
     )]
     staking_data: CpiAccount<'info, StakingData>,
-    #[account(mut)]
+    #[account(mut,
+        constraint = proposal.governance == governance.key()
+    )]
+    proposal: ProgramAccount<'info, Proposal>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    governance: ProgramAccount<'info, Governance>,
+    #[account(mut,
+        constraint = proposal.governance == governance.key()
+    )]
     proposal: ProgramAccount<'info, Proposal>,
     #[account(mut,
         constraint = treasury.owner == treasury_owner.key(),
@@ -765,6 +1141,38 @@ pub struct FinalizeProposal<'info> {
     #[account(constraint = token_program.key == &anchor_spl::token::ID)]
     token_program: AccountInfo<'info>,
     clock: Sysvar<'info, Clock>,
+    // Instructions accounts and programs are described in ctx.remaining_accounts
+}
+
+#[derive(Accounts)]
+pub struct ApplyConfigProposal<'info> {
+    #[account(mut)]
+    governance: ProgramAccount<'info, Governance>,
+    #[account(mut,
+        constraint = proposal.governance == governance.key()
+    )]
+    proposal: ProgramAccount<'info, Proposal>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct CancelQueuedProposal<'info> {
+    governance: ProgramAccount<'info, Governance>,
+    #[account(mut,
+        constraint = proposal.governance == governance.key()
+    )]
+    proposal: ProgramAccount<'info, Proposal>,
+    // Other accounts is signers, described in ctx.remaining_accounts
+}
+
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    #[account(mut,
+        constraint = proposal.author == *author.key
+    )]
+    proposal: ProgramAccount<'info, Proposal>,
+    #[account(signer)]
+    author: AccountInfo<'info>,
 }
 
 // Program accounts
@@ -779,6 +1187,15 @@ pub const MIN_SPONSORS: usize = 3;
 pub const MAX_SPONSORS: usize = 16;
 pub const MAX_SPONSORS_VEC_SIZE: usize = 4 + 32 * MAX_SPONSORS;
 pub const SPONSORS_LIST_APPROVAL_PERCENT: u8 = 60;
+pub const MAX_APPROVAL_FIXED_PERIOD_IN_SECONDS: i64 = 90 * 24 * 60 * 60;
+pub const MIN_CONFIG_APPROVAL_PERCENT: u8 = 50;
+pub const MAX_EXECUTION_DELAY_SECONDS: i64 = 30 * 24 * 60 * 60;
+pub const EXECUTION_GRACE_PERIOD_SECONDS: i64 = 14 * 24 * 60 * 60;
+pub const MAX_PROPOSAL_INSTRUCTIONS: usize = 4;
+pub const MAX_PROPOSAL_INSTRUCTION_ACCOUNTS: usize = 12;
+pub const MAX_PROPOSAL_INSTRUCTION_DATA_LEN: usize = 128;
+pub const MAX_PROPOSAL_INSTRUCTIONS_VEC_SIZE: usize =
+    4 + ProposalInstruction::LEN * MAX_PROPOSAL_INSTRUCTIONS;
 
 /// Account for storing common information about Governance.
 #[account]
@@ -791,13 +1208,66 @@ pub struct Governance {
     pub payment_period_sec: i64,
     pub min_approval_percent: u8,
     pub treasury_owner_bump: u8,
+    /// Timelock between proposal approval and execution
+    pub execution_delay_seconds: i64,
     /// Pubkeys list of staking user data accounts owners
     pub sponsors: Vec<Pubkey>,
 }
 
 impl Governance {
     pub const LEN: usize =
-        DISCRIMINATOR_LEN + 32 + 8 + 8 + 1 + 8 + 8 + 1 + 1 + MAX_SPONSORS_VEC_SIZE;
+        DISCRIMINATOR_LEN + 32 + 8 + 8 + 1 + 8 + 8 + 1 + 1 + 8 + MAX_SPONSORS_VEC_SIZE;
+
+    /// Check the 60% of sponsors signed the instruction (signers are in remaining accounts)
+    pub fn check_sponsors_signatures(&self, signers: &[AccountInfo]) -> ProgramResult {
+        if signers.is_empty() {
+            msg!("Not enough sponsors signatures");
+            return Err(GovernanceError::MissingSponsorsSignatures.into());
+        }
+
+        // There is no access to HashSet
+        let mut sponsors_keys_set = vec![];
+        let mut signature_count = 0;
+        for account in signers {
+            if sponsors_keys_set.contains(account.key) {
+                msg!("Skip sponsor key duplicate");
+                continue;
+            }
+            sponsors_keys_set.push(account.key());
+
+            if self.sponsors.contains(account.key) && account.is_signer {
+                signature_count += 1;
+            }
+        }
+
+        let signature_count = signature_count as f64;
+        let sponsors_len = self.sponsors.len() as f64;
+        let signers_percent = ((signature_count / sponsors_len) * 100.0) as u8;
+        msg!("Signers percent: {}", signers_percent);
+        if signers_percent < SPONSORS_LIST_APPROVAL_PERCENT {
+            msg!("Not enough sponsors signatures");
+            return Err(GovernanceError::MissingSponsorsSignatures.into());
+        }
+
+        Ok(())
+    }
+
+    /// Check the user has enough staked tokens to make proposal
+    pub fn check_proposer(&self, staking_user_data: &StakingUserData) -> ProgramResult {
+        // Check sender has staked tokens
+        if staking_user_data.ownership_share == 0 {
+            msg!("Only Staking token holders can make proposals");
+            return Err(GovernanceError::InvalidStakingHolder.into());
+        }
+
+        // Check min_stake_to_propose
+        if staking_user_data.ownership_share < self.min_stake_to_propose {
+            msg!("There is not enough staked tokens to make proposal");
+            return Err(GovernanceError::InsufficientStakedTokens.into());
+        }
+
+        Ok(())
+    }
 }
 
 /// Account for storing information about treasury for current SPL Token
@@ -812,24 +1282,319 @@ pub struct TreasuryStats {
 
 impl TreasuryStats {
     pub const LEN: usize = DISCRIMINATOR_LEN + 32 + 1 + 1 + 8 + 8;
+
+    /// Check the proposal payment limits and count the payment in the current payment period
+    pub fn count_payment(
+        &mut self,
+        governance: &mut Governance,
+        treasury_amount: u64,
+        payment_amount: u64,
+        unix_timestamp: i64,
+    ) -> ProgramResult {
+        // Check payment amount
+        if payment_amount > treasury_amount {
+            msg!("There is not enough tokens in governance to make proposal");
+            return Err(GovernanceError::InsufficientFunds.into());
+        }
+
+        // Check max proposal amount limit (percent)
+        let highest_balance = self.highest_balance as f64;
+        let max_proposal_payment_percent = self.max_proposal_payment_percent as f64 / 100.0;
+        let max_proposal_payment_amount = (highest_balance * max_proposal_payment_percent) as u64;
+        if payment_amount > max_proposal_payment_amount {
+            msg!("Max proposal payment amount limit exceeded");
+            return Err(GovernanceError::MaxProposalAmountLimit.into());
+        }
+
+        // Check ending of the period
+        let period_ending_time = governance.payment_period_start + governance.payment_period_sec;
+        if unix_timestamp >= period_ending_time {
+            // Reset payment counter if period ends
+            governance.payment_period_start = unix_timestamp;
+            self.payment_amount_in_period = 0;
+        }
+
+        // Check payment amount in period limit (percent)
+        let payment_amount_in_period_limit_percent =
+            self.payment_amount_in_period_limit_percent as f64 / 100.0;
+        let payment_amount_in_period_limit =
+            (highest_balance * payment_amount_in_period_limit_percent) as u64;
+        if (payment_amount + self.payment_amount_in_period) > payment_amount_in_period_limit {
+            msg!("This proposal exceeds the maximum amount that can be paid for current period");
+            return Err(GovernanceError::PaymentAmountLimitExceeded.into());
+        }
+
+        // update treasury stats
+        self.payment_amount_in_period += payment_amount;
+
+        Ok(())
+    }
 }
 
 /// Account for storing common information about Proposal.
 #[account]
 pub struct Proposal {
     pub governance: Pubkey,
+    /// Token wallet owner who made the proposal
+    pub author: Pubkey,
     pub starting_timestamp: i64,
-    pub is_closed: bool,
+    /// End of the voting, fixed when the proposal is made
+    pub voting_ends_at: i64,
+    pub state: ProposalState,
+    /// Why the proposal is defeated (for `Defeated` state)
+    pub defeat_reason: ProposalDefeatReason,
     pub recipient: Pubkey,
     pub payment_amount: u64,
     pub ipfs_hash: String,
     pub pros_weight: u64,
     pub cons_weight: u64,
     pub is_sponsored: bool,
+    /// Voting weight is the user shares before this slot
+    pub snapshot_slot: u64,
+    pub snapshot_timestamp: i64,
+    pub kind: ProposalKind,
+    /// Instructions of executable proposal, invoked with treasury owner as signer
+    pub instructions: Vec<ProposalInstruction>,
+    /// New governance config of config change proposal
+    pub config: GovernanceConfig,
+    /// Approved proposal is queued and can be executed from this timestamp
+    pub eta: i64,
 }
 
 impl Proposal {
-    pub const LEN: usize = DISCRIMINATOR_LEN + 32 + 8 + 1 + 32 + 8 + MAX_IPFS_HASH_LEN + 4 + 4 + 1;
+    /// Size of payment proposal (empty instructions list)
+    pub const LEN: usize = DISCRIMINATOR_LEN
+        + 32
+        + 32
+        + 8
+        + 8
+        + 1
+        + 1
+        + 32
+        + 8
+        + 4
+        + MAX_IPFS_HASH_LEN
+        + 8
+        + 8
+        + 1
+        + 8
+        + 8
+        + 1
+        + 4
+        + GovernanceConfig::LEN
+        + 8;
+    /// Size of executable proposal
+    pub const EXECUTABLE_LEN: usize = Proposal::LEN - 4 + MAX_PROPOSAL_INSTRUCTIONS_VEC_SIZE;
+
+    /// Initialize new proposal in `Voting` state without payment, instructions and config
+    pub fn init(
+        &mut self,
+        governance: &ProgramAccount<Governance>,
+        author: Pubkey,
+        ipfs_hash: String,
+        kind: ProposalKind,
+        clock: &Clock,
+    ) {
+        self.governance = governance.key();
+        self.author = author;
+        self.state = ProposalState::Voting;
+        self.defeat_reason = ProposalDefeatReason::None;
+        self.starting_timestamp = clock.unix_timestamp;
+        self.voting_ends_at = clock.unix_timestamp + governance.approval_fixed_period_in_seconds;
+        // Votes are weighted by the shares before the proposal slot
+        self.snapshot_slot = clock.slot;
+        self.snapshot_timestamp = clock.unix_timestamp;
+        self.payment_amount = 0;
+        self.ipfs_hash = ipfs_hash;
+        self.pros_weight = 0;
+        self.cons_weight = 0;
+        self.is_sponsored = false;
+        self.kind = kind;
+        self.instructions = vec![];
+        self.eta = 0;
+        self.config = GovernanceConfig::default();
+    }
+
+    /// Check the proposal is queued and can be executed at the provided time
+    pub fn check_execution(&self, unix_timestamp: i64) -> ProgramResult {
+        match self.state {
+            ProposalState::Succeeded => {}
+            ProposalState::Cancelled => {
+                msg!("Proposal is cancelled");
+                return Err(GovernanceError::CancelledProposal.into());
+            }
+            ProposalState::Executed => {
+                msg!("Proposal is already executed");
+                return Err(GovernanceError::ProposalAlreadyExecuted.into());
+            }
+            ProposalState::Expired => {
+                msg!("Proposal execution grace period is expired");
+                return Err(GovernanceError::ProposalExpired.into());
+            }
+            ProposalState::Voting | ProposalState::Defeated => {
+                msg!("Proposal is not approved");
+                return Err(GovernanceError::ProposalNotApproved.into());
+            }
+        }
+        if unix_timestamp < self.eta {
+            msg!("Proposal is queued until: {}", self.eta);
+            return Err(GovernanceError::ProposalInTimelock.into());
+        }
+
+        Ok(())
+    }
+
+    /// Queued proposal which is not executed during the grace period
+    pub fn is_expired(&self, unix_timestamp: i64) -> bool {
+        self.state == ProposalState::Succeeded
+            && unix_timestamp >= self.eta + EXECUTION_GRACE_PERIOD_SECONDS
+    }
+
+    /// Close the proposal without approval
+    pub fn defeat(&mut self, reason: ProposalDefeatReason) {
+        self.state = ProposalState::Defeated;
+        self.defeat_reason = reason;
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum ProposalState {
+    /// The vote is in progress
+    Voting,
+    /// Cancelled by the author before votes or by sponsors while queued
+    Cancelled,
+    /// Finalized without approval, see the defeat reason
+    Defeated,
+    /// Approved and queued for execution
+    Succeeded,
+    Executed,
+    /// Not executed during the grace period
+    Expired,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum ProposalDefeatReason {
+    None,
+    /// The minimum participation percent not reached
+    Quorum,
+    /// The minimum approval percent not reached
+    Approval,
+    NotSponsored,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum ProposalKind {
+    /// Transfer payment amount to recipient
+    Payment,
+    /// Execute proposal instructions
+    Executable,
+    /// Apply new governance config
+    ConfigChange,
+}
+
+/// Governance parameters which can be changed by config change proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct GovernanceConfig {
+    pub approval_fixed_period_in_seconds: i64,
+    pub min_approval_percent: u8,
+    pub min_stake_to_propose: u64,
+    pub min_vote_participation_percent: u8,
+    pub payment_period_sec: i64,
+    pub execution_delay_seconds: i64,
+}
+
+impl GovernanceConfig {
+    pub const LEN: usize = 8 + 1 + 8 + 1 + 8 + 8;
+
+    /// Check the config is in sanity bounds
+    pub fn validate(&self) -> ProgramResult {
+        if self.approval_fixed_period_in_seconds <= 0
+            || self.approval_fixed_period_in_seconds > MAX_APPROVAL_FIXED_PERIOD_IN_SECONDS
+        {
+            msg!("Invalid approval fixed period");
+            return Err(GovernanceError::InvalidGovernanceConfig.into());
+        }
+        if self.min_approval_percent < MIN_CONFIG_APPROVAL_PERCENT
+            || self.min_approval_percent > 100
+        {
+            msg!("Invalid minimum approval percent");
+            return Err(GovernanceError::InvalidGovernanceConfig.into());
+        }
+        if self.min_vote_participation_percent > 100 {
+            msg!("Invalid minimum vote participation percent");
+            return Err(GovernanceError::InvalidGovernanceConfig.into());
+        }
+        if self.payment_period_sec <= 0 {
+            msg!("Invalid payment period");
+            return Err(GovernanceError::InvalidGovernanceConfig.into());
+        }
+        if !(0..=MAX_EXECUTION_DELAY_SECONDS).contains(&self.execution_delay_seconds) {
+            msg!("Invalid execution delay");
+            return Err(GovernanceError::InvalidGovernanceConfig.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Serialized instruction stored in executable proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ProposalInstruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<ProposalAccountMeta>,
+    pub data: Vec<u8>,
+}
+
+impl ProposalInstruction {
+    pub const LEN: usize = 32
+        + 4
+        + ProposalAccountMeta::LEN * MAX_PROPOSAL_INSTRUCTION_ACCOUNTS
+        + 4
+        + MAX_PROPOSAL_INSTRUCTION_DATA_LEN;
+
+    pub fn to_instruction(&self) -> Instruction {
+        let accounts = self
+            .accounts
+            .iter()
+            .map(|meta| {
+                if meta.is_writable {
+                    AccountMeta::new(meta.pubkey, meta.is_signer)
+                } else {
+                    AccountMeta::new_readonly(meta.pubkey, meta.is_signer)
+                }
+            })
+            .collect();
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: self.data.clone(),
+        }
+    }
+
+    /// Amount of SPL Token transfer from the treasury, fails for other token instructions
+    pub fn treasury_transfer_amount(&self, treasury: &Pubkey) -> Result<u64> {
+        let from_treasury = self.accounts.first().map(|meta| meta.pubkey) == Some(*treasury);
+        match TokenInstruction::unpack(&self.data) {
+            Ok(TokenInstruction::Transfer { amount }) if from_treasury => Ok(amount),
+            Ok(TokenInstruction::TransferChecked { amount, .. }) if from_treasury => Ok(amount),
+            _ => {
+                msg!("Only SPL Token transfers from the treasury can be signed by treasury owner");
+                Err(GovernanceError::InvalidProposalInstructions.into())
+            }
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ProposalAccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl ProposalAccountMeta {
+    pub const LEN: usize = 32 + 1 + 1;
 }
 
 #[account]
@@ -875,4 +1640,28 @@ pub enum GovernanceError {
     InvalidMaxProposalPaymentPercent,
     #[msg("Invalid payment amount in period percent")]
     InvalidPaymentAmountInPeriodPercent,
+    #[msg("Invalid proposal instructions")]
+    InvalidProposalInstructions,
+    #[msg("Config change proposal must be applied")]
+    NotExecutableProposal,
+    #[msg("Proposal is not approved")]
+    ProposalNotApproved,
+    #[msg("Proposal is already executed")]
+    ProposalAlreadyExecuted,
+    #[msg("Proposal has no config to apply")]
+    NotConfigProposal,
+    #[msg("Governance config is out of bounds")]
+    InvalidGovernanceConfig,
+    #[msg("Invalid execution delay")]
+    InvalidExecutionDelay,
+    #[msg("Proposal is in timelock")]
+    ProposalInTimelock,
+    #[msg("Proposal execution grace period is expired")]
+    ProposalExpired,
+    #[msg("Proposal is cancelled")]
+    CancelledProposal,
+    #[msg("Proposal already has votes")]
+    ProposalHasVotes,
+    #[msg("Staked shares are below the snapshot shares")]
+    SharesBelowSnapshot,
 }
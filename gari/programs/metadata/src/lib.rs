@@ -79,21 +79,21 @@ pub mod metadata {
 pub struct CreateMetadata<'info> {
     /// pda of ['metadata', metadata_program_id, mint_id]
     #[account(mut)]
-    metadata: AccountInfo<'info>,
+    pub metadata: AccountInfo<'info>,
     #[account(mut, signer)]
-    payer: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
     #[account(
         constraint = mint.mint_authority == COption::Some(*mint_authority.key),
     )]
-    mint: CpiAccount<'info, Mint>,
+    pub mint: CpiAccount<'info, Mint>,
     #[account(signer)]
-    mint_authority: AccountInfo<'info>,
+    pub mint_authority: AccountInfo<'info>,
     #[account(address = spl_token_metadata::id())]
-    token_metadata_program: AccountInfo<'info>,
+    pub token_metadata_program: AccountInfo<'info>,
     #[account(address = anchor_spl::token::ID)]
-    token_program: AccountInfo<'info>,
-    system_program: AccountInfo<'info>,
-    rent: Sysvar<'info, Rent>,
+    pub token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
 #[error]
@@ -3,7 +3,8 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::clock::SECONDS_PER_DAY;
 use anchor_lang::solana_program::program_option::COption;
-use anchor_spl::token::{self, Mint, TokenAccount, Transfer};
+use anchor_lang::AccountsClose;
+use anchor_spl::token::{self, Burn, Mint, MintTo, TokenAccount, Transfer};
 use std::ops::Sub;
 
 pub mod utils;
@@ -87,26 +88,67 @@ pub mod staking {
         staking_data.holding_bump = holding_bump;
         staking_data.mint_auth_bump = mint_auth_bump;
         staking_data.cap = cap;
+        staking_data.lock_tiers = vec![];
+        staking_data.authorized_lockers = vec![];
+        staking_data.cooldown_seconds = 0;
+        staking_data.pending_withdrawals = 0;
+        staking_data.guardian = Pubkey::default();
+        staking_data.slasher = Pubkey::default();
+        staking_data.pause_level = PauseLevel::Unpaused;
+        staking_data.pending_owner = Pubkey::default();
+        staking_data.rate_schedule = vec![];
+        staking_data.rate_model = None;
+        staking_data.reward_streams = vec![];
+        staking_data.reward_mode = RewardMode::Mint;
+        staking_data.reward_reserve = 0;
+        staking_data.reserve_exhausted = false;
+        staking_data.early_unstake_penalty = 0;
+        staking_data.penalty_window_seconds = 0;
+        staking_data.penalty_wallet = staking_data.holding_wallet;
+        staking_data.deposit_fee_bps = 0;
+        staking_data.withdraw_fee_bps = 0;
+        staking_data.fee_collector = staking_data.holding_wallet;
+        staking_data.allow_list_enabled = false;
+        staking_data.user_deposit_cap = 0;
+        staking_data.total_deposit_cap = 0;
+        staking_data.receipt_mint = Pubkey::default();
+        staking_data.receipt_mint_auth_bump = 0;
+        staking_data.receipt_shares = 0;
 
         Ok(())
     }
 
     /// Initialize new StakingUserData account for provided StakingData. Every user who wants to stake tokens should
-    /// first initialize StakingUserData account.
+    /// first initialize StakingUserData account. In permissioned staking pool the user has to be in the allow-list.
     ///
     /// Accounts expected by this instruction:
     /// 0. `[writable, signer]` UserStakingData account
     /// 1. `[]` User Token Wallet (SPL Token Account)
     /// 2. `[signer]` User Token Wallet owner
     /// 3. `[]` Staking data account
-    /// 4. `[]` Staking token metadata
-    /// 5. `[]` System program
-    /// 6. `[]` Rent sysvar
+    /// 4. `[]` AllowListEntry account pda of ["allow_list", staking_program_id, staking_data, user_token_wallet_owner]
+    /// 5. `[]` Staking token metadata
+    /// 6. `[]` System program
+    /// 7. `[]` Rent sysvar
     pub fn initialize_staking_user(ctx: Context<InitializeStakingUser>) -> ProgramResult {
+        utils::check_allow_list(
+            ctx.program_id,
+            &ctx.accounts.staking_data,
+            ctx.accounts.user_token_wallet_owner.key,
+            &ctx.accounts.allow_list_entry,
+        )?;
+
         let staking_user_data = &mut ctx.accounts.staking_user_data;
         staking_user_data.user_token_wallet = *ctx.accounts.user_token_wallet.to_account_info().key;
         staking_user_data.staking_data = *ctx.accounts.staking_data.to_account_info().key;
         staking_user_data.ownership_share = 0;
+        staking_user_data.share_checkpoints = vec![];
+        staking_user_data.tier_locked_shares = 0;
+        staking_user_data.interest_mode = InterestMode::Compound;
+        staking_user_data.principal = 0;
+        staking_user_data.claimed_interest = 0;
+        staking_user_data.last_stake_timestamp = 0;
+        staking_user_data.pending_withdrawals = 0;
 
         Ok(())
     }
@@ -131,6 +173,8 @@ pub mod staking {
     ) -> ProgramResult {
         msg!("Instruction: set interest rate");
 
+        ctx.accounts.staking_data.check_not_paused()?;
+
         if new_interest_rate > ctx.accounts.staking_data.max_interest_rate_daily {
             msg!("Error: New interest rate is higher than allowed maximum");
             msg!("New interest rate: {}", new_interest_rate);
@@ -150,34 +194,61 @@ pub mod staking {
         )?;
 
         let staking_data = &mut ctx.accounts.staking_data;
+        let old_interest_rate = staking_data.interest_rate_daily;
         staking_data.interest_rate_daily = new_interest_rate;
 
+        emit!(InterestRateChangedEvent {
+            staking_data: staking_data.key(),
+            old_interest_rate,
+            new_interest_rate,
+            timestamp: ctx.accounts.clock.unix_timestamp,
+        });
+
         Ok(())
     }
 
-    /// Stake the tokens to staking pool. Accrues interest first.
+    /// Replace the interest rate schedule for provided StakingData account. Since every breakpoint
+    /// timestamp its daily rate replaces `interest_rate_daily`. Accrues interest first.
     ///
     /// Accounts expected by this instruction:
-    /// 0. `[writable]` StakingUserData account
-    /// 1. `[writable]` User SPL Token account
-    /// 2. `[signer]` User SPL Token owner
-    /// 3. `[writable]` StakingData account
-    /// 4. `[writable]` StakingHoldingWallet account
-    /// 5. `[]` TokenMetadata account
-    /// 6. `[writable]` TokenMetadata Mint account
-    /// 7. `[]` TokenMetadata Mint authority is pda of [staking_program_id, staking_data, token_metadata]
-    /// 8. `[]` SPL token program account
-    /// 9. `[]` Clock sysvar
+    /// 0. `[writable]` StakingData account
+    /// 1. `[signer]` StakingData owner
+    /// 2. `[writable]` StakingHoldingWallet account
+    /// 3. `[]` Staking Token Metadata account
+    /// 4. `[writable]` Token Mint account
+    /// 5. `[]` TokenMetadata Mint mint_authority (pda of [staking_program_id, staking_data, token_metadata])
+    /// 6. `[]` SPL Token program account
+    /// 7. `[]` Clock Sysvar
     ///
-    /// \param amount: the amount to stake
-    pub fn stake(ctx: Context<Stake>, amount: u64) -> ProgramResult {
-        msg!("Instruction: stake");
+    /// \param rate_schedule: breakpoints sorted by effective timestamp
+    pub fn set_rate_schedule(
+        ctx: Context<SetInterestRate>,
+        rate_schedule: Vec<RatePoint>,
+    ) -> ProgramResult {
+        msg!("Instruction: set rate schedule");
 
-        // Attempt to unlock amount
-        if ctx.accounts.clock.unix_timestamp >= ctx.accounts.staking_user_data.locked_until {
-            let staking_user_data = &mut ctx.accounts.staking_user_data;
-            staking_user_data.locked_amount = 0;
-            staking_user_data.locked_until = 0;
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        if ctx.accounts.staking_data.rate_model.is_some() && !rate_schedule.is_empty() {
+            msg!("Error: Rate schedule is not used with the rate model");
+            return Err(StakingError::InvalidRateSchedule.into());
+        }
+        if rate_schedule.len() > MAX_RATE_SCHEDULE_POINTS {
+            msg!("Error: Rate schedule points limit exceeded");
+            return Err(StakingError::InvalidRateSchedule.into());
+        }
+        for (index, point) in rate_schedule.iter().enumerate() {
+            if index > 0
+                && point.effective_timestamp <= rate_schedule[index - 1].effective_timestamp
+            {
+                msg!("Error: Rate schedule must be sorted by effective timestamp");
+                return Err(StakingError::InvalidRateSchedule.into());
+            }
+            if point.daily_rate > ctx.accounts.staking_data.max_interest_rate_daily {
+                msg!("Error: Scheduled interest rate is higher than allowed maximum");
+                msg!("Scheduled interest rate: {}", point.daily_rate);
+                return Err(StakingError::InvalidInterestRate.into());
+            }
         }
 
         // Accrue interest
@@ -192,80 +263,44 @@ pub mod staking {
             &ctx.accounts.clock,
         )?;
 
-        if amount == 0 {
-            msg!("Error: Stake amount cannot be zero");
-            return Err(StakingError::InvalidAmount.into());
-        }
-
-        if ctx.accounts.user_token_wallet.amount < amount {
-            msg!("Error: User's SPL wallet balance is less than requested stake amount");
-            return Err(ProgramError::InsufficientFunds);
-        }
-
-        // Transfer SPL Tokens from user wallet to holding wallet
-        token::transfer(
-            CpiContext::new(
-                ctx.accounts.token_program.clone(),
-                Transfer {
-                    from: ctx.accounts.user_token_wallet.to_account_info().clone(),
-                    to: ctx.accounts.holding_wallet.to_account_info().clone(),
-                    authority: ctx.accounts.user_token_wallet_owner.clone(),
-                },
-            ),
-            amount,
-        )?;
-
-        // Calculate new shares
-        let staking_data = &ctx.accounts.staking_data;
-        let new_shares = utils::calculate_new_shares(
-            staking_data.total_shares,
-            staking_data.total_staked,
-            amount,
-        );
-        msg!("New shares: {}", new_shares);
-
-        // Save accounts data
         let staking_data = &mut ctx.accounts.staking_data;
-        let staking_user_data = &mut ctx.accounts.staking_user_data;
-        staking_data.total_staked = staking_data.total_staked.checked_add(amount).unwrap();
-        staking_data.total_shares = staking_data.total_shares.checked_add(new_shares).unwrap();
-        staking_user_data.ownership_share = staking_user_data
-            .ownership_share
-            .checked_add(new_shares)
-            .unwrap();
+        staking_data.rate_schedule = rate_schedule;
 
         Ok(())
     }
 
-    /// User takes tokens from deposit with interest rate. Accrues interest first.
+    /// Set the utilization based rate model for provided StakingData account. With the model
+    /// the daily rate is calculated from `total_staked / mint.supply` and the rate schedule is cleared.
+    /// None returns the pool to the fixed interest rate. Accrues interest first.
     ///
     /// Accounts expected by this instruction:
-    /// 0. `[writable]` StakingUserData account
-    /// 1. `[writable]` User SPL Token account
-    /// 2. `[signer]` User SPL Token owner
-    /// 3. `[writable]` StakingData account
-    /// 4. `[writable]` StakingHoldingWallet account
-    /// 5. `[]`  StakingHoldingWallet owner (pda of [staking_program_id, staking_data])
-    /// 6. `[]` TokenMetadata account
-    /// 7. `[writable]` TokenMetadata Mint account
-    /// 8. `[]` TokenMetadata Mint authority (pda of [staking_program_id, staking_data, token_metadata])
-    /// 9. `[]` SPL token program account
-    /// 10. `[]` Clock sysvar
+    /// 0. `[writable]` StakingData account
+    /// 1. `[signer]` StakingData owner
+    /// 2. `[writable]` StakingHoldingWallet account
+    /// 3. `[]` Staking Token Metadata account
+    /// 4. `[writable]` Token Mint account
+    /// 5. `[]` TokenMetadata Mint mint_authority (pda of [staking_program_id, staking_data, token_metadata])
+    /// 6. `[]` SPL Token program account
+    /// 7. `[]` Clock Sysvar
     ///
-    /// \param amount: the amount to unstake
-    pub fn unstake(ctx: Context<Unstake>, amount: u64) -> ProgramResult {
-        msg!("Instruction: unstake");
+    /// \param rate_model: kinked rate model or None for the fixed rate
+    pub fn set_rate_model(
+        ctx: Context<SetInterestRate>,
+        rate_model: Option<RateModel>,
+    ) -> ProgramResult {
+        msg!("Instruction: set rate model");
 
-        // Attempt to unlock amount
-        if ctx.accounts.clock.unix_timestamp >= ctx.accounts.staking_user_data.locked_until {
-            let staking_user_data = &mut ctx.accounts.staking_user_data;
-            staking_user_data.locked_amount = 0;
-            staking_user_data.locked_until = 0;
-        }
+        ctx.accounts.staking_data.check_not_paused()?;
 
-        if amount == 0 {
-            msg!("Error: Unstake amount cannot be zero");
-            return Err(StakingError::InvalidAmount.into());
+        if let Some(rate_model) = rate_model {
+            if rate_model.kink > utils::UTILIZATION_MUL_FACTOR {
+                msg!("Error: Rate model kink is higher than 100%");
+                return Err(StakingError::InvalidRateModel.into());
+            }
+            if rate_model.base_rate > ctx.accounts.staking_data.max_interest_rate_daily {
+                msg!("Error: Rate model base rate is higher than allowed maximum");
+                return Err(StakingError::InvalidInterestRate.into());
+            }
         }
 
         // Accrue interest
@@ -280,45 +315,115 @@ pub mod staking {
             &ctx.accounts.clock,
         )?;
 
+        let supply = ctx.accounts.mint.supply;
         let staking_data = &mut ctx.accounts.staking_data;
-        let shares_to_burn = utils::calculate_shares_to_burn(
-            staking_data.total_shares,
-            staking_data.total_staked,
-            amount,
-        );
-        msg!("Shares to burn: {}", shares_to_burn);
-
-        if shares_to_burn == 0 {
-            msg!("Staking: User tries to unstake 0 or there are no stakers");
-            return Err(StakingError::UnstakeFundsError.into());
+        staking_data.rate_model = rate_model;
+        if let Some(rate_model) = rate_model {
+            staking_data.rate_schedule = vec![];
+            staking_data.interest_rate_daily = utils::calculate_utilization_interest_rate(
+                &rate_model,
+                staking_data.total_staked,
+                supply,
+                staking_data.max_interest_rate_daily,
+            );
         }
 
-        let avail_amount;
-        if ctx.accounts.staking_user_data.locked_amount
-            > ctx.accounts.staking_user_data.ownership_share
-        {
-            avail_amount = ctx.accounts.staking_user_data.locked_amount;
-        } else {
-            avail_amount = ctx.accounts.staking_user_data.ownership_share
-                - ctx.accounts.staking_user_data.locked_amount;
-        }
-        if shares_to_burn > avail_amount {
-            msg!("Staking: User tries to unstake more than their available balance");
-            return Err(ProgramError::InsufficientFunds);
+        Ok(())
+    }
+
+    /// Set the source of the interest for provided StakingData account. Mint mode mints new
+    /// staking tokens with the mint authority PDA, reserve mode pays the interest from the reward
+    /// reserve and doesn't need the mint authority. Interest to date should be accrued first,
+    /// otherwise it is paid in the new mode.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingData account
+    /// 1. `[signer]` StakingData owner
+    ///
+    /// \param reward_mode: new source of the interest
+    pub fn set_reward_mode(ctx: Context<SetRewardMode>, reward_mode: RewardMode) -> ProgramResult {
+        msg!("Instruction: set reward mode");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        staking_data.reward_mode = reward_mode;
+        staking_data.reserve_exhausted = false;
+
+        Ok(())
+    }
+
+    /// Transfer the tokens to the reward reserve of the staking pool. Reserve tokens are stored
+    /// in the holding wallet and pay the interest in reserve mode.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingData account
+    /// 1. `[writable]` StakingHoldingWallet account
+    /// 2. `[writable]` Funder SPL Token account
+    /// 3. `[signer]` Funder SPL Token owner
+    /// 4. `[]` SPL token program account
+    ///
+    /// \param amount: the amount to add to the reserve
+    pub fn fund_reward_reserve(ctx: Context<FundRewardReserve>, amount: u64) -> ProgramResult {
+        msg!("Instruction: fund reward reserve");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        if amount == 0 {
+            msg!("Error: Fund amount cannot be zero");
+            return Err(StakingError::InvalidAmount.into());
         }
 
-        let staking_user_data = &mut ctx.accounts.staking_user_data;
-        staking_user_data.ownership_share = staking_user_data
-            .ownership_share
-            .checked_sub(shares_to_burn)
-            .unwrap();
-        staking_data.total_shares = staking_data
-            .total_shares
-            .checked_sub(shares_to_burn)
-            .unwrap();
-        staking_data.total_staked = staking_data.total_staked.checked_sub(amount).unwrap();
+        // Transfer SPL Tokens from funder wallet to holding wallet
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.clone(),
+                Transfer {
+                    from: ctx.accounts.funder_wallet.to_account_info().clone(),
+                    to: ctx.accounts.holding_wallet.to_account_info().clone(),
+                    authority: ctx.accounts.funder.clone(),
+                },
+            ),
+            amount,
+        )?;
 
-        // Transfer amount of tokens from holding wallet to user
+        let staking_data = &mut ctx.accounts.staking_data;
+        staking_data.reward_reserve = staking_data.reward_reserve.checked_add(amount).unwrap();
+        staking_data.reserve_exhausted = false;
+
+        Ok(())
+    }
+
+    /// Transfer the unused tokens of the reward reserve to the owner wallet, e.g. before switching
+    /// back to mint mode. Accrue interest first, otherwise the interest to date is paid from the
+    /// rest of the reserve.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingData account
+    /// 1. `[signer]` StakingData owner
+    /// 2. `[writable]` StakingHoldingWallet account
+    /// 3. `[]` StakingHoldingWallet owner (pda of [staking_program_id, staking_data])
+    /// 4. `[writable]` Recipient SPL Token account
+    /// 5. `[]` SPL token program account
+    ///
+    /// \param amount: the amount to withdraw from the reserve
+    pub fn withdraw_reward_reserve(
+        ctx: Context<WithdrawRewardReserve>,
+        amount: u64,
+    ) -> ProgramResult {
+        msg!("Instruction: withdraw reward reserve");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        if amount == 0 || amount > ctx.accounts.staking_data.reward_reserve {
+            msg!(
+                "Error: Withdraw amount must be from 1 to the reward reserve {}",
+                ctx.accounts.staking_data.reward_reserve
+            );
+            return Err(StakingError::InvalidAmount.into());
+        }
+
+        // Transfer SPL Tokens from holding wallet to recipient wallet
         let seeds = &[
             ctx.program_id.as_ref(),
             ctx.accounts.staking_data.to_account_info().key.as_ref(),
@@ -330,7 +435,7 @@ pub mod staking {
                 ctx.accounts.token_program.clone(),
                 Transfer {
                     from: ctx.accounts.holding_wallet.to_account_info().clone(),
-                    to: ctx.accounts.user_token_wallet.to_account_info().clone(),
+                    to: ctx.accounts.recipient_wallet.to_account_info().clone(),
                     authority: ctx.accounts.holding_wallet_owner.clone(),
                 },
                 signer,
@@ -338,21 +443,52 @@ pub mod staking {
             amount,
         )?;
 
+        let staking_data = &mut ctx.accounts.staking_data;
+        staking_data.reward_reserve = staking_data.reward_reserve.checked_sub(amount).unwrap();
+
         Ok(())
     }
 
-    /// Accrue the interest from the staked tokens
+    /// Stake the tokens to staking pool. Deposit fee is taken from the amount. Accrues interest first.
+    /// In receipt mode the new shares are minted as receipts instead of the StakingUserData shares.
     ///
     /// Accounts expected by this instruction:
-    /// 0. `[writable]` StakingData account
-    /// 1. `[writable]` StakingHoldingWallet account
-    /// 2. `[]` Token Metadata account
-    /// 3. `[writable]` Token Mint account
-    /// 4. `[]` Token Metadata Mint authority is pda of [staking_program_id, staking_data, token_metadata]
-    /// 5. `[]` SPL Token program account
-    /// 6. `[]` Clock sysvar
-    pub fn accrue_interest(ctx: Context<AccrueInterest>) -> ProgramResult {
-        msg!("Instruction: accrue interest");
+    /// 0. `[writable]` StakingUserData account
+    /// 1. `[writable]` User SPL Token account
+    /// 2. `[signer]` User SPL Token owner
+    /// 3. `[writable]` StakingData account
+    /// 4. `[]` AllowListEntry account pda of ["allow_list", staking_program_id, staking_data, user_token_wallet_owner]
+    /// 5. `[writable]` StakingHoldingWallet account
+    /// 6. `[writable]` Fee collector SPL Token account
+    /// 7. `[]` TokenMetadata account
+    /// 8. `[writable]` TokenMetadata Mint account
+    /// 9. `[]` TokenMetadata Mint authority is pda of [staking_program_id, staking_data, token_metadata]
+    /// 10. `[]` SPL token program account
+    /// 11. `[]` Clock sysvar
+    /// 12..N. `[writable]` Receipt mint, receipt mint authority (pda of ["receipt_mint", staking_program_id, staking_data])
+    ///       and receipt SPL Token account in receipt mode only, followed by RewardStream and UserReward
+    ///       account pairs for every StakingData reward stream
+    ///
+    /// \param amount: the amount to stake
+    pub fn stake<'info>(
+        ctx: Context<'_, '_, '_, 'info, Stake<'info>>,
+        amount: u64,
+    ) -> ProgramResult {
+        msg!("Instruction: stake");
+
+        ctx.accounts.staking_data.check_deposits_allowed()?;
+        utils::check_allow_list(
+            ctx.program_id,
+            &ctx.accounts.staking_data,
+            ctx.accounts.user_token_wallet_owner.key,
+            &ctx.accounts.allow_list_entry,
+        )?;
+
+        // Attempt to unlock amount
+        let unix_timestamp = ctx.accounts.clock.unix_timestamp;
+        ctx.accounts
+            .staking_user_data
+            .remove_expired_locks(unix_timestamp);
 
         // Accrue interest
         utils::accrue_interest_internal(
@@ -366,52 +502,3316 @@ pub mod staking {
             &ctx.accounts.clock,
         )?;
 
-        Ok(())
-    }
+        if amount == 0 {
+            msg!("Error: Stake amount cannot be zero");
+            return Err(StakingError::InvalidAmount.into());
+        }
 
-    /// Lock the current user ownership share. Instruction for cross program invocation
-    /// from Governance program.
-    pub fn lock_amount(ctx: Context<LockAmount>, until: i64, amount: u64) -> ProgramResult {
-        msg!("Instruction: lock amount");
-        if until < ctx.accounts.staking_user_data.locked_until {
-            msg!("Trying to unlock by time.");
-            return Err(StakingError::InvalidLockParams.into());
+        if ctx.accounts.user_token_wallet.amount < amount {
+            msg!("Error: User's SPL wallet balance is less than requested stake amount");
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        // Skim deposit fee, the rest is staked
+        let fee = utils::calculate_fee(amount, ctx.accounts.staking_data.deposit_fee_bps);
+        msg!("Deposit fee: {}", fee);
+        let amount = amount.checked_sub(fee).unwrap();
+        if amount == 0 {
+            msg!("Error: Stake amount without fee cannot be zero");
+            return Err(StakingError::InvalidAmount.into());
+        }
+        ctx.accounts
+            .staking_data
+            .check_deposit_caps(ctx.accounts.staking_user_data.ownership_share, amount)?;
+        let receipts_enabled = ctx.accounts.staking_data.receipts_enabled();
+        let (receipt_accounts, reward_accounts) =
+            utils::split_receipt_accounts(&ctx.accounts.staking_data, ctx.remaining_accounts, 3)?;
+
+        // Transfer fee from user wallet to fee collector
+        if fee > 0 {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.clone(),
+                    Transfer {
+                        from: ctx.accounts.user_token_wallet.to_account_info().clone(),
+                        to: ctx.accounts.fee_collector.to_account_info().clone(),
+                        authority: ctx.accounts.user_token_wallet_owner.clone(),
+                    },
+                ),
+                fee,
+            )?;
         }
-        if amount < ctx.accounts.staking_user_data.locked_amount {
-            msg!("Trying to unlock amount. Do nothing.");
-            return Ok(());
+
+        // Transfer SPL Tokens from user wallet to holding wallet
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.clone(),
+                Transfer {
+                    from: ctx.accounts.user_token_wallet.to_account_info().clone(),
+                    to: ctx.accounts.holding_wallet.to_account_info().clone(),
+                    authority: ctx.accounts.user_token_wallet_owner.clone(),
+                },
+            ),
+            amount,
+        )?;
+
+        // Calculate new shares
+        let staking_data = &ctx.accounts.staking_data;
+        let new_shares = utils::calculate_new_shares(
+            staking_data.total_shares,
+            staking_data.total_staked,
+            amount,
+        );
+        msg!("New shares: {}", new_shares);
+
+        // Settle reward streams before the shares change, receipts hold the shares in receipt mode
+        let user_new_shares = if receipts_enabled { 0 } else { new_shares };
+        utils::settle_rewards_internal(
+            ctx.program_id,
+            &ctx.accounts.staking_data,
+            &ctx.accounts.staking_user_data,
+            user_new_shares,
+            0,
+            reward_accounts,
+            &ctx.accounts.clock,
+        )?;
+
+        if receipts_enabled {
+            utils::mint_receipts_internal(
+                ctx.program_id,
+                &ctx.accounts.staking_data,
+                receipt_accounts,
+                &ctx.accounts.token_program,
+                new_shares,
+            )?;
         }
 
+        // Save accounts data
+        let staking_data = &mut ctx.accounts.staking_data;
         let staking_user_data = &mut ctx.accounts.staking_user_data;
-        staking_user_data.locked_amount = amount;
-        staking_user_data.locked_until = until;
+        staking_data.total_staked = staking_data.total_staked.checked_add(amount).unwrap();
+        staking_data.total_shares = staking_data.total_shares.checked_add(new_shares).unwrap();
+        if receipts_enabled {
+            staking_data.receipt_shares =
+                staking_data.receipt_shares.checked_add(new_shares).unwrap();
+        } else {
+            staking_user_data.ownership_share = staking_user_data
+                .ownership_share
+                .checked_add(new_shares)
+                .unwrap();
+            staking_user_data.checkpoint_shares(ctx.accounts.clock.slot);
+            staking_user_data.add_principal(
+                staking_data.total_shares,
+                staking_data.total_staked,
+                new_shares,
+            );
+        }
+        staking_user_data.last_stake_timestamp = ctx.accounts.clock.unix_timestamp;
+
+        emit!(StakeEvent {
+            staking_data: staking_data.key(),
+            staking_user_data: staking_user_data.key(),
+            user: *ctx.accounts.user_token_wallet_owner.key,
+            amount,
+            fee,
+            shares_minted: new_shares,
+            user_shares: staking_user_data.ownership_share,
+            total_staked: staking_data.total_staked,
+            total_shares: staking_data.total_shares,
+            timestamp: ctx.accounts.clock.unix_timestamp,
+        });
 
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-pub struct InitializeStaking<'info> {
-    #[account(init, payer = staking_owner, space = StakingData::LEN)]
-    staking_data: ProgramAccount<'info, StakingData>,
+    /// Set the list of lock tiers available for `stake_locked` for provided StakingData account.
+    /// Positions already locked keep their tier parameters.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingData account
+    /// 1. `[signer]` StakingData owner
+    ///
+    /// \param lock_tiers: new list of lock tiers (lock duration and shares boost)
+    pub fn set_lock_tiers(ctx: Context<SetLockTiers>, lock_tiers: Vec<LockTier>) -> ProgramResult {
+        msg!("Instruction: set lock tiers");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        if lock_tiers.len() > MAX_LOCK_TIERS {
+            msg!("Error: Lock tiers limit exceeded");
+            return Err(StakingError::InvalidLockTier.into());
+        }
+        for tier in &lock_tiers {
+            if tier.duration <= 0 {
+                msg!("Error: Lock tier duration must be positive");
+                return Err(StakingError::InvalidLockTier.into());
+            }
+            if tier.boost < utils::BOOST_MUL_FACTOR || tier.boost > MAX_LOCK_BOOST {
+                msg!("Error: Lock tier boost is out of range");
+                msg!("Boost: {}", tier.boost);
+                return Err(StakingError::InvalidLockTier.into());
+            }
+        }
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        staking_data.lock_tiers = lock_tiers;
+
+        Ok(())
+    }
+
+    /// Stake the tokens to staking pool for the lock duration of the chosen tier. The new shares are
+    /// boosted by the tier multiplier and can't be unstaked until the position is released after the
+    /// lock expires. Deposit fee is taken from the amount. Accrues interest first. Locked shares
+    /// can't be transferred, so in receipt mode they stay in StakingUserData and are moved into
+    /// receipts by mint_receipts after the release.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingUserData account
+    /// 1. `[writable, signer]` LockedStake account to initialize
+    /// 2. `[writable]` User SPL Token account
+    /// 3. `[writable, signer]` User SPL Token owner
+    /// 4. `[writable]` StakingData account
+    /// 5. `[]` AllowListEntry account pda of ["allow_list", staking_program_id, staking_data, user_token_wallet_owner]
+    /// 6. `[writable]` StakingHoldingWallet account
+    /// 7. `[writable]` Fee collector SPL Token account
+    /// 8. `[]` TokenMetadata account
+    /// 9. `[writable]` TokenMetadata Mint account
+    /// 10. `[]` TokenMetadata Mint authority is pda of [staking_program_id, staking_data, token_metadata]
+    /// 11. `[]` SPL token program account
+    /// 12. `[]` System program
+    /// 13. `[]` Rent sysvar
+    /// 14. `[]` Clock sysvar
+    /// 15..N. `[writable]` RewardStream and UserReward account pairs for every StakingData reward stream
+    ///
+    /// \param amount: the amount to stake
+    /// \param tier: index of the lock tier in StakingData lock_tiers
+    pub fn stake_locked(ctx: Context<StakeLocked>, amount: u64, tier: u8) -> ProgramResult {
+        msg!("Instruction: stake locked");
+
+        ctx.accounts.staking_data.check_deposits_allowed()?;
+        utils::check_allow_list(
+            ctx.program_id,
+            &ctx.accounts.staking_data,
+            ctx.accounts.user_token_wallet_owner.key,
+            &ctx.accounts.allow_list_entry,
+        )?;
+
+        let lock_tier = match ctx.accounts.staking_data.lock_tiers.get(tier as usize) {
+            Some(lock_tier) => *lock_tier,
+            None => {
+                msg!("Error: Unknown lock tier {}", tier);
+                return Err(StakingError::InvalidLockTier.into());
+            }
+        };
+
+        // Accrue interest
+        utils::accrue_interest_internal(
+            ctx.program_id,
+            &mut ctx.accounts.staking_data,
+            &ctx.accounts.holding_wallet,
+            &ctx.accounts.staking_token_metadata,
+            &ctx.accounts.mint,
+            &ctx.accounts.mint_authority,
+            &ctx.accounts.token_program,
+            &ctx.accounts.clock,
+        )?;
+
+        if amount == 0 {
+            msg!("Error: Stake amount cannot be zero");
+            return Err(StakingError::InvalidAmount.into());
+        }
+
+        if ctx.accounts.user_token_wallet.amount < amount {
+            msg!("Error: User's SPL wallet balance is less than requested stake amount");
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        // Skim deposit fee, the rest is staked
+        let fee = utils::calculate_fee(amount, ctx.accounts.staking_data.deposit_fee_bps);
+        msg!("Deposit fee: {}", fee);
+        let amount = amount.checked_sub(fee).unwrap();
+        if amount == 0 {
+            msg!("Error: Stake amount without fee cannot be zero");
+            return Err(StakingError::InvalidAmount.into());
+        }
+        ctx.accounts
+            .staking_data
+            .check_deposit_caps(ctx.accounts.staking_user_data.ownership_share, amount)?;
+
+        // Transfer fee from user wallet to fee collector
+        if fee > 0 {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.clone(),
+                    Transfer {
+                        from: ctx.accounts.user_token_wallet.to_account_info().clone(),
+                        to: ctx.accounts.fee_collector.to_account_info().clone(),
+                        authority: ctx.accounts.user_token_wallet_owner.clone(),
+                    },
+                ),
+                fee,
+            )?;
+        }
+
+        // Transfer SPL Tokens from user wallet to holding wallet
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.clone(),
+                Transfer {
+                    from: ctx.accounts.user_token_wallet.to_account_info().clone(),
+                    to: ctx.accounts.holding_wallet.to_account_info().clone(),
+                    authority: ctx.accounts.user_token_wallet_owner.clone(),
+                },
+            ),
+            amount,
+        )?;
+
+        // Calculate new boosted shares
+        let staking_data = &ctx.accounts.staking_data;
+        let new_shares = utils::calculate_new_boosted_shares(
+            staking_data.total_shares,
+            staking_data.total_staked,
+            amount,
+            lock_tier.boost,
+        );
+        let boost_shares = new_shares
+            .checked_sub(utils::calculate_new_shares(
+                staking_data.total_shares,
+                staking_data.total_staked,
+                amount,
+            ))
+            .unwrap();
+        msg!("New shares: {}", new_shares);
+
+        let locked_until = ctx
+            .accounts
+            .clock
+            .unix_timestamp
+            .checked_add(lock_tier.duration)
+            .unwrap();
+        msg!("Locked until: {}", locked_until);
+
+        // Settle reward streams before the shares change
+        utils::settle_rewards_internal(
+            ctx.program_id,
+            &ctx.accounts.staking_data,
+            &ctx.accounts.staking_user_data,
+            new_shares,
+            0,
+            ctx.remaining_accounts,
+            &ctx.accounts.clock,
+        )?;
+
+        // Save accounts data
+        let staking_data = &mut ctx.accounts.staking_data;
+        let staking_user_data = &mut ctx.accounts.staking_user_data;
+        let locked_stake = &mut ctx.accounts.locked_stake;
+        staking_data.total_staked = staking_data.total_staked.checked_add(amount).unwrap();
+        staking_data.total_shares = staking_data.total_shares.checked_add(new_shares).unwrap();
+        staking_user_data.ownership_share = staking_user_data
+            .ownership_share
+            .checked_add(new_shares)
+            .unwrap();
+        staking_user_data.checkpoint_shares(ctx.accounts.clock.slot);
+        staking_user_data.tier_locked_shares = staking_user_data
+            .tier_locked_shares
+            .checked_add(new_shares)
+            .unwrap();
+        staking_user_data.add_principal(
+            staking_data.total_shares,
+            staking_data.total_staked,
+            new_shares,
+        );
+        staking_user_data.last_stake_timestamp = ctx.accounts.clock.unix_timestamp;
+        locked_stake.staking_user_data = staking_user_data.key();
+        locked_stake.amount = amount;
+        locked_stake.shares = new_shares;
+        locked_stake.boost_shares = boost_shares;
+        locked_stake.boost = lock_tier.boost;
+        locked_stake.locked_until = locked_until;
+
+        emit!(StakeLockedEvent {
+            staking_data: staking_data.key(),
+            staking_user_data: staking_user_data.key(),
+            user: *ctx.accounts.user_token_wallet_owner.key,
+            locked_stake: locked_stake.key(),
+            amount,
+            fee,
+            shares_minted: new_shares,
+            boost: lock_tier.boost,
+            locked_until,
+            user_shares: staking_user_data.ownership_share,
+            total_staked: staking_data.total_staked,
+            total_shares: staking_data.total_shares,
+            timestamp: ctx.accounts.clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Release the expired time-locked stake position. Shares added by the tier boost are burned,
+    /// so their value goes back to the staking pool and the boost only counts while the stake is
+    /// locked. The rest of the position shares become available to unstake and the LockedStake
+    /// account rent is returned to the user.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingUserData account
+    /// 1. `[writable]` LockedStake account
+    /// 2. `[]` User SPL Token account
+    /// 3. `[writable, signer]` User SPL Token owner
+    /// 4. `[writable]` StakingData account
+    /// 5. `[]` Clock sysvar
+    /// 6..N. `[writable]` RewardStream and UserReward account pairs for every StakingData reward stream
+    pub fn release_locked_stake(ctx: Context<ReleaseLockedStake>) -> ProgramResult {
+        msg!("Instruction: release locked stake");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        if ctx.accounts.clock.unix_timestamp < ctx.accounts.locked_stake.locked_until {
+            msg!(
+                "Error: Stake is locked until {}",
+                ctx.accounts.locked_stake.locked_until
+            );
+            return Err(StakingError::StakeStillLocked.into());
+        }
+
+        // Slashed user may have less shares than the boost
+        let shares_to_burn = ctx
+            .accounts
+            .locked_stake
+            .boost_shares
+            .min(ctx.accounts.staking_user_data.ownership_share);
+        msg!("Boost shares to burn: {}", shares_to_burn);
+
+        // Settle reward streams before the shares change
+        utils::settle_rewards_internal(
+            ctx.program_id,
+            &ctx.accounts.staking_data,
+            &ctx.accounts.staking_user_data,
+            0,
+            shares_to_burn,
+            ctx.remaining_accounts,
+            &ctx.accounts.clock,
+        )?;
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        let staking_user_data = &mut ctx.accounts.staking_user_data;
+        staking_user_data.tier_locked_shares = staking_user_data
+            .tier_locked_shares
+            .saturating_sub(ctx.accounts.locked_stake.shares);
+        staking_user_data.burn_principal(shares_to_burn);
+        staking_user_data.ownership_share = staking_user_data
+            .ownership_share
+            .checked_sub(shares_to_burn)
+            .unwrap();
+        staking_user_data.checkpoint_shares(ctx.accounts.clock.slot);
+        staking_data.total_shares = staking_data
+            .total_shares
+            .checked_sub(shares_to_burn)
+            .unwrap();
+
+        Ok(())
+    }
+
+    /// User takes tokens from deposit with interest rate. Early unstake penalty and withdraw fee
+    /// are taken from the amount. Accrues interest first. In receipt mode the receipts of the user
+    /// are burned instead of the StakingUserData shares.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingUserData account
+    /// 1. `[writable]` User SPL Token account
+    /// 2. `[signer]` User SPL Token owner
+    /// 3. `[writable]` StakingData account
+    /// 4. `[writable]` StakingHoldingWallet account
+    /// 5. `[]`  StakingHoldingWallet owner (pda of [staking_program_id, staking_data])
+    /// 6. `[writable]` Penalty wallet, StakingHoldingWallet when the penalty stays in the pool
+    /// 7. `[writable]` Fee collector SPL Token account
+    /// 8. `[]` TokenMetadata account
+    /// 9. `[writable]` TokenMetadata Mint account
+    /// 10. `[]` TokenMetadata Mint authority (pda of [staking_program_id, staking_data, token_metadata])
+    /// 11. `[]` SPL token program account
+    /// 12. `[]` Clock sysvar
+    /// 13..N. `[writable]` Receipt mint and receipt SPL Token account of the user in receipt mode only,
+    ///       followed by RewardStream and UserReward account pairs for every StakingData reward stream
+    ///
+    /// \param amount: the amount to unstake
+    pub fn unstake<'info>(
+        ctx: Context<'_, '_, '_, 'info, Unstake<'info>>,
+        amount: u64,
+    ) -> ProgramResult {
+        msg!("Instruction: unstake");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        if ctx.accounts.staking_data.cooldown_seconds > 0 {
+            msg!("Error: Staking pool has unstake cooldown, use request unstake instead");
+            return Err(StakingError::CooldownRequired.into());
+        }
+
+        // Attempt to unlock amount
+        let unix_timestamp = ctx.accounts.clock.unix_timestamp;
+        ctx.accounts
+            .staking_user_data
+            .remove_expired_locks(unix_timestamp);
+
+        if amount == 0 {
+            msg!("Error: Unstake amount cannot be zero");
+            return Err(StakingError::InvalidAmount.into());
+        }
+
+        // Accrue interest
+        utils::accrue_interest_internal(
+            ctx.program_id,
+            &mut ctx.accounts.staking_data,
+            &ctx.accounts.holding_wallet,
+            &ctx.accounts.staking_token_metadata,
+            &ctx.accounts.mint,
+            &ctx.accounts.mint_authority,
+            &ctx.accounts.token_program,
+            &ctx.accounts.clock,
+        )?;
+
+        let staking_data = &ctx.accounts.staking_data;
+        let shares_to_burn = utils::calculate_shares_to_burn(
+            staking_data.total_shares,
+            staking_data.total_staked,
+            amount,
+        );
+        msg!("Shares to burn: {}", shares_to_burn);
+
+        if shares_to_burn == 0 {
+            msg!("Staking: User tries to unstake 0 or there are no stakers");
+            return Err(StakingError::UnstakeFundsError.into());
+        }
+
+        // Receipt balance is checked by the burn
+        let receipts_enabled = ctx.accounts.staking_data.receipts_enabled();
+        let (receipt_accounts, reward_accounts) =
+            utils::split_receipt_accounts(&ctx.accounts.staking_data, ctx.remaining_accounts, 2)?;
+        let avail_amount = ctx
+            .accounts
+            .staking_user_data
+            .available_shares(ctx.accounts.clock.unix_timestamp);
+        if !receipts_enabled && shares_to_burn > avail_amount {
+            msg!("Staking: User tries to unstake more than their available balance");
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        let penalty = utils::calculate_early_unstake_penalty(
+            amount,
+            ctx.accounts.staking_data.early_unstake_penalty,
+            ctx.accounts.staking_data.penalty_window_seconds,
+            ctx.accounts.staking_user_data.last_stake_timestamp,
+            ctx.accounts.clock.unix_timestamp,
+        );
+        msg!("Early unstake penalty: {}", penalty);
+        let fee = utils::calculate_fee(amount, ctx.accounts.staking_data.withdraw_fee_bps);
+        msg!("Withdraw fee: {}", fee);
+
+        // Settle reward streams before the shares change, receipts hold the shares in receipt mode
+        let user_burned_shares = if receipts_enabled { 0 } else { shares_to_burn };
+        utils::settle_rewards_internal(
+            ctx.program_id,
+            &ctx.accounts.staking_data,
+            &ctx.accounts.staking_user_data,
+            0,
+            user_burned_shares,
+            reward_accounts,
+            &ctx.accounts.clock,
+        )?;
+
+        if receipts_enabled {
+            utils::burn_receipts_internal(
+                receipt_accounts,
+                &ctx.accounts.user_token_wallet_owner,
+                &ctx.accounts.token_program,
+                shares_to_burn,
+            )?;
+        }
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        let staking_user_data = &mut ctx.accounts.staking_user_data;
+        if receipts_enabled {
+            staking_data.receipt_shares = staking_data
+                .receipt_shares
+                .checked_sub(shares_to_burn)
+                .unwrap();
+        } else {
+            staking_user_data.burn_principal(shares_to_burn);
+            staking_user_data.ownership_share = staking_user_data
+                .ownership_share
+                .checked_sub(shares_to_burn)
+                .unwrap();
+            staking_user_data.checkpoint_shares(ctx.accounts.clock.slot);
+        }
+        staking_data.total_shares = staking_data
+            .total_shares
+            .checked_sub(shares_to_burn)
+            .unwrap();
+        // Penalty left in the holding wallet boosts the shares of other stakers
+        let penalty_stays = staking_data.penalty_wallet == staking_data.holding_wallet;
+        let amount_leaving = if penalty_stays {
+            amount.checked_sub(penalty).unwrap()
+        } else {
+            amount
+        };
+        staking_data.total_staked = staking_data
+            .total_staked
+            .checked_sub(amount_leaving)
+            .unwrap();
+
+        // Transfer amount of tokens from holding wallet to user
+        let seeds = &[
+            ctx.program_id.as_ref(),
+            ctx.accounts.staking_data.to_account_info().key.as_ref(),
+            &[ctx.accounts.staking_data.holding_bump],
+        ];
+        let signer = &[&seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.clone(),
+                Transfer {
+                    from: ctx.accounts.holding_wallet.to_account_info().clone(),
+                    to: ctx.accounts.user_token_wallet.to_account_info().clone(),
+                    authority: ctx.accounts.holding_wallet_owner.clone(),
+                },
+                signer,
+            ),
+            amount
+                .checked_sub(penalty)
+                .unwrap()
+                .checked_sub(fee)
+                .unwrap(),
+        )?;
+
+        // Transfer fee from holding wallet to fee collector
+        if fee > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.clone(),
+                    Transfer {
+                        from: ctx.accounts.holding_wallet.to_account_info().clone(),
+                        to: ctx.accounts.fee_collector.to_account_info().clone(),
+                        authority: ctx.accounts.holding_wallet_owner.clone(),
+                    },
+                    signer,
+                ),
+                fee,
+            )?;
+        }
+
+        // Transfer penalty from holding wallet to penalty wallet
+        if !penalty_stays && penalty > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.clone(),
+                    Transfer {
+                        from: ctx.accounts.holding_wallet.to_account_info().clone(),
+                        to: ctx.accounts.penalty_wallet.to_account_info().clone(),
+                        authority: ctx.accounts.holding_wallet_owner.clone(),
+                    },
+                    signer,
+                ),
+                penalty,
+            )?;
+        }
+
+        emit!(UnstakeEvent {
+            staking_data: ctx.accounts.staking_data.key(),
+            staking_user_data: ctx.accounts.staking_user_data.key(),
+            user: *ctx.accounts.user_token_wallet_owner.key,
+            amount,
+            penalty,
+            fee,
+            shares_burned: shares_to_burn,
+            user_shares: ctx.accounts.staking_user_data.ownership_share,
+            total_staked: ctx.accounts.staking_data.total_staked,
+            total_shares: ctx.accounts.staking_data.total_shares,
+            timestamp: ctx.accounts.clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Set the interest payout of the staking pool user. In claim mode the current value of the
+    /// shares becomes the principal and the interest above it is claimed by `claim_interest`.
+    /// Interest should be accrued first, otherwise the interest to date is paid in the new mode.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingUserData account
+    /// 1. `[]` User SPL Token account
+    /// 2. `[signer]` User SPL Token owner
+    /// 3. `[]` StakingData account
+    ///
+    /// \param interest_mode: new interest payout
+    pub fn set_interest_mode(
+        ctx: Context<SetInterestMode>,
+        interest_mode: InterestMode,
+    ) -> ProgramResult {
+        msg!("Instruction: set interest mode");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        let staking_data = &ctx.accounts.staking_data;
+        let staking_user_data = &mut ctx.accounts.staking_user_data;
+        if staking_user_data.interest_mode == interest_mode {
+            msg!("Error: Interest mode is already {:?}", interest_mode);
+            return Err(StakingError::WrongInterestMode.into());
+        }
+
+        let ownership_share = staking_user_data.ownership_share;
+        staking_user_data.interest_mode = interest_mode;
+        staking_user_data.principal = 0;
+        staking_user_data.add_principal(
+            staking_data.total_shares,
+            staking_data.total_staked,
+            ownership_share,
+        );
+        msg!("Principal: {}", staking_user_data.principal);
+
+        Ok(())
+    }
+
+    /// Transfer the interest of claim mode user to the user wallet. Shares worth the interest
+    /// above the principal are burned, so the shares price doesn't change. Accrues interest first.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingUserData account
+    /// 1. `[writable]` User SPL Token account
+    /// 2. `[signer]` User SPL Token owner
+    /// 3. `[writable]` StakingData account
+    /// 4. `[writable]` StakingHoldingWallet account
+    /// 5. `[]`  StakingHoldingWallet owner (pda of [staking_program_id, staking_data])
+    /// 6. `[]` TokenMetadata account
+    /// 7. `[writable]` TokenMetadata Mint account
+    /// 8. `[]` TokenMetadata Mint authority (pda of [staking_program_id, staking_data, token_metadata])
+    /// 9. `[]` SPL token program account
+    /// 10. `[]` Clock sysvar
+    /// 11..N. `[writable]` RewardStream and UserReward account pairs for every StakingData reward stream
+    pub fn claim_interest(ctx: Context<ClaimInterest>) -> ProgramResult {
+        msg!("Instruction: claim interest");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        if ctx.accounts.staking_user_data.interest_mode != InterestMode::Claim {
+            msg!("Error: User interest is compounded");
+            return Err(StakingError::WrongInterestMode.into());
+        }
+
+        // Attempt to unlock amount
+        let unix_timestamp = ctx.accounts.clock.unix_timestamp;
+        ctx.accounts
+            .staking_user_data
+            .remove_expired_locks(unix_timestamp);
+
+        // Accrue interest
+        utils::accrue_interest_internal(
+            ctx.program_id,
+            &mut ctx.accounts.staking_data,
+            &ctx.accounts.holding_wallet,
+            &ctx.accounts.staking_token_metadata,
+            &ctx.accounts.mint,
+            &ctx.accounts.mint_authority,
+            &ctx.accounts.token_program,
+            &ctx.accounts.clock,
+        )?;
+
+        let staking_data = &ctx.accounts.staking_data;
+        let staking_user_data = &ctx.accounts.staking_user_data;
+        let (interest, shares_to_burn) = utils::calculate_claimable_interest(
+            staking_data.total_shares,
+            staking_data.total_staked,
+            staking_user_data.ownership_share,
+            staking_user_data.principal,
+            staking_user_data.available_shares(ctx.accounts.clock.unix_timestamp),
+        );
+        msg!("Interest to claim: {}", interest);
+        msg!("Shares to burn: {}", shares_to_burn);
+
+        if interest == 0 {
+            msg!("Error: Nothing to claim");
+            return Err(StakingError::InvalidAmount.into());
+        }
+
+        // Settle reward streams before the shares change
+        utils::settle_rewards_internal(
+            ctx.program_id,
+            &ctx.accounts.staking_data,
+            &ctx.accounts.staking_user_data,
+            0,
+            shares_to_burn,
+            ctx.remaining_accounts,
+            &ctx.accounts.clock,
+        )?;
+
+        // Principal stays, only the interest leaves the staking pool
+        let staking_data = &mut ctx.accounts.staking_data;
+        let staking_user_data = &mut ctx.accounts.staking_user_data;
+        staking_user_data.ownership_share = staking_user_data
+            .ownership_share
+            .checked_sub(shares_to_burn)
+            .unwrap();
+        staking_user_data.checkpoint_shares(ctx.accounts.clock.slot);
+        staking_user_data.claimed_interest = staking_user_data
+            .claimed_interest
+            .checked_add(interest)
+            .unwrap();
+        staking_data.total_shares = staking_data
+            .total_shares
+            .checked_sub(shares_to_burn)
+            .unwrap();
+        staking_data.total_staked = staking_data.total_staked.checked_sub(interest).unwrap();
+
+        // Transfer interest from holding wallet to user
+        let seeds = &[
+            ctx.program_id.as_ref(),
+            ctx.accounts.staking_data.to_account_info().key.as_ref(),
+            &[ctx.accounts.staking_data.holding_bump],
+        ];
+        let signer = &[&seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.clone(),
+                Transfer {
+                    from: ctx.accounts.holding_wallet.to_account_info().clone(),
+                    to: ctx.accounts.user_token_wallet.to_account_info().clone(),
+                    authority: ctx.accounts.holding_wallet_owner.clone(),
+                },
+                signer,
+            ),
+            interest,
+        )?;
+
+        emit!(InterestClaimedEvent {
+            staking_data: ctx.accounts.staking_data.key(),
+            staking_user_data: ctx.accounts.staking_user_data.key(),
+            user: *ctx.accounts.user_token_wallet_owner.key,
+            amount: interest,
+            shares_burned: shares_to_burn,
+            user_shares: ctx.accounts.staking_user_data.ownership_share,
+            total_staked: ctx.accounts.staking_data.total_staked,
+            total_shares: ctx.accounts.staking_data.total_shares,
+            timestamp: ctx.accounts.clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Set the unstake cooldown for provided StakingData account. With non-zero cooldown tokens
+    /// are taken by `request_unstake` and `withdraw` after the cooldown elapses.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingData account
+    /// 1. `[signer]` StakingData owner
+    ///
+    /// \param cooldown_seconds: new unstake cooldown in seconds
+    pub fn set_cooldown(ctx: Context<SetCooldown>, cooldown_seconds: i64) -> ProgramResult {
+        msg!("Instruction: set cooldown");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        if !(0..=MAX_COOLDOWN_SECONDS).contains(&cooldown_seconds) {
+            msg!("Error: Cooldown is out of range");
+            msg!("Cooldown seconds: {}", cooldown_seconds);
+            return Err(StakingError::InvalidCooldown.into());
+        }
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        staking_data.cooldown_seconds = cooldown_seconds;
+
+        Ok(())
+    }
+
+    /// Switch the staking pool to receipt mode. Stake mints receipt SPL Token for the new shares and
+    /// unstake burns it, so the staked position can be used in other apps. Shares staked before
+    /// are moved into receipts with mint_receipts, and any receipt holder redeems them back into
+    /// its StakingUserData with redeem_receipts. Metadata of the receipt mint is created by the
+    /// metadata program.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingData account
+    /// 1. `[writable, signer]` StakingData owner
+    /// 2. `[]` Receipt mint with zero supply, mint authority has to be the receipt mint authority
+    /// 3. `[]` Receipt mint authority (pda of ["receipt_mint", staking_program_id, staking_data])
+    /// 4. `[writable]` Metaplex Token Metadata account of the receipt mint
+    /// 5. `[]` Metadata program
+    /// 6. `[]` Metaplex Token Metadata program
+    /// 7. `[]` SPL token program account
+    /// 8. `[]` System program
+    /// 9. `[]` Rent sysvar
+    ///
+    /// \param receipt_mint_auth_bump: PDA bump for receipt mint authority
+    /// \param name: receipt token name
+    /// \param symbol: receipt token symbol
+    pub fn enable_receipts(
+        ctx: Context<EnableReceipts>,
+        receipt_mint_auth_bump: u8,
+        name: String,
+        symbol: String,
+    ) -> ProgramResult {
+        msg!("Instruction: enable receipts");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        if ctx.accounts.staking_data.receipts_enabled() {
+            msg!("Error: Receipts are already enabled");
+            return Err(StakingError::InvalidReceiptMint.into());
+        }
+
+        let seeds = &[
+            RECEIPT_MINT_PREFIX.as_bytes(),
+            ctx.program_id.as_ref(),
+            ctx.accounts.staking_data.to_account_info().key.as_ref(),
+            &[receipt_mint_auth_bump],
+        ];
+        let signer = &[&seeds[..]];
+        metadata::cpi::create_metadata(
+            CpiContext::new_with_signer(
+                ctx.accounts.metadata_program.clone(),
+                metadata::CreateMetadata {
+                    metadata: ctx.accounts.receipt_metadata.clone(),
+                    payer: ctx.accounts.staking_owner.clone(),
+                    mint: ctx.accounts.receipt_mint.clone(),
+                    mint_authority: ctx.accounts.receipt_mint_authority.clone(),
+                    token_metadata_program: ctx.accounts.token_metadata_program.clone(),
+                    token_program: ctx.accounts.token_program.clone(),
+                    system_program: ctx.accounts.system_program.clone(),
+                    rent: ctx.accounts.rent.clone(),
+                },
+                signer,
+            ),
+            name,
+            symbol,
+        )?;
+
+        // Metadata program id is not known at build time, so the result of the call is checked:
+        // the metadata is created by Metaplex and no receipts were minted with the mint authority
+        let (expected_metadata, _) = Pubkey::find_program_address(
+            &[
+                spl_token_metadata::state::PREFIX.as_bytes(),
+                spl_token_metadata::id().as_ref(),
+                ctx.accounts.receipt_mint.key().as_ref(),
+            ],
+            &spl_token_metadata::id(),
+        );
+        if *ctx.accounts.receipt_metadata.key != expected_metadata
+            || *ctx.accounts.receipt_metadata.owner != spl_token_metadata::id()
+        {
+            msg!("Error: Receipt metadata is not created");
+            return Err(StakingError::InvalidReceiptMint.into());
+        }
+        ctx.accounts.receipt_mint.reload()?;
+        if ctx.accounts.receipt_mint.supply > 0 {
+            msg!("Error: Receipts were minted by the metadata program");
+            return Err(StakingError::InvalidReceiptMint.into());
+        }
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        staking_data.receipt_mint = ctx.accounts.receipt_mint.key();
+        staking_data.receipt_mint_auth_bump = receipt_mint_auth_bump;
+
+        Ok(())
+    }
+
+    /// Move the available shares of the user into receipt SPL Token. Receipts are the transferable
+    /// claim on the shares: the shares stop counting for the voting weight and the reward streams
+    /// until any receipt holder redeems them. Shares in the early unstake penalty window can't be
+    /// moved.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingUserData account
+    /// 1. `[]` User SPL Token account
+    /// 2. `[signer]` User SPL Token owner
+    /// 3. `[writable]` StakingData account
+    /// 4. `[writable]` Receipt mint
+    /// 5. `[]` Receipt mint authority (pda of ["receipt_mint", staking_program_id, staking_data])
+    /// 6. `[writable]` Receipt SPL Token account receiving the receipts
+    /// 7. `[]` SPL token program account
+    /// 8. `[]` Clock sysvar
+    /// 9..N. `[writable]` RewardStream and UserReward account pairs for every StakingData reward stream
+    ///
+    /// \param shares: the amount of shares to move into receipts
+    pub fn mint_receipts(ctx: Context<MintReceipts>, shares: u64) -> ProgramResult {
+        msg!("Instruction: mint receipts");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        if shares == 0 {
+            msg!("Error: Shares amount cannot be zero");
+            return Err(StakingError::InvalidAmount.into());
+        }
+
+        // Attempt to unlock amount
+        let unix_timestamp = ctx.accounts.clock.unix_timestamp;
+        ctx.accounts
+            .staking_user_data
+            .remove_expired_locks(unix_timestamp);
+
+        if ctx
+            .accounts
+            .staking_user_data
+            .available_shares(unix_timestamp)
+            < shares
+        {
+            msg!("Error: User available shares are less than requested shares");
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        // Receipts would let the holder unstake without the penalty
+        let staking_data = &ctx.accounts.staking_data;
+        let penalty = utils::calculate_early_unstake_penalty(
+            utils::calculate_shares_value(
+                staking_data.total_shares,
+                staking_data.total_staked,
+                shares,
+            ),
+            staking_data.early_unstake_penalty,
+            staking_data.penalty_window_seconds,
+            ctx.accounts.staking_user_data.last_stake_timestamp,
+            unix_timestamp,
+        );
+        if penalty > 0 {
+            msg!("Error: Shares are in the early unstake penalty window");
+            return Err(StakingError::PenaltyWindowActive.into());
+        }
+
+        // Settle reward streams before the shares change
+        utils::settle_rewards_internal(
+            ctx.program_id,
+            &ctx.accounts.staking_data,
+            &ctx.accounts.staking_user_data,
+            0,
+            shares,
+            ctx.remaining_accounts,
+            &ctx.accounts.clock,
+        )?;
+
+        let seeds = &[
+            RECEIPT_MINT_PREFIX.as_bytes(),
+            ctx.program_id.as_ref(),
+            ctx.accounts.staking_data.to_account_info().key.as_ref(),
+            &[ctx.accounts.staking_data.receipt_mint_auth_bump],
+        ];
+        let signer = &[&seeds[..]];
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.clone(),
+                MintTo {
+                    mint: ctx.accounts.receipt_mint.to_account_info().clone(),
+                    to: ctx.accounts.receipt_wallet.to_account_info().clone(),
+                    authority: ctx.accounts.receipt_mint_authority.clone(),
+                },
+                signer,
+            ),
+            shares,
+        )?;
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        let staking_user_data = &mut ctx.accounts.staking_user_data;
+        staking_user_data.burn_principal(shares);
+        staking_user_data.ownership_share = staking_user_data
+            .ownership_share
+            .checked_sub(shares)
+            .unwrap();
+        staking_user_data.checkpoint_shares(ctx.accounts.clock.slot);
+        staking_data.receipt_shares = staking_data.receipt_shares.checked_add(shares).unwrap();
+
+        Ok(())
+    }
+
+    /// Redeem the receipts of the holder into its StakingUserData. Receipts are burned and the
+    /// shares count for the voting weight and the reward streams of the holder, they have to be
+    /// moved back into receipts to unstake. Interest should be accrued first.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingUserData account
+    /// 1. `[]` User SPL Token account
+    /// 2. `[signer]` User SPL Token owner, owner of the receipts
+    /// 3. `[writable]` StakingData account
+    /// 4. `[]` AllowListEntry account pda of ["allow_list", staking_program_id, staking_data, user_token_wallet_owner]
+    /// 5. `[writable]` Receipt mint
+    /// 6. `[writable]` Receipt SPL Token account of the user
+    /// 7. `[]` SPL token program account
+    /// 8. `[]` Clock sysvar
+    /// 9..N. `[writable]` RewardStream and UserReward account pairs for every StakingData reward stream
+    ///
+    /// \param shares: the amount of receipts to redeem
+    pub fn redeem_receipts(ctx: Context<RedeemReceipts>, shares: u64) -> ProgramResult {
+        msg!("Instruction: redeem receipts");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+        utils::check_allow_list(
+            ctx.program_id,
+            &ctx.accounts.staking_data,
+            ctx.accounts.user_token_wallet_owner.key,
+            &ctx.accounts.allow_list_entry,
+        )?;
+
+        if shares == 0 {
+            msg!("Error: Shares amount cannot be zero");
+            return Err(StakingError::InvalidAmount.into());
+        }
+
+        if ctx.accounts.receipt_wallet.amount < shares {
+            msg!("Error: User's receipt wallet balance is less than requested shares");
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        let staking_data = &ctx.accounts.staking_data;
+        staking_data.check_user_deposit_cap(
+            ctx.accounts.staking_user_data.ownership_share,
+            utils::calculate_shares_value(
+                staking_data.total_shares,
+                staking_data.total_staked,
+                shares,
+            ),
+        )?;
+
+        // Settle reward streams before the shares change
+        utils::settle_rewards_internal(
+            ctx.program_id,
+            &ctx.accounts.staking_data,
+            &ctx.accounts.staking_user_data,
+            shares,
+            0,
+            ctx.remaining_accounts,
+            &ctx.accounts.clock,
+        )?;
+
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.clone(),
+                Burn {
+                    mint: ctx.accounts.receipt_mint.to_account_info().clone(),
+                    to: ctx.accounts.receipt_wallet.to_account_info().clone(),
+                    authority: ctx.accounts.user_token_wallet_owner.clone(),
+                },
+            ),
+            shares,
+        )?;
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        let staking_user_data = &mut ctx.accounts.staking_user_data;
+        staking_data.receipt_shares = staking_data.receipt_shares.checked_sub(shares).unwrap();
+        staking_user_data.ownership_share = staking_user_data
+            .ownership_share
+            .checked_add(shares)
+            .unwrap();
+        staking_user_data.checkpoint_shares(ctx.accounts.clock.slot);
+        staking_user_data.add_principal(
+            staking_data.total_shares,
+            staking_data.total_staked,
+            shares,
+        );
+
+        Ok(())
+    }
+
+    /// Set the allow-list and deposit caps for provided StakingData account. Caps don't affect
+    /// the tokens already staked.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingData account
+    /// 1. `[signer]` StakingData owner
+    ///
+    /// \param allow_list_enabled: only users with AllowListEntry can initialize StakingUserData and stake
+    /// \param user_deposit_cap: maximum value of the user stake, 0 for no cap
+    /// \param total_deposit_cap: maximum total staked of the staking pool, 0 for no cap
+    pub fn set_deposit_limits(
+        ctx: Context<SetDepositLimits>,
+        allow_list_enabled: bool,
+        user_deposit_cap: u64,
+        total_deposit_cap: u64,
+    ) -> ProgramResult {
+        msg!("Instruction: set deposit limits");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        staking_data.allow_list_enabled = allow_list_enabled;
+        staking_data.user_deposit_cap = user_deposit_cap;
+        staking_data.total_deposit_cap = total_deposit_cap;
+
+        Ok(())
+    }
+
+    /// Add the wallet to the allow-list of provided StakingData account.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` AllowListEntry account pda of ["allow_list", staking_program_id, staking_data, wallet]
+    /// 1. `[]` StakingData account
+    /// 2. `[writable, signer]` StakingData owner
+    /// 3. `[]` Wallet to allow
+    /// 4. `[]` System program
+    /// 5. `[]` Rent sysvar
+    ///
+    /// \param _allow_list_bump: PDA bump for allow-list entry account (needs for creating account, never saved)
+    pub fn add_to_allow_list(ctx: Context<AddToAllowList>, _allow_list_bump: u8) -> ProgramResult {
+        msg!("Instruction: add to allow list");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        let allow_list_entry = &mut ctx.accounts.allow_list_entry;
+        allow_list_entry.staking_data = ctx.accounts.staking_data.key();
+        allow_list_entry.wallet = *ctx.accounts.wallet.key;
+
+        Ok(())
+    }
+
+    /// Remove the wallet from the allow-list of provided StakingData account. The user keeps
+    /// the tokens already staked and can unstake them. AllowListEntry rent is returned to the owner.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` AllowListEntry account
+    /// 1. `[]` StakingData account
+    /// 2. `[writable, signer]` StakingData owner
+    pub fn remove_from_allow_list(ctx: Context<RemoveFromAllowList>) -> ProgramResult {
+        msg!("Instruction: remove from allow list");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        Ok(())
+    }
+
+    /// Set the deposit and withdraw fees for provided StakingData account. Fees are transferred
+    /// to the fee collector on stake and unstake.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingData account
+    /// 1. `[signer]` StakingData owner
+    /// 2. `[]` StakingHoldingWallet account
+    /// 3. `[]` Fee collector SPL Token account
+    ///
+    /// \param deposit_fee_bps: deposit fee in 1e-4 (basis point)
+    /// \param withdraw_fee_bps: withdraw fee in 1e-4 (basis point)
+    pub fn set_fees(
+        ctx: Context<SetFees>,
+        deposit_fee_bps: u64,
+        withdraw_fee_bps: u64,
+    ) -> ProgramResult {
+        msg!("Instruction: set fees");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        if deposit_fee_bps > MAX_FEE_BPS || withdraw_fee_bps > MAX_FEE_BPS {
+            msg!("Error: Fee is higher than maximum");
+            msg!("Deposit fee: {}", deposit_fee_bps);
+            msg!("Withdraw fee: {}", withdraw_fee_bps);
+            return Err(StakingError::InvalidFee.into());
+        }
+
+        // Fee in the holding wallet would be left outside of total staked
+        let fee_collector = ctx.accounts.fee_collector.key();
+        if fee_collector == ctx.accounts.staking_data.holding_wallet
+            && (deposit_fee_bps > 0 || withdraw_fee_bps > 0)
+        {
+            msg!("Error: Fee collector cannot be the holding wallet");
+            return Err(StakingError::InvalidFee.into());
+        }
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        staking_data.deposit_fee_bps = deposit_fee_bps;
+        staking_data.withdraw_fee_bps = withdraw_fee_bps;
+        staking_data.fee_collector = fee_collector;
+
+        Ok(())
+    }
+
+    /// Set the early unstake penalty for provided StakingData account. The penalty decays linearly
+    /// from the full rate right after the user stake to zero at the end of the window.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingData account
+    /// 1. `[signer]` StakingData owner
+    /// 2. `[]` StakingHoldingWallet account
+    /// 3. `[]` Penalty wallet, StakingHoldingWallet leaves the penalty to the remaining stakers
+    ///
+    /// \param penalty: penalty in 1e-4 (basis point) right after the stake
+    /// \param window_seconds: penalty decay duration in seconds
+    pub fn set_early_unstake_penalty(
+        ctx: Context<SetEarlyUnstakePenalty>,
+        penalty: u64,
+        window_seconds: i64,
+    ) -> ProgramResult {
+        msg!("Instruction: set early unstake penalty");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        if penalty > MAX_EARLY_UNSTAKE_PENALTY {
+            msg!("Error: Early unstake penalty is higher than maximum");
+            msg!("Penalty: {}", penalty);
+            return Err(StakingError::InvalidPenalty.into());
+        }
+
+        if !(0..=MAX_PENALTY_WINDOW_SECONDS).contains(&window_seconds) {
+            msg!("Error: Penalty window is out of range");
+            msg!("Window seconds: {}", window_seconds);
+            return Err(StakingError::InvalidPenalty.into());
+        }
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        staking_data.early_unstake_penalty = penalty;
+        staking_data.penalty_window_seconds = window_seconds;
+        staking_data.penalty_wallet = ctx.accounts.penalty_wallet.key();
+
+        Ok(())
+    }
+
+    /// User requests to take tokens from deposit. Shares are burned and the amount without early
+    /// unstake penalty and withdraw fee is moved into PendingWithdrawal that stops earning interest.
+    /// Accrues interest first. In receipt mode the receipts of the user are burned instead of the
+    /// StakingUserData shares.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingUserData account
+    /// 1. `[writable, signer]` PendingWithdrawal account to initialize
+    /// 2. `[]` User SPL Token account
+    /// 3. `[writable, signer]` User SPL Token owner
+    /// 4. `[writable]` StakingData account
+    /// 5. `[writable]` StakingHoldingWallet account
+    /// 6. `[]`  StakingHoldingWallet owner (pda of [staking_program_id, staking_data])
+    /// 7. `[writable]` Penalty wallet, StakingHoldingWallet when the penalty stays in the pool
+    /// 8. `[writable]` Fee collector SPL Token account
+    /// 9. `[]` TokenMetadata account
+    /// 10. `[writable]` TokenMetadata Mint account
+    /// 11. `[]` TokenMetadata Mint authority (pda of [staking_program_id, staking_data, token_metadata])
+    /// 12. `[]` SPL token program account
+    /// 13. `[]` System program
+    /// 14. `[]` Rent sysvar
+    /// 15. `[]` Clock sysvar
+    /// 16..N. `[writable]` Receipt mint and receipt SPL Token account of the user in receipt mode only,
+    ///       followed by RewardStream and UserReward account pairs for every StakingData reward stream
+    ///
+    /// \param amount: the amount to unstake
+    pub fn request_unstake<'info>(
+        ctx: Context<'_, '_, '_, 'info, RequestUnstake<'info>>,
+        amount: u64,
+    ) -> ProgramResult {
+        msg!("Instruction: request unstake");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        // Attempt to unlock amount
+        let unix_timestamp = ctx.accounts.clock.unix_timestamp;
+        ctx.accounts
+            .staking_user_data
+            .remove_expired_locks(unix_timestamp);
+
+        if amount == 0 {
+            msg!("Error: Unstake amount cannot be zero");
+            return Err(StakingError::InvalidAmount.into());
+        }
+
+        // Accrue interest
+        utils::accrue_interest_internal(
+            ctx.program_id,
+            &mut ctx.accounts.staking_data,
+            &ctx.accounts.holding_wallet,
+            &ctx.accounts.staking_token_metadata,
+            &ctx.accounts.mint,
+            &ctx.accounts.mint_authority,
+            &ctx.accounts.token_program,
+            &ctx.accounts.clock,
+        )?;
+
+        let staking_data = &ctx.accounts.staking_data;
+        let shares_to_burn = utils::calculate_shares_to_burn(
+            staking_data.total_shares,
+            staking_data.total_staked,
+            amount,
+        );
+        msg!("Shares to burn: {}", shares_to_burn);
+
+        if shares_to_burn == 0 {
+            msg!("Staking: User tries to unstake 0 or there are no stakers");
+            return Err(StakingError::UnstakeFundsError.into());
+        }
+
+        // Receipt balance is checked by the burn
+        let receipts_enabled = ctx.accounts.staking_data.receipts_enabled();
+        let (receipt_accounts, reward_accounts) =
+            utils::split_receipt_accounts(&ctx.accounts.staking_data, ctx.remaining_accounts, 2)?;
+        let avail_amount = ctx
+            .accounts
+            .staking_user_data
+            .available_shares(ctx.accounts.clock.unix_timestamp);
+        if !receipts_enabled && shares_to_burn > avail_amount {
+            msg!("Staking: User tries to unstake more than their available balance");
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        let penalty = utils::calculate_early_unstake_penalty(
+            amount,
+            ctx.accounts.staking_data.early_unstake_penalty,
+            ctx.accounts.staking_data.penalty_window_seconds,
+            ctx.accounts.staking_user_data.last_stake_timestamp,
+            ctx.accounts.clock.unix_timestamp,
+        );
+        msg!("Early unstake penalty: {}", penalty);
+        let fee = utils::calculate_fee(amount, ctx.accounts.staking_data.withdraw_fee_bps);
+        msg!("Withdraw fee: {}", fee);
+
+        // Settle reward streams before the shares change, receipts hold the shares in receipt mode
+        let user_burned_shares = if receipts_enabled { 0 } else { shares_to_burn };
+        utils::settle_rewards_internal(
+            ctx.program_id,
+            &ctx.accounts.staking_data,
+            &ctx.accounts.staking_user_data,
+            0,
+            user_burned_shares,
+            reward_accounts,
+            &ctx.accounts.clock,
+        )?;
+
+        if receipts_enabled {
+            utils::burn_receipts_internal(
+                receipt_accounts,
+                &ctx.accounts.user_token_wallet_owner,
+                &ctx.accounts.token_program,
+                shares_to_burn,
+            )?;
+        }
+
+        let unlock_timestamp = ctx
+            .accounts
+            .clock
+            .unix_timestamp
+            .checked_add(staking_data.cooldown_seconds)
+            .unwrap();
+        msg!("Unlock timestamp: {}", unlock_timestamp);
+
+        // Pending amount leaves total_staked, so it doesn't earn interest anymore
+        let staking_data = &mut ctx.accounts.staking_data;
+        let staking_user_data = &mut ctx.accounts.staking_user_data;
+        if receipts_enabled {
+            staking_data.receipt_shares = staking_data
+                .receipt_shares
+                .checked_sub(shares_to_burn)
+                .unwrap();
+        } else {
+            staking_user_data.burn_principal(shares_to_burn);
+            staking_user_data.ownership_share = staking_user_data
+                .ownership_share
+                .checked_sub(shares_to_burn)
+                .unwrap();
+            staking_user_data.checkpoint_shares(ctx.accounts.clock.slot);
+        }
+        staking_data.total_shares = staking_data
+            .total_shares
+            .checked_sub(shares_to_burn)
+            .unwrap();
+        let withdrawal_amount = amount
+            .checked_sub(penalty)
+            .unwrap()
+            .checked_sub(fee)
+            .unwrap();
+        // Penalty left in the holding wallet boosts the shares of other stakers
+        let penalty_stays = staking_data.penalty_wallet == staking_data.holding_wallet;
+        let amount_leaving = if penalty_stays {
+            amount.checked_sub(penalty).unwrap()
+        } else {
+            amount
+        };
+        staking_data.total_staked = staking_data
+            .total_staked
+            .checked_sub(amount_leaving)
+            .unwrap();
+        staking_data.pending_withdrawals = staking_data
+            .pending_withdrawals
+            .checked_add(withdrawal_amount)
+            .unwrap();
+
+        staking_user_data.pending_withdrawals = staking_user_data
+            .pending_withdrawals
+            .checked_add(withdrawal_amount)
+            .unwrap();
+
+        let pending_withdrawal = &mut ctx.accounts.pending_withdrawal;
+        pending_withdrawal.staking_user_data = staking_user_data.key();
+        pending_withdrawal.amount = withdrawal_amount;
+        pending_withdrawal.unlock_timestamp = unlock_timestamp;
+
+        let seeds = &[
+            ctx.program_id.as_ref(),
+            ctx.accounts.staking_data.to_account_info().key.as_ref(),
+            &[ctx.accounts.staking_data.holding_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        // Transfer penalty from holding wallet to penalty wallet
+        if !penalty_stays && penalty > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.clone(),
+                    Transfer {
+                        from: ctx.accounts.holding_wallet.to_account_info().clone(),
+                        to: ctx.accounts.penalty_wallet.to_account_info().clone(),
+                        authority: ctx.accounts.holding_wallet_owner.clone(),
+                    },
+                    signer,
+                ),
+                penalty,
+            )?;
+        }
+
+        // Transfer fee from holding wallet to fee collector
+        if fee > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.clone(),
+                    Transfer {
+                        from: ctx.accounts.holding_wallet.to_account_info().clone(),
+                        to: ctx.accounts.fee_collector.to_account_info().clone(),
+                        authority: ctx.accounts.holding_wallet_owner.clone(),
+                    },
+                    signer,
+                ),
+                fee,
+            )?;
+        }
+
+        emit!(UnstakeRequestedEvent {
+            staking_data: ctx.accounts.staking_data.key(),
+            staking_user_data: ctx.accounts.staking_user_data.key(),
+            user: *ctx.accounts.user_token_wallet_owner.key,
+            pending_withdrawal: ctx.accounts.pending_withdrawal.key(),
+            amount,
+            penalty,
+            fee,
+            shares_burned: shares_to_burn,
+            unlock_timestamp,
+            user_shares: ctx.accounts.staking_user_data.ownership_share,
+            total_staked: ctx.accounts.staking_data.total_staked,
+            total_shares: ctx.accounts.staking_data.total_shares,
+            timestamp: ctx.accounts.clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Transfer the tokens of PendingWithdrawal to the user after the cooldown elapses.
+    /// PendingWithdrawal account is closed and its rent is returned to the user.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingUserData account
+    /// 1. `[writable]` PendingWithdrawal account
+    /// 2. `[writable]` User SPL Token account
+    /// 3. `[writable, signer]` User SPL Token owner
+    /// 4. `[writable]` StakingData account
+    /// 5. `[writable]` StakingHoldingWallet account
+    /// 6. `[]` StakingHoldingWallet owner (pda of [staking_program_id, staking_data])
+    /// 7. `[]` SPL token program account
+    /// 8. `[]` Clock sysvar
+    pub fn withdraw(ctx: Context<Withdraw>) -> ProgramResult {
+        msg!("Instruction: withdraw");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        let amount = ctx.accounts.pending_withdrawal.amount;
+        let unlock_timestamp = ctx.accounts.pending_withdrawal.unlock_timestamp;
+        if ctx.accounts.clock.unix_timestamp < unlock_timestamp {
+            msg!("Error: Withdrawal is locked until {}", unlock_timestamp);
+            return Err(StakingError::CooldownNotElapsed.into());
+        }
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        let staking_user_data = &mut ctx.accounts.staking_user_data;
+        staking_data.pending_withdrawals = staking_data
+            .pending_withdrawals
+            .checked_sub(amount)
+            .unwrap();
+        staking_user_data.pending_withdrawals = staking_user_data
+            .pending_withdrawals
+            .checked_sub(amount)
+            .unwrap();
+
+        // Transfer amount of tokens from holding wallet to user
+        let seeds = &[
+            ctx.program_id.as_ref(),
+            ctx.accounts.staking_data.to_account_info().key.as_ref(),
+            &[ctx.accounts.staking_data.holding_bump],
+        ];
+        let signer = &[&seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.clone(),
+                Transfer {
+                    from: ctx.accounts.holding_wallet.to_account_info().clone(),
+                    to: ctx.accounts.user_token_wallet.to_account_info().clone(),
+                    authority: ctx.accounts.holding_wallet_owner.clone(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        emit!(WithdrawEvent {
+            staking_data: ctx.accounts.staking_data.key(),
+            staking_user_data: ctx.accounts.staking_user_data.key(),
+            user: *ctx.accounts.user_token_wallet_owner.key,
+            pending_withdrawal: ctx.accounts.pending_withdrawal.key(),
+            amount,
+            timestamp: ctx.accounts.clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Cancel the PendingWithdrawal and stake its amount back with the current shares price.
+    /// PendingWithdrawal account is closed and its rent is returned to the user. Accrues interest first.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingUserData account
+    /// 1. `[writable]` PendingWithdrawal account
+    /// 2. `[]` User SPL Token account
+    /// 3. `[writable, signer]` User SPL Token owner
+    /// 4. `[writable]` StakingData account
+    /// 5. `[]` AllowListEntry account pda of ["allow_list", staking_program_id, staking_data, user_token_wallet_owner]
+    /// 6. `[writable]` StakingHoldingWallet account
+    /// 7. `[]` TokenMetadata account
+    /// 8. `[writable]` TokenMetadata Mint account
+    /// 9. `[]` TokenMetadata Mint authority (pda of [staking_program_id, staking_data, token_metadata])
+    /// 10. `[]` SPL token program account
+    /// 11. `[]` Clock sysvar
+    /// 12..N. `[writable]` RewardStream and UserReward account pairs for every StakingData reward stream
+    pub fn cancel_unstake(ctx: Context<CancelUnstake>) -> ProgramResult {
+        msg!("Instruction: cancel unstake");
+
+        ctx.accounts.staking_data.check_deposits_allowed()?;
+        utils::check_allow_list(
+            ctx.program_id,
+            &ctx.accounts.staking_data,
+            ctx.accounts.user_token_wallet_owner.key,
+            &ctx.accounts.allow_list_entry,
+        )?;
+
+        // Accrue interest
+        utils::accrue_interest_internal(
+            ctx.program_id,
+            &mut ctx.accounts.staking_data,
+            &ctx.accounts.holding_wallet,
+            &ctx.accounts.staking_token_metadata,
+            &ctx.accounts.mint,
+            &ctx.accounts.mint_authority,
+            &ctx.accounts.token_program,
+            &ctx.accounts.clock,
+        )?;
+
+        // Calculate new shares
+        let amount = ctx.accounts.pending_withdrawal.amount;
+        let staking_data = &ctx.accounts.staking_data;
+        staking_data.check_deposit_caps(ctx.accounts.staking_user_data.ownership_share, amount)?;
+        let new_shares = utils::calculate_new_shares(
+            staking_data.total_shares,
+            staking_data.total_staked,
+            amount,
+        );
+        msg!("New shares: {}", new_shares);
+
+        // Settle reward streams before the shares change
+        utils::settle_rewards_internal(
+            ctx.program_id,
+            &ctx.accounts.staking_data,
+            &ctx.accounts.staking_user_data,
+            new_shares,
+            0,
+            ctx.remaining_accounts,
+            &ctx.accounts.clock,
+        )?;
+
+        // Save accounts data
+        let staking_data = &mut ctx.accounts.staking_data;
+        let staking_user_data = &mut ctx.accounts.staking_user_data;
+        staking_data.pending_withdrawals = staking_data
+            .pending_withdrawals
+            .checked_sub(amount)
+            .unwrap();
+        staking_user_data.pending_withdrawals = staking_user_data
+            .pending_withdrawals
+            .checked_sub(amount)
+            .unwrap();
+        staking_data.total_staked = staking_data.total_staked.checked_add(amount).unwrap();
+        staking_data.total_shares = staking_data.total_shares.checked_add(new_shares).unwrap();
+        staking_user_data.ownership_share = staking_user_data
+            .ownership_share
+            .checked_add(new_shares)
+            .unwrap();
+        staking_user_data.checkpoint_shares(ctx.accounts.clock.slot);
+        staking_user_data.add_principal(
+            staking_data.total_shares,
+            staking_data.total_staked,
+            new_shares,
+        );
+
+        emit!(UnstakeCancelledEvent {
+            staking_data: staking_data.key(),
+            staking_user_data: staking_user_data.key(),
+            user: *ctx.accounts.user_token_wallet_owner.key,
+            pending_withdrawal: ctx.accounts.pending_withdrawal.key(),
+            amount,
+            shares_minted: new_shares,
+            user_shares: staking_user_data.ownership_share,
+            total_staked: staking_data.total_staked,
+            total_shares: staking_data.total_shares,
+            timestamp: ctx.accounts.clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Move the shares to StakingUserData of another user in the same staking pool. Only shares
+    /// available to unstake can be moved, governance locks stay with the sender and time-locked
+    /// stakes are moved with [transfer_locked_stake]. The recipient has to pass the allow-list and
+    /// the user deposit cap. Claim mode users should claim the interest first, the recipient
+    /// receives the shares value as the principal. Interest should be accrued first.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingUserData account of the sender
+    /// 1. `[]` User SPL Token account of the sender
+    /// 2. `[signer]` User SPL Token owner of the sender
+    /// 3. `[writable]` StakingUserData account of the recipient
+    /// 4. `[]` User SPL Token account of the recipient
+    /// 5. `[]` AllowListEntry account pda of ["allow_list", staking_program_id, staking_data, recipient_wallet_owner]
+    /// 6. `[]` StakingData account
+    /// 7. `[]` Clock sysvar
+    /// 8..N. `[writable]` RewardStream and UserReward account pairs for every StakingData reward stream
+    /// of the sender followed by the pairs of the recipient
+    ///
+    /// \param shares: the amount of shares to move
+    pub fn transfer_shares(ctx: Context<TransferShares>, shares: u64) -> ProgramResult {
+        msg!("Instruction: transfer shares");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+        utils::check_allow_list(
+            ctx.program_id,
+            &ctx.accounts.staking_data,
+            &ctx.accounts.recipient_token_wallet.owner,
+            &ctx.accounts.recipient_allow_list_entry,
+        )?;
+
+        if shares == 0 {
+            msg!("Error: Shares to transfer cannot be zero");
+            return Err(StakingError::InvalidAmount.into());
+        }
+
+        // Attempt to unlock amount
+        let unix_timestamp = ctx.accounts.clock.unix_timestamp;
+        ctx.accounts
+            .staking_user_data
+            .remove_expired_locks(unix_timestamp);
+
+        if shares
+            > ctx
+                .accounts
+                .staking_user_data
+                .available_shares(ctx.accounts.clock.unix_timestamp)
+        {
+            msg!("Staking: User tries to transfer more than their available balance");
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        let staking_data = &ctx.accounts.staking_data;
+        staking_data.check_user_deposit_cap(
+            ctx.accounts.recipient_staking_user_data.ownership_share,
+            utils::calculate_shares_value(
+                staking_data.total_shares,
+                staking_data.total_staked,
+                shares,
+            ),
+        )?;
+
+        utils::move_shares_internal(
+            ctx.program_id,
+            &ctx.accounts.staking_data,
+            &mut ctx.accounts.staking_user_data,
+            &mut ctx.accounts.recipient_staking_user_data,
+            shares,
+            0,
+            ctx.remaining_accounts,
+            &ctx.accounts.clock,
+        )?;
+
+        emit!(SharesTransferredEvent {
+            staking_data: ctx.accounts.staking_data.key(),
+            staking_user_data: ctx.accounts.staking_user_data.key(),
+            user: *ctx.accounts.user_token_wallet_owner.key,
+            recipient_staking_user_data: ctx.accounts.recipient_staking_user_data.key(),
+            shares,
+            locked_stake: None,
+            timestamp: ctx.accounts.clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Move the time-locked stake position with its shares to StakingUserData of another user in
+    /// the same staking pool. The shares stay locked until the position is released by the
+    /// recipient, who also receives the LockedStake account rent on release. Governance locks of
+    /// the sender have to stay covered by the remaining sender shares. The recipient has to pass
+    /// the allow-list and the user deposit cap. Interest should be accrued first.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingUserData account of the sender
+    /// 1. `[writable]` LockedStake account
+    /// 2. `[]` User SPL Token account of the sender
+    /// 3. `[signer]` User SPL Token owner of the sender
+    /// 4. `[writable]` StakingUserData account of the recipient
+    /// 5. `[]` User SPL Token account of the recipient
+    /// 6. `[]` AllowListEntry account pda of ["allow_list", staking_program_id, staking_data, recipient_wallet_owner]
+    /// 7. `[]` StakingData account
+    /// 8. `[]` Clock sysvar
+    /// 9..N. `[writable]` RewardStream and UserReward account pairs for every StakingData reward stream
+    /// of the sender followed by the pairs of the recipient
+    pub fn transfer_locked_stake(ctx: Context<TransferLockedStake>) -> ProgramResult {
+        msg!("Instruction: transfer locked stake");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+        utils::check_allow_list(
+            ctx.program_id,
+            &ctx.accounts.staking_data,
+            &ctx.accounts.recipient_token_wallet.owner,
+            &ctx.accounts.recipient_allow_list_entry,
+        )?;
+
+        // Attempt to unlock amount
+        let unix_timestamp = ctx.accounts.clock.unix_timestamp;
+        ctx.accounts
+            .staking_user_data
+            .remove_expired_locks(unix_timestamp);
+
+        let shares = ctx.accounts.locked_stake.shares;
+        let staking_user_data = &ctx.accounts.staking_user_data;
+        let locked_amount =
+            utils::calculate_locked_amount(&staking_user_data.locks, unix_timestamp);
+        if shares
+            > staking_user_data
+                .ownership_share
+                .saturating_sub(locked_amount)
+        {
+            msg!("Staking: Locked stake shares are used by the governance locks");
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        let staking_data = &ctx.accounts.staking_data;
+        staking_data.check_user_deposit_cap(
+            ctx.accounts.recipient_staking_user_data.ownership_share,
+            utils::calculate_shares_value(
+                staking_data.total_shares,
+                staking_data.total_staked,
+                shares,
+            ),
+        )?;
+
+        utils::move_shares_internal(
+            ctx.program_id,
+            &ctx.accounts.staking_data,
+            &mut ctx.accounts.staking_user_data,
+            &mut ctx.accounts.recipient_staking_user_data,
+            shares,
+            shares,
+            ctx.remaining_accounts,
+            &ctx.accounts.clock,
+        )?;
+
+        ctx.accounts.locked_stake.staking_user_data =
+            ctx.accounts.recipient_staking_user_data.key();
+
+        emit!(SharesTransferredEvent {
+            staking_data: ctx.accounts.staking_data.key(),
+            staking_user_data: ctx.accounts.staking_user_data.key(),
+            user: *ctx.accounts.user_token_wallet_owner.key,
+            recipient_staking_user_data: ctx.accounts.recipient_staking_user_data.key(),
+            shares,
+            locked_stake: Some(ctx.accounts.locked_stake.key()),
+            timestamp: ctx.accounts.clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Close StakingUserData account of the user who fully unstaked and return its rent to the user.
+    /// Time-locked stakes have to be released, pending withdrawals and rewards of all reward streams have to be withdrawn and claimed first.
+    /// UserReward accounts of the reward streams are closed too, the ones of removed reward streams
+    /// have to be closed before by `close_user_reward`.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingUserData account
+    /// 1. `[]` User SPL Token account
+    /// 2. `[writable, signer]` User SPL Token owner
+    /// 3. `[]` StakingData account
+    /// 4. `[]` Clock sysvar
+    /// 5..N. `[writable]` RewardStream and UserReward account pairs for every StakingData reward stream
+    pub fn close_staking_user<'info>(
+        ctx: Context<'_, '_, '_, 'info, CloseStakingUser<'info>>,
+    ) -> ProgramResult {
+        msg!("Instruction: close staking user");
+
+        let staking_user_data = &ctx.accounts.staking_user_data;
+        if staking_user_data.ownership_share > 0 {
+            msg!(
+                "Error: User still owns {} shares",
+                staking_user_data.ownership_share
+            );
+            return Err(StakingError::StakingUserNotEmpty.into());
+        }
+
+        if staking_user_data.tier_locked_shares > 0 {
+            msg!(
+                "Error: User has {} time-locked shares",
+                staking_user_data.tier_locked_shares
+            );
+            return Err(StakingError::StakingUserNotEmpty.into());
+        }
+
+        if staking_user_data.pending_withdrawals > 0 {
+            msg!(
+                "Error: User has {} tokens pending withdrawal",
+                staking_user_data.pending_withdrawals
+            );
+            return Err(StakingError::StakingUserNotEmpty.into());
+        }
+
+        if let Some(locked_until) =
+            staking_user_data.locked_until(ctx.accounts.clock.unix_timestamp)
+        {
+            msg!("Error: Amount is locked until {}", locked_until);
+            return Err(StakingError::StakeStillLocked.into());
+        }
+
+        // Validates reward stream accounts, the user without shares earns nothing
+        utils::settle_rewards_internal(
+            ctx.program_id,
+            &ctx.accounts.staking_data,
+            &ctx.accounts.staking_user_data,
+            0,
+            0,
+            ctx.remaining_accounts,
+            &ctx.accounts.clock,
+        )?;
+
+        for reward_accounts in ctx.remaining_accounts.chunks(2) {
+            let user_reward: ProgramAccount<UserReward> =
+                ProgramAccount::try_from(ctx.program_id, &reward_accounts[1])?;
+            if user_reward.pending_rewards > 0 {
+                msg!(
+                    "Error: User has {} rewards to claim from reward stream {}",
+                    user_reward.pending_rewards,
+                    user_reward.reward_stream
+                );
+                return Err(StakingError::StakingUserNotEmpty.into());
+            }
+            user_reward.close(ctx.accounts.user_token_wallet_owner.clone())?;
+        }
+
+        Ok(())
+    }
+
+    /// Add the external reward stream to the staking pool. Reward tokens of the stream vault are
+    /// emitted per second and distributed pro rata to the users ownership share. Reward vault is
+    /// funded by the sponsor and the staking token mint is never touched.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingData account
+    /// 1. `[signer]` StakingData owner
+    /// 2. `[writable, signer]` RewardStream account to initialize
+    /// 3. `[writable, signer]` Reward sponsor
+    /// 4. `[]` Reward vault (SPL Token account of the reward mint)
+    /// 5. `[]` Reward vault owner pda of [staking_program_id, reward_stream]
+    /// 6. `[]` System program
+    /// 7. `[]` Rent sysvar
+    /// 8. `[]` Clock sysvar
+    ///
+    /// \param emission_per_second: amount of reward tokens distributed every second
+    /// \param start_timestamp: start of the emissions
+    /// \param end_timestamp: end of the emissions
+    /// \param vault_bump: PDA bump for reward vault owner (needs for signatures)
+    pub fn add_reward_stream(
+        ctx: Context<AddRewardStream>,
+        emission_per_second: u64,
+        start_timestamp: i64,
+        end_timestamp: i64,
+        vault_bump: u8,
+    ) -> ProgramResult {
+        msg!("Instruction: add reward stream");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        if ctx.accounts.staking_data.reward_streams.len() >= MAX_REWARD_STREAMS {
+            msg!(
+                "Error: Too many reward streams, maximum is {}",
+                MAX_REWARD_STREAMS
+            );
+            return Err(StakingError::InvalidRewardStream.into());
+        }
+
+        if emission_per_second == 0 {
+            msg!("Error: Emission per second cannot be zero");
+            return Err(StakingError::InvalidRewardStream.into());
+        }
+
+        // Emissions never start in the past
+        let start_timestamp = start_timestamp.max(ctx.accounts.clock.unix_timestamp);
+        if end_timestamp <= start_timestamp {
+            msg!("Error: Reward stream ends before it starts");
+            msg!("Start timestamp: {}", start_timestamp);
+            msg!("End timestamp: {}", end_timestamp);
+            return Err(StakingError::InvalidRewardStream.into());
+        }
+
+        let reward_stream = &mut ctx.accounts.reward_stream;
+        reward_stream.staking_data = ctx.accounts.staking_data.key();
+        reward_stream.sponsor = *ctx.accounts.sponsor.key;
+        reward_stream.reward_mint = ctx.accounts.vault.mint;
+        reward_stream.vault = ctx.accounts.vault.key();
+        reward_stream.vault_bump = vault_bump;
+        reward_stream.emission_per_second = emission_per_second;
+        reward_stream.start_timestamp = start_timestamp;
+        reward_stream.end_timestamp = end_timestamp;
+        reward_stream.last_update_timestamp = start_timestamp;
+        reward_stream.reward_per_share = 0;
+        reward_stream.distributed_rewards = 0;
+        reward_stream.claimed_rewards = 0;
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        staking_data.reward_streams.push(reward_stream.key());
+
+        Ok(())
+    }
+
+    /// Remove the finished reward stream from the staking pool to free its slot. Only the reward
+    /// tokens not owed to the users are returned to the sponsor, the RewardStream account stays
+    /// so the users can still claim their rewards.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingData account
+    /// 1. `[signer]` StakingData owner
+    /// 2. `[writable]` RewardStream account to remove
+    /// 3. `[]` Reward sponsor
+    /// 4. `[writable]` Reward vault
+    /// 5. `[]` Reward vault owner (pda of [staking_program_id, reward_stream])
+    /// 6. `[writable]` Sponsor reward token account
+    /// 7. `[]` SPL token program account
+    /// 8. `[]` Clock sysvar
+    pub fn remove_reward_stream(ctx: Context<RemoveRewardStream>) -> ProgramResult {
+        msg!("Instruction: remove reward stream");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        if ctx.accounts.clock.unix_timestamp < ctx.accounts.reward_stream.end_timestamp {
+            msg!(
+                "Error: Reward stream emits until {}",
+                ctx.accounts.reward_stream.end_timestamp
+            );
+            return Err(StakingError::InvalidRewardStream.into());
+        }
+
+        let reward_stream_key = ctx.accounts.reward_stream.key();
+        if !ctx
+            .accounts
+            .staking_data
+            .reward_streams
+            .contains(&reward_stream_key)
+        {
+            msg!("Error: Reward stream is already removed");
+            return Err(StakingError::InvalidRewardStream.into());
+        }
+
+        // Last update of the stream, share changes are not settled after the removal
+        let reward_stream = &mut ctx.accounts.reward_stream;
+        utils::update_reward_stream(
+            reward_stream,
+            ctx.accounts.staking_data.reward_shares(),
+            ctx.accounts.clock.unix_timestamp,
+        );
+
+        // Order of the rest streams is kept for the reward accounts of other instructions
+        let staking_data = &mut ctx.accounts.staking_data;
+        staking_data
+            .reward_streams
+            .retain(|key| *key != reward_stream_key);
+
+        let owed_rewards = reward_stream
+            .distributed_rewards
+            .checked_sub(reward_stream.claimed_rewards)
+            .unwrap();
+        msg!("Reward tokens owed to users: {}", owed_rewards);
+        let amount = ctx.accounts.vault.amount.saturating_sub(owed_rewards);
+        msg!("Reward tokens to return: {}", amount);
+        if amount > 0 {
+            // Transfer the rest of rewards from reward vault to sponsor
+            let seeds = &[
+                ctx.program_id.as_ref(),
+                ctx.accounts.reward_stream.to_account_info().key.as_ref(),
+                &[ctx.accounts.reward_stream.vault_bump],
+            ];
+            let signer = &[&seeds[..]];
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.clone(),
+                    Transfer {
+                        from: ctx.accounts.vault.to_account_info().clone(),
+                        to: ctx.accounts.sponsor_reward_wallet.to_account_info().clone(),
+                        authority: ctx.accounts.vault_owner.clone(),
+                    },
+                    signer,
+                ),
+                amount,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Initialize UserReward account of the staking pool user for the reward stream. Every user
+    /// has to initialize UserReward accounts for all reward streams before changing the stake.
+    /// Can be called by anyone.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` UserReward account pda of ["user_reward", staking_program_id, reward_stream, staking_user_data]
+    /// 1. `[]` RewardStream account
+    /// 2. `[]` StakingUserData account
+    /// 3. `[writable, signer]` Payer
+    /// 4. `[]` System program
+    /// 5. `[]` Rent sysvar
+    ///
+    /// \param _user_reward_bump: PDA bump for user reward account (needs for creating account, never saved)
+    pub fn init_user_reward(ctx: Context<InitUserReward>, _user_reward_bump: u8) -> ProgramResult {
+        msg!("Instruction: init user reward");
+
+        // Shares can't change without settling all reward streams, so the user holds the same
+        // shares since the stream start and zero debt accounts all of the stream emissions
+        let user_reward = &mut ctx.accounts.user_reward;
+        user_reward.reward_stream = ctx.accounts.reward_stream.key();
+        user_reward.staking_user_data = ctx.accounts.staking_user_data.key();
+        user_reward.reward_debt = 0;
+        user_reward.pending_rewards = 0;
+        user_reward.shares = ctx.accounts.staking_user_data.ownership_share;
+
+        Ok(())
+    }
+
+    /// Transfer the rewards of the reward stream earned by the user. Pays no more than the reward
+    /// vault balance, the rest stays pending until the vault is funded. Rewards of the removed
+    /// reward stream are claimable too.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[]` StakingData account
+    /// 1. `[]` StakingUserData account
+    /// 2. `[]` User SPL Token account
+    /// 3. `[signer]` User SPL Token owner
+    /// 4. `[writable]` RewardStream account
+    /// 5. `[writable]` UserReward account
+    /// 6. `[writable]` Reward vault
+    /// 7. `[]` Reward vault owner (pda of [staking_program_id, reward_stream])
+    /// 8. `[writable]` User reward token account
+    /// 9. `[]` SPL token program account
+    /// 10. `[]` Clock sysvar
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> ProgramResult {
+        msg!("Instruction: claim rewards");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        let reward_stream = &mut ctx.accounts.reward_stream;
+        let user_reward = &mut ctx.accounts.user_reward;
+        // Share changes after the removal are not settled, so the removed stream uses the
+        // shares of the last settlement
+        let shares = if ctx
+            .accounts
+            .staking_data
+            .reward_streams
+            .contains(&reward_stream.key())
+        {
+            ctx.accounts.staking_user_data.ownership_share
+        } else {
+            user_reward.shares
+        };
+        utils::update_reward_stream(
+            reward_stream,
+            ctx.accounts.staking_data.reward_shares(),
+            ctx.accounts.clock.unix_timestamp,
+        );
+        utils::settle_user_reward(reward_stream, user_reward, shares, shares);
+
+        let amount = user_reward.pending_rewards.min(ctx.accounts.vault.amount);
+        msg!("Rewards to claim: {}", amount);
+        if amount == 0 {
+            msg!("Error: Nothing to claim");
+            return Err(StakingError::InvalidAmount.into());
+        }
+        user_reward.pending_rewards = user_reward.pending_rewards.checked_sub(amount).unwrap();
+        reward_stream.claimed_rewards = reward_stream.claimed_rewards.checked_add(amount).unwrap();
+
+        // Transfer rewards from reward vault to user
+        let seeds = &[
+            ctx.program_id.as_ref(),
+            ctx.accounts.reward_stream.to_account_info().key.as_ref(),
+            &[ctx.accounts.reward_stream.vault_bump],
+        ];
+        let signer = &[&seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.clone(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info().clone(),
+                    to: ctx.accounts.user_reward_wallet.to_account_info().clone(),
+                    authority: ctx.accounts.vault_owner.clone(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        Ok(())
+    }
+
+    /// Close UserReward account of the removed reward stream and return its rent to the user.
+    /// Rewards of the stream have to be claimed first.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[]` StakingData account
+    /// 1. `[]` StakingUserData account
+    /// 2. `[]` User SPL Token account
+    /// 3. `[writable, signer]` User SPL Token owner
+    /// 4. `[]` RewardStream account
+    /// 5. `[writable]` UserReward account to close
+    pub fn close_user_reward(ctx: Context<CloseUserReward>) -> ProgramResult {
+        msg!("Instruction: close user reward");
+
+        let reward_stream = &ctx.accounts.reward_stream;
+        if ctx
+            .accounts
+            .staking_data
+            .reward_streams
+            .contains(&reward_stream.key())
+        {
+            msg!("Error: Reward stream is not removed");
+            return Err(StakingError::InvalidRewardStream.into());
+        }
+
+        // Removed stream is not updated anymore
+        let user_reward = &mut ctx.accounts.user_reward;
+        let shares = user_reward.shares;
+        utils::settle_user_reward(reward_stream, user_reward, shares, shares);
+        if user_reward.pending_rewards > 0 {
+            msg!(
+                "Error: User has {} rewards to claim",
+                user_reward.pending_rewards
+            );
+            return Err(StakingError::StakingUserNotEmpty.into());
+        }
+
+        Ok(())
+    }
+
+    /// Set the guardian who is allowed to pause and unpause the staking pool
+    /// along with the owner. Default pubkey means no guardian.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingData account
+    /// 1. `[signer]` StakingData owner
+    ///
+    /// \param guardian: pubkey of new guardian
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> ProgramResult {
+        msg!("Instruction: set guardian");
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        staking_data.guardian = guardian;
+
+        Ok(())
+    }
+
+    /// Pause the staking pool. `UnstakeOnly` level blocks deposits only, so users can still exit.
+    /// `Full` level blocks every instruction changing the staking pool.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingData account
+    /// 1. `[signer]` StakingData owner or guardian
+    ///
+    /// \param level: pause level
+    pub fn pause(ctx: Context<SetPause>, level: PauseLevel) -> ProgramResult {
+        msg!("Instruction: pause");
+
+        if level == PauseLevel::Unpaused {
+            msg!("Error: Use unpause instruction instead");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        staking_data.pause_level = level;
+
+        Ok(())
+    }
+
+    /// Unpause the staking pool.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingData account
+    /// 1. `[signer]` StakingData owner or guardian
+    pub fn unpause(ctx: Context<SetPause>) -> ProgramResult {
+        msg!("Instruction: unpause");
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        staking_data.pause_level = PauseLevel::Unpaused;
+
+        Ok(())
+    }
+
+    /// Set the slasher who is allowed to slash the staking pool users. Default pubkey means
+    /// no slashing.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingData account
+    /// 1. `[signer]` StakingData owner
+    ///
+    /// \param slasher: pubkey of new slasher
+    pub fn set_slasher(ctx: Context<SetSlasher>, slasher: Pubkey) -> ProgramResult {
+        msg!("Instruction: set slasher");
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        staking_data.slasher = slasher;
+
+        Ok(())
+    }
+
+    /// Slash the part of the user shares by the slasher. Tokens of the slashed shares are burned
+    /// when the slash wallet is the holding wallet, otherwise they are redirected to the slash
+    /// wallet. Not available in receipt mode. Accrues interest first.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingData account
+    /// 1. `[signer]` Slasher
+    /// 2. `[writable]` StakingUserData account to slash
+    /// 3. `[writable]` StakingHoldingWallet account
+    /// 4. `[]` StakingHoldingWallet owner pda of [staking_program_id, staking_data]
+    /// 5. `[writable]` Slash wallet SPL Token account
+    /// 6. `[]` TokenMetadata account
+    /// 7. `[writable]` Mint account
+    /// 8. `[]` Mint authority pda of [staking_program_id, staking_data, token_metadata]
+    /// 9. `[]` Token program
+    /// 10. `[]` Clock sysvar
+    /// 11..N. `[writable]` Reward stream and UserReward account pairs of the user
+    ///
+    /// \param slash_bps: part of the user shares to slash in 1e-4 (basis point)
+    /// \param reason: reason code of the slasher
+    pub fn slash(ctx: Context<Slash>, slash_bps: u64, reason: u16) -> ProgramResult {
+        msg!("Instruction: slash");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        if slash_bps == 0 || slash_bps > utils::BPS_MUL_FACTOR {
+            msg!("Error: Slash is out of range");
+            msg!("Slash: {}", slash_bps);
+            return Err(StakingError::InvalidSlash.into());
+        }
+
+        // Accrue interest
+        utils::accrue_interest_internal(
+            ctx.program_id,
+            &mut ctx.accounts.staking_data,
+            &ctx.accounts.holding_wallet,
+            &ctx.accounts.staking_token_metadata,
+            &ctx.accounts.mint,
+            &ctx.accounts.mint_authority,
+            &ctx.accounts.token_program,
+            &ctx.accounts.clock,
+        )?;
+
+        let staking_data = &ctx.accounts.staking_data;
+        let shares_to_slash = utils::calculate_slashed_shares(
+            ctx.accounts.staking_user_data.ownership_share,
+            slash_bps,
+        );
+        let amount = utils::calculate_shares_value(
+            staking_data.total_shares,
+            staking_data.total_staked,
+            shares_to_slash,
+        );
+        msg!("Shares to slash: {}", shares_to_slash);
+        msg!("Amount to slash: {}", amount);
+
+        if shares_to_slash == 0 {
+            msg!("Error: User has no shares to slash");
+            return Err(StakingError::InvalidSlash.into());
+        }
+
+        // Settle reward streams before the shares change
+        utils::settle_rewards_internal(
+            ctx.program_id,
+            &ctx.accounts.staking_data,
+            &ctx.accounts.staking_user_data,
+            0,
+            shares_to_slash,
+            ctx.remaining_accounts,
+            &ctx.accounts.clock,
+        )?;
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        let staking_user_data = &mut ctx.accounts.staking_user_data;
+        staking_user_data.burn_principal(shares_to_slash);
+        staking_user_data.ownership_share = staking_user_data
+            .ownership_share
+            .checked_sub(shares_to_slash)
+            .unwrap();
+        staking_user_data.checkpoint_shares(ctx.accounts.clock.slot);
+        staking_data.total_shares = staking_data
+            .total_shares
+            .checked_sub(shares_to_slash)
+            .unwrap();
+        staking_data.total_staked = staking_data.total_staked.checked_sub(amount).unwrap();
+
+        let seeds = &[
+            ctx.program_id.as_ref(),
+            ctx.accounts.staking_data.to_account_info().key.as_ref(),
+            &[ctx.accounts.staking_data.holding_bump],
+        ];
+        let signer = &[&seeds[..]];
+        let burned = ctx.accounts.slash_wallet.key() == ctx.accounts.staking_data.holding_wallet;
+        if amount > 0 {
+            if burned {
+                token::burn(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.clone(),
+                        Burn {
+                            mint: ctx.accounts.mint.to_account_info().clone(),
+                            to: ctx.accounts.holding_wallet.to_account_info().clone(),
+                            authority: ctx.accounts.holding_wallet_owner.clone(),
+                        },
+                        signer,
+                    ),
+                    amount,
+                )?;
+            } else {
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.clone(),
+                        Transfer {
+                            from: ctx.accounts.holding_wallet.to_account_info().clone(),
+                            to: ctx.accounts.slash_wallet.to_account_info().clone(),
+                            authority: ctx.accounts.holding_wallet_owner.clone(),
+                        },
+                        signer,
+                    ),
+                    amount,
+                )?;
+            }
+        }
+
+        emit!(SlashEvent {
+            staking_data: ctx.accounts.staking_data.key(),
+            staking_user_data: ctx.accounts.staking_user_data.key(),
+            shares: shares_to_slash,
+            amount,
+            slash_wallet: ctx.accounts.slash_wallet.key(),
+            burned,
+            reason,
+        });
+
+        Ok(())
+    }
+
+    /// Propose the new owner of the staking pool. The ownership is transferred after
+    /// the new owner accepts it. Default pubkey cancels the proposal.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingData account
+    /// 1. `[signer]` StakingData owner
+    ///
+    /// \param new_owner: pubkey of proposed owner
+    pub fn propose_owner(ctx: Context<ProposeOwner>, new_owner: Pubkey) -> ProgramResult {
+        msg!("Instruction: propose owner");
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        staking_data.pending_owner = new_owner;
+
+        Ok(())
+    }
+
+    /// Accept the ownership of the staking pool by the proposed owner.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingData account
+    /// 1. `[signer]` StakingData pending owner
+    pub fn accept_owner(ctx: Context<AcceptOwner>) -> ProgramResult {
+        msg!("Instruction: accept owner");
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        staking_data.owner = staking_data.pending_owner;
+        staking_data.pending_owner = Pubkey::default();
+
+        Ok(())
+    }
+
+    /// Renounce the ownership of the staking pool. After that all owner instructions
+    /// are disabled forever.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingData account
+    /// 1. `[signer]` StakingData owner
+    pub fn renounce_owner(ctx: Context<ProposeOwner>) -> ProgramResult {
+        msg!("Instruction: renounce owner");
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        staking_data.owner = Pubkey::default();
+        staking_data.pending_owner = Pubkey::default();
+
+        Ok(())
+    }
+
+    /// Accrue the interest from the staked tokens
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingData account
+    /// 1. `[writable]` StakingHoldingWallet account
+    /// 2. `[]` Token Metadata account
+    /// 3. `[writable]` Token Mint account
+    /// 4. `[]` Token Metadata Mint authority is pda of [staking_program_id, staking_data, token_metadata]
+    /// 5. `[]` SPL Token program account
+    /// 6. `[]` Clock sysvar
+    pub fn accrue_interest(ctx: Context<AccrueInterest>) -> ProgramResult {
+        msg!("Instruction: accrue interest");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        // Accrue interest
+        utils::accrue_interest_internal(
+            ctx.program_id,
+            &mut ctx.accounts.staking_data,
+            &ctx.accounts.holding_wallet,
+            &ctx.accounts.staking_token_metadata,
+            &ctx.accounts.mint,
+            &ctx.accounts.mint_authority,
+            &ctx.accounts.token_program,
+            &ctx.accounts.clock,
+        )?;
+
+        Ok(())
+    }
+
+    /// Set the list of programs allowed to lock the user shares for provided StakingData account.
+    /// Locks already placed stay until expired or released by their locker.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[writable]` StakingData account
+    /// 1. `[signer]` StakingData owner
+    ///
+    /// \param authorized_lockers: new list of locker program ids
+    pub fn set_authorized_lockers(
+        ctx: Context<SetAuthorizedLockers>,
+        authorized_lockers: Vec<Pubkey>,
+    ) -> ProgramResult {
+        msg!("Instruction: set authorized lockers");
+
+        ctx.accounts.staking_data.check_not_paused()?;
+
+        if authorized_lockers.len() > MAX_AUTHORIZED_LOCKERS {
+            msg!(
+                "Error: Too many authorized lockers, max: {}",
+                MAX_AUTHORIZED_LOCKERS
+            );
+            return Err(StakingError::TooManyLockers.into());
+        }
+
+        let staking_data = &mut ctx.accounts.staking_data;
+        staking_data.authorized_lockers = authorized_lockers;
+
+        Ok(())
+    }
+
+    /// Lock the current user ownership share. Instruction for cross program invocation
+    /// from the authorized locker program (Governance). Locker program signs with its locker
+    /// authority pda. Every locker gets the own lock entry, expired entries are removed.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[]` StakingData account
+    /// 1. `[writable]` StakingUserData account
+    /// 2. `[]` User SPL Token account
+    /// 3. `[signer]` User SPL Token owner
+    /// 4. `[]` Locker program
+    /// 5. `[signer]` Locker authority pda of ["locker_authority"] owned by the locker program
+    /// 6. `[]` Clock sysvar
+    ///
+    /// \param until: Timestamp until which the amount is locked
+    /// \param amount: Amount of shares to lock
+    /// \param locker: Key of the lock owner in the locker program (proposal)
+    /// \param locker_bump: PDA bump for locker authority
+    pub fn lock_amount(
+        ctx: Context<LockAmount>,
+        until: i64,
+        amount: u64,
+        locker: Pubkey,
+        locker_bump: u8,
+    ) -> ProgramResult {
+        msg!("Instruction: lock amount");
+        ctx.accounts.staking_data.check_not_paused()?;
+        ctx.accounts.staking_data.check_locker(
+            ctx.accounts.locker_program.key,
+            ctx.accounts.locker_authority.key,
+            locker_bump,
+        )?;
+
+        if until <= ctx.accounts.clock.unix_timestamp {
+            msg!("Trying to unlock by time.");
+            return Err(StakingError::InvalidLockParams.into());
+        }
+
+        let locker_program = *ctx.accounts.locker_program.key;
+        let staking_user_data = &mut ctx.accounts.staking_user_data;
+        staking_user_data.remove_expired_locks(ctx.accounts.clock.unix_timestamp);
+
+        // Lock of the same locker can only be extended
+        if let Some(lock) = staking_user_data
+            .locks
+            .iter_mut()
+            .find(|lock| lock.locker_program == locker_program && lock.locker == locker)
+        {
+            if until < lock.until {
+                msg!("Trying to unlock by time.");
+                return Err(StakingError::InvalidLockParams.into());
+            }
+            if amount < lock.amount {
+                msg!("Trying to unlock amount. Do nothing.");
+                return Ok(());
+            }
+            lock.amount = amount;
+            lock.until = until;
+        } else {
+            if staking_user_data.locks.len() >= MAX_LOCK_ENTRIES {
+                msg!("Error: Too many locks, max: {}", MAX_LOCK_ENTRIES);
+                return Err(StakingError::TooManyLocks.into());
+            }
+
+            staking_user_data.locks.push(LockEntry {
+                amount,
+                until,
+                locker_program,
+                locker,
+            });
+        }
+
+        emit!(LockEvent {
+            staking_data: ctx.accounts.staking_data.key(),
+            staking_user_data: staking_user_data.key(),
+            user: *ctx.accounts.user_token_wallet_owner.key,
+            locker_program,
+            locker,
+            amount,
+            until,
+            timestamp: ctx.accounts.clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Release the user lock before it expires. Instruction for cross program invocation from
+    /// the locker program which placed the lock. Not blocked by the pause: releasing only frees
+    /// the user shares, so the lockers can settle their locks (e.g. cancelled proposals) anytime.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[]` StakingData account
+    /// 1. `[writable]` StakingUserData account
+    /// 2. `[]` Locker program
+    /// 3. `[signer]` Locker authority pda of ["locker_authority"] owned by the locker program
+    ///
+    /// \param locker: Key of the lock owner in the locker program (proposal)
+    /// \param locker_bump: PDA bump for locker authority
+    pub fn release_lock(
+        ctx: Context<ReleaseLock>,
+        locker: Pubkey,
+        locker_bump: u8,
+    ) -> ProgramResult {
+        msg!("Instruction: release lock");
+
+        // Locker removed from the authorized list still can release its locks
+        utils::check_locker_authority(
+            ctx.accounts.locker_program.key,
+            ctx.accounts.locker_authority.key,
+            locker_bump,
+        )?;
+
+        let locker_program = *ctx.accounts.locker_program.key;
+        let staking_user_data = &mut ctx.accounts.staking_user_data;
+        let is_released_lock =
+            |lock: &LockEntry| lock.locker_program == locker_program && lock.locker == locker;
+        let amount: u64 = staking_user_data
+            .locks
+            .iter()
+            .filter(|lock| is_released_lock(lock))
+            .map(|lock| lock.amount)
+            .sum();
+        let locks_len = staking_user_data.locks.len();
+        staking_user_data
+            .locks
+            .retain(|lock| !is_released_lock(lock));
+        if staking_user_data.locks.len() == locks_len {
+            msg!("Error: Lock of the locker {} is not found", locker);
+            return Err(StakingError::InvalidLockParams.into());
+        }
+
+        emit!(ReleaseLockEvent {
+            staking_data: ctx.accounts.staking_data.key(),
+            staking_user_data: staking_user_data.key(),
+            locker_program,
+            locker,
+            amount,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeStaking<'info> {
+    #[account(init, payer = staking_owner, space = StakingData::LEN)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(signer)]
+    staking_owner: AccountInfo<'info>,
+    #[account(
+        constraint = holding_wallet.owner == *holding_wallet_owner.key,
+    )]
+    holding_wallet: CpiAccount<'info, TokenAccount>,
+    // pda [staking_program_id, staking_data]
+    holding_wallet_owner: AccountInfo<'info>,
+    staking_token_metadata: AccountInfo<'info>,
+    staking_token_mint: CpiAccount<'info, Mint>,
+    system_program: AccountInfo<'info>,
+    clock: Sysvar<'info, Clock>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeStakingUser<'info> {
+    #[account(init, payer = user_token_wallet_owner, space = StakingUserData::LEN)]
+    staking_user_data: ProgramAccount<'info, StakingUserData>,
+    #[account(
+        constraint = user_token_wallet.owner == *user_token_wallet_owner.key,
+    )]
+    user_token_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(signer)]
+    user_token_wallet_owner: AccountInfo<'info>,
+    #[account(
+        constraint = staking_data.stake_token_metadata == staking_token_metadata.key(),
+    )]
+    staking_data: ProgramAccount<'info, StakingData>,
+    allow_list_entry: AccountInfo<'info>,
+    staking_token_metadata: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SetInterestRate<'info> {
+    #[account(mut, constraint = staking_data.owner == *staking_owner.key)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(signer)]
+    staking_owner: AccountInfo<'info>,
+    #[account(mut,
+        constraint = holding_wallet.key() == staking_data.holding_wallet,
+        constraint = holding_wallet.mint == mint.key(),
+    )]
+    holding_wallet: CpiAccount<'info, TokenAccount>,
+    staking_token_metadata: AccountInfo<'info>,
+    #[account(mut,
+        constraint = staking_data.reward_mode == RewardMode::Reserve
+            || mint.mint_authority == COption::Some(*mint_authority.key)
+    )]
+    mint: CpiAccount<'info, Mint>,
+    #[account(
+        seeds = [
+            program_id.as_ref(),
+            staking_data.to_account_info().key.as_ref(),
+            staking_token_metadata.key.as_ref()
+        ],
+        bump = staking_data.mint_auth_bump,
+    )]
+    mint_authority: AccountInfo<'info>,
+    #[account(constraint = token_program.key == &anchor_spl::token::ID)]
+    token_program: AccountInfo<'info>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct SetRewardMode<'info> {
+    #[account(mut, constraint = staking_data.owner == *staking_owner.key)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(signer)]
+    staking_owner: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FundRewardReserve<'info> {
+    #[account(mut)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(mut,
+        constraint = holding_wallet.key() == staking_data.holding_wallet,
+    )]
+    holding_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(mut,
+        constraint = funder_wallet.owner == *funder.key,
+        constraint = funder_wallet.mint == holding_wallet.mint,
+    )]
+    funder_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(signer)]
+    funder: AccountInfo<'info>,
+    #[account(constraint = token_program.key == &anchor_spl::token::ID)]
+    token_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawRewardReserve<'info> {
+    #[account(mut, constraint = staking_data.owner == *staking_owner.key)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(signer)]
+    staking_owner: AccountInfo<'info>,
+    #[account(mut,
+        constraint = holding_wallet.owner == *holding_wallet_owner.key,
+        constraint = holding_wallet.key() == staking_data.holding_wallet,
+    )]
+    holding_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [program_id.as_ref(), staking_data.to_account_info().key.as_ref()],
+        bump = staking_data.holding_bump,
+    )]
+    holding_wallet_owner: AccountInfo<'info>,
+    #[account(mut,
+        constraint = recipient_wallet.mint == holding_wallet.mint,
+    )]
+    recipient_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(constraint = token_program.key == &anchor_spl::token::ID)]
+    token_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(mut,
+        constraint = staking_user_data.user_token_wallet == user_token_wallet.key(),
+        constraint = staking_user_data.staking_data == staking_data.key(),
+    )]
+    staking_user_data: ProgramAccount<'info, StakingUserData>,
+    #[account(mut,
+        constraint = user_token_wallet.owner == *user_token_wallet_owner.key,
+        constraint = user_token_wallet.mint == mint.key(),
+    )]
+    user_token_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(signer)]
+    user_token_wallet_owner: AccountInfo<'info>,
+    #[account(mut)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    allow_list_entry: AccountInfo<'info>,
+    #[account(mut,
+        constraint = holding_wallet.key() == staking_data.holding_wallet,
+        constraint = holding_wallet.mint == mint.key(),
+    )]
+    holding_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(mut,
+        constraint = fee_collector.key() == staking_data.fee_collector,
+    )]
+    fee_collector: CpiAccount<'info, TokenAccount>,
+    staking_token_metadata: AccountInfo<'info>,
+    #[account(mut,
+        constraint = staking_data.reward_mode == RewardMode::Reserve
+            || mint.mint_authority == COption::Some(*mint_authority.key)
+    )]
+    mint: CpiAccount<'info, Mint>,
+    #[account(
+        seeds = [
+            program_id.as_ref(),
+            staking_data.to_account_info().key.as_ref(),
+            staking_token_metadata.key.as_ref()
+        ],
+        bump = staking_data.mint_auth_bump,
+    )]
+    mint_authority: AccountInfo<'info>,
+    #[account(constraint = token_program.key == &anchor_spl::token::ID)]
+    token_program: AccountInfo<'info>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct SetLockTiers<'info> {
+    #[account(mut, constraint = staking_data.owner == *staking_owner.key)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(signer)]
+    staking_owner: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StakeLocked<'info> {
+    #[account(mut,
+        constraint = staking_user_data.user_token_wallet == user_token_wallet.key(),
+        constraint = staking_user_data.staking_data == staking_data.key(),
+    )]
+    staking_user_data: ProgramAccount<'info, StakingUserData>,
+    #[account(init, payer = user_token_wallet_owner, space = LockedStake::LEN)]
+    locked_stake: ProgramAccount<'info, LockedStake>,
+    #[account(mut,
+        constraint = user_token_wallet.owner == *user_token_wallet_owner.key,
+        constraint = user_token_wallet.mint == mint.key(),
+    )]
+    user_token_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(mut, signer)]
+    user_token_wallet_owner: AccountInfo<'info>,
+    #[account(mut)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    allow_list_entry: AccountInfo<'info>,
+    #[account(mut,
+        constraint = holding_wallet.key() == staking_data.holding_wallet,
+        constraint = holding_wallet.mint == mint.key(),
+    )]
+    holding_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(mut,
+        constraint = fee_collector.key() == staking_data.fee_collector,
+    )]
+    fee_collector: CpiAccount<'info, TokenAccount>,
+    staking_token_metadata: AccountInfo<'info>,
+    #[account(mut,
+        constraint = staking_data.reward_mode == RewardMode::Reserve
+            || mint.mint_authority == COption::Some(*mint_authority.key)
+    )]
+    mint: CpiAccount<'info, Mint>,
+    #[account(
+        seeds = [
+            program_id.as_ref(),
+            staking_data.to_account_info().key.as_ref(),
+            staking_token_metadata.key.as_ref()
+        ],
+        bump = staking_data.mint_auth_bump,
+    )]
+    mint_authority: AccountInfo<'info>,
+    #[account(constraint = token_program.key == &anchor_spl::token::ID)]
+    token_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    rent: Sysvar<'info, Rent>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseLockedStake<'info> {
+    #[account(mut,
+        constraint = staking_user_data.user_token_wallet == user_token_wallet.key(),
+        constraint = staking_user_data.staking_data == staking_data.key(),
+    )]
+    staking_user_data: ProgramAccount<'info, StakingUserData>,
+    #[account(mut,
+        constraint = locked_stake.staking_user_data == staking_user_data.key(),
+        close = user_token_wallet_owner,
+    )]
+    locked_stake: ProgramAccount<'info, LockedStake>,
+    #[account(
+        constraint = user_token_wallet.owner == *user_token_wallet_owner.key,
+    )]
+    user_token_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(mut, signer)]
+    user_token_wallet_owner: AccountInfo<'info>,
+    #[account(mut)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    #[account(mut,
+        constraint = staking_user_data.user_token_wallet == user_token_wallet.key(),
+        constraint = staking_user_data.staking_data == staking_data.key(),
+    )]
+    staking_user_data: ProgramAccount<'info, StakingUserData>,
+    #[account(mut,
+        constraint = user_token_wallet.owner == *user_token_wallet_owner.key,
+        constraint = user_token_wallet.mint == mint.key(),
+    )]
+    user_token_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(signer)]
+    user_token_wallet_owner: AccountInfo<'info>,
+    #[account(mut)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(mut,
+        constraint = holding_wallet.owner == *holding_wallet_owner.key,
+        constraint = holding_wallet.key() == staking_data.holding_wallet,
+        constraint = holding_wallet.mint == mint.key(),
+    )]
+    holding_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [program_id.as_ref(), staking_data.to_account_info().key.as_ref()],
+        bump = staking_data.holding_bump,
+    )]
+    holding_wallet_owner: AccountInfo<'info>,
+    #[account(mut,
+        constraint = penalty_wallet.key() == staking_data.penalty_wallet,
+    )]
+    penalty_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(mut,
+        constraint = fee_collector.key() == staking_data.fee_collector,
+    )]
+    fee_collector: CpiAccount<'info, TokenAccount>,
+    staking_token_metadata: AccountInfo<'info>,
+    #[account(mut,
+        constraint = staking_data.reward_mode == RewardMode::Reserve
+            || mint.mint_authority == COption::Some(*mint_authority.key)
+    )]
+    mint: CpiAccount<'info, Mint>,
+    #[account(
+        seeds = [
+            program_id.as_ref(),
+            staking_data.to_account_info().key.as_ref(),
+            staking_token_metadata.key.as_ref()
+        ],
+        bump = staking_data.mint_auth_bump,
+    )]
+    mint_authority: AccountInfo<'info>,
+    #[account(constraint = token_program.key == &anchor_spl::token::ID)]
+    token_program: AccountInfo<'info>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct SetInterestMode<'info> {
+    #[account(mut,
+        constraint = staking_user_data.user_token_wallet == user_token_wallet.key(),
+        constraint = staking_user_data.staking_data == staking_data.key(),
+    )]
+    staking_user_data: ProgramAccount<'info, StakingUserData>,
+    #[account(
+        constraint = user_token_wallet.owner == *user_token_wallet_owner.key,
+    )]
+    user_token_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(signer)]
+    user_token_wallet_owner: AccountInfo<'info>,
+    staking_data: ProgramAccount<'info, StakingData>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimInterest<'info> {
+    #[account(mut,
+        constraint = staking_user_data.user_token_wallet == user_token_wallet.key(),
+        constraint = staking_user_data.staking_data == staking_data.key(),
+    )]
+    staking_user_data: ProgramAccount<'info, StakingUserData>,
+    #[account(mut,
+        constraint = user_token_wallet.owner == *user_token_wallet_owner.key,
+        constraint = user_token_wallet.mint == mint.key(),
+    )]
+    user_token_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(signer)]
+    user_token_wallet_owner: AccountInfo<'info>,
+    #[account(mut)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(mut,
+        constraint = holding_wallet.owner == *holding_wallet_owner.key,
+        constraint = holding_wallet.key() == staking_data.holding_wallet,
+        constraint = holding_wallet.mint == mint.key(),
+    )]
+    holding_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [program_id.as_ref(), staking_data.to_account_info().key.as_ref()],
+        bump = staking_data.holding_bump,
+    )]
+    holding_wallet_owner: AccountInfo<'info>,
+    staking_token_metadata: AccountInfo<'info>,
+    #[account(mut,
+        constraint = staking_data.reward_mode == RewardMode::Reserve
+            || mint.mint_authority == COption::Some(*mint_authority.key)
+    )]
+    mint: CpiAccount<'info, Mint>,
+    #[account(
+        seeds = [
+            program_id.as_ref(),
+            staking_data.to_account_info().key.as_ref(),
+            staking_token_metadata.key.as_ref()
+        ],
+        bump = staking_data.mint_auth_bump,
+    )]
+    mint_authority: AccountInfo<'info>,
+    #[account(constraint = token_program.key == &anchor_spl::token::ID)]
+    token_program: AccountInfo<'info>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct SetCooldown<'info> {
+    #[account(mut, constraint = staking_data.owner == *staking_owner.key)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(signer)]
+    staking_owner: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(receipt_mint_auth_bump: u8)]
+pub struct EnableReceipts<'info> {
+    #[account(mut, constraint = staking_data.owner == *staking_owner.key)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(mut, signer)]
+    staking_owner: AccountInfo<'info>,
+    #[account(
+        constraint = receipt_mint.mint_authority == COption::Some(*receipt_mint_authority.key),
+        constraint = receipt_mint.supply == 0,
+    )]
+    receipt_mint: CpiAccount<'info, Mint>,
+    #[account(
+        seeds = [
+            RECEIPT_MINT_PREFIX.as_bytes(),
+            program_id.as_ref(),
+            staking_data.to_account_info().key.as_ref()
+        ],
+        bump = receipt_mint_auth_bump,
+    )]
+    receipt_mint_authority: AccountInfo<'info>,
+    #[account(mut)]
+    receipt_metadata: AccountInfo<'info>,
+    #[account(executable)]
+    metadata_program: AccountInfo<'info>,
+    #[account(address = spl_token_metadata::id())]
+    token_metadata_program: AccountInfo<'info>,
+    #[account(constraint = token_program.key == &anchor_spl::token::ID)]
+    token_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct MintReceipts<'info> {
+    #[account(mut,
+        constraint = staking_user_data.user_token_wallet == user_token_wallet.key(),
+        constraint = staking_user_data.staking_data == staking_data.key(),
+    )]
+    staking_user_data: ProgramAccount<'info, StakingUserData>,
+    #[account(
+        constraint = user_token_wallet.owner == *user_token_wallet_owner.key,
+    )]
+    user_token_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(signer)]
+    user_token_wallet_owner: AccountInfo<'info>,
+    #[account(mut)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(mut,
+        constraint = staking_data.receipts_enabled(),
+        constraint = receipt_mint.key() == staking_data.receipt_mint,
+    )]
+    receipt_mint: CpiAccount<'info, Mint>,
+    #[account(
+        seeds = [
+            RECEIPT_MINT_PREFIX.as_bytes(),
+            program_id.as_ref(),
+            staking_data.to_account_info().key.as_ref()
+        ],
+        bump = staking_data.receipt_mint_auth_bump,
+    )]
+    receipt_mint_authority: AccountInfo<'info>,
+    #[account(mut,
+        constraint = receipt_wallet.mint == staking_data.receipt_mint,
+    )]
+    receipt_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(constraint = token_program.key == &anchor_spl::token::ID)]
+    token_program: AccountInfo<'info>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemReceipts<'info> {
+    #[account(mut,
+        constraint = staking_user_data.user_token_wallet == user_token_wallet.key(),
+        constraint = staking_user_data.staking_data == staking_data.key(),
+    )]
+    staking_user_data: ProgramAccount<'info, StakingUserData>,
+    #[account(
+        constraint = user_token_wallet.owner == *user_token_wallet_owner.key,
+    )]
+    user_token_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(signer)]
+    user_token_wallet_owner: AccountInfo<'info>,
+    #[account(mut)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    allow_list_entry: AccountInfo<'info>,
+    #[account(mut,
+        constraint = staking_data.receipts_enabled(),
+        constraint = receipt_mint.key() == staking_data.receipt_mint,
+    )]
+    receipt_mint: CpiAccount<'info, Mint>,
+    #[account(mut,
+        constraint = receipt_wallet.mint == staking_data.receipt_mint,
+        constraint = receipt_wallet.owner == *user_token_wallet_owner.key,
+    )]
+    receipt_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(constraint = token_program.key == &anchor_spl::token::ID)]
+    token_program: AccountInfo<'info>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct SetDepositLimits<'info> {
+    #[account(mut, constraint = staking_data.owner == *staking_owner.key)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(signer)]
+    staking_owner: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(_allow_list_bump: u8)]
+pub struct AddToAllowList<'info> {
+    // pda of ["allow_list", staking_program_id, staking_data, wallet]
+    #[account(init, payer = staking_owner, space = AllowListEntry::LEN,
+        seeds = [
+            ALLOW_LIST_PREFIX.as_bytes(),
+            program_id.as_ref(),
+            staking_data.key().as_ref(),
+            wallet.key.as_ref()
+        ],
+        bump = _allow_list_bump,
+    )]
+    allow_list_entry: ProgramAccount<'info, AllowListEntry>,
+    #[account(constraint = staking_data.owner == *staking_owner.key)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(mut, signer)]
+    staking_owner: AccountInfo<'info>,
+    wallet: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveFromAllowList<'info> {
+    #[account(mut,
+        constraint = allow_list_entry.staking_data == staking_data.key(),
+        close = staking_owner,
+    )]
+    allow_list_entry: ProgramAccount<'info, AllowListEntry>,
+    #[account(constraint = staking_data.owner == *staking_owner.key)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(mut, signer)]
+    staking_owner: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetFees<'info> {
+    #[account(mut, constraint = staking_data.owner == *staking_owner.key)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(signer)]
+    staking_owner: AccountInfo<'info>,
+    #[account(
+        constraint = holding_wallet.key() == staking_data.holding_wallet,
+    )]
+    holding_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(
+        constraint = fee_collector.mint == holding_wallet.mint,
+    )]
+    fee_collector: CpiAccount<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct SetEarlyUnstakePenalty<'info> {
+    #[account(mut, constraint = staking_data.owner == *staking_owner.key)]
+    staking_data: ProgramAccount<'info, StakingData>,
     #[account(signer)]
     staking_owner: AccountInfo<'info>,
     #[account(
+        constraint = holding_wallet.key() == staking_data.holding_wallet,
+    )]
+    holding_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(
+        constraint = penalty_wallet.mint == holding_wallet.mint,
+    )]
+    penalty_wallet: CpiAccount<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct RequestUnstake<'info> {
+    #[account(mut,
+        constraint = staking_user_data.user_token_wallet == user_token_wallet.key(),
+        constraint = staking_user_data.staking_data == staking_data.key(),
+    )]
+    staking_user_data: ProgramAccount<'info, StakingUserData>,
+    #[account(init, payer = user_token_wallet_owner, space = PendingWithdrawal::LEN)]
+    pending_withdrawal: ProgramAccount<'info, PendingWithdrawal>,
+    #[account(
+        constraint = user_token_wallet.owner == *user_token_wallet_owner.key,
+        constraint = user_token_wallet.mint == mint.key(),
+    )]
+    user_token_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(mut, signer)]
+    user_token_wallet_owner: AccountInfo<'info>,
+    #[account(mut)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(mut,
         constraint = holding_wallet.owner == *holding_wallet_owner.key,
+        constraint = holding_wallet.key() == staking_data.holding_wallet,
+        constraint = holding_wallet.mint == mint.key(),
     )]
     holding_wallet: CpiAccount<'info, TokenAccount>,
-    // pda [staking_program_id, staking_data]
+    #[account(
+        seeds = [program_id.as_ref(), staking_data.to_account_info().key.as_ref()],
+        bump = staking_data.holding_bump,
+    )]
     holding_wallet_owner: AccountInfo<'info>,
+    #[account(mut,
+        constraint = penalty_wallet.key() == staking_data.penalty_wallet,
+    )]
+    penalty_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(mut,
+        constraint = fee_collector.key() == staking_data.fee_collector,
+    )]
+    fee_collector: CpiAccount<'info, TokenAccount>,
     staking_token_metadata: AccountInfo<'info>,
-    staking_token_mint: CpiAccount<'info, Mint>,
+    #[account(mut,
+        constraint = staking_data.reward_mode == RewardMode::Reserve
+            || mint.mint_authority == COption::Some(*mint_authority.key)
+    )]
+    mint: CpiAccount<'info, Mint>,
+    #[account(
+        seeds = [
+            program_id.as_ref(),
+            staking_data.to_account_info().key.as_ref(),
+            staking_token_metadata.key.as_ref()
+        ],
+        bump = staking_data.mint_auth_bump,
+    )]
+    mint_authority: AccountInfo<'info>,
+    #[account(constraint = token_program.key == &anchor_spl::token::ID)]
+    token_program: AccountInfo<'info>,
     system_program: AccountInfo<'info>,
-    clock: Sysvar<'info, Clock>,
     rent: Sysvar<'info, Rent>,
+    clock: Sysvar<'info, Clock>,
 }
 
 #[derive(Accounts)]
-pub struct InitializeStakingUser<'info> {
-    #[account(init, payer = user_token_wallet_owner, space = StakingUserData::LEN)]
+pub struct Withdraw<'info> {
+    #[account(mut,
+        constraint = staking_user_data.user_token_wallet == user_token_wallet.key(),
+        constraint = staking_user_data.staking_data == staking_data.key(),
+    )]
+    staking_user_data: ProgramAccount<'info, StakingUserData>,
+    #[account(mut,
+        constraint = pending_withdrawal.staking_user_data == staking_user_data.key(),
+        close = user_token_wallet_owner,
+    )]
+    pending_withdrawal: ProgramAccount<'info, PendingWithdrawal>,
+    #[account(mut,
+        constraint = user_token_wallet.owner == *user_token_wallet_owner.key,
+        constraint = user_token_wallet.mint == holding_wallet.mint,
+    )]
+    user_token_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(mut, signer)]
+    user_token_wallet_owner: AccountInfo<'info>,
+    #[account(mut)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(mut,
+        constraint = holding_wallet.owner == *holding_wallet_owner.key,
+        constraint = holding_wallet.key() == staking_data.holding_wallet,
+    )]
+    holding_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [program_id.as_ref(), staking_data.to_account_info().key.as_ref()],
+        bump = staking_data.holding_bump,
+    )]
+    holding_wallet_owner: AccountInfo<'info>,
+    #[account(constraint = token_program.key == &anchor_spl::token::ID)]
+    token_program: AccountInfo<'info>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct TransferShares<'info> {
+    #[account(mut,
+        constraint = staking_user_data.user_token_wallet == user_token_wallet.key(),
+        constraint = staking_user_data.staking_data == staking_data.key(),
+    )]
     staking_user_data: ProgramAccount<'info, StakingUserData>,
     #[account(
         constraint = user_token_wallet.owner == *user_token_wallet_owner.key,
@@ -419,28 +3819,99 @@ pub struct InitializeStakingUser<'info> {
     user_token_wallet: CpiAccount<'info, TokenAccount>,
     #[account(signer)]
     user_token_wallet_owner: AccountInfo<'info>,
+    #[account(mut,
+        constraint = recipient_staking_user_data.staking_data == staking_data.key(),
+        constraint = recipient_staking_user_data.key() != staking_user_data.key(),
+    )]
+    recipient_staking_user_data: ProgramAccount<'info, StakingUserData>,
     #[account(
-        constraint = staking_data.stake_token_metadata == staking_token_metadata.key(),
+        constraint = recipient_token_wallet.key() == recipient_staking_user_data.user_token_wallet,
     )]
+    recipient_token_wallet: CpiAccount<'info, TokenAccount>,
+    recipient_allow_list_entry: AccountInfo<'info>,
     staking_data: ProgramAccount<'info, StakingData>,
-    staking_token_metadata: AccountInfo<'info>,
-    system_program: AccountInfo<'info>,
-    rent: Sysvar<'info, Rent>,
+    clock: Sysvar<'info, Clock>,
 }
 
 #[derive(Accounts)]
-pub struct SetInterestRate<'info> {
-    #[account(mut, constraint = staking_data.owner == *staking_owner.key)]
-    staking_data: ProgramAccount<'info, StakingData>,
+pub struct TransferLockedStake<'info> {
+    #[account(mut,
+        constraint = staking_user_data.user_token_wallet == user_token_wallet.key(),
+        constraint = staking_user_data.staking_data == staking_data.key(),
+    )]
+    staking_user_data: ProgramAccount<'info, StakingUserData>,
+    #[account(mut,
+        constraint = locked_stake.staking_user_data == staking_user_data.key(),
+    )]
+    locked_stake: ProgramAccount<'info, LockedStake>,
+    #[account(
+        constraint = user_token_wallet.owner == *user_token_wallet_owner.key,
+    )]
+    user_token_wallet: CpiAccount<'info, TokenAccount>,
     #[account(signer)]
-    staking_owner: AccountInfo<'info>,
+    user_token_wallet_owner: AccountInfo<'info>,
+    #[account(mut,
+        constraint = recipient_staking_user_data.staking_data == staking_data.key(),
+        constraint = recipient_staking_user_data.key() != staking_user_data.key(),
+    )]
+    recipient_staking_user_data: ProgramAccount<'info, StakingUserData>,
+    #[account(
+        constraint = recipient_token_wallet.key() == recipient_staking_user_data.user_token_wallet,
+    )]
+    recipient_token_wallet: CpiAccount<'info, TokenAccount>,
+    recipient_allow_list_entry: AccountInfo<'info>,
+    staking_data: ProgramAccount<'info, StakingData>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct CloseStakingUser<'info> {
+    #[account(mut,
+        constraint = staking_user_data.user_token_wallet == user_token_wallet.key(),
+        constraint = staking_user_data.staking_data == staking_data.key(),
+        close = user_token_wallet_owner,
+    )]
+    staking_user_data: ProgramAccount<'info, StakingUserData>,
+    #[account(
+        constraint = user_token_wallet.owner == *user_token_wallet_owner.key,
+    )]
+    user_token_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(mut, signer)]
+    user_token_wallet_owner: AccountInfo<'info>,
+    staking_data: ProgramAccount<'info, StakingData>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct CancelUnstake<'info> {
+    #[account(mut,
+        constraint = staking_user_data.user_token_wallet == user_token_wallet.key(),
+        constraint = staking_user_data.staking_data == staking_data.key(),
+    )]
+    staking_user_data: ProgramAccount<'info, StakingUserData>,
+    #[account(mut,
+        constraint = pending_withdrawal.staking_user_data == staking_user_data.key(),
+        close = user_token_wallet_owner,
+    )]
+    pending_withdrawal: ProgramAccount<'info, PendingWithdrawal>,
+    #[account(
+        constraint = user_token_wallet.owner == *user_token_wallet_owner.key,
+    )]
+    user_token_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(mut, signer)]
+    user_token_wallet_owner: AccountInfo<'info>,
+    #[account(mut)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    allow_list_entry: AccountInfo<'info>,
     #[account(mut,
+        constraint = holding_wallet.key() == staking_data.holding_wallet,
         constraint = holding_wallet.mint == mint.key(),
     )]
     holding_wallet: CpiAccount<'info, TokenAccount>,
     staking_token_metadata: AccountInfo<'info>,
     #[account(mut,
-        constraint = mint.mint_authority == COption::Some(*mint_authority.key)
+        constraint = staking_data.reward_mode == RewardMode::Reserve
+            || mint.mint_authority == COption::Some(*mint_authority.key)
     )]
     mint: CpiAccount<'info, Mint>,
     #[account(
@@ -451,69 +3922,196 @@ pub struct SetInterestRate<'info> {
         ],
         bump = staking_data.mint_auth_bump,
     )]
-    mint_authority: AccountInfo<'info>,
-    #[account(constraint = token_program.key == &anchor_spl::token::ID)]
-    token_program: AccountInfo<'info>,
-    clock: Sysvar<'info, Clock>,
+    mint_authority: AccountInfo<'info>,
+    #[account(constraint = token_program.key == &anchor_spl::token::ID)]
+    token_program: AccountInfo<'info>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    emission_per_second: u64,
+    start_timestamp: i64,
+    end_timestamp: i64,
+    vault_bump: u8
+)]
+pub struct AddRewardStream<'info> {
+    #[account(mut, constraint = staking_data.owner == *staking_owner.key)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(signer)]
+    staking_owner: AccountInfo<'info>,
+    #[account(init, payer = sponsor, space = RewardStream::LEN)]
+    reward_stream: ProgramAccount<'info, RewardStream>,
+    #[account(mut, signer)]
+    sponsor: AccountInfo<'info>,
+    #[account(
+        constraint = vault.owner == *vault_owner.key,
+    )]
+    vault: CpiAccount<'info, TokenAccount>,
+    // pda [staking_program_id, reward_stream]
+    #[account(
+        seeds = [program_id.as_ref(), reward_stream.to_account_info().key.as_ref()],
+        bump = vault_bump,
+    )]
+    vault_owner: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    rent: Sysvar<'info, Rent>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveRewardStream<'info> {
+    #[account(mut, constraint = staking_data.owner == *staking_owner.key)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(signer)]
+    staking_owner: AccountInfo<'info>,
+    #[account(mut,
+        constraint = reward_stream.staking_data == staking_data.key(),
+    )]
+    reward_stream: ProgramAccount<'info, RewardStream>,
+    #[account(constraint = reward_stream.sponsor == *sponsor.key)]
+    sponsor: AccountInfo<'info>,
+    #[account(mut,
+        constraint = vault.key() == reward_stream.vault,
+    )]
+    vault: CpiAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [program_id.as_ref(), reward_stream.to_account_info().key.as_ref()],
+        bump = reward_stream.vault_bump,
+    )]
+    vault_owner: AccountInfo<'info>,
+    #[account(mut,
+        constraint = sponsor_reward_wallet.mint == reward_stream.reward_mint,
+        constraint = sponsor_reward_wallet.owner == reward_stream.sponsor,
+    )]
+    sponsor_reward_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(constraint = token_program.key == &anchor_spl::token::ID)]
+    token_program: AccountInfo<'info>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+#[instruction(_user_reward_bump: u8)]
+pub struct InitUserReward<'info> {
+    // pda of ["user_reward", staking_program_id, reward_stream, staking_user_data]
+    #[account(init, payer = payer, space = UserReward::LEN,
+        seeds = [
+            USER_REWARD_PREFIX.as_bytes(),
+            program_id.as_ref(),
+            reward_stream.key().as_ref(),
+            staking_user_data.key().as_ref()
+        ],
+        bump = _user_reward_bump,
+    )]
+    user_reward: ProgramAccount<'info, UserReward>,
+    reward_stream: ProgramAccount<'info, RewardStream>,
+    #[account(
+        constraint = staking_user_data.staking_data == reward_stream.staking_data,
+    )]
+    staking_user_data: ProgramAccount<'info, StakingUserData>,
+    #[account(mut, signer)]
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct Stake<'info> {
-    #[account(mut,
+pub struct ClaimRewards<'info> {
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(
         constraint = staking_user_data.user_token_wallet == user_token_wallet.key(),
         constraint = staking_user_data.staking_data == staking_data.key(),
     )]
     staking_user_data: ProgramAccount<'info, StakingUserData>,
-    #[account(mut,
+    #[account(
         constraint = user_token_wallet.owner == *user_token_wallet_owner.key,
-        constraint = user_token_wallet.mint == mint.key(),
     )]
     user_token_wallet: CpiAccount<'info, TokenAccount>,
     #[account(signer)]
     user_token_wallet_owner: AccountInfo<'info>,
-    #[account(mut)]
-    staking_data: ProgramAccount<'info, StakingData>,
     #[account(mut,
-        constraint = holding_wallet.mint == mint.key(),
+        constraint = reward_stream.staking_data == staking_data.key(),
     )]
-    holding_wallet: CpiAccount<'info, TokenAccount>,
-    staking_token_metadata: AccountInfo<'info>,
+    reward_stream: ProgramAccount<'info, RewardStream>,
     #[account(mut,
-        constraint = mint.mint_authority == COption::Some(*mint_authority.key)
+        constraint = user_reward.reward_stream == reward_stream.key(),
+        constraint = user_reward.staking_user_data == staking_user_data.key(),
     )]
-    mint: CpiAccount<'info, Mint>,
+    user_reward: ProgramAccount<'info, UserReward>,
+    #[account(mut,
+        constraint = vault.key() == reward_stream.vault,
+    )]
+    vault: CpiAccount<'info, TokenAccount>,
     #[account(
-        seeds = [
-            program_id.as_ref(),
-            staking_data.to_account_info().key.as_ref(),
-            staking_token_metadata.key.as_ref()
-        ],
-        bump = staking_data.mint_auth_bump,
+        seeds = [program_id.as_ref(), reward_stream.to_account_info().key.as_ref()],
+        bump = reward_stream.vault_bump,
     )]
-    mint_authority: AccountInfo<'info>,
+    vault_owner: AccountInfo<'info>,
+    #[account(mut,
+        constraint = user_reward_wallet.mint == reward_stream.reward_mint,
+    )]
+    user_reward_wallet: CpiAccount<'info, TokenAccount>,
     #[account(constraint = token_program.key == &anchor_spl::token::ID)]
     token_program: AccountInfo<'info>,
     clock: Sysvar<'info, Clock>,
 }
 
 #[derive(Accounts)]
-pub struct Unstake<'info> {
-    #[account(mut,
+pub struct CloseUserReward<'info> {
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(
         constraint = staking_user_data.user_token_wallet == user_token_wallet.key(),
         constraint = staking_user_data.staking_data == staking_data.key(),
     )]
     staking_user_data: ProgramAccount<'info, StakingUserData>,
-    #[account(mut,
+    #[account(
         constraint = user_token_wallet.owner == *user_token_wallet_owner.key,
-        constraint = user_token_wallet.mint == mint.key(),
     )]
     user_token_wallet: CpiAccount<'info, TokenAccount>,
-    #[account(signer)]
+    #[account(mut, signer)]
     user_token_wallet_owner: AccountInfo<'info>,
-    #[account(mut)]
+    #[account(
+        constraint = reward_stream.staking_data == staking_data.key(),
+    )]
+    reward_stream: ProgramAccount<'info, RewardStream>,
+    #[account(mut,
+        constraint = user_reward.reward_stream == reward_stream.key(),
+        constraint = user_reward.staking_user_data == staking_user_data.key(),
+        close = user_token_wallet_owner,
+    )]
+    user_reward: ProgramAccount<'info, UserReward>,
+}
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(mut, constraint = staking_data.owner == *staking_owner.key)]
     staking_data: ProgramAccount<'info, StakingData>,
+    #[account(signer)]
+    staking_owner: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetSlasher<'info> {
+    #[account(mut, constraint = staking_data.owner == *staking_owner.key)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(signer)]
+    staking_owner: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Slash<'info> {
+    #[account(mut,
+        constraint = staking_data.slasher != Pubkey::default(),
+        constraint = staking_data.slasher == *slasher.key,
+    )]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(signer)]
+    slasher: AccountInfo<'info>,
+    #[account(mut, constraint = staking_user_data.staking_data == staking_data.key())]
+    staking_user_data: ProgramAccount<'info, StakingUserData>,
     #[account(mut,
         constraint = holding_wallet.owner == *holding_wallet_owner.key,
+        constraint = holding_wallet.key() == staking_data.holding_wallet,
         constraint = holding_wallet.mint == mint.key(),
     )]
     holding_wallet: CpiAccount<'info, TokenAccount>,
@@ -522,9 +4120,12 @@ pub struct Unstake<'info> {
         bump = staking_data.holding_bump,
     )]
     holding_wallet_owner: AccountInfo<'info>,
+    #[account(mut, constraint = slash_wallet.mint == mint.key())]
+    slash_wallet: CpiAccount<'info, TokenAccount>,
     staking_token_metadata: AccountInfo<'info>,
     #[account(mut,
-        constraint = mint.mint_authority == COption::Some(*mint_authority.key)
+        constraint = staking_data.reward_mode == RewardMode::Reserve
+            || mint.mint_authority == COption::Some(*mint_authority.key)
     )]
     mint: CpiAccount<'info, Mint>,
     #[account(
@@ -541,17 +4142,45 @@ pub struct Unstake<'info> {
     clock: Sysvar<'info, Clock>,
 }
 
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(mut,
+        constraint = staking_data.owner == *authority.key || staking_data.guardian == *authority.key,
+    )]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(signer)]
+    authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeOwner<'info> {
+    #[account(mut, constraint = staking_data.owner == *staking_owner.key)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(signer)]
+    staking_owner: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptOwner<'info> {
+    #[account(mut, constraint = staking_data.pending_owner == *pending_owner.key)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(signer)]
+    pending_owner: AccountInfo<'info>,
+}
+
 #[derive(Accounts)]
 pub struct AccrueInterest<'info> {
     #[account(mut)]
     staking_data: ProgramAccount<'info, StakingData>,
     #[account(mut,
+        constraint = holding_wallet.key() == staking_data.holding_wallet,
         constraint = holding_wallet.mint == mint.key(),
     )]
     holding_wallet: CpiAccount<'info, TokenAccount>,
     staking_token_metadata: AccountInfo<'info>,
     #[account(mut,
-        constraint = mint.mint_authority == COption::Some(*mint_authority.key)
+        constraint = staking_data.reward_mode == RewardMode::Reserve
+            || mint.mint_authority == COption::Some(*mint_authority.key)
     )]
     mint: CpiAccount<'info, Mint>,
     #[account(
@@ -568,6 +4197,14 @@ pub struct AccrueInterest<'info> {
     clock: Sysvar<'info, Clock>,
 }
 
+#[derive(Accounts)]
+pub struct SetAuthorizedLockers<'info> {
+    #[account(mut, constraint = staking_data.owner == *staking_owner.key)]
+    staking_data: ProgramAccount<'info, StakingData>,
+    #[account(signer)]
+    staking_owner: AccountInfo<'info>,
+}
+
 #[derive(Accounts)]
 pub struct LockAmount<'info> {
     pub staking_data: CpiAccount<'info, StakingData>,
@@ -582,11 +4219,147 @@ pub struct LockAmount<'info> {
     pub user_token_wallet: CpiAccount<'info, TokenAccount>,
     #[account(signer)]
     pub user_token_wallet_owner: AccountInfo<'info>,
+    pub locker_program: AccountInfo<'info>,
+    #[account(signer)]
+    pub locker_authority: AccountInfo<'info>,
     pub clock: Sysvar<'info, Clock>,
 }
 
+#[derive(Accounts)]
+pub struct ReleaseLock<'info> {
+    pub staking_data: CpiAccount<'info, StakingData>,
+    #[account(mut, constraint = staking_user_data.staking_data == staking_data.key())]
+    pub staking_user_data: ProgramAccount<'info, StakingUserData>,
+    pub locker_program: AccountInfo<'info>,
+    #[account(signer)]
+    pub locker_authority: AccountInfo<'info>,
+}
+
 // Program accounts
 pub const DISCRIMINATOR_LEN: usize = 8;
+pub const MAX_LOCK_TIERS: usize = 8;
+pub const MAX_LOCK_TIERS_VEC_SIZE: usize = 4 + LockTier::LEN * MAX_LOCK_TIERS;
+/// Hard cap for lock tier boost (3x shares)
+pub const MAX_LOCK_BOOST: u64 = 3 * utils::BOOST_MUL_FACTOR;
+pub const MAX_RATE_SCHEDULE_POINTS: usize = 16;
+pub const MAX_RATE_SCHEDULE_VEC_SIZE: usize = 4 + RatePoint::LEN * MAX_RATE_SCHEDULE_POINTS;
+/// Hard cap for unstake cooldown (30 days)
+pub const MAX_COOLDOWN_SECONDS: i64 = 30 * SECONDS_PER_DAY as i64;
+pub const MAX_AUTHORIZED_LOCKERS: usize = 4;
+pub const MAX_AUTHORIZED_LOCKERS_VEC_SIZE: usize = 4 + 32 * MAX_AUTHORIZED_LOCKERS;
+pub const MAX_SHARE_CHECKPOINTS: usize = 32;
+pub const MAX_SHARE_CHECKPOINTS_VEC_SIZE: usize = 4 + ShareCheckpoint::LEN * MAX_SHARE_CHECKPOINTS;
+pub const MAX_LOCK_ENTRIES: usize = 8;
+pub const MAX_LOCK_ENTRIES_VEC_SIZE: usize = 4 + LockEntry::LEN * MAX_LOCK_ENTRIES;
+pub const MAX_REWARD_STREAMS: usize = 4;
+pub const MAX_REWARD_STREAMS_VEC_SIZE: usize = 4 + 32 * MAX_REWARD_STREAMS;
+pub const USER_REWARD_PREFIX: &str = "user_reward";
+pub const ALLOW_LIST_PREFIX: &str = "allow_list";
+pub const RECEIPT_MINT_PREFIX: &str = "receipt_mint";
+pub const LOCKER_AUTHORITY_PREFIX: &str = "locker_authority";
+/// Hard cap for early unstake penalty (50%)
+pub const MAX_EARLY_UNSTAKE_PENALTY: u64 = utils::BPS_MUL_FACTOR / 2;
+/// Hard cap for early unstake penalty window (365 days)
+pub const MAX_PENALTY_WINDOW_SECONDS: i64 = 365 * SECONDS_PER_DAY as i64;
+/// Hard cap for deposit and withdraw fees (10%)
+pub const MAX_FEE_BPS: u64 = utils::BPS_MUL_FACTOR / 10;
+
+/// Breakpoint of the interest rate schedule
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct RatePoint {
+    pub effective_timestamp: i64,
+    /// Daily interest rate in 1e-6 (1/100 of a basis point)
+    pub daily_rate: u64,
+}
+
+impl RatePoint {
+    pub const LEN: usize = 8 + 8;
+}
+
+/// Kinked curve of the daily interest rate falling with the staked supply utilization
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct RateModel {
+    /// Daily interest rate in 1e-6 with nothing staked
+    pub base_rate: u64,
+    /// Rate decrease for the full utilization below the kink
+    pub slope_low: u64,
+    /// Rate decrease for the full utilization above the kink
+    pub slope_high: u64,
+    /// Utilization in 1e-6 where the slope changes
+    pub kink: u64,
+}
+
+impl RateModel {
+    pub const LEN: usize = 8 + 8 + 8 + 8;
+}
+
+/// Source of the staking pool interest
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum RewardMode {
+    /// Interest is minted by the mint authority PDA
+    Mint,
+    /// Interest is paid from the pre-funded reward reserve
+    Reserve,
+}
+
+/// Interest payout of the staking pool user
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum InterestMode {
+    /// Interest stays staked and compounds
+    Compound,
+    /// Interest above the principal is claimed by `claim_interest`
+    Claim,
+}
+
+/// Pause level of the staking pool
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum PauseLevel {
+    Unpaused,
+    /// Deposits are blocked, withdrawals are allowed
+    UnstakeOnly,
+    /// All instructions changing the staking pool are blocked
+    Full,
+}
+
+/// Lock duration and shares boost for time-locked stakes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct LockTier {
+    /// Lock duration in seconds
+    pub duration: i64,
+    /// Shares multiplier in 1e-4 (basis point), 10000 is no boost
+    pub boost: u64,
+}
+
+impl LockTier {
+    pub const LEN: usize = 8 + 8;
+}
+
+/// Shares locked by the governance proposal vote
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct LockEntry {
+    /// Amount of locked shares
+    pub amount: u64,
+    pub until: i64,
+    /// Program which placed the lock and can release it early
+    pub locker_program: Pubkey,
+    /// Key of the lock owner in the locker program (proposal)
+    pub locker: Pubkey,
+}
+
+impl LockEntry {
+    pub const LEN: usize = 8 + 8 + 32 + 32;
+}
+
+/// User shares since the slot, used as the voting weight snapshot
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ShareCheckpoint {
+    pub slot: u64,
+    pub shares: u64,
+}
+
+impl ShareCheckpoint {
+    pub const LEN: usize = 8 + 8;
+}
 
 /// Account for storing common information about staking pool.
 #[account]
@@ -609,10 +4382,174 @@ pub struct StakingData {
     pub last_interest_accrued_timestamp: i64,
     /// Hard cap for staking token supply
     pub cap: u64,
+    /// Available tiers for time-locked stakes
+    pub lock_tiers: Vec<LockTier>,
+    /// Delay between unstake request and withdrawal, zero allows instant unstake
+    pub cooldown_seconds: i64,
+    /// Amount of tokens requested to unstake and waiting for withdrawal
+    pub pending_withdrawals: u64,
+    /// Authority allowed to pause the staking pool along with the owner
+    pub guardian: Pubkey,
+    pub pause_level: PauseLevel,
+    /// Proposed owner who has to accept the ownership
+    pub pending_owner: Pubkey,
+    /// Upcoming interest rate changes, passed breakpoints are moved to `interest_rate_daily`
+    pub rate_schedule: Vec<RatePoint>,
+    /// Utilization based rate model, None means the fixed interest rate
+    pub rate_model: Option<RateModel>,
+    /// External reward streams distributed to the users ownership share
+    pub reward_streams: Vec<Pubkey>,
+    pub reward_mode: RewardMode,
+    /// Tokens of the holding wallet reserved for the interest in reserve mode
+    pub reward_reserve: u64,
+    /// Reward reserve ran dry and the interest is not accrued until it is funded
+    pub reserve_exhausted: bool,
+    /// Early unstake penalty in 1e-4 (basis point) right after the stake
+    pub early_unstake_penalty: u64,
+    /// Time since the last stake the penalty decays to zero over
+    pub penalty_window_seconds: i64,
+    /// Recipient of the penalty, penalty stays in the pool when it's the holding wallet
+    pub penalty_wallet: Pubkey,
+    /// Fee taken from the staked amount in 1e-4 (basis point)
+    pub deposit_fee_bps: u64,
+    /// Fee taken from the unstaked amount in 1e-4 (basis point)
+    pub withdraw_fee_bps: u64,
+    /// Token account receiving deposit and withdraw fees
+    pub fee_collector: Pubkey,
+    /// Only users with AllowListEntry can initialize StakingUserData and stake
+    pub allow_list_enabled: bool,
+    /// Maximum value of the user stake, 0 for no cap
+    pub user_deposit_cap: u64,
+    /// Maximum total staked, 0 for no cap
+    pub total_deposit_cap: u64,
+    /// Mint of the receipt tokens, default pubkey if receipts are disabled
+    pub receipt_mint: Pubkey,
+    pub receipt_mint_auth_bump: u8,
+    /// Shares moved into receipts, they are not owned by any StakingUserData
+    pub receipt_shares: u64,
+    /// Programs allowed to lock the user shares
+    pub authorized_lockers: Vec<Pubkey>,
+    /// Authority allowed to slash the users, default pubkey disables slashing
+    pub slasher: Pubkey,
 }
 
 impl StakingData {
-    pub const LEN: usize = DISCRIMINATOR_LEN + 32 + 32 + 32 + 1 + 1 + 8 + 8 + 8 + 8 + 8 + 8;
+    pub const LEN: usize = DISCRIMINATOR_LEN
+        + 32
+        + 32
+        + 32
+        + 1
+        + 1
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + MAX_LOCK_TIERS_VEC_SIZE
+        + 8
+        + 8
+        + 32
+        + 1
+        + 32
+        + MAX_RATE_SCHEDULE_VEC_SIZE
+        + 1
+        + RateModel::LEN
+        + MAX_REWARD_STREAMS_VEC_SIZE
+        + 1
+        + 8
+        + 1
+        + 8
+        + 8
+        + 32
+        + 8
+        + 8
+        + 32
+        + 1
+        + 8
+        + 8
+        + 32
+        + 1
+        + 8
+        + MAX_AUTHORIZED_LOCKERS_VEC_SIZE
+        + 32;
+
+    /// Part of the accrued interest the staking pool is able to pay in reserve mode
+    pub fn payable_interest(&self, interest: u64) -> u64 {
+        match self.reward_mode {
+            RewardMode::Mint => interest,
+            RewardMode::Reserve => interest.min(self.reward_reserve),
+        }
+    }
+
+    /// Staking pool mints receipts for the shares
+    pub fn receipts_enabled(&self) -> bool {
+        self.receipt_mint != Pubkey::default()
+    }
+
+    /// Shares of the StakingUserData accounts earning the reward streams, receipts don't earn them
+    pub fn reward_shares(&self) -> u64 {
+        self.total_shares.checked_sub(self.receipt_shares).unwrap()
+    }
+
+    /// Fails if the locker program is not authorized or the locker authority is not its pda
+    pub fn check_locker(
+        &self,
+        locker_program: &Pubkey,
+        locker_authority: &Pubkey,
+        locker_bump: u8,
+    ) -> ProgramResult {
+        if !self.authorized_lockers.contains(locker_program) {
+            msg!("Error: Locker program {} is not authorized", locker_program);
+            return Err(StakingError::UnauthorizedLocker.into());
+        }
+        utils::check_locker_authority(locker_program, locker_authority, locker_bump)
+    }
+
+    /// Fails if the staking pool is fully paused
+    pub fn check_not_paused(&self) -> ProgramResult {
+        if self.pause_level == PauseLevel::Full {
+            msg!("Error: Staking is paused");
+            return Err(StakingError::Paused.into());
+        }
+        Ok(())
+    }
+
+    /// Fails if the staking pool is paused at any level
+    pub fn check_deposits_allowed(&self) -> ProgramResult {
+        if self.pause_level != PauseLevel::Unpaused {
+            msg!("Error: Staking deposits are paused");
+            return Err(StakingError::Paused.into());
+        }
+        Ok(())
+    }
+
+    /// Fails if the deposit exceeds the user or the total deposit cap
+    pub fn check_deposit_caps(&self, user_shares: u64, amount: u64) -> ProgramResult {
+        if self.total_deposit_cap > 0
+            && self.total_staked.checked_add(amount).unwrap() > self.total_deposit_cap
+        {
+            msg!("Error: Total deposit cap exceeded");
+            msg!("Total deposit cap: {}", self.total_deposit_cap);
+            return Err(StakingError::TotalDepositCapExceeded.into());
+        }
+
+        self.check_user_deposit_cap(user_shares, amount)
+    }
+
+    /// Fails if the value moved to the user within the staking pool exceeds the user deposit cap
+    pub fn check_user_deposit_cap(&self, user_shares: u64, amount: u64) -> ProgramResult {
+        if self.user_deposit_cap > 0 {
+            let user_staked =
+                utils::calculate_shares_value(self.total_shares, self.total_staked, user_shares);
+            if user_staked.checked_add(amount).unwrap() > self.user_deposit_cap {
+                msg!("Error: User deposit cap exceeded");
+                msg!("User deposit cap: {}", self.user_deposit_cap);
+                return Err(StakingError::UserDepositCapExceeded.into());
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Account for storing common information about staking pool user.
@@ -623,15 +4560,404 @@ pub struct StakingUserData {
     /// Link to staking pool
     pub staking_data: Pubkey,
     pub ownership_share: u64,
-    /// Amount of shares locked for the governance proposal vote
-    pub locked_amount: u64,
-    pub locked_until: i64,
+    /// Shares locked for the governance proposal votes
+    pub locks: Vec<LockEntry>,
+    /// Amount of shares locked in time-locked stakes
+    pub tier_locked_shares: u64,
+    pub interest_mode: InterestMode,
+    /// Value of the shares without interest in claim mode
+    pub principal: u64,
+    /// Total interest claimed in claim mode
+    pub claimed_interest: u64,
+    /// Start of the early unstake penalty window
+    pub last_stake_timestamp: i64,
+    /// Amount of tokens in PendingWithdrawal accounts of the user
+    pub pending_withdrawals: u64,
+    /// History of the ownership share changes, the oldest checkpoints are folded when full
+    pub share_checkpoints: Vec<ShareCheckpoint>,
 }
 
 impl StakingUserData {
+    pub const LEN: usize = DISCRIMINATOR_LEN
+        + 32
+        + 32
+        + 8
+        + MAX_LOCK_ENTRIES_VEC_SIZE
+        + 8
+        + 1
+        + 8
+        + 8
+        + 8
+        + 8
+        + MAX_SHARE_CHECKPOINTS_VEC_SIZE;
+
+    /// Amount of shares the user is allowed to unstake
+    pub fn available_shares(&self, unix_timestamp: i64) -> u64 {
+        let locked_amount = utils::calculate_locked_amount(&self.locks, unix_timestamp);
+        let avail_amount = self.ownership_share.saturating_sub(locked_amount);
+        // Shares of time-locked stakes stay unavailable until released
+        let free_shares = self.ownership_share.saturating_sub(self.tier_locked_shares);
+        avail_amount.min(free_shares)
+    }
+
+    /// Records the current ownership share since the slot. Has to be called after every change
+    /// of the shares.
+    pub fn checkpoint_shares(&mut self, slot: u64) {
+        utils::push_share_checkpoint(
+            &mut self.share_checkpoints,
+            MAX_SHARE_CHECKPOINTS,
+            slot,
+            self.ownership_share,
+        );
+    }
+
+    /// Ownership share before the slot
+    pub fn shares_at(&self, slot: u64) -> u64 {
+        utils::calculate_shares_at(&self.share_checkpoints, slot)
+    }
+
+    /// Removes the expired lock entries
+    pub fn remove_expired_locks(&mut self, unix_timestamp: i64) {
+        self.locks.retain(|lock| lock.until > unix_timestamp);
+    }
+
+    /// The latest end of the unexpired lock entries
+    pub fn locked_until(&self, unix_timestamp: i64) -> Option<i64> {
+        self.locks
+            .iter()
+            .filter(|lock| lock.until > unix_timestamp)
+            .map(|lock| lock.until)
+            .max()
+    }
+
+    /// Increases the claim mode principal by the value of the new shares. Shares and staking pool
+    /// totals have to be updated first.
+    pub fn add_principal(&mut self, total_shares: u64, total_staked: u64, new_shares: u64) {
+        if self.interest_mode == InterestMode::Claim {
+            let new_shares_value =
+                utils::calculate_shares_value(total_shares, total_staked, new_shares);
+            self.principal = self.principal.checked_add(new_shares_value).unwrap();
+        }
+    }
+
+    /// Decreases the claim mode principal proportionally to the burned shares. Has to be called
+    /// before the shares are burned.
+    pub fn burn_principal(&mut self, shares_to_burn: u64) {
+        if self.interest_mode == InterestMode::Claim {
+            self.principal = utils::calculate_remaining_principal(
+                self.principal,
+                self.ownership_share,
+                shares_to_burn,
+            );
+        }
+    }
+}
+
+/// Account for storing the time-locked stake position of staking pool user.
+#[account]
+pub struct LockedStake {
+    /// Link to staking pool user
+    pub staking_user_data: Pubkey,
+    /// Amount of staked tokens
+    pub amount: u64,
+    /// Shares minted for the stake including the tier boost
+    pub shares: u64,
+    /// Part of the shares added by the tier boost, burned on release
+    pub boost_shares: u64,
+    /// Tier boost in 1e-4 (basis point)
+    pub boost: u64,
+    pub locked_until: i64,
+}
+
+impl LockedStake {
+    pub const LEN: usize = DISCRIMINATOR_LEN + 32 + 8 + 8 + 8 + 8 + 8;
+}
+
+/// Account for storing the unstake request of staking pool user waiting for the cooldown.
+#[account]
+pub struct PendingWithdrawal {
+    /// Link to staking pool user
+    pub staking_user_data: Pubkey,
+    /// Amount of tokens to withdraw
+    pub amount: u64,
+    /// Timestamp since which the amount can be withdrawn
+    pub unlock_timestamp: i64,
+}
+
+impl PendingWithdrawal {
+    pub const LEN: usize = DISCRIMINATOR_LEN + 32 + 8 + 8;
+}
+
+/// Account for storing the external reward stream of staking pool.
+#[account]
+pub struct RewardStream {
+    /// Link to staking pool
+    pub staking_data: Pubkey,
+    /// Funder of the reward vault
+    pub sponsor: Pubkey,
+    pub reward_mint: Pubkey,
+    /// Wallet for storing reward token
+    pub vault: Pubkey,
+    /// PDA bump for reward vault owner (needs for signatures)
+    pub vault_bump: u8,
+    /// Amount of reward tokens distributed every second
+    pub emission_per_second: u64,
+    pub start_timestamp: i64,
+    pub end_timestamp: i64,
+    pub last_update_timestamp: i64,
+    /// Accumulated rewards per share in 1e-18
+    pub reward_per_share: u128,
+    /// Emissions accumulated to the users shares
+    pub distributed_rewards: u64,
+    /// Rewards transferred to the users
+    pub claimed_rewards: u64,
+}
+
+impl RewardStream {
+    pub const LEN: usize = DISCRIMINATOR_LEN + 32 + 32 + 32 + 32 + 1 + 8 + 8 + 8 + 8 + 16 + 8 + 8;
+}
+
+/// Account for storing the reward stream debt of staking pool user.
+#[account]
+pub struct UserReward {
+    /// Link to reward stream
+    pub reward_stream: Pubkey,
+    /// Link to staking pool user
+    pub staking_user_data: Pubkey,
+    /// Rewards of the user shares already settled
+    pub reward_debt: u64,
+    /// Settled rewards waiting for claim
+    pub pending_rewards: u64,
+    /// User shares of the last settlement
+    pub shares: u64,
+}
+
+impl UserReward {
     pub const LEN: usize = DISCRIMINATOR_LEN + 32 + 32 + 8 + 8 + 8;
 }
 
+/// Account for allowing the wallet to stake in permissioned staking pool.
+#[account]
+pub struct AllowListEntry {
+    /// Link to staking pool
+    pub staking_data: Pubkey,
+    /// Allowed user wallet (owner of the user token wallet)
+    pub wallet: Pubkey,
+}
+
+impl AllowListEntry {
+    pub const LEN: usize = DISCRIMINATOR_LEN + 32 + 32;
+}
+
+/// Emitted when the user stakes the tokens
+#[event]
+pub struct StakeEvent {
+    pub staking_data: Pubkey,
+    pub staking_user_data: Pubkey,
+    /// Owner of the user token wallet
+    pub user: Pubkey,
+    /// Staked amount without deposit fee
+    pub amount: u64,
+    pub fee: u64,
+    pub shares_minted: u64,
+    /// User shares after the stake
+    pub user_shares: u64,
+    pub total_staked: u64,
+    pub total_shares: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the user unstakes the tokens
+#[event]
+pub struct UnstakeEvent {
+    pub staking_data: Pubkey,
+    pub staking_user_data: Pubkey,
+    /// Owner of the user token wallet
+    pub user: Pubkey,
+    /// Unstaked amount including penalty and fee
+    pub amount: u64,
+    pub penalty: u64,
+    pub fee: u64,
+    pub shares_burned: u64,
+    /// User shares after the unstake
+    pub user_shares: u64,
+    pub total_staked: u64,
+    pub total_shares: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the interest is accrued to the staking pool
+#[event]
+pub struct InterestAccruedEvent {
+    pub staking_data: Pubkey,
+    /// Interest added to total staked
+    pub interest: u64,
+    /// Current daily interest rate after the accrual
+    pub interest_rate_daily: u64,
+    pub total_staked: u64,
+    pub total_shares: u64,
+    /// Timestamp the interest is accrued until
+    pub accrued_timestamp: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the owner changes the daily interest rate
+#[event]
+pub struct InterestRateChangedEvent {
+    pub staking_data: Pubkey,
+    pub old_interest_rate: u64,
+    pub new_interest_rate: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the locker program locks the user shares
+#[event]
+pub struct LockEvent {
+    pub staking_data: Pubkey,
+    pub staking_user_data: Pubkey,
+    /// Owner of the user token wallet
+    pub user: Pubkey,
+    pub locker_program: Pubkey,
+    pub locker: Pubkey,
+    /// Locked shares of the locker
+    pub amount: u64,
+    pub until: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the locker program releases the user lock before it expires
+#[event]
+pub struct ReleaseLockEvent {
+    pub staking_data: Pubkey,
+    pub staking_user_data: Pubkey,
+    pub locker_program: Pubkey,
+    pub locker: Pubkey,
+    /// Released shares of the locker
+    pub amount: u64,
+}
+
+/// Emitted when the user stakes the tokens in the time-locked position
+#[event]
+pub struct StakeLockedEvent {
+    pub staking_data: Pubkey,
+    pub staking_user_data: Pubkey,
+    /// Owner of the user token wallet
+    pub user: Pubkey,
+    pub locked_stake: Pubkey,
+    /// Staked amount without deposit fee
+    pub amount: u64,
+    pub fee: u64,
+    /// Shares minted including the tier boost
+    pub shares_minted: u64,
+    pub boost: u64,
+    pub locked_until: i64,
+    /// User shares after the stake
+    pub user_shares: u64,
+    pub total_staked: u64,
+    pub total_shares: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the user requests the unstake with the cooldown
+#[event]
+pub struct UnstakeRequestedEvent {
+    pub staking_data: Pubkey,
+    pub staking_user_data: Pubkey,
+    /// Owner of the user token wallet
+    pub user: Pubkey,
+    pub pending_withdrawal: Pubkey,
+    /// Unstaked amount including penalty and fee
+    pub amount: u64,
+    pub penalty: u64,
+    pub fee: u64,
+    pub shares_burned: u64,
+    /// Pending withdrawal can be withdrawn from this timestamp
+    pub unlock_timestamp: i64,
+    /// User shares after the unstake
+    pub user_shares: u64,
+    pub total_staked: u64,
+    pub total_shares: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the user cancels the pending withdrawal and stakes it back
+#[event]
+pub struct UnstakeCancelledEvent {
+    pub staking_data: Pubkey,
+    pub staking_user_data: Pubkey,
+    /// Owner of the user token wallet
+    pub user: Pubkey,
+    pub pending_withdrawal: Pubkey,
+    /// Staked back amount
+    pub amount: u64,
+    pub shares_minted: u64,
+    /// User shares after the stake
+    pub user_shares: u64,
+    pub total_staked: u64,
+    pub total_shares: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the user withdraws the pending withdrawal
+#[event]
+pub struct WithdrawEvent {
+    pub staking_data: Pubkey,
+    pub staking_user_data: Pubkey,
+    /// Owner of the user token wallet
+    pub user: Pubkey,
+    pub pending_withdrawal: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the claim mode user claims the interest
+#[event]
+pub struct InterestClaimedEvent {
+    pub staking_data: Pubkey,
+    pub staking_user_data: Pubkey,
+    /// Owner of the user token wallet
+    pub user: Pubkey,
+    /// Claimed interest
+    pub amount: u64,
+    pub shares_burned: u64,
+    /// User shares after the claim
+    pub user_shares: u64,
+    pub total_staked: u64,
+    pub total_shares: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the user moves the shares to another user
+#[event]
+pub struct SharesTransferredEvent {
+    pub staking_data: Pubkey,
+    /// StakingUserData of the sender
+    pub staking_user_data: Pubkey,
+    /// Owner of the sender token wallet
+    pub user: Pubkey,
+    pub recipient_staking_user_data: Pubkey,
+    pub shares: u64,
+    /// Time-locked stake position moved with the shares
+    pub locked_stake: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+/// Emitted when the user shares are slashed
+#[event]
+pub struct SlashEvent {
+    pub staking_data: Pubkey,
+    pub staking_user_data: Pubkey,
+    /// Slashed shares
+    pub shares: u64,
+    /// Value of the slashed shares
+    pub amount: u64,
+    pub slash_wallet: Pubkey,
+    /// Tokens are burned instead of redirected to the slash wallet
+    pub burned: bool,
+    /// Reason code of the slasher
+    pub reason: u16,
+}
+
 #[error]
 pub enum StakingError {
     #[msg("Invalid starting timestamp")]
@@ -646,4 +4972,50 @@ pub enum StakingError {
     InvalidLockParams,
     #[msg("Invalid token supply cap")]
     InvalidCap,
+    #[msg("Invalid lock tier")]
+    InvalidLockTier,
+    #[msg("Stake is still locked")]
+    StakeStillLocked,
+    #[msg("Invalid cooldown")]
+    InvalidCooldown,
+    #[msg("Unstake cooldown is required")]
+    CooldownRequired,
+    #[msg("Unstake cooldown is not elapsed")]
+    CooldownNotElapsed,
+    #[msg("Staking is paused")]
+    Paused,
+    #[msg("Invalid rate schedule")]
+    InvalidRateSchedule,
+    #[msg("Invalid rate model")]
+    InvalidRateModel,
+    #[msg("Invalid reward stream")]
+    InvalidRewardStream,
+    #[msg("Reward stream accounts don't match the staking pool")]
+    RewardAccountsMismatch,
+    #[msg("Wrong interest mode")]
+    WrongInterestMode,
+    #[msg("Invalid early unstake penalty")]
+    InvalidPenalty,
+    #[msg("Invalid fee")]
+    InvalidFee,
+    #[msg("Wallet is not in the allow-list")]
+    NotInAllowList,
+    #[msg("User deposit cap exceeded")]
+    UserDepositCapExceeded,
+    #[msg("Total deposit cap exceeded")]
+    TotalDepositCapExceeded,
+    #[msg("Staking user still has shares, pending withdrawals or rewards")]
+    StakingUserNotEmpty,
+    #[msg("Invalid receipt mint")]
+    InvalidReceiptMint,
+    #[msg("Too many locks")]
+    TooManyLocks,
+    #[msg("Unauthorized locker")]
+    UnauthorizedLocker,
+    #[msg("Invalid slash")]
+    InvalidSlash,
+    #[msg("Shares are in the early unstake penalty window")]
+    PenaltyWindowActive,
+    #[msg("Too many authorized lockers")]
+    TooManyLockers,
 }
@@ -2,7 +2,7 @@ use anchor_lang::{
     prelude::*,
     solana_program::clock::{self, UnixTimestamp},
 };
-use anchor_spl::token::{self, Mint, MintTo, TokenAccount};
+use anchor_spl::token::{self, Burn, Mint, MintTo, TokenAccount};
 use bigint::U256;
 use std::ops::{Add, Div, Mul, Sub};
 
@@ -10,6 +10,14 @@ use std::ops::{Add, Div, Mul, Sub};
 const INTEREST_MUL_FACTOR: u64 = 1_000_000;
 /// Limits the exponent for one interest calc iteration to avoid overflows
 const MAX_DAYS_INTEREST_ACCRUE: u64 = 10;
+/// Equals number of decimals in the lock tier boost (basis points)
+pub const BOOST_MUL_FACTOR: u64 = 10_000;
+/// Equals number of decimals in the fees, penalties and slashes (basis points)
+pub const BPS_MUL_FACTOR: u64 = 10_000;
+/// Equals number of decimals in the staked supply utilization
+pub const UTILIZATION_MUL_FACTOR: u64 = 1_000_000;
+/// Equals number of decimals in the reward stream accumulated rewards per share
+pub const REWARD_PER_SHARE_MUL_FACTOR: u64 = 1_000_000_000_000_000_000;
 
 /// Calculate the users shares after staking
 pub fn calculate_new_shares(total_shares: u64, total_staked: u64, amount: u64) -> u64 {
@@ -23,6 +31,19 @@ pub fn calculate_new_shares(total_shares: u64, total_staked: u64, amount: u64) -
     return total_shares_bn.mul(amount_bn).div(total_staked_bn).as_u64();
 }
 
+/// Calculate the users shares after staking with the lock tier boost applied
+pub fn calculate_new_boosted_shares(
+    total_shares: u64,
+    total_staked: u64,
+    amount: u64,
+    boost: u64,
+) -> u64 {
+    let new_shares_bn: U256 = calculate_new_shares(total_shares, total_staked, amount).into();
+    let boost_bn: U256 = boost.into();
+    let boost_mul_factor: U256 = BOOST_MUL_FACTOR.into();
+    return new_shares_bn.mul(boost_bn).div(boost_mul_factor).as_u64();
+}
+
 /// After calculate_accrued_interest total_staked will increase and the user shares_to_burn will be less than amount.
 /// The difference between amount and shares_to_burn is the user's income. This difference can be unstaked later.
 pub fn calculate_shares_to_burn(total_shares: u64, total_staked: u64, amount: u64) -> u64 {
@@ -32,12 +53,204 @@ pub fn calculate_shares_to_burn(total_shares: u64, total_staked: u64, amount: u6
     return total_shares_bn.mul(amount_bn).div(total_staked_bn).as_u64();
 }
 
+/// Calculate the value of the shares in staked tokens
+pub fn calculate_shares_value(total_shares: u64, total_staked: u64, shares: u64) -> u64 {
+    if total_shares == 0 {
+        return 0;
+    }
+
+    let total_shares_bn: U256 = total_shares.into();
+    let total_staked_bn: U256 = total_staked.into();
+    let shares_bn: U256 = shares.into();
+    return total_staked_bn.mul(shares_bn).div(total_shares_bn).as_u64();
+}
+
+/// Calculate the principal of claim mode user left after burning the part of the shares
+pub fn calculate_remaining_principal(principal: u64, shares: u64, shares_to_burn: u64) -> u64 {
+    if shares == 0 {
+        return 0;
+    }
+
+    let principal_bn: U256 = principal.into();
+    let shares_bn: U256 = shares.into();
+    let remaining_shares_bn: U256 = shares.saturating_sub(shares_to_burn).into();
+    return principal_bn
+        .mul(remaining_shares_bn)
+        .div(shares_bn)
+        .as_u64();
+}
+
+/// Calculate the interest of claim mode user above the principal and the shares to burn for it.
+/// Shares to burn are rounded up, so the claims never take the value of other stakers.
+/// Only the available shares are burned, interest of the locked shares stays until unlock.
+pub fn calculate_claimable_interest(
+    total_shares: u64,
+    total_staked: u64,
+    shares: u64,
+    principal: u64,
+    available_shares: u64,
+) -> (u64, u64) {
+    let value = calculate_shares_value(total_shares, total_staked, shares);
+    if value <= principal {
+        return (0, 0);
+    }
+
+    let interest = value - principal;
+    let total_shares_bn: U256 = total_shares.into();
+    let total_staked_bn: U256 = total_staked.into();
+    let interest_bn: U256 = interest.into();
+    let shares_to_burn = total_shares_bn
+        .mul(interest_bn)
+        .add(total_staked_bn)
+        .sub(U256::one())
+        .div(total_staked_bn)
+        .as_u64();
+
+    if shares_to_burn > available_shares {
+        let interest = calculate_shares_value(total_shares, total_staked, available_shares);
+        return (interest, available_shares);
+    }
+    return (interest, shares_to_burn);
+}
+
+/// Calculate the fee in basis points taken from the amount
+pub fn calculate_fee(amount: u64, fee_bps: u64) -> u64 {
+    let amount_bn: U256 = amount.into();
+    let fee_bps_bn: U256 = fee_bps.into();
+    let bps_mul_factor: U256 = BPS_MUL_FACTOR.into();
+    return amount_bn.mul(fee_bps_bn).div(bps_mul_factor).as_u64();
+}
+
+/// Calculate the part of the user shares to slash in basis points
+pub fn calculate_slashed_shares(ownership_share: u64, slash_bps: u64) -> u64 {
+    let ownership_share_bn: U256 = ownership_share.into();
+    let slash_bps_bn: U256 = slash_bps.into();
+    let bps_mul_factor: U256 = BPS_MUL_FACTOR.into();
+    return ownership_share_bn
+        .mul(slash_bps_bn)
+        .div(bps_mul_factor)
+        .as_u64();
+}
+
+/// Calculate the early unstake penalty decaying linearly over the window since the last stake
+pub fn calculate_early_unstake_penalty(
+    amount: u64,
+    penalty: u64,
+    window_seconds: i64,
+    last_stake_timestamp: UnixTimestamp,
+    current_timestamp: UnixTimestamp,
+) -> u64 {
+    let elapsed = current_timestamp.saturating_sub(last_stake_timestamp);
+    if penalty == 0 || elapsed >= window_seconds {
+        return 0;
+    }
+
+    let amount_bn: U256 = amount.into();
+    let penalty_bn: U256 = penalty.into();
+    let remaining_bn: U256 = ((window_seconds - elapsed.max(0)) as u64).into();
+    let window_bn: U256 = (window_seconds as u64).into();
+    let bps_mul_factor: U256 = BPS_MUL_FACTOR.into();
+    return amount_bn
+        .mul(penalty_bn)
+        .mul(remaining_bn)
+        .div(window_bn.mul(bps_mul_factor))
+        .as_u64();
+}
+
+/// Calculate the shares of the last checkpoint before the slot. Shares are zero before the
+/// first checkpoint.
+pub fn calculate_shares_at(checkpoints: &[crate::ShareCheckpoint], slot: u64) -> u64 {
+    return checkpoints
+        .iter()
+        .rev()
+        .find(|checkpoint| checkpoint.slot < slot)
+        .map_or(0, |checkpoint| checkpoint.shares);
+}
+
+/// Record the shares since the slot. When the list is full the two oldest checkpoints are folded
+/// into a base checkpoint with the smaller shares, so the history is never dropped and the voting
+/// weight from the folded range can only be understated.
+pub fn push_share_checkpoint(
+    checkpoints: &mut Vec<crate::ShareCheckpoint>,
+    max_checkpoints: usize,
+    slot: u64,
+    shares: u64,
+) {
+    if let Some(checkpoint) = checkpoints.last_mut() {
+        if checkpoint.slot == slot {
+            checkpoint.shares = shares;
+            return;
+        }
+    }
+    if checkpoints.len() >= max_checkpoints {
+        let evicted = checkpoints.remove(0);
+        let base = &mut checkpoints[0];
+        base.slot = evicted.slot;
+        base.shares = base.shares.min(evicted.shares);
+    }
+    checkpoints.push(crate::ShareCheckpoint { slot, shares });
+}
+
+/// Calculate the amount of shares locked by the unexpired lock entries
+pub fn calculate_locked_amount(
+    locks: &[crate::LockEntry],
+    current_timestamp: UnixTimestamp,
+) -> u64 {
+    return locks
+        .iter()
+        .filter(|lock| lock.until > current_timestamp)
+        .fold(0u64, |locked, lock| locked.saturating_add(lock.amount));
+}
+
+/// Compounds the balance with the daily interest rate for provided number of days
+fn compound_daily_interest(balance: U256, interest_rate: u64, days: u64) -> U256 {
+    let mut new_balance = balance;
+    let interest_mul_factor: U256 = INTEREST_MUL_FACTOR.into();
+    let daily_rate: U256 = INTEREST_MUL_FACTOR.add(interest_rate).into();
+
+    // Overflow check
+    let mut days_remain = days;
+    while days_remain > 0 {
+        if days_remain < MAX_DAYS_INTEREST_ACCRUE {
+            new_balance = new_balance
+                .mul(daily_rate.pow(days_remain.into()))
+                .div(interest_mul_factor.pow(days_remain.into()));
+            days_remain = 0;
+        } else {
+            new_balance = new_balance
+                .mul(daily_rate.pow(MAX_DAYS_INTEREST_ACCRUE.into()))
+                .div(interest_mul_factor.pow(MAX_DAYS_INTEREST_ACCRUE.into()));
+            days_remain = days_remain.sub(MAX_DAYS_INTEREST_ACCRUE);
+        }
+    }
+
+    return new_balance;
+}
+
 /// Calculates the interest that should be accrued to date and the timestamp this accrual happened at.
 pub fn calculate_accrued_interest(
     last_interest_accrued_timestamp: UnixTimestamp,
     current_timestamp: UnixTimestamp,
     total_staked: u64,
     interest_rate: u64,
+) -> (u64, UnixTimestamp) {
+    return calculate_scheduled_accrued_interest(
+        last_interest_accrued_timestamp,
+        current_timestamp,
+        total_staked,
+        interest_rate,
+        &[],
+    );
+}
+
+/// Calculates the interest with the rate schedule. `interest_rate` is applied until the first breakpoint,
+/// every day is accrued with the rate effective at the start of the day. Breakpoints must be sorted.
+pub fn calculate_scheduled_accrued_interest(
+    last_interest_accrued_timestamp: UnixTimestamp,
+    current_timestamp: UnixTimestamp,
+    total_staked: u64,
+    interest_rate: u64,
+    rate_schedule: &[crate::RatePoint],
 ) -> (u64, UnixTimestamp) {
     let mut timestamp = last_interest_accrued_timestamp;
     let mut interest = 0;
@@ -45,37 +258,97 @@ pub fn calculate_accrued_interest(
     let timestamp_diff = current_timestamp
         .checked_sub(last_interest_accrued_timestamp)
         .unwrap();
-    // If there is went more than one day by last accrued interest
-    if timestamp_diff > (clock::SECONDS_PER_DAY as i64) {
+    // If there is went at least one day by last accrued interest
+    if timestamp_diff >= (clock::SECONDS_PER_DAY as i64) {
         let days_elapsed = timestamp_diff.div(clock::SECONDS_PER_DAY as i64) as u64;
         let mut new_balance: U256 = total_staked.into();
 
-        let interest_mul_factor: U256 = INTEREST_MUL_FACTOR.into();
-        let daily_rate: U256 = INTEREST_MUL_FACTOR.add(interest_rate).into();
-
-        // Overflow check
-        let mut days_remain = days_elapsed;
-        while days_remain > 0 {
-            if days_remain < MAX_DAYS_INTEREST_ACCRUE {
-                new_balance = new_balance
-                    .mul(daily_rate.pow(days_remain.into()))
-                    .div(interest_mul_factor.pow(days_remain.into()));
-                days_remain = 0;
-            } else {
-                new_balance = new_balance
-                    .mul(daily_rate.pow(MAX_DAYS_INTEREST_ACCRUE.into()))
-                    .div(interest_mul_factor.pow(MAX_DAYS_INTEREST_ACCRUE.into()));
-                days_remain = days_remain.sub(MAX_DAYS_INTEREST_ACCRUE);
+        // Accrue segment by segment between breakpoints
+        let mut rate = interest_rate;
+        let mut days_accrued = 0;
+        for point in rate_schedule {
+            let point_diff = point
+                .effective_timestamp
+                .checked_sub(last_interest_accrued_timestamp)
+                .unwrap()
+                .max(0) as u64;
+            // Index of the first day starting at or after the breakpoint
+            let point_day = (point_diff + clock::SECONDS_PER_DAY - 1) / clock::SECONDS_PER_DAY;
+            if point_day >= days_elapsed {
+                break;
+            }
+            if point_day > days_accrued {
+                new_balance = compound_daily_interest(new_balance, rate, point_day - days_accrued);
+                days_accrued = point_day;
             }
+            rate = point.daily_rate;
         }
+        new_balance = compound_daily_interest(new_balance, rate, days_elapsed - days_accrued);
 
         interest = new_balance.as_u64().sub(total_staked);
-        timestamp = current_timestamp;
+        // The partial day remainder is carried over to the next accrual
+        timestamp = last_interest_accrued_timestamp
+            .checked_add((days_elapsed * clock::SECONDS_PER_DAY) as i64)
+            .unwrap();
     }
 
     return (interest, timestamp);
 }
 
+/// Daily interest rate effective at the timestamp
+pub fn effective_interest_rate(
+    interest_rate: u64,
+    rate_schedule: &[crate::RatePoint],
+    timestamp: UnixTimestamp,
+) -> u64 {
+    let mut rate = interest_rate;
+    for point in rate_schedule {
+        if point.effective_timestamp > timestamp {
+            break;
+        }
+        rate = point.daily_rate;
+    }
+
+    return rate;
+}
+
+/// Calculates the daily interest rate of the kinked rate model. The rate falls from `base_rate`
+/// by `slope_low` below the kink and by `slope_high` above it, clamped by `max_interest_rate`.
+pub fn calculate_utilization_interest_rate(
+    rate_model: &crate::RateModel,
+    total_staked: u64,
+    supply: u64,
+    max_interest_rate: u64,
+) -> u64 {
+    let mut utilization = 0;
+    if supply > 0 {
+        let total_staked_bn: U256 = total_staked.into();
+        let supply_bn: U256 = supply.into();
+        let utilization_mul_factor: U256 = UTILIZATION_MUL_FACTOR.into();
+        utilization = total_staked_bn
+            .mul(utilization_mul_factor)
+            .div(supply_bn)
+            .as_u64()
+            .min(UTILIZATION_MUL_FACTOR);
+    }
+
+    let utilization_low: U256 = utilization.min(rate_model.kink).into();
+    let utilization_high: U256 = utilization.saturating_sub(rate_model.kink).into();
+    let slope_low: U256 = rate_model.slope_low.into();
+    let slope_high: U256 = rate_model.slope_high.into();
+    let utilization_mul_factor: U256 = UTILIZATION_MUL_FACTOR.into();
+    let decrease = slope_low
+        .mul(utilization_low)
+        .add(slope_high.mul(utilization_high))
+        .div(utilization_mul_factor);
+    let base_rate: U256 = rate_model.base_rate.into();
+    if decrease >= base_rate {
+        return 0;
+    }
+
+    return base_rate.sub(decrease).as_u64().min(max_interest_rate);
+}
+
 /// Accrues interest to date. Internal function for calling from instruction functions.
 pub fn accrue_interest_internal<'info>(
     program_id: &Pubkey,
@@ -87,21 +360,64 @@ pub fn accrue_interest_internal<'info>(
     token_program: &AccountInfo<'info>,
     clock: &Sysvar<'info, Clock>,
 ) -> ProgramResult {
-    let (mut tokens_to_mint, new_timestamp) = calculate_accrued_interest(
-        staking_data.last_interest_accrued_timestamp,
-        clock.unix_timestamp,
-        staking_data.total_staked,
-        staking_data.interest_rate_daily,
-    );
+    // Rate model replaces the fixed rate and the schedule
+    let mut model_interest_rate = None;
+    if let Some(rate_model) = staking_data.rate_model {
+        let interest_rate = calculate_utilization_interest_rate(
+            &rate_model,
+            staking_data.total_staked,
+            mint.supply,
+            staking_data.max_interest_rate_daily,
+        );
+        msg!("Utilization interest rate: {}", interest_rate);
+        model_interest_rate = Some(interest_rate);
+    }
+
+    // Pending withdrawals are excluded from total_staked and don't earn interest
+    let (mut tokens_to_mint, new_timestamp) = match model_interest_rate {
+        Some(interest_rate) => calculate_accrued_interest(
+            staking_data.last_interest_accrued_timestamp,
+            clock.unix_timestamp,
+            staking_data.total_staked,
+            interest_rate,
+        ),
+        None => calculate_scheduled_accrued_interest(
+            staking_data.last_interest_accrued_timestamp,
+            clock.unix_timestamp,
+            staking_data.total_staked,
+            staking_data.interest_rate_daily,
+            &staking_data.rate_schedule,
+        ),
+    };
 
-    let new_supply = mint.supply + tokens_to_mint;
-    if new_supply > staking_data.cap {
-        msg!("Token supply cap exceeded");
-        tokens_to_mint = 0;
+    let mut reserve_exhausted = false;
+    let mut supply_capped = false;
+    match staking_data.reward_mode {
+        crate::RewardMode::Mint => {
+            let new_supply = mint.supply + tokens_to_mint;
+            if new_supply > staking_data.cap {
+                msg!("Token supply cap exceeded");
+                tokens_to_mint = 0;
+                supply_capped = true;
+            }
+        }
+        crate::RewardMode::Reserve => {
+            if tokens_to_mint > staking_data.reward_reserve {
+                msg!("Reward reserve exhausted");
+                tokens_to_mint = staking_data.reward_reserve;
+                reserve_exhausted = true;
+            }
+        }
     }
 
     msg!("Tokens to mint: {}", tokens_to_mint);
-    if tokens_to_mint > 0 {
+    if tokens_to_mint > 0 && staking_data.reward_mode == crate::RewardMode::Reserve {
+        // Reserve tokens are already in HoldingWallet account
+        staking_data.reward_reserve = staking_data
+            .reward_reserve
+            .checked_sub(tokens_to_mint)
+            .unwrap();
+    } else if tokens_to_mint > 0 {
         // Mint tokens to HoldingWallet account
         let seeds = &[
             program_id.as_ref(),
@@ -122,13 +438,410 @@ pub fn accrue_interest_internal<'info>(
             ),
             tokens_to_mint,
         )?;
+    }
+    staking_data.total_staked = staking_data
+        .total_staked
+        .checked_add(tokens_to_mint)
+        .unwrap();
+    if staking_data.reward_mode == crate::RewardMode::Reserve {
+        staking_data.reserve_exhausted = reserve_exhausted;
+    }
+
+    // Keep the last utilization rate as current for off-chain estimations
+    if let Some(interest_rate) = model_interest_rate {
+        staking_data.interest_rate_daily = interest_rate;
+    }
 
+    let accrued = is_interest_period_settled(
+        staking_data.last_interest_accrued_timestamp,
+        new_timestamp,
+        supply_capped,
+    );
+    if accrued {
         staking_data.last_interest_accrued_timestamp = new_timestamp;
-        staking_data.total_staked = staking_data
-            .total_staked
-            .checked_add(tokens_to_mint)
+
+        // Passed breakpoints become the current interest rate, unless the rate model sets it
+        if model_interest_rate.is_none() {
+            staking_data.interest_rate_daily = effective_interest_rate(
+                staking_data.interest_rate_daily,
+                &staking_data.rate_schedule,
+                new_timestamp,
+            );
+        }
+        staking_data
+            .rate_schedule
+            .retain(|point| point.effective_timestamp > new_timestamp);
+
+        emit!(crate::InterestAccruedEvent {
+            staking_data: staking_data.key(),
+            interest: tokens_to_mint,
+            interest_rate_daily: staking_data.interest_rate_daily,
+            total_staked: staking_data.total_staked,
+            total_shares: staking_data.total_shares,
+            accrued_timestamp: staking_data.last_interest_accrued_timestamp,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+/// Check the accrued days are settled and the last accrued timestamp moves to `new_timestamp`.
+/// Days of zero interest (zero rate or empty pool) and of exhausted reserve are settled,
+/// the interest over the mint cap is held back until the cap allows it.
+pub fn is_interest_period_settled(
+    last_interest_accrued_timestamp: UnixTimestamp,
+    new_timestamp: UnixTimestamp,
+    supply_capped: bool,
+) -> bool {
+    !supply_capped && new_timestamp > last_interest_accrued_timestamp
+}
+
+fn u128_to_u256(value: u128) -> U256 {
+    return U256([value as u64, (value >> 64) as u64, 0, 0]);
+}
+
+fn u256_to_u128(value: U256) -> u128 {
+    if value.0[2] != 0 || value.0[3] != 0 {
+        panic!("Integer overflow when casting U256 to u128");
+    }
+    return ((value.0[1] as u128) << 64) | value.0[0] as u128;
+}
+
+/// Calculate accumulated rewards per share after the emission of provided number of seconds.
+/// Emissions are not distributed while there are no shares.
+pub fn calculate_reward_per_share(
+    reward_per_share: u128,
+    emission_per_second: u64,
+    elapsed_seconds: u64,
+    total_shares: u64,
+) -> u128 {
+    if total_shares == 0 {
+        return reward_per_share;
+    }
+
+    let emission_bn: U256 = emission_per_second.into();
+    let elapsed_bn: U256 = elapsed_seconds.into();
+    let mul_factor: U256 = REWARD_PER_SHARE_MUL_FACTOR.into();
+    let total_shares_bn: U256 = total_shares.into();
+    let increase = emission_bn
+        .mul(elapsed_bn)
+        .mul(mul_factor)
+        .div(total_shares_bn);
+    return u256_to_u128(u128_to_u256(reward_per_share).add(increase));
+}
+
+/// Calculate rewards of the shares accumulated since the reward stream start
+pub fn calculate_accumulated_reward(shares: u64, reward_per_share: u128) -> u64 {
+    let shares_bn: U256 = shares.into();
+    let mul_factor: U256 = REWARD_PER_SHARE_MUL_FACTOR.into();
+    return shares_bn
+        .mul(u128_to_u256(reward_per_share))
+        .div(mul_factor)
+        .as_u64();
+}
+
+/// Accumulates the reward stream emissions up to the timestamp
+pub fn update_reward_stream(
+    reward_stream: &mut crate::RewardStream,
+    total_shares: u64,
+    timestamp: UnixTimestamp,
+) {
+    let update_timestamp = timestamp.min(reward_stream.end_timestamp);
+    if update_timestamp <= reward_stream.last_update_timestamp {
+        return;
+    }
+
+    let elapsed_seconds = (update_timestamp - reward_stream.last_update_timestamp) as u64;
+    reward_stream.reward_per_share = calculate_reward_per_share(
+        reward_stream.reward_per_share,
+        reward_stream.emission_per_second,
+        elapsed_seconds,
+        total_shares,
+    );
+    // Emissions without shares are not owed to anyone
+    if total_shares > 0 {
+        let emitted = reward_stream
+            .emission_per_second
+            .checked_mul(elapsed_seconds)
+            .unwrap();
+        reward_stream.distributed_rewards = reward_stream
+            .distributed_rewards
+            .checked_add(emitted)
             .unwrap();
     }
+    reward_stream.last_update_timestamp = update_timestamp;
+}
+
+/// Moves the user rewards earned with the old shares to pending and resets the debt for the new shares.
+/// Reward stream has to be updated first.
+pub fn settle_user_reward(
+    reward_stream: &crate::RewardStream,
+    user_reward: &mut crate::UserReward,
+    shares_before: u64,
+    shares_after: u64,
+) {
+    let earned = calculate_accumulated_reward(shares_before, reward_stream.reward_per_share)
+        .saturating_sub(user_reward.reward_debt);
+    user_reward.pending_rewards = user_reward.pending_rewards.checked_add(earned).unwrap();
+    user_reward.reward_debt =
+        calculate_accumulated_reward(shares_after, reward_stream.reward_per_share);
+    user_reward.shares = shares_after;
+}
+
+/// Settles all reward streams of the staking pool before the user shares change. Internal function
+/// for calling from instruction functions. Expects RewardStream and UserReward account pairs in the
+/// order of StakingData reward streams.
+pub fn settle_rewards_internal(
+    program_id: &Pubkey,
+    staking_data: &crate::StakingData,
+    staking_user_data: &ProgramAccount<crate::StakingUserData>,
+    shares_added: u64,
+    shares_removed: u64,
+    reward_accounts: &[AccountInfo],
+    clock: &Sysvar<Clock>,
+) -> ProgramResult {
+    if reward_accounts.len() != staking_data.reward_streams.len() * 2 {
+        msg!(
+            "Error: Expected {} reward stream accounts",
+            staking_data.reward_streams.len() * 2
+        );
+        return Err(crate::StakingError::RewardAccountsMismatch.into());
+    }
+
+    let shares_before = staking_user_data.ownership_share;
+    let shares_after = shares_before
+        .checked_add(shares_added)
+        .unwrap()
+        .checked_sub(shares_removed)
+        .unwrap();
+
+    for (reward_stream_key, accounts) in staking_data
+        .reward_streams
+        .iter()
+        .zip(reward_accounts.chunks(2))
+    {
+        if accounts[0].key != reward_stream_key {
+            msg!("Error: Expected reward stream {}", reward_stream_key);
+            return Err(crate::StakingError::RewardAccountsMismatch.into());
+        }
+
+        let mut reward_stream: ProgramAccount<crate::RewardStream> =
+            ProgramAccount::try_from(program_id, &accounts[0])?;
+        let mut user_reward: ProgramAccount<crate::UserReward> =
+            ProgramAccount::try_from(program_id, &accounts[1])?;
+        if user_reward.reward_stream != *reward_stream_key
+            || user_reward.staking_user_data != staking_user_data.key()
+        {
+            msg!("Error: User reward doesn't match the reward stream");
+            return Err(crate::StakingError::RewardAccountsMismatch.into());
+        }
+
+        update_reward_stream(
+            &mut reward_stream,
+            staking_data.reward_shares(),
+            clock.unix_timestamp,
+        );
+        settle_user_reward(
+            &reward_stream,
+            &mut user_reward,
+            shares_before,
+            shares_after,
+        );
+
+        reward_stream.exit(program_id)?;
+        user_reward.exit(program_id)?;
+    }
+
+    Ok(())
+}
+
+/// Moves the shares between users of the same staking pool, `tier_locked_shares` of them stay
+/// time-locked for the recipient. Internal function for calling from instruction functions.
+/// Expects RewardStream and UserReward account pairs of the sender followed by the pairs of
+/// the recipient.
+pub fn move_shares_internal(
+    program_id: &Pubkey,
+    staking_data: &crate::StakingData,
+    sender: &mut ProgramAccount<crate::StakingUserData>,
+    recipient: &mut ProgramAccount<crate::StakingUserData>,
+    shares: u64,
+    tier_locked_shares: u64,
+    reward_accounts: &[AccountInfo],
+    clock: &Sysvar<Clock>,
+) -> ProgramResult {
+    let reward_accounts_len = staking_data.reward_streams.len() * 2;
+    if reward_accounts.len() != reward_accounts_len * 2 {
+        msg!(
+            "Error: Expected {} reward stream accounts",
+            reward_accounts_len * 2
+        );
+        return Err(crate::StakingError::RewardAccountsMismatch.into());
+    }
+    let (sender_reward_accounts, recipient_reward_accounts) =
+        reward_accounts.split_at(reward_accounts_len);
+
+    // Settle reward streams of both users before the shares change
+    settle_rewards_internal(
+        program_id,
+        staking_data,
+        sender,
+        0,
+        shares,
+        sender_reward_accounts,
+        clock,
+    )?;
+    settle_rewards_internal(
+        program_id,
+        staking_data,
+        recipient,
+        shares,
+        0,
+        recipient_reward_accounts,
+        clock,
+    )?;
+
+    sender.burn_principal(shares);
+    sender.ownership_share = sender.ownership_share.checked_sub(shares).unwrap();
+    sender.tier_locked_shares = sender
+        .tier_locked_shares
+        .checked_sub(tier_locked_shares)
+        .unwrap();
+    sender.checkpoint_shares(clock.slot);
+    recipient.ownership_share = recipient.ownership_share.checked_add(shares).unwrap();
+    recipient.tier_locked_shares = recipient
+        .tier_locked_shares
+        .checked_add(tier_locked_shares)
+        .unwrap();
+    recipient.checkpoint_shares(clock.slot);
+    recipient.add_principal(staking_data.total_shares, staking_data.total_staked, shares);
+    // Moved shares can't escape the early unstake penalty of the sender
+    recipient.last_stake_timestamp = recipient
+        .last_stake_timestamp
+        .max(sender.last_stake_timestamp);
+
+    Ok(())
+}
+
+/// Fails if the allow-list of the staking pool is enabled and the allow-list entry of the wallet
+/// is missing. Internal function for calling from instruction functions.
+pub fn check_allow_list(
+    program_id: &Pubkey,
+    staking_data: &ProgramAccount<crate::StakingData>,
+    wallet: &Pubkey,
+    allow_list_entry: &AccountInfo,
+) -> ProgramResult {
+    if !staking_data.allow_list_enabled {
+        return Ok(());
+    }
+
+    let allowed =
+        match ProgramAccount::<crate::AllowListEntry>::try_from(program_id, allow_list_entry) {
+            Ok(entry) => entry.staking_data == staking_data.key() && entry.wallet == *wallet,
+            Err(_) => false,
+        };
+    if !allowed {
+        msg!("Error: Wallet {} is not in the allow-list", wallet);
+        return Err(crate::StakingError::NotInAllowList.into());
+    }
+
+    Ok(())
+}
+
+/// Splits the remaining accounts into the receipt accounts, expected in front of them in receipt
+/// mode only, and the reward stream accounts. Internal function for calling from instruction
+/// functions.
+pub fn split_receipt_accounts<'a, 'info>(
+    staking_data: &crate::StakingData,
+    accounts: &'a [AccountInfo<'info>],
+    receipt_accounts_len: usize,
+) -> std::result::Result<(&'a [AccountInfo<'info>], &'a [AccountInfo<'info>]), ProgramError> {
+    if !staking_data.receipts_enabled() {
+        return Ok((&[], accounts));
+    }
+
+    if accounts.len() < receipt_accounts_len || *accounts[0].key != staking_data.receipt_mint {
+        msg!("Error: Expected receipt mint {}", staking_data.receipt_mint);
+        return Err(crate::StakingError::InvalidReceiptMint.into());
+    }
+
+    Ok(accounts.split_at(receipt_accounts_len))
+}
+
+/// Mints the receipts of the new shares. Expects receipt mint, receipt mint authority and receipt
+/// SPL Token accounts. Internal function for calling from instruction functions.
+pub fn mint_receipts_internal<'info>(
+    program_id: &Pubkey,
+    staking_data: &ProgramAccount<'info, crate::StakingData>,
+    receipt_accounts: &[AccountInfo<'info>],
+    token_program: &AccountInfo<'info>,
+    shares: u64,
+) -> ProgramResult {
+    let staking_data_key = staking_data.key();
+    let seeds = &[
+        crate::RECEIPT_MINT_PREFIX.as_bytes(),
+        program_id.as_ref(),
+        staking_data_key.as_ref(),
+        &[staking_data.receipt_mint_auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+    token::mint_to(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            MintTo {
+                mint: receipt_accounts[0].clone(),
+                to: receipt_accounts[2].clone(),
+                authority: receipt_accounts[1].clone(),
+            },
+            signer,
+        ),
+        shares,
+    )
+}
+
+/// Burns the receipts of the burned shares. Expects receipt mint and receipt SPL Token accounts
+/// of the owner. Internal function for calling from instruction functions.
+pub fn burn_receipts_internal<'info>(
+    receipt_accounts: &[AccountInfo<'info>],
+    owner: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    shares: u64,
+) -> ProgramResult {
+    let receipt_wallet = CpiAccount::<TokenAccount>::try_from(&receipt_accounts[1])?;
+    if receipt_wallet.amount < shares {
+        msg!("Error: User's receipt wallet balance is less than requested shares");
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    token::burn(
+        CpiContext::new(
+            token_program.clone(),
+            Burn {
+                mint: receipt_accounts[0].clone(),
+                to: receipt_accounts[1].clone(),
+                authority: owner.clone(),
+            },
+        ),
+        shares,
+    )
+}
+
+/// Fails if the locker authority is not the pda of ["locker_authority"] owned by the locker
+/// program. Locker authority signature proves the call from the locker program.
+pub fn check_locker_authority(
+    locker_program: &Pubkey,
+    locker_authority: &Pubkey,
+    locker_bump: u8,
+) -> ProgramResult {
+    let expected_authority = Pubkey::create_program_address(
+        &[crate::LOCKER_AUTHORITY_PREFIX.as_bytes(), &[locker_bump]],
+        locker_program,
+    )
+    .map_err(|_| crate::StakingError::UnauthorizedLocker)?;
+    if expected_authority != *locker_authority {
+        msg!("Error: Locker authority doesn't match the locker program");
+        return Err(crate::StakingError::UnauthorizedLocker.into());
+    }
 
     Ok(())
 }
@@ -226,6 +939,38 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_calculate_new_boosted_shares() {
+        // Case: no boost equals to regular shares
+        let new_shares = calculate_new_boosted_shares(0, 0, 25_000_000_000, BOOST_MUL_FACTOR);
+        assert_eq!(new_shares, 25_000_000_000);
+        let new_shares = calculate_new_boosted_shares(
+            24_967_400_530,
+            25_047_657_370,
+            10_000_000,
+            BOOST_MUL_FACTOR,
+        );
+        assert_eq!(
+            new_shares,
+            calculate_new_shares(24_967_400_530, 25_047_657_370, 10_000_000)
+        );
+
+        // Case: first staker with 1.5x boost
+        let new_shares = calculate_new_boosted_shares(0, 0, 10_000_000_000, 15_000);
+        assert_eq!(new_shares, 15_000_000_000);
+
+        // Case: 2x boost after interest accrued
+        let total_shares = 36_500_000_000;
+        let total_staked = 36_547_657_370;
+        let amount = 11_500_000_000;
+        let new_shares = calculate_new_boosted_shares(total_shares, total_staked, amount, 20_000);
+        assert_eq!(new_shares, 22_970_008_486);
+        assert_eq!(
+            new_shares,
+            calculate_new_shares(total_shares, total_staked, amount) * 2
+        );
+    }
+
     #[test]
     fn test_calculate_accrued_interest() {
         // Case: 0 days from last accrued timestamp
@@ -249,7 +994,7 @@ mod test {
             interest_rate,
         );
         assert_eq!(interest, 91756484);
-        assert_eq!(timestamp, current_timestamp);
+        assert_eq!(timestamp, current_timestamp - 1); // the second is carried over
 
         // Case: 10% per year for 365 days of total_staked: 25.5 tokens
         let last_interest_accrued_timestamp = 1631012856;
@@ -264,7 +1009,7 @@ mod test {
             interest_rate,
         );
         assert_eq!(interest, 2548384076); // ~ 2.55
-        assert_eq!(timestamp, current_timestamp);
+        assert_eq!(timestamp, current_timestamp - 1); // the second is carried over
 
         // Case: 6% per year for 365 days of total_staked: 1150.11223344 tokens
         let last_interest_accrued_timestamp = 1631012856;
@@ -279,6 +1024,708 @@ mod test {
             interest_rate,
         );
         assert_eq!(interest, 68715982681);
-        assert_eq!(timestamp, current_timestamp);
+        assert_eq!(timestamp, current_timestamp - 1); // the second is carried over
+    }
+
+    #[test]
+    fn test_is_interest_period_settled() {
+        let last_interest_accrued_timestamp = 1631012856;
+
+        // Case: zero rate settles the elapsed days without interest
+        let current_timestamp =
+            last_interest_accrued_timestamp + 3 * clock::SECONDS_PER_DAY as i64 + 10;
+        let (interest, timestamp) = calculate_accrued_interest(
+            last_interest_accrued_timestamp,
+            current_timestamp,
+            ui_amount_to_amount(10.0),
+            0,
+        );
+        assert_eq!(interest, 0);
+        assert_eq!(timestamp, current_timestamp - 10);
+        assert!(is_interest_period_settled(
+            last_interest_accrued_timestamp,
+            timestamp,
+            false
+        ));
+
+        // Case: interest over the mint cap is held back
+        assert!(!is_interest_period_settled(
+            last_interest_accrued_timestamp,
+            timestamp,
+            true
+        ));
+
+        // Case: less than a day is not accrued yet
+        let (_, timestamp) = calculate_accrued_interest(
+            last_interest_accrued_timestamp,
+            last_interest_accrued_timestamp + 10,
+            ui_amount_to_amount(10.0),
+            261,
+        );
+        assert!(!is_interest_period_settled(
+            last_interest_accrued_timestamp,
+            timestamp,
+            false
+        ));
+    }
+
+    #[test]
+    fn test_calculate_scheduled_accrued_interest() {
+        let last_interest_accrued_timestamp = 1631012856;
+        let total_staked = ui_amount_to_amount(1150.11223344);
+        let interest_rate = 261;
+        let current_timestamp =
+            last_interest_accrued_timestamp + 35 * clock::SECONDS_PER_DAY as i64 + 1;
+        let (flat_interest, _) = calculate_accrued_interest(
+            last_interest_accrued_timestamp,
+            current_timestamp,
+            total_staked,
+            interest_rate,
+        );
+
+        // Case: empty schedule equals to flat interest rate
+        let (interest, timestamp) = calculate_scheduled_accrued_interest(
+            last_interest_accrued_timestamp,
+            current_timestamp,
+            total_staked,
+            interest_rate,
+            &[],
+        );
+        assert_eq!(interest, flat_interest);
+        assert_eq!(timestamp, current_timestamp - 1); // the second is carried over
+
+        // Case: breakpoint after the accrual period is not applied
+        let rate_schedule = [crate::RatePoint {
+            effective_timestamp: current_timestamp + 1,
+            daily_rate: 159,
+        }];
+        let (interest, _) = calculate_scheduled_accrued_interest(
+            last_interest_accrued_timestamp,
+            current_timestamp,
+            total_staked,
+            interest_rate,
+            &rate_schedule,
+        );
+        assert_eq!(interest, flat_interest);
+
+        // Case: passed breakpoint is applied for all days
+        let rate_schedule = [crate::RatePoint {
+            effective_timestamp: last_interest_accrued_timestamp - 1,
+            daily_rate: 159,
+        }];
+        let (interest, _) = calculate_scheduled_accrued_interest(
+            last_interest_accrued_timestamp,
+            current_timestamp,
+            total_staked,
+            interest_rate,
+            &rate_schedule,
+        );
+        let (expected_interest, _) = calculate_accrued_interest(
+            last_interest_accrued_timestamp,
+            current_timestamp,
+            total_staked,
+            159,
+        );
+        assert_eq!(interest, expected_interest);
+
+        // Case: 10 days of 261, then 25 days of 159
+        let (first_interest, _) = calculate_accrued_interest(
+            last_interest_accrued_timestamp,
+            last_interest_accrued_timestamp + 10 * clock::SECONDS_PER_DAY as i64 + 1,
+            total_staked,
+            interest_rate,
+        );
+        let (second_interest, _) = calculate_accrued_interest(
+            last_interest_accrued_timestamp,
+            last_interest_accrued_timestamp + 25 * clock::SECONDS_PER_DAY as i64 + 1,
+            total_staked + first_interest,
+            159,
+        );
+        assert_eq!(first_interest, 3_005_320_990);
+        assert_eq!(second_interest, 4_592_398_537);
+        for effective_timestamp in [
+            last_interest_accrued_timestamp + 9 * clock::SECONDS_PER_DAY as i64 + 1,
+            last_interest_accrued_timestamp + 10 * clock::SECONDS_PER_DAY as i64,
+        ] {
+            let rate_schedule = [crate::RatePoint {
+                effective_timestamp: effective_timestamp,
+                daily_rate: 159,
+            }];
+            let (interest, _) = calculate_scheduled_accrued_interest(
+                last_interest_accrued_timestamp,
+                current_timestamp,
+                total_staked,
+                interest_rate,
+                &rate_schedule,
+            );
+            assert_eq!(interest, first_interest + second_interest);
+        }
+
+        // Case: several breakpoints, the last one starts after the period
+        let rate_schedule = [
+            crate::RatePoint {
+                effective_timestamp: last_interest_accrued_timestamp
+                    + 10 * clock::SECONDS_PER_DAY as i64,
+                daily_rate: 159,
+            },
+            crate::RatePoint {
+                effective_timestamp: last_interest_accrued_timestamp
+                    + 35 * clock::SECONDS_PER_DAY as i64,
+                daily_rate: 0,
+            },
+        ];
+        let (interest, _) = calculate_scheduled_accrued_interest(
+            last_interest_accrued_timestamp,
+            current_timestamp,
+            total_staked,
+            interest_rate,
+            &rate_schedule,
+        );
+        assert_eq!(interest, first_interest + second_interest);
+    }
+
+    #[test]
+    fn test_effective_interest_rate() {
+        let rate_schedule = [
+            crate::RatePoint {
+                effective_timestamp: 1000,
+                daily_rate: 200,
+            },
+            crate::RatePoint {
+                effective_timestamp: 2000,
+                daily_rate: 100,
+            },
+        ];
+        assert_eq!(effective_interest_rate(261, &[], 1500), 261);
+        assert_eq!(effective_interest_rate(261, &rate_schedule, 999), 261);
+        assert_eq!(effective_interest_rate(261, &rate_schedule, 1000), 200);
+        assert_eq!(effective_interest_rate(261, &rate_schedule, 1999), 200);
+        assert_eq!(effective_interest_rate(261, &rate_schedule, 2000), 100);
+    }
+
+    #[test]
+    fn test_calculate_utilization_interest_rate() {
+        let rate_model = crate::RateModel {
+            base_rate: 400,
+            slope_low: 200,
+            slope_high: 1000,
+            kink: 600_000,
+        };
+        let supply = ui_amount_to_amount(1000.0);
+
+        // Case: nothing staked gets the base rate
+        assert_eq!(
+            calculate_utilization_interest_rate(&rate_model, 0, supply, 1000),
+            400
+        );
+        assert_eq!(
+            calculate_utilization_interest_rate(&rate_model, 0, 0, 1000),
+            400
+        );
+
+        // Case: below the kink, 30% staked
+        let total_staked = ui_amount_to_amount(300.0);
+        assert_eq!(
+            calculate_utilization_interest_rate(&rate_model, total_staked, supply, 1000),
+            340
+        );
+
+        // Case: at the kink
+        let total_staked = ui_amount_to_amount(600.0);
+        assert_eq!(
+            calculate_utilization_interest_rate(&rate_model, total_staked, supply, 1000),
+            280
+        );
+
+        // Case: above the kink, 90% staked
+        let total_staked = ui_amount_to_amount(900.0);
+        assert_eq!(
+            calculate_utilization_interest_rate(&rate_model, total_staked, supply, 1000),
+            0
+        );
+        let total_staked = ui_amount_to_amount(700.0);
+        assert_eq!(
+            calculate_utilization_interest_rate(&rate_model, total_staked, supply, 1000),
+            180
+        );
+
+        // Case: clamped by the max interest rate
+        assert_eq!(
+            calculate_utilization_interest_rate(&rate_model, 0, supply, 261),
+            261
+        );
+    }
+
+    /// Accrues interest as accrue_interest_internal does with calls after every step of `steps` (cycled)
+    fn accrue_by_steps(
+        total_staked: u64,
+        interest_rate: u64,
+        rate_schedule: &[crate::RatePoint],
+        start_timestamp: UnixTimestamp,
+        end_timestamp: UnixTimestamp,
+        steps: &[i64],
+    ) -> (u64, UnixTimestamp, u64) {
+        let mut total_staked = total_staked;
+        let mut interest_rate = interest_rate;
+        let mut rate_schedule = rate_schedule.to_vec();
+        let mut last_interest_accrued_timestamp = start_timestamp;
+        let mut current_timestamp = start_timestamp;
+        let mut calls = 0;
+
+        for step in steps.iter().cycle() {
+            current_timestamp = (current_timestamp + step).min(end_timestamp);
+            let (interest, timestamp) = calculate_scheduled_accrued_interest(
+                last_interest_accrued_timestamp,
+                current_timestamp,
+                total_staked,
+                interest_rate,
+                &rate_schedule,
+            );
+            if interest > 0 {
+                calls += 1;
+                total_staked += interest;
+                last_interest_accrued_timestamp = timestamp;
+                interest_rate = effective_interest_rate(interest_rate, &rate_schedule, timestamp);
+                rate_schedule.retain(|point| point.effective_timestamp > timestamp);
+            }
+            if current_timestamp == end_timestamp {
+                break;
+            }
+        }
+
+        (total_staked, last_interest_accrued_timestamp, calls)
+    }
+
+    #[test]
+    fn test_accrued_interest_carry_over() {
+        let last_interest_accrued_timestamp = 1631012856;
+        let total_staked = ui_amount_to_amount(1000.0);
+        let interest_rate = 261;
+
+        // Case: less than a day accrues nothing and keeps the timestamp
+        let (interest, timestamp) = calculate_accrued_interest(
+            last_interest_accrued_timestamp,
+            last_interest_accrued_timestamp + clock::SECONDS_PER_DAY as i64 - 1,
+            total_staked,
+            interest_rate,
+        );
+        assert_eq!(interest, 0);
+        assert_eq!(timestamp, last_interest_accrued_timestamp);
+
+        // Case: exactly one day
+        let (one_day_interest, timestamp) = calculate_accrued_interest(
+            last_interest_accrued_timestamp,
+            last_interest_accrued_timestamp + clock::SECONDS_PER_DAY as i64,
+            total_staked,
+            interest_rate,
+        );
+        assert_eq!(one_day_interest, 261_000_000);
+        assert_eq!(
+            timestamp,
+            last_interest_accrued_timestamp + clock::SECONDS_PER_DAY as i64
+        );
+
+        // Case: almost two days accrues one day and carries the remainder over
+        let (interest, timestamp) = calculate_accrued_interest(
+            last_interest_accrued_timestamp,
+            last_interest_accrued_timestamp + 2 * clock::SECONDS_PER_DAY as i64 - 1,
+            total_staked,
+            interest_rate,
+        );
+        assert_eq!(interest, one_day_interest);
+        assert_eq!(
+            timestamp,
+            last_interest_accrued_timestamp + clock::SECONDS_PER_DAY as i64
+        );
+
+        // Case: the carried over remainder is accrued with the next call
+        let (interest, timestamp) = calculate_accrued_interest(
+            timestamp,
+            last_interest_accrued_timestamp + 2 * clock::SECONDS_PER_DAY as i64,
+            total_staked + one_day_interest,
+            interest_rate,
+        );
+        assert_eq!(interest, 261_068_121);
+        assert_eq!(
+            timestamp,
+            last_interest_accrued_timestamp + 2 * clock::SECONDS_PER_DAY as i64
+        );
+    }
+
+    #[test]
+    fn test_accrued_interest_call_frequency() {
+        let start_timestamp = 1631012856;
+        let end_timestamp = start_timestamp + 90 * clock::SECONDS_PER_DAY as i64 + 12345;
+        let total_staked = ui_amount_to_amount(1150.11223344);
+        let interest_rate = 261;
+        let rate_schedule = [
+            crate::RatePoint {
+                effective_timestamp: start_timestamp + 20 * clock::SECONDS_PER_DAY as i64 + 777,
+                daily_rate: 159,
+            },
+            crate::RatePoint {
+                effective_timestamp: start_timestamp + 61 * clock::SECONDS_PER_DAY as i64,
+                daily_rate: 300,
+            },
+        ];
+        let hour = 3600;
+        let day = clock::SECONDS_PER_DAY as i64;
+        let step_patterns: [&[i64]; 7] = [
+            &[end_timestamp - start_timestamp],
+            &[hour],
+            &[7 * hour + 13 * 60],
+            &[day - 1],
+            &[day + 1],
+            &[day / 2 * 7],
+            &[1, 13 * hour, 3 * day + 17, 59, 2 * day - hour, 11 * day],
+        ];
+
+        for schedule in [&rate_schedule[..], &[]] {
+            let (expected_staked, expected_timestamp, _) = accrue_by_steps(
+                total_staked,
+                interest_rate,
+                schedule,
+                start_timestamp,
+                end_timestamp,
+                step_patterns[0],
+            );
+            assert_eq!(expected_timestamp, start_timestamp + 90 * day);
+
+            for steps in step_patterns.iter() {
+                let (staked, timestamp, calls) = accrue_by_steps(
+                    total_staked,
+                    interest_rate,
+                    schedule,
+                    start_timestamp,
+                    end_timestamp,
+                    steps,
+                );
+                // All the days are accrued whatever the calls frequency
+                assert_eq!(timestamp, expected_timestamp);
+                // Only the rounding of every call is lost
+                assert!(staked <= expected_staked);
+                assert!(expected_staked - staked <= 2 * calls);
+            }
+        }
+    }
+
+    #[test]
+    fn test_calculate_reward_per_share() {
+        // No shares, nothing is distributed
+        assert_eq!(calculate_reward_per_share(0, 1_000, 3600, 0), 0);
+        assert_eq!(calculate_reward_per_share(5, 1_000, 3600, 0), 5);
+
+        // 1000 per second during an hour for 1000 shares is 3600 per share
+        assert_eq!(
+            calculate_reward_per_share(0, 1_000, 3600, 1_000),
+            3600 * REWARD_PER_SHARE_MUL_FACTOR as u128
+        );
+        assert_eq!(
+            calculate_reward_per_share(7, 1_000, 3600, 1_000),
+            3600 * REWARD_PER_SHARE_MUL_FACTOR as u128 + 7
+        );
+
+        // Large pool, the accumulator keeps precision below a token
+        let reward_per_share =
+            calculate_reward_per_share(0, 1, 1, ui_amount_to_amount(1_000_000_000.0));
+        assert_eq!(reward_per_share, 1);
+        assert_eq!(
+            calculate_accumulated_reward(ui_amount_to_amount(1_000_000_000.0), reward_per_share),
+            1
+        );
+        assert_eq!(
+            calculate_accumulated_reward(ui_amount_to_amount(1_000.0), 2_000_000),
+            2
+        );
+    }
+
+    #[test]
+    fn test_settle_user_reward() {
+        let mut reward_stream = crate::RewardStream {
+            staking_data: Pubkey::default(),
+            sponsor: Pubkey::default(),
+            reward_mint: Pubkey::default(),
+            vault: Pubkey::default(),
+            vault_bump: 0,
+            emission_per_second: 100,
+            start_timestamp: 1000,
+            end_timestamp: 2000,
+            last_update_timestamp: 1000,
+            reward_per_share: 0,
+            distributed_rewards: 0,
+            claimed_rewards: 0,
+        };
+        let mut first_user = crate::UserReward {
+            reward_stream: Pubkey::default(),
+            staking_user_data: Pubkey::default(),
+            reward_debt: 0,
+            pending_rewards: 0,
+            shares: 100,
+        };
+        let mut second_user = first_user.clone();
+        second_user.shares = 0;
+
+        // Stream has not started yet
+        update_reward_stream(&mut reward_stream, 100, 900);
+        assert_eq!(reward_stream.reward_per_share, 0);
+        assert_eq!(reward_stream.last_update_timestamp, 1000);
+
+        // First user holds all 100 shares for 100 seconds, second user stakes 300 shares
+        update_reward_stream(&mut reward_stream, 100, 1100);
+        settle_user_reward(&reward_stream, &mut second_user, 0, 300);
+        assert_eq!(second_user.pending_rewards, 0);
+
+        // Both users hold the shares until the stream ends
+        update_reward_stream(&mut reward_stream, 400, 2500);
+        assert_eq!(reward_stream.last_update_timestamp, 2000);
+        assert_eq!(reward_stream.distributed_rewards, 100_000);
+        settle_user_reward(&reward_stream, &mut first_user, 100, 100);
+        settle_user_reward(&reward_stream, &mut second_user, 300, 300);
+        assert_eq!(first_user.pending_rewards, 10_000 + 22_500);
+        assert_eq!(second_user.pending_rewards, 67_500);
+
+        // Nothing is emitted after the end
+        update_reward_stream(&mut reward_stream, 400, 3000);
+        settle_user_reward(&reward_stream, &mut first_user, 100, 0);
+        assert_eq!(first_user.pending_rewards, 32_500);
+        assert_eq!(first_user.reward_debt, 0);
+        assert_eq!(first_user.shares, 0);
+        assert_eq!(second_user.shares, 300);
+        assert_eq!(reward_stream.distributed_rewards, 100_000);
+    }
+
+    #[test]
+    fn test_calculate_claimable_interest() {
+        let total_shares = ui_amount_to_amount(1_000.0);
+        let total_staked = ui_amount_to_amount(1_100.0);
+        let shares = ui_amount_to_amount(100.0);
+        let principal = ui_amount_to_amount(100.0);
+
+        assert_eq!(calculate_shares_value(0, 0, shares), 0);
+        assert_eq!(
+            calculate_shares_value(total_shares, total_staked, shares),
+            ui_amount_to_amount(110.0)
+        );
+
+        // No interest above the principal
+        assert_eq!(
+            calculate_claimable_interest(total_shares, total_shares, shares, principal, shares),
+            (0, 0)
+        );
+
+        // Shares to burn are rounded up
+        let (interest, shares_to_burn) =
+            calculate_claimable_interest(total_shares, total_staked, shares, principal, shares);
+        assert_eq!(interest, ui_amount_to_amount(10.0));
+        assert_eq!(shares_to_burn, 9_090_909_091);
+
+        // Claim doesn't take the value of other stakers
+        let others_shares = total_shares - shares;
+        let others_value = calculate_shares_value(total_shares, total_staked, others_shares);
+        let new_total_shares = total_shares - shares_to_burn;
+        let new_total_staked = total_staked - interest;
+        assert!(
+            calculate_shares_value(new_total_shares, new_total_staked, others_shares)
+                >= others_value
+        );
+        assert!(
+            calculate_shares_value(new_total_shares, new_total_staked, shares - shares_to_burn)
+                >= principal - 1
+        );
+
+        // Only available shares are burned
+        let available_shares = ui_amount_to_amount(5.0);
+        assert_eq!(
+            calculate_claimable_interest(
+                total_shares,
+                total_staked,
+                shares,
+                principal,
+                available_shares
+            ),
+            (ui_amount_to_amount(5.5), available_shares)
+        );
+    }
+
+    #[test]
+    fn test_calculate_remaining_principal() {
+        assert_eq!(calculate_remaining_principal(100, 0, 0), 0);
+        assert_eq!(calculate_remaining_principal(100, 100, 0), 100);
+        assert_eq!(calculate_remaining_principal(100, 100, 25), 75);
+        assert_eq!(calculate_remaining_principal(100, 300, 100), 66);
+        assert_eq!(calculate_remaining_principal(100, 100, 100), 0);
+    }
+
+    #[test]
+    fn test_calculate_early_unstake_penalty() {
+        let amount = ui_amount_to_amount(1_000.0);
+        let day = clock::SECONDS_PER_DAY as i64;
+
+        // No penalty configured
+        assert_eq!(
+            calculate_early_unstake_penalty(amount, 0, 10 * day, 0, 0),
+            0
+        );
+        assert_eq!(calculate_early_unstake_penalty(amount, 1_000, 0, 0, 0), 0);
+
+        // 10% penalty decays over 10 days
+        assert_eq!(
+            calculate_early_unstake_penalty(amount, 1_000, 10 * day, day, day),
+            ui_amount_to_amount(100.0)
+        );
+        assert_eq!(
+            calculate_early_unstake_penalty(amount, 1_000, 10 * day, day, 6 * day),
+            ui_amount_to_amount(50.0)
+        );
+        assert_eq!(
+            calculate_early_unstake_penalty(amount, 1_000, 10 * day, day, 10 * day),
+            ui_amount_to_amount(10.0)
+        );
+        assert_eq!(
+            calculate_early_unstake_penalty(amount, 1_000, 10 * day, day, 11 * day),
+            0
+        );
+        assert_eq!(
+            calculate_early_unstake_penalty(amount, 1_000, 10 * day, day, 100 * day),
+            0
+        );
+
+        // Users who never staked after the penalty was introduced
+        assert_eq!(
+            calculate_early_unstake_penalty(amount, 1_000, 10 * day, 0, 1_640_000_000),
+            0
+        );
+    }
+
+    #[test]
+    fn test_calculate_slashed_shares() {
+        let shares = ui_amount_to_amount(1_000.0);
+        assert_eq!(calculate_slashed_shares(shares, 0), 0);
+        assert_eq!(
+            calculate_slashed_shares(shares, 500),
+            ui_amount_to_amount(50.0)
+        );
+        assert_eq!(calculate_slashed_shares(shares, BPS_MUL_FACTOR), shares);
+        assert_eq!(calculate_slashed_shares(0, 1_000), 0);
+        // Rounded down in favor of the user
+        assert_eq!(calculate_slashed_shares(19, 500), 0);
+    }
+
+    #[test]
+    fn test_calculate_shares_at() {
+        let checkpoint = |slot: u64, shares: f64| crate::ShareCheckpoint {
+            slot,
+            shares: ui_amount_to_amount(shares),
+        };
+        let checkpoints = vec![
+            checkpoint(100, 10.0),
+            checkpoint(200, 30.0),
+            checkpoint(300, 5.0),
+        ];
+
+        assert_eq!(calculate_shares_at(&[], 1_000), 0);
+        assert_eq!(calculate_shares_at(&checkpoints, 50), 0);
+        // Shares changed in the snapshot slot are not counted
+        assert_eq!(calculate_shares_at(&checkpoints, 100), 0);
+        assert_eq!(
+            calculate_shares_at(&checkpoints, 101),
+            ui_amount_to_amount(10.0)
+        );
+        assert_eq!(
+            calculate_shares_at(&checkpoints, 250),
+            ui_amount_to_amount(30.0)
+        );
+        assert_eq!(
+            calculate_shares_at(&checkpoints, 1_000),
+            ui_amount_to_amount(5.0)
+        );
+    }
+
+    #[test]
+    fn test_push_share_checkpoint() {
+        let mut checkpoints = vec![];
+        push_share_checkpoint(&mut checkpoints, 3, 100, ui_amount_to_amount(10.0));
+        push_share_checkpoint(&mut checkpoints, 3, 200, ui_amount_to_amount(30.0));
+        // Same slot updates the last checkpoint
+        push_share_checkpoint(&mut checkpoints, 3, 200, ui_amount_to_amount(20.0));
+        assert_eq!(checkpoints.len(), 2);
+        assert_eq!(
+            calculate_shares_at(&checkpoints, 250),
+            ui_amount_to_amount(20.0)
+        );
+
+        push_share_checkpoint(&mut checkpoints, 3, 300, ui_amount_to_amount(40.0));
+        push_share_checkpoint(&mut checkpoints, 3, 400, ui_amount_to_amount(50.0));
+        assert_eq!(checkpoints.len(), 3);
+        // Two oldest checkpoints are folded into the base with the smaller shares
+        assert_eq!(checkpoints[0].slot, 100);
+        assert_eq!(
+            calculate_shares_at(&checkpoints, 150),
+            ui_amount_to_amount(10.0)
+        );
+        assert_eq!(
+            calculate_shares_at(&checkpoints, 250),
+            ui_amount_to_amount(10.0)
+        );
+        assert_eq!(
+            calculate_shares_at(&checkpoints, 350),
+            ui_amount_to_amount(40.0)
+        );
+        assert_eq!(
+            calculate_shares_at(&checkpoints, 1_000),
+            ui_amount_to_amount(50.0)
+        );
+
+        // Incoming shares can't push the base history out
+        for slot in 500..600 {
+            push_share_checkpoint(&mut checkpoints, 3, slot, ui_amount_to_amount(60.0));
+        }
+        assert_eq!(checkpoints[0].slot, 100);
+        assert_eq!(
+            calculate_shares_at(&checkpoints, 150),
+            ui_amount_to_amount(10.0)
+        );
+    }
+
+    #[test]
+    fn test_calculate_locked_amount() {
+        let lock = |amount: f64, until: UnixTimestamp| crate::LockEntry {
+            amount: ui_amount_to_amount(amount),
+            until,
+            locker_program: Pubkey::default(),
+            locker: Pubkey::default(),
+        };
+        let locks = vec![lock(100.0, 1_000), lock(50.0, 2_000), lock(25.0, 3_000)];
+
+        assert_eq!(calculate_locked_amount(&[], 0), 0);
+        assert_eq!(
+            calculate_locked_amount(&locks, 0),
+            ui_amount_to_amount(175.0)
+        );
+        // Expired entries are not counted
+        assert_eq!(
+            calculate_locked_amount(&locks, 1_000),
+            ui_amount_to_amount(75.0)
+        );
+        assert_eq!(
+            calculate_locked_amount(&locks, 2_500),
+            ui_amount_to_amount(25.0)
+        );
+        assert_eq!(calculate_locked_amount(&locks, 3_000), 0);
+    }
+
+    #[test]
+    fn test_calculate_fee() {
+        assert_eq!(calculate_fee(ui_amount_to_amount(1_000.0), 0), 0);
+        assert_eq!(
+            calculate_fee(ui_amount_to_amount(1_000.0), 30),
+            ui_amount_to_amount(3.0)
+        );
+        assert_eq!(
+            calculate_fee(ui_amount_to_amount(1_000.0), 1_000),
+            ui_amount_to_amount(100.0)
+        );
+        // Fee of dust amount rounds down to zero
+        assert_eq!(calculate_fee(333, 1), 0);
     }
 }
@@ -0,0 +1,129 @@
+//! Locker program for the staking functional tests. Locks and releases the user shares with
+//! its locker authority, as the governance does for the proposal votes.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use staking::{LockAmount, ReleaseLock, StakingData, StakingUserData};
+
+#[program]
+pub mod test_locker {
+    use super::*;
+
+    /// Lock the user shares by the staking program `lock_amount`.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[]` Staking data account
+    /// 1. `[writable]` Staking user data account
+    /// 2. `[]` User token wallet
+    /// 3. `[signer]` User token wallet owner
+    /// 4. `[]` Locker authority (pda of ['locker_authority'])
+    /// 5. `[]` This program
+    /// 6. `[]` Staking program
+    /// 7. `[]` Clock sysvar
+    ///
+    /// \param until: Timestamp until which the amount is locked
+    /// \param amount: Amount of shares to lock
+    /// \param locker: Key of the lock owner
+    /// \param locker_bump: PDA bump for locker authority
+    pub fn lock(
+        ctx: Context<Lock>,
+        until: i64,
+        amount: u64,
+        locker: Pubkey,
+        locker_bump: u8,
+    ) -> ProgramResult {
+        msg!("Instruction: lock");
+
+        let seeds = &[staking::LOCKER_AUTHORITY_PREFIX.as_bytes(), &[locker_bump]];
+        let signer = &[&seeds[..]];
+        staking::cpi::lock_amount(
+            CpiContext::new_with_signer(
+                ctx.accounts.staking_program.clone(),
+                LockAmount {
+                    staking_data: ctx.accounts.staking_data.clone(),
+                    staking_user_data: ctx.accounts.staking_user_data.clone().into(),
+                    user_token_wallet: ctx.accounts.user_token_wallet.clone(),
+                    user_token_wallet_owner: ctx.accounts.user_token_wallet_owner.clone(),
+                    locker_program: ctx.accounts.locker_program.clone(),
+                    locker_authority: ctx.accounts.locker_authority.clone(),
+                    clock: ctx.accounts.clock.clone(),
+                },
+                signer,
+            ),
+            until,
+            amount,
+            locker,
+            locker_bump,
+        )
+    }
+
+    /// Release the user lock by the staking program `release_lock`.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[]` Staking data account
+    /// 1. `[writable]` Staking user data account
+    /// 2. `[]` Locker authority (pda of ['locker_authority'])
+    /// 3. `[]` This program
+    /// 4. `[]` Staking program
+    ///
+    /// \param locker: Key of the lock owner
+    /// \param locker_bump: PDA bump for locker authority
+    pub fn release(ctx: Context<Release>, locker: Pubkey, locker_bump: u8) -> ProgramResult {
+        msg!("Instruction: release");
+
+        let seeds = &[staking::LOCKER_AUTHORITY_PREFIX.as_bytes(), &[locker_bump]];
+        let signer = &[&seeds[..]];
+        staking::cpi::release_lock(
+            CpiContext::new_with_signer(
+                ctx.accounts.staking_program.clone(),
+                ReleaseLock {
+                    staking_data: ctx.accounts.staking_data.clone(),
+                    staking_user_data: ctx.accounts.staking_user_data.clone().into(),
+                    locker_program: ctx.accounts.locker_program.clone(),
+                    locker_authority: ctx.accounts.locker_authority.clone(),
+                },
+                signer,
+            ),
+            locker,
+            locker_bump,
+        )
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(until: i64, amount: u64, locker: Pubkey, locker_bump: u8)]
+pub struct Lock<'info> {
+    staking_data: CpiAccount<'info, StakingData>,
+    #[account(mut)]
+    staking_user_data: CpiAccount<'info, StakingUserData>,
+    user_token_wallet: CpiAccount<'info, TokenAccount>,
+    #[account(signer)]
+    user_token_wallet_owner: AccountInfo<'info>,
+    // pda of ['locker_authority']
+    #[account(
+        seeds = [staking::LOCKER_AUTHORITY_PREFIX.as_bytes()],
+        bump = locker_bump,
+    )]
+    locker_authority: AccountInfo<'info>,
+    #[account(constraint = locker_program.key == program_id)]
+    locker_program: AccountInfo<'info>,
+    staking_program: AccountInfo<'info>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+#[instruction(locker: Pubkey, locker_bump: u8)]
+pub struct Release<'info> {
+    staking_data: CpiAccount<'info, StakingData>,
+    #[account(mut)]
+    staking_user_data: CpiAccount<'info, StakingUserData>,
+    // pda of ['locker_authority']
+    #[account(
+        seeds = [staking::LOCKER_AUTHORITY_PREFIX.as_bytes()],
+        bump = locker_bump,
+    )]
+    locker_authority: AccountInfo<'info>,
+    #[account(constraint = locker_program.key == program_id)]
+    locker_program: AccountInfo<'info>,
+    staking_program: AccountInfo<'info>,
+}
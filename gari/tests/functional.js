@@ -8,6 +8,11 @@ const TREASURY_PREFIX = 'treasury';
 const TREASURY_STATS_PREFIX = 'treasury_stats';
 const PROPOSAL_PREFIX = 'proposal';
 const VOTE_MARKER_PREFIX = 'vote';
+const ALLOW_LIST_PREFIX = 'allow_list';
+const LOCKER_AUTHORITY_PREFIX = 'locker_authority';
+const RECEIPT_MINT_PREFIX = 'receipt_mint';
+const USER_REWARD_PREFIX = 'user_reward';
+const TOKEN_METADATA_PROGRAM_ID = new anchor.web3.PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s');
 
 function ui_amount_to_amount(ui_amount, decimals) {
   return Number(ui_amount * Math.pow(10, decimals))
@@ -57,6 +62,19 @@ describe('functional tests', () => {
   let user4StakingData = null;
   let user4TokenWalletPK = null;
 
+  async function allowListEntryPK(walletPK) {
+    const pda = await anchor.web3.PublicKey.findProgramAddress(
+      [
+        Buffer.from(ALLOW_LIST_PREFIX),
+        stakingProgram.programId.toBuffer(),
+        stakingAccount.publicKey.toBuffer(),
+        walletPK.toBuffer(),
+      ],
+      stakingProgram.programId
+    );
+    return pda[0];
+  }
+
   // Token tests
   describe('Token tests', () => {
     it('Create metadata test', async () => {
@@ -298,6 +316,7 @@ describe('functional tests', () => {
             userTokenWallet: user1TokenWalletPK,
             userTokenWalletOwner: user1Authority.publicKey,
             stakingData: stakingAccount.publicKey,
+            allowListEntry: await allowListEntryPK(user1Authority.publicKey),
             stakingTokenMetadata: metadataAccount.publicKey,
             systemProgram: anchor.web3.SystemProgram.programId,
             rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -318,8 +337,7 @@ describe('functional tests', () => {
       assert.ok(checkUser1Data.userTokenWallet.equals(user1TokenWalletPK));
       assert.ok(checkUser1Data.stakingData.equals(stakingAccount.publicKey));
       assert.equal(checkUser1Data.ownershipShare, 0);
-      assert.equal(checkUser1Data.lockedAmount, 0);
-      assert.equal(checkUser1Data.lockedUntil, 0);
+      assert.equal(checkUser1Data.locks.length, 0);
 
       await stakingProgram.rpc.initializeStakingUser(
         {
@@ -328,6 +346,7 @@ describe('functional tests', () => {
             userTokenWallet: user2TokenWalletPK,
             userTokenWalletOwner: user2Authority.publicKey,
             stakingData: stakingAccount.publicKey,
+            allowListEntry: await allowListEntryPK(user2Authority.publicKey),
             stakingTokenMetadata: metadataAccount.publicKey,
             systemProgram: anchor.web3.SystemProgram.programId,
             rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -348,8 +367,7 @@ describe('functional tests', () => {
       assert.ok(checkUser2Data.userTokenWallet.equals(user2TokenWalletPK));
       assert.ok(checkUser2Data.stakingData.equals(stakingAccount.publicKey));
       assert.equal(checkUser2Data.ownershipShare, 0);
-      assert.equal(checkUser2Data.lockedAmount, 0);
-      assert.equal(checkUser2Data.lockedUntil, 0);
+      assert.equal(checkUser2Data.locks.length, 0);
 
       await stakingProgram.rpc.initializeStakingUser(
         {
@@ -358,6 +376,7 @@ describe('functional tests', () => {
             userTokenWallet: user3TokenWalletPK,
             userTokenWalletOwner: user3Authority.publicKey,
             stakingData: stakingAccount.publicKey,
+            allowListEntry: await allowListEntryPK(user3Authority.publicKey),
             stakingTokenMetadata: metadataAccount.publicKey,
             systemProgram: anchor.web3.SystemProgram.programId,
             rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -378,8 +397,7 @@ describe('functional tests', () => {
       assert.ok(checkUser3Data.userTokenWallet.equals(user3TokenWalletPK));
       assert.ok(checkUser3Data.stakingData.equals(stakingAccount.publicKey));
       assert.equal(checkUser3Data.ownershipShare, 0);
-      assert.equal(checkUser3Data.lockedAmount, 0);
-      assert.equal(checkUser3Data.lockedUntil, 0);
+      assert.equal(checkUser3Data.locks.length, 0);
 
       await stakingProgram.rpc.initializeStakingUser(
         {
@@ -388,6 +406,7 @@ describe('functional tests', () => {
             userTokenWallet: user4TokenWalletPK,
             userTokenWalletOwner: user4Authority.publicKey,
             stakingData: stakingAccount.publicKey,
+            allowListEntry: await allowListEntryPK(user4Authority.publicKey),
             stakingTokenMetadata: metadataAccount.publicKey,
             systemProgram: anchor.web3.SystemProgram.programId,
             rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -408,8 +427,7 @@ describe('functional tests', () => {
       assert.ok(checkUser4Data.userTokenWallet.equals(user4TokenWalletPK));
       assert.ok(checkUser4Data.stakingData.equals(stakingAccount.publicKey));
       assert.equal(checkUser4Data.ownershipShare, 0);
-      assert.equal(checkUser4Data.lockedAmount, 0);
-      assert.equal(checkUser4Data.lockedUntil, 0);
+      assert.equal(checkUser4Data.locks.length, 0);
     });
 
     it('Set interest rate test', async () => {
@@ -492,6 +510,36 @@ describe('functional tests', () => {
     let user2StakeAmount = 9.5;
 
     it('Stake test', async () => {
+      // BadCase: holding wallet is not the staking pool one
+      await assert.rejects(
+        async () => {
+          await stakingProgram.rpc.stake(
+            new anchor.BN(ui_amount_to_amount(user1StakeAmount, 9)),
+            {
+              accounts: {
+                stakingUserData: user1StakingData.publicKey,
+                userTokenWallet: user1TokenWalletPK,
+                userTokenWalletOwner: user1Authority.publicKey,
+                stakingData: stakingAccount.publicKey,
+                allowListEntry: await allowListEntryPK(user1Authority.publicKey),
+                holdingWallet: user2TokenWalletPK,
+                feeCollector: holdingWalletPK,
+                stakingTokenMetadata: metadataAccount.publicKey,
+                mint: mint.publicKey,
+                mintAuthority: pdaMintAuthority,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+              },
+              signers: [user1Authority]
+            }
+          );
+        },
+        (err) => {
+          assert.equal(err.code, 143);
+          return true;
+        }
+      );
+
       // User1 stake tokens
       await stakingProgram.rpc.stake(
         new anchor.BN(ui_amount_to_amount(user1StakeAmount, 9)),
@@ -501,7 +549,9 @@ describe('functional tests', () => {
             userTokenWallet: user1TokenWalletPK,
             userTokenWalletOwner: user1Authority.publicKey,
             stakingData: stakingAccount.publicKey,
+            allowListEntry: await allowListEntryPK(user1Authority.publicKey),
             holdingWallet: holdingWalletPK,
+            feeCollector: holdingWalletPK,
             stakingTokenMetadata: metadataAccount.publicKey,
             mint: mint.publicKey,
             mintAuthority: pdaMintAuthority,
@@ -533,7 +583,9 @@ describe('functional tests', () => {
             userTokenWallet: user2TokenWalletPK,
             userTokenWalletOwner: user2Authority.publicKey,
             stakingData: stakingAccount.publicKey,
+            allowListEntry: await allowListEntryPK(user2Authority.publicKey),
             holdingWallet: holdingWalletPK,
+            feeCollector: holdingWalletPK,
             stakingTokenMetadata: metadataAccount.publicKey,
             mint: mint.publicKey,
             mintAuthority: pdaMintAuthority,
@@ -570,6 +622,8 @@ describe('functional tests', () => {
             stakingData: stakingAccount.publicKey,
             holdingWallet: holdingWalletPK,
             holdingWalletOwner: holdingWalletOwnerPK,
+            penaltyWallet: holdingWalletPK,
+            feeCollector: holdingWalletPK,
             stakingTokenMetadata: metadataAccount.publicKey,
             mint: mint.publicKey,
             mintAuthority: pdaMintAuthority,
@@ -604,6 +658,8 @@ describe('functional tests', () => {
             stakingData: stakingAccount.publicKey,
             holdingWallet: holdingWalletPK,
             holdingWalletOwner: holdingWalletOwnerPK,
+            penaltyWallet: holdingWalletPK,
+            feeCollector: holdingWalletPK,
             stakingTokenMetadata: metadataAccount.publicKey,
             mint: mint.publicKey,
             mintAuthority: pdaMintAuthority,
@@ -627,1131 +683,3555 @@ describe('functional tests', () => {
       assert.equal(checkStakingUserData.ownershipShare.toNumber(), 4_500_000_000);
     });
 
-    it('Accrue interest instruction call test', async () => {
-      await stakingProgram.rpc.accrueInterest(
-        {
-          accounts: {
-            stakingData: stakingAccount.publicKey,
-            holdingWallet: holdingWalletPK,
-            stakingTokenMetadata: metadataAccount.publicKey,
-            mint: mint.publicKey,
-            mintAuthority: pdaMintAuthority,
-            tokenProgram: TOKEN_PROGRAM_ID,
-            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
-          }
-        }
-      );
-    });
-  });
-
-  // Governance tests
-  const govUser1Auth = anchor.web3.Keypair.generate();
-  const govUser2Auth = anchor.web3.Keypair.generate();
-
-  let govUser1TokenWallet = null;
-  let govUser2TokenWallet = null;
-
-  let govUser1TokenBalance = 50.0;
-  let govUser2TokenBalance = 50.0;
-  let treasuryMint = null;
-
-  let treasuryMint2 = null;
-  let govUser1TokenWallet2 = null;
-  let govUser2TokenWallet2 = null;
-
-  describe('Governance tests', () => {
-    it('Prepairing users to funding treasury', async () => {
-      treasuryMetadata = anchor.web3.Keypair.generate();
-
-      // Create gov token
-      treasuryMint = await Token.createMint(
-        provider.connection,
-        payer,
-        mintAuthority.publicKey,
-        null,
-        0,
-        TOKEN_PROGRAM_ID
+    it('Receipts test', async () => {
+      let pda = await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from(RECEIPT_MINT_PREFIX),
+          stakingProgram.programId.toBuffer(),
+          stakingAccount.publicKey.toBuffer(),
+        ],
+        stakingProgram.programId
       );
-
-      // Create another token for gov
-      treasuryMint2 = await Token.createMint(
+      const receiptMintAuthority = pda[0];
+      const receiptMintAuthBump = pda[1];
+      const receiptMint = await Token.createMint(
         provider.connection,
         payer,
-        mintAuthority.publicKey,
+        receiptMintAuthority,
         null,
-        0,
+        9,
         TOKEN_PROGRAM_ID
       );
-
-      govUser1TokenWallet = await treasuryMint.createAccount(govUser1Auth.publicKey);
-      await treasuryMint.mintTo(
-        govUser1TokenWallet,
-        mintAuthority,
-        [],
-        ui_amount_to_amount(govUser1TokenBalance, 9)
-      );
-
-      govUser2TokenWallet = await treasuryMint.createAccount(govUser2Auth.publicKey);
-      await treasuryMint.mintTo(
-        govUser2TokenWallet,
-        mintAuthority,
-        [],
-        ui_amount_to_amount(govUser2TokenBalance, 9)
-      );
-
-      govUser1TokenWallet2 = await treasuryMint2.createAccount(govUser1Auth.publicKey);
-      await treasuryMint2.mintTo(
-        govUser1TokenWallet2,
-        mintAuthority,
-        [],
-        ui_amount_to_amount(govUser1TokenBalance, 9)
-      );
-
-      govUser2TokenWallet2 = await treasuryMint2.createAccount(govUser2Auth.publicKey);
-      await treasuryMint2.mintTo(
-        govUser2TokenWallet2,
-        mintAuthority,
-        [],
-        ui_amount_to_amount(govUser2TokenBalance, 9)
-      );
-    });
-
-    const govProgram = anchor.workspace.Governance;
-    let governanceAccount = null;
-    let treasuryOwnerPK = null;
-    let sponsors = null;
-
-    const approvalFixedPeriodInSeconds = 15;
-    const minApprovalPercent = 51;
-    const minStakeToPropose = 1_000_000_000;
-    const minVoteParticipationPercent = 50;
-
-    const maxProposalPaymentPercent = 50;
-    const paymentInPeriodLimitPercent = 50;
-    const paymentPeriodSec = 15;
-
-    it('Initialize governance', async () => {
-      governanceAccount = anchor.web3.Keypair.generate();
-
-      let pda = await anchor.web3.PublicKey.findProgramAddress(
+      pda = await anchor.web3.PublicKey.findProgramAddress(
         [
-          Buffer.from(TREASURY_PREFIX),
-          govProgram.programId.toBuffer(),
-          governanceAccount.publicKey.toBuffer()
+          Buffer.from('metadata'),
+          TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+          receiptMint.publicKey.toBuffer(),
         ],
-        govProgram.programId
+        TOKEN_METADATA_PROGRAM_ID
       );
-      treasuryOwnerPK = pda[0];
-      let treasuryOwnerBump = pda[1];
+      const receiptMetadata = pda[0];
+      const user2ReceiptWalletPK = await receiptMint.createAccount(user2Authority.publicKey);
 
-      // BadCase: not enough sponsors
-      sponsors = [
-        user1Authority.publicKey,
-        user2Authority.publicKey,
-      ];
+      // BadCase: Receipt metadata is created by another program than Metaplex
       await assert.rejects(
         async () => {
-          await govProgram.rpc.initializeGovernance(
-            stakingAccount.publicKey,
-            new anchor.BN(approvalFixedPeriodInSeconds),
-            minApprovalPercent,
-            new anchor.BN(minStakeToPropose),
-            minVoteParticipationPercent,
-            new anchor.BN(paymentPeriodSec),
-            treasuryOwnerBump,
-            sponsors,
+          await stakingProgram.rpc.enableReceipts(
+            receiptMintAuthBump,
+            "Staked Token",
+            "STKN",
             {
               accounts: {
-                governance: governanceAccount.publicKey,
-                payer: payer.publicKey,
-                treasuryOwner: treasuryOwnerPK,
+                stakingData: stakingAccount.publicKey,
+                stakingOwner: stakingOwner.publicKey,
+                receiptMint: receiptMint.publicKey,
+                receiptMintAuthority: receiptMintAuthority,
+                receiptMetadata: receiptMetadata,
+                metadataProgram: metadataProgram.programId,
+                tokenMetadataProgram: TOKEN_PROGRAM_ID,
+                tokenProgram: TOKEN_PROGRAM_ID,
                 systemProgram: anchor.web3.SystemProgram.programId,
                 rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-                clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
               },
-              signers: [governanceAccount]
+              signers: [stakingOwner]
             }
           );
         },
         (err) => {
-          assert.equal(err.code, 308);
-          assert.equal(err.msg, "Not enough sponsors");
+          assert.equal(err.code, 152);
+          assert.equal(err.msg, "An address constraint was violated");
           return true;
         }
       );
 
-      // BadCase: too much sponsors (17)
-      sponsors = [
-        user1Authority.publicKey, user2Authority.publicKey, user1Authority.publicKey,
-        user2Authority.publicKey, user1Authority.publicKey, user2Authority.publicKey,
-        user1Authority.publicKey, user2Authority.publicKey, user1Authority.publicKey,
-        user2Authority.publicKey, user1Authority.publicKey, user2Authority.publicKey,
-        user1Authority.publicKey, user2Authority.publicKey, user1Authority.publicKey,
-        user2Authority.publicKey, user2Authority.publicKey,
-      ];
+      // BadCase: Metadata program is not a program
       await assert.rejects(
         async () => {
-          await govProgram.rpc.initializeGovernance(
-            stakingAccount.publicKey,
-            new anchor.BN(approvalFixedPeriodInSeconds),
-            minApprovalPercent,
-            new anchor.BN(minStakeToPropose),
-            minVoteParticipationPercent,
-            new anchor.BN(paymentPeriodSec),
-            treasuryOwnerBump,
-            sponsors,
+          await stakingProgram.rpc.enableReceipts(
+            receiptMintAuthBump,
+            "Staked Token",
+            "STKN",
             {
               accounts: {
-                governance: governanceAccount.publicKey,
-                payer: payer.publicKey,
-                treasuryOwner: treasuryOwnerPK,
+                stakingData: stakingAccount.publicKey,
+                stakingOwner: stakingOwner.publicKey,
+                receiptMint: receiptMint.publicKey,
+                receiptMintAuthority: receiptMintAuthority,
+                receiptMetadata: receiptMetadata,
+                metadataProgram: user2Authority.publicKey,
+                tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+                tokenProgram: TOKEN_PROGRAM_ID,
                 systemProgram: anchor.web3.SystemProgram.programId,
                 rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-                clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
               },
-              signers: [governanceAccount]
+              signers: [stakingOwner]
             }
           );
         },
         (err) => {
-          assert.equal(err.code, 309);
-          assert.equal(err.msg, "Sponsors limit exceeded");
+          assert.equal(err.code, 147);
+          assert.equal(err.msg, "An executable constraint was violated");
           return true;
         }
       );
 
-      sponsors = [
-        user1Authority.publicKey,
-        user2Authority.publicKey,
-        user3Authority.publicKey,
-      ];
-      await govProgram.rpc.initializeGovernance(
-        stakingAccount.publicKey,
-        new anchor.BN(approvalFixedPeriodInSeconds),
-        minApprovalPercent,
-        new anchor.BN(minStakeToPropose),
-        minVoteParticipationPercent,
-        new anchor.BN(paymentPeriodSec),
-        treasuryOwnerBump,
-        sponsors,
-        {
-          accounts: {
-            governance: governanceAccount.publicKey,
-            payer: payer.publicKey,
-            treasuryOwner: treasuryOwnerPK,
-            systemProgram: anchor.web3.SystemProgram.programId,
-            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
-          },
-          signers: [governanceAccount]
-        }
-      );
-
-      // check data
-      let checkGovData = await govProgram.account.governance.fetch(governanceAccount.publicKey);
-      assert.ok(checkGovData.stakingData.equals(stakingAccount.publicKey));
-      assert.equal(checkGovData.approvalFixedPeriodInSeconds, approvalFixedPeriodInSeconds);
-      assert.equal(checkGovData.minApprovalPercent, minApprovalPercent);
-      assert.equal(checkGovData.treasuryOwnerBump, treasuryOwnerBump);
-      assert.equal(checkGovData.minStakeToPropose, minStakeToPropose);
-      assert.equal(checkGovData.minVoteParticipationPercent, minVoteParticipationPercent);
-      assert.deepStrictEqual(checkGovData.sponsors, sponsors);
-      assert.equal(checkGovData.paymentPeriodSec, paymentPeriodSec);
-    });
-
-    it('Changing sponsors list test', async () => {
-      let newSponsor1 = anchor.web3.Keypair.generate();
-      let newSponsor2 = anchor.web3.Keypair.generate();
+      // ************
+      // Receipt mode can't be enabled without Metaplex Token Metadata program in test localnetwork,
+      // so stake and unstake minting and burning the receipts are not covered.
+      // ************
 
-      // BadCase: not enough signers
+      // BadCase: Receipts can't be minted while receipts are disabled
       await assert.rejects(
         async () => {
-          await govProgram.rpc.addSponsor(
-            newSponsor1.publicKey,
+          await stakingProgram.rpc.mintReceipts(
+            new anchor.BN(1_000_000_000),
             {
               accounts: {
-                governance: governanceAccount.publicKey,
+                stakingUserData: user2StakingData.publicKey,
+                userTokenWallet: user2TokenWalletPK,
+                userTokenWalletOwner: user2Authority.publicKey,
+                stakingData: stakingAccount.publicKey,
+                receiptMint: receiptMint.publicKey,
+                receiptMintAuthority: receiptMintAuthority,
+                receiptWallet: user2ReceiptWalletPK,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
               },
-              remainingAccounts: [
-                {
-                  pubkey: user1Authority.publicKey,
-                  isSigner: true,
-                  isWritable: false,
-                },
-              ],
-              signers: [user1Authority],
+              signers: [user2Authority]
             }
           );
         },
         (err) => {
-          assert.equal(err.code, 311);
-          assert.equal(err.msg, "Not enough signers to change sponsors list");
+          assert.equal(err.code, 143);
+          assert.equal(err.msg, "A raw constraint was violated");
           return true;
         }
       );
 
-      // BadCase: duplicates in signers list
+      // BadCase: Receipts can't be redeemed while receipts are disabled
       await assert.rejects(
         async () => {
-          await govProgram.rpc.addSponsor(
-            newSponsor1.publicKey,
+          await stakingProgram.rpc.redeemReceipts(
+            new anchor.BN(1_000_000_000),
             {
               accounts: {
-                governance: governanceAccount.publicKey,
+                stakingUserData: user2StakingData.publicKey,
+                userTokenWallet: user2TokenWalletPK,
+                userTokenWalletOwner: user2Authority.publicKey,
+                stakingData: stakingAccount.publicKey,
+                allowListEntry: await allowListEntryPK(user2Authority.publicKey),
+                receiptMint: receiptMint.publicKey,
+                receiptWallet: user2ReceiptWalletPK,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
               },
-              remainingAccounts: [
-                {
-                  pubkey: user1Authority.publicKey,
-                  isSigner: true,
-                  isWritable: false,
-                },
-                {
-                  pubkey: user1Authority.publicKey,
-                  isSigner: true,
-                  isWritable: false,
-                },
-              ],
-              signers: [user1Authority, user1Authority],
+              signers: [user2Authority]
             }
           );
         },
         (err) => {
-          assert.equal(err.code, 311);
-          assert.equal(err.msg, "Not enough signers to change sponsors list");
+          assert.equal(err.code, 143);
+          assert.equal(err.msg, "A raw constraint was violated");
           return true;
         }
       );
 
-      await govProgram.rpc.addSponsor(
-        newSponsor1.publicKey,
+      // Shares are not moved
+      const checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.ok(checkStakingData.receiptMint.equals(new anchor.web3.PublicKey(0)));
+      assert.equal(checkStakingData.receiptShares.toNumber(), 0);
+      const checkStakingUserData = await stakingProgram.account.stakingUserData.fetch(user2StakingData.publicKey);
+      assert.equal(checkStakingUserData.ownershipShare.toNumber(), 4_500_000_000);
+    });
+
+    it('Locked stake test', async () => {
+      const lockDuration = 3;
+      await stakingProgram.rpc.setLockTiers(
+        [{duration: new anchor.BN(lockDuration), boost: new anchor.BN(20000)}],
         {
           accounts: {
-            governance: governanceAccount.publicKey,
+            stakingData: stakingAccount.publicKey,
+            stakingOwner: stakingOwner.publicKey,
           },
-          remainingAccounts: [
-            {
-              pubkey: user1Authority.publicKey,
-              isSigner: true,
-              isWritable: false,
-            },
-            {
-              pubkey: user2Authority.publicKey,
-              isSigner: true,
-              isWritable: false,
-            },
-          ],
-          signers: [user1Authority, user2Authority],
+          signers: [stakingOwner]
         }
       );
 
-      sponsors = [user1Authority.publicKey, user2Authority.publicKey, user3Authority.publicKey, newSponsor1.publicKey];
-      checkGovData = await govProgram.account.governance.fetch(governanceAccount.publicKey);
-      assert.equal(checkGovData.sponsors.length, 4);
-      assert.deepStrictEqual(checkGovData.sponsors, sponsors);
+      // User3 stake tokens with 2x boost
+      const lockedStake = anchor.web3.Keypair.generate();
+      await stakingProgram.rpc.stakeLocked(
+        new anchor.BN(2_000_000_000),
+        0,
+        {
+          accounts: {
+            stakingUserData: user3StakingData.publicKey,
+            lockedStake: lockedStake.publicKey,
+            userTokenWallet: user3TokenWalletPK,
+            userTokenWalletOwner: user3Authority.publicKey,
+            stakingData: stakingAccount.publicKey,
+            allowListEntry: await allowListEntryPK(user3Authority.publicKey),
+            holdingWallet: holdingWalletPK,
+            feeCollector: holdingWalletPK,
+            stakingTokenMetadata: metadataAccount.publicKey,
+            mint: mint.publicKey,
+            mintAuthority: pdaMintAuthority,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          signers: [user3Authority, lockedStake]
+        }
+      );
 
-      // BadCase: sponsor already in list
+      let checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.equal(checkStakingData.totalStaked.toNumber(), 6_500_000_000);
+      assert.equal(checkStakingData.totalShares.toNumber(), 8_500_000_000);
+      let checkStakingUserData = await stakingProgram.account.stakingUserData.fetch(user3StakingData.publicKey);
+      assert.equal(checkStakingUserData.ownershipShare.toNumber(), 4_000_000_000);
+      assert.equal(checkStakingUserData.tierLockedShares.toNumber(), 4_000_000_000);
+      const checkLockedStake = await stakingProgram.account.lockedStake.fetch(lockedStake.publicKey);
+      assert.equal(checkLockedStake.shares.toNumber(), 4_000_000_000);
+      assert.equal(checkLockedStake.boostShares.toNumber(), 2_000_000_000);
+
+      const releaseAccounts = {
+        stakingUserData: user3StakingData.publicKey,
+        lockedStake: lockedStake.publicKey,
+        userTokenWallet: user3TokenWalletPK,
+        userTokenWalletOwner: user3Authority.publicKey,
+        stakingData: stakingAccount.publicKey,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      };
+      const unstakeAccounts = {
+        stakingUserData: user3StakingData.publicKey,
+        userTokenWallet: user3TokenWalletPK,
+        userTokenWalletOwner: user3Authority.publicKey,
+        stakingData: stakingAccount.publicKey,
+        holdingWallet: holdingWalletPK,
+        holdingWalletOwner: holdingWalletOwnerPK,
+        penaltyWallet: holdingWalletPK,
+        feeCollector: holdingWalletPK,
+        stakingTokenMetadata: metadataAccount.publicKey,
+        mint: mint.publicKey,
+        mintAuthority: pdaMintAuthority,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      };
+
+      // BadCase: release before the lock expires
       await assert.rejects(
         async () => {
-          await govProgram.rpc.addSponsor(
-            newSponsor1.publicKey,
+          await stakingProgram.rpc.releaseLockedStake(
             {
-              accounts: {
-                governance: governanceAccount.publicKey,
-              },
-              remainingAccounts: [
-                {
-                  pubkey: user1Authority.publicKey,
-                  isSigner: true,
-                  isWritable: false,
-                },
-                {
-                  pubkey: user2Authority.publicKey,
-                  isSigner: true,
-                  isWritable: false,
-                },
-                {
-                  pubkey: newSponsor1.publicKey,
-                  isSigner: true,
-                  isWritable: false,
-                },
-              ],
-              signers: [user1Authority, user2Authority, newSponsor1],
+              accounts: releaseAccounts,
+              signers: [user3Authority]
             }
           );
         },
         (err) => {
-          assert.equal(err.code, 312);
-          assert.equal(err.msg, "Sponsor is already in list");
+          assert.equal(err.code, 307);
+          assert.equal(err.msg, "Stake is still locked");
           return true;
         }
       );
 
-      await govProgram.rpc.addSponsor(
-        newSponsor2.publicKey,
-        {
-          accounts: {
-            governance: governanceAccount.publicKey,
-          },
-          remainingAccounts: [
-            {
-              pubkey: user1Authority.publicKey,
-              isSigner: true,
-              isWritable: false,
-            },
-            {
-              pubkey: user2Authority.publicKey,
-              isSigner: true,
-              isWritable: false,
-            },
-            {
-              pubkey: newSponsor1.publicKey,
-              isSigner: true,
-              isWritable: false,
-            },
-          ],
-          signers: [user1Authority, user2Authority, newSponsor1],
-        }
-      );
-
-      sponsors = [user1Authority.publicKey, user2Authority.publicKey, user3Authority.publicKey, newSponsor1.publicKey, newSponsor2.publicKey];
-      checkGovData = await govProgram.account.governance.fetch(governanceAccount.publicKey);
-      assert.equal(checkGovData.sponsors.length, 5);
-      assert.deepStrictEqual(checkGovData.sponsors, sponsors);
-
-      // BadCase: There is such sponsor in list
+      // BadCase: unstake locked shares
       await assert.rejects(
         async () => {
-          let fakeSponsor = anchor.web3.Keypair.generate();
-          await govProgram.rpc.removeSponsor(
-            fakeSponsor.publicKey,
+          await stakingProgram.rpc.unstake(
+            new anchor.BN(2_000_000_000),
             {
-              accounts: {
-                governance: governanceAccount.publicKey,
-              },
-              remainingAccounts: [
-                {
-                  pubkey: user1Authority.publicKey,
-                  isSigner: true,
-                  isWritable: false,
-                },
-                {
-                  pubkey: user2Authority.publicKey,
-                  isSigner: true,
-                  isWritable: false,
-                },
-                {
-                  pubkey: newSponsor1.publicKey,
-                  isSigner: true,
-                  isWritable: false,
-                },
-              ],
-              signers: [user1Authority, user2Authority, newSponsor1],
+              accounts: unstakeAccounts,
+              signers: [user3Authority]
             }
           );
-        },
-        (err) => {
-          assert.equal(err.code, 313);
-          assert.equal(err.msg, "Sponsor is already not in list");
-          return true;
         }
       );
 
-      // Remove newSponsor2 from list
-      await govProgram.rpc.removeSponsor(
-        newSponsor2.publicKey,
+      sleep((lockDuration + 2) * 1000);
+
+      await stakingProgram.rpc.releaseLockedStake(
         {
-          accounts: {
-            governance: governanceAccount.publicKey,
-          },
-          remainingAccounts: [
-            {
-              pubkey: user1Authority.publicKey,
-              isSigner: true,
-              isWritable: false,
-            },
-            {
-              pubkey: user2Authority.publicKey,
-              isSigner: true,
-              isWritable: false,
-            },
-            {
-              pubkey: newSponsor1.publicKey,
-              isSigner: true,
-              isWritable: false,
-            },
-          ],
-          signers: [user1Authority, user2Authority, newSponsor1],
+          accounts: releaseAccounts,
+          signers: [user3Authority]
         }
       );
 
-      sponsors = [user1Authority.publicKey, user2Authority.publicKey, user3Authority.publicKey, newSponsor1.publicKey];
-      checkGovData = await govProgram.account.governance.fetch(governanceAccount.publicKey);
-      assert.equal(checkGovData.sponsors.length, 4);
-      assert.deepStrictEqual(checkGovData.sponsors, sponsors);
+      // Boost shares are burned
+      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.equal(checkStakingData.totalStaked.toNumber(), 6_500_000_000);
+      assert.equal(checkStakingData.totalShares.toNumber(), 6_500_000_000);
+      checkStakingUserData = await stakingProgram.account.stakingUserData.fetch(user3StakingData.publicKey);
+      assert.equal(checkStakingUserData.ownershipShare.toNumber(), 2_000_000_000);
+      assert.equal(checkStakingUserData.tierLockedShares.toNumber(), 0);
+      assert.equal(await provider.connection.getAccountInfo(lockedStake.publicKey), null);
 
-      // BadCase: not enough signers
+      await stakingProgram.rpc.unstake(
+        new anchor.BN(2_000_000_000),
+        {
+          accounts: unstakeAccounts,
+          signers: [user3Authority]
+        }
+      );
+
+      // User3 gets the staked amount back, other stakers are not diluted
+      const userTokenInfo = await mint.getAccountInfo(user3TokenWalletPK);
+      assert.equal(userTokenInfo.amount, ui_amount_to_amount(user3TokenBalance, 9));
+      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.equal(checkStakingData.totalStaked.toNumber(), 4_500_000_000);
+      assert.equal(checkStakingData.totalShares.toNumber(), 4_500_000_000);
+      checkStakingUserData = await stakingProgram.account.stakingUserData.fetch(user3StakingData.publicKey);
+      assert.equal(checkStakingUserData.ownershipShare.toNumber(), 0);
+    });
+
+    it('Transfer shares test', async () => {
+      const setDepositLimits = async (allowListEnabled, userDepositCap) => {
+        await stakingProgram.rpc.setDepositLimits(
+          allowListEnabled,
+          new anchor.BN(userDepositCap),
+          new anchor.BN(0),
+          {
+            accounts: {
+              stakingData: stakingAccount.publicKey,
+              stakingOwner: stakingOwner.publicKey,
+            },
+            signers: [stakingOwner]
+          }
+        );
+      };
+      const transferSharesAccounts = async (sender, recipient) => {
+        return {
+          stakingUserData: sender.stakingData.publicKey,
+          userTokenWallet: sender.tokenWallet,
+          userTokenWalletOwner: sender.authority.publicKey,
+          recipientStakingUserData: recipient.stakingData.publicKey,
+          recipientTokenWallet: recipient.tokenWallet,
+          recipientAllowListEntry: await allowListEntryPK(recipient.authority.publicKey),
+          stakingData: stakingAccount.publicKey,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        };
+      };
+      const user1 = {stakingData: user1StakingData, tokenWallet: user1TokenWalletPK, authority: user1Authority};
+      const user2 = {stakingData: user2StakingData, tokenWallet: user2TokenWalletPK, authority: user2Authority};
+      const user3 = {stakingData: user3StakingData, tokenWallet: user3TokenWalletPK, authority: user3Authority};
+
+      // BadCase: recipient is not in the allow-list
+      await setDepositLimits(true, 0);
       await assert.rejects(
         async () => {
-          await govProgram.rpc.removeSponsor(
-            newSponsor1.publicKey,
+          await stakingProgram.rpc.transferShares(
+            new anchor.BN(1_000_000_000),
             {
-              accounts: {
-                governance: governanceAccount.publicKey,
-              },
-              remainingAccounts: [
-                {
-                  pubkey: user1Authority.publicKey,
-                  isSigner: true,
-                  isWritable: false,
-                },
-                {
-                  pubkey: user2Authority.publicKey,
-                  isSigner: true,
-                  isWritable: false,
-                },
-              ],
-              signers: [user1Authority, user2Authority],
+              accounts: await transferSharesAccounts(user2, user3),
+              signers: [user2Authority]
             }
           );
         },
         (err) => {
-          assert.equal(err.code, 311);
-          assert.equal(err.msg, "Not enough signers to change sponsors list");
+          assert.equal(err.code, 319);
+          assert.equal(err.msg, "Wallet is not in the allow-list");
           return true;
         }
       );
 
-      // BadCase: not enough signers (0)
+      // BadCase: recipient exceeds the user deposit cap
+      await setDepositLimits(false, 500_000_000);
       await assert.rejects(
         async () => {
-          await govProgram.rpc.removeSponsor(
-            newSponsor1.publicKey,
+          await stakingProgram.rpc.transferShares(
+            new anchor.BN(1_000_000_000),
             {
-              accounts: {
-                governance: governanceAccount.publicKey,
-              },
-              remainingAccounts: [],
-              signers: [],
+              accounts: await transferSharesAccounts(user2, user3),
+              signers: [user2Authority]
             }
           );
         },
         (err) => {
-          assert.equal(err.code, 311);
-          assert.equal(err.msg, "Not enough signers to change sponsors list");
+          assert.equal(err.code, 320);
+          assert.equal(err.msg, "User deposit cap exceeded");
           return true;
         }
       );
+      await setDepositLimits(false, 0);
 
-      // BadCase: duplicate signers
+      // User3 stake tokens locked and move the position to user1
+      const lockedStake = anchor.web3.Keypair.generate();
+      await stakingProgram.rpc.stakeLocked(
+        new anchor.BN(1_000_000_000),
+        0,
+        {
+          accounts: {
+            stakingUserData: user3StakingData.publicKey,
+            lockedStake: lockedStake.publicKey,
+            userTokenWallet: user3TokenWalletPK,
+            userTokenWalletOwner: user3Authority.publicKey,
+            stakingData: stakingAccount.publicKey,
+            allowListEntry: await allowListEntryPK(user3Authority.publicKey),
+            holdingWallet: holdingWalletPK,
+            feeCollector: holdingWalletPK,
+            stakingTokenMetadata: metadataAccount.publicKey,
+            mint: mint.publicKey,
+            mintAuthority: pdaMintAuthority,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          signers: [user3Authority, lockedStake]
+        }
+      );
+
+      // BadCase: locked shares can't be moved with transfer shares
       await assert.rejects(
         async () => {
-          await govProgram.rpc.removeSponsor(
-            newSponsor1.publicKey,
+          await stakingProgram.rpc.transferShares(
+            new anchor.BN(1_000_000_000),
             {
-              accounts: {
-                governance: governanceAccount.publicKey,
-              },
-              remainingAccounts: [
-                {
-                  pubkey: user1Authority.publicKey,
-                  isSigner: true,
-                  isWritable: false,
-                },
-                {
-                  pubkey: user2Authority.publicKey,
-                  isSigner: true,
-                  isWritable: false,
-                },
-                {
-                  pubkey: user2Authority.publicKey,
-                  isSigner: true,
-                  isWritable: false,
-                },
-              ],
-              signers: [user1Authority, user2Authority, user2Authority],
+              accounts: await transferSharesAccounts(user3, user1),
+              signers: [user3Authority]
             }
           );
-        },
-        (err) => {
-          assert.equal(err.code, 311);
-          assert.equal(err.msg, "Not enough signers to change sponsors list");
-          return true;
         }
       );
 
-      // Remove newSponsor1 from list
-      await govProgram.rpc.removeSponsor(
-        newSponsor1.publicKey,
+      let accounts = await transferSharesAccounts(user3, user1);
+      accounts.lockedStake = lockedStake.publicKey;
+      await stakingProgram.rpc.transferLockedStake(
+        {
+          accounts: accounts,
+          signers: [user3Authority]
+        }
+      );
+
+      let checkLockedStake = await stakingProgram.account.lockedStake.fetch(lockedStake.publicKey);
+      assert.ok(checkLockedStake.stakingUserData.equals(user1StakingData.publicKey));
+      let checkUser3Data = await stakingProgram.account.stakingUserData.fetch(user3StakingData.publicKey);
+      assert.equal(checkUser3Data.ownershipShare.toNumber(), 0);
+      assert.equal(checkUser3Data.tierLockedShares.toNumber(), 0);
+      let checkUser1Data = await stakingProgram.account.stakingUserData.fetch(user1StakingData.publicKey);
+      assert.equal(checkUser1Data.ownershipShare.toNumber(), 2_000_000_000);
+      assert.equal(checkUser1Data.tierLockedShares.toNumber(), 2_000_000_000);
+
+      // Recipient releases the position and moves the shares back with transfer shares
+      sleep(5000);
+      await stakingProgram.rpc.releaseLockedStake(
         {
           accounts: {
-            governance: governanceAccount.publicKey,
+            stakingUserData: user1StakingData.publicKey,
+            lockedStake: lockedStake.publicKey,
+            userTokenWallet: user1TokenWalletPK,
+            userTokenWalletOwner: user1Authority.publicKey,
+            stakingData: stakingAccount.publicKey,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           },
-          remainingAccounts: [
-            {
-              pubkey: user1Authority.publicKey,
-              isSigner: true,
-              isWritable: false,
+          signers: [user1Authority]
+        }
+      );
+      await stakingProgram.rpc.transferShares(
+        new anchor.BN(1_000_000_000),
+        {
+          accounts: await transferSharesAccounts(user1, user3),
+          signers: [user1Authority]
+        }
+      );
+
+      checkUser1Data = await stakingProgram.account.stakingUserData.fetch(user1StakingData.publicKey);
+      assert.equal(checkUser1Data.ownershipShare.toNumber(), 0);
+      assert.equal(checkUser1Data.tierLockedShares.toNumber(), 0);
+      checkUser3Data = await stakingProgram.account.stakingUserData.fetch(user3StakingData.publicKey);
+      assert.equal(checkUser3Data.ownershipShare.toNumber(), 1_000_000_000);
+
+      // User3 unstake the moved shares
+      await stakingProgram.rpc.unstake(
+        new anchor.BN(1_000_000_000),
+        {
+          accounts: {
+            stakingUserData: user3StakingData.publicKey,
+            userTokenWallet: user3TokenWalletPK,
+            userTokenWalletOwner: user3Authority.publicKey,
+            stakingData: stakingAccount.publicKey,
+            holdingWallet: holdingWalletPK,
+            holdingWalletOwner: holdingWalletOwnerPK,
+            penaltyWallet: holdingWalletPK,
+            feeCollector: holdingWalletPK,
+            stakingTokenMetadata: metadataAccount.publicKey,
+            mint: mint.publicKey,
+            mintAuthority: pdaMintAuthority,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          signers: [user3Authority]
+        }
+      );
+
+      const userTokenInfo = await mint.getAccountInfo(user3TokenWalletPK);
+      assert.equal(userTokenInfo.amount, ui_amount_to_amount(user3TokenBalance, 9));
+      const checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.equal(checkStakingData.totalStaked.toNumber(), 4_500_000_000);
+      assert.equal(checkStakingData.totalShares.toNumber(), 4_500_000_000);
+    });
+
+    it('Cooldown unstake test', async () => {
+      const setCooldown = async (cooldownSeconds, owner) => {
+        await stakingProgram.rpc.setCooldown(
+          new anchor.BN(cooldownSeconds),
+          {
+            accounts: {
+              stakingData: stakingAccount.publicKey,
+              stakingOwner: owner.publicKey,
             },
-            {
-              pubkey: user2Authority.publicKey,
-              isSigner: true,
-              isWritable: false,
+            signers: [owner]
+          }
+        );
+      };
+      const requestUnstake = async (amount, pendingWithdrawal) => {
+        await stakingProgram.rpc.requestUnstake(
+          new anchor.BN(amount),
+          {
+            accounts: {
+              stakingUserData: user2StakingData.publicKey,
+              pendingWithdrawal: pendingWithdrawal.publicKey,
+              userTokenWallet: user2TokenWalletPK,
+              userTokenWalletOwner: user2Authority.publicKey,
+              stakingData: stakingAccount.publicKey,
+              holdingWallet: holdingWalletPK,
+              holdingWalletOwner: holdingWalletOwnerPK,
+              penaltyWallet: holdingWalletPK,
+              feeCollector: holdingWalletPK,
+              stakingTokenMetadata: metadataAccount.publicKey,
+              mint: mint.publicKey,
+              mintAuthority: pdaMintAuthority,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              systemProgram: anchor.web3.SystemProgram.programId,
+              rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+              clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             },
-            {
-              pubkey: user3Authority.publicKey,
-              isSigner: true,
-              isWritable: false,
+            signers: [user2Authority, pendingWithdrawal]
+          }
+        );
+      };
+      const withdraw = async (pendingWithdrawal) => {
+        await stakingProgram.rpc.withdraw(
+          {
+            accounts: {
+              stakingUserData: user2StakingData.publicKey,
+              pendingWithdrawal: pendingWithdrawal.publicKey,
+              userTokenWallet: user2TokenWalletPK,
+              userTokenWalletOwner: user2Authority.publicKey,
+              stakingData: stakingAccount.publicKey,
+              holdingWallet: holdingWalletPK,
+              holdingWalletOwner: holdingWalletOwnerPK,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             },
-          ],
-          signers: [user1Authority, user2Authority, user3Authority],
+            signers: [user2Authority]
+          }
+        );
+      };
+      const checkPending = async (userShares, totalStaked, pendingWithdrawals) => {
+        const checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+        assert.equal(checkStakingData.totalStaked.toNumber(), totalStaked);
+        assert.equal(checkStakingData.totalShares.toNumber(), totalStaked);
+        assert.equal(checkStakingData.pendingWithdrawals.toNumber(), pendingWithdrawals);
+        const checkUser2Data = await stakingProgram.account.stakingUserData.fetch(user2StakingData.publicKey);
+        assert.equal(checkUser2Data.ownershipShare.toNumber(), userShares);
+        assert.equal(checkUser2Data.pendingWithdrawals.toNumber(), pendingWithdrawals);
+      };
+
+      // BadCase: cooldown longer than 30 days
+      await assert.rejects(
+        async () => {
+          await setCooldown(30 * SECONDS_PER_DAY + 1, stakingOwner);
+        },
+        (err) => {
+          assert.equal(err.code, 308);
+          assert.equal(err.msg, "Invalid cooldown");
+          return true;
         }
       );
 
-      sponsors = [user1Authority.publicKey, user2Authority.publicKey, user3Authority.publicKey];
-      checkGovData = await govProgram.account.governance.fetch(governanceAccount.publicKey);
-      assert.equal(checkGovData.sponsors.length, 3);
-      assert.deepStrictEqual(checkGovData.sponsors, sponsors);
+      // BadCase: only owner can set the cooldown
+      await assert.rejects(
+        async () => {
+          await setCooldown(3, user2Authority);
+        },
+        (err) => {
+          assert.equal(err.code, 143);
+          return true;
+        }
+      );
 
-      // BadCase: less than 3 sponsors
+      await setCooldown(3, stakingOwner);
+      let checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.equal(checkStakingData.cooldownSeconds.toNumber(), 3);
+
+      // BadCase: instant unstake is disabled by the cooldown
       await assert.rejects(
         async () => {
-          await govProgram.rpc.removeSponsor(
-            user3Authority.publicKey,
+          await stakingProgram.rpc.unstake(
+            new anchor.BN(1_000_000_000),
             {
               accounts: {
-                governance: governanceAccount.publicKey,
+                stakingUserData: user2StakingData.publicKey,
+                userTokenWallet: user2TokenWalletPK,
+                userTokenWalletOwner: user2Authority.publicKey,
+                stakingData: stakingAccount.publicKey,
+                holdingWallet: holdingWalletPK,
+                holdingWalletOwner: holdingWalletOwnerPK,
+                penaltyWallet: holdingWalletPK,
+                feeCollector: holdingWalletPK,
+                stakingTokenMetadata: metadataAccount.publicKey,
+                mint: mint.publicKey,
+                mintAuthority: pdaMintAuthority,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
               },
-              remainingAccounts: [
-                {
-                  pubkey: user1Authority.publicKey,
-                  isSigner: true,
-                  isWritable: false,
-                },
-                {
-                  pubkey: user2Authority.publicKey,
-                  isSigner: true,
-                  isWritable: false,
-                },
-              ],
-              signers: [user1Authority, user2Authority],
+              signers: [user2Authority]
             }
           );
         },
         (err) => {
           assert.equal(err.code, 309);
-          assert.equal(err.msg, "Sponsors limit exceeded");
+          assert.equal(err.msg, "Unstake cooldown is required");
           return true;
         }
       );
-    });
 
-    it('Sponsors limit list test', async () => {
-      let governanceAccount2 = anchor.web3.Keypair.generate();
-      let pda = await anchor.web3.PublicKey.findProgramAddress(
-        [
-          Buffer.from(TREASURY_PREFIX),
-          govProgram.programId.toBuffer(),
-          governanceAccount2.publicKey.toBuffer()
-        ],
-        govProgram.programId
+      // BadCase: zero amount
+      await assert.rejects(
+        async () => {
+          await requestUnstake(0, anchor.web3.Keypair.generate());
+        },
+        (err) => {
+          assert.equal(err.code, 302);
+          assert.equal(err.msg, "Invalid amount");
+          return true;
+        }
       );
-      treasuryOwnerPK2 = pda[0];
-      treasuryOwnerBump2 = pda[1];
-      treasuryPK2 = await treasuryMint.createAccount(treasuryOwnerPK2);
 
-      sponsors = [
-        anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(),
-        anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(),
-        anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(),
-        anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(),
-        anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(),
-      ];
-      sponsorsPubkeys = [];
-      for (i = 0; i < sponsors.length; i++) {
-        sponsorsPubkeys.push(sponsors[i].publicKey);
-      }
-      await govProgram.rpc.initializeGovernance(
-        stakingAccount.publicKey,
-        new anchor.BN(approvalFixedPeriodInSeconds),
-        minApprovalPercent,
-        new anchor.BN(minStakeToPropose),
-        minVoteParticipationPercent,
-        new anchor.BN(paymentPeriodSec),
-        treasuryOwnerBump2,
-        sponsorsPubkeys,
+      // BadCase: more than the user shares
+      await assert.rejects(
+        async () => {
+          await requestUnstake(4_500_000_001, anchor.web3.Keypair.generate());
+        },
+        (err) => {
+          return true;
+        }
+      );
+      await checkPending(4_500_000_000, 4_500_000_000, 0);
+
+      // Requested amount leaves the pool and waits for the cooldown
+      const pendingWithdrawal1 = anchor.web3.Keypair.generate();
+      await requestUnstake(1_000_000_000, pendingWithdrawal1);
+      await checkPending(3_500_000_000, 3_500_000_000, 1_000_000_000);
+      const checkPendingWithdrawal = await stakingProgram.account.pendingWithdrawal.fetch(pendingWithdrawal1.publicKey);
+      assert.ok(checkPendingWithdrawal.stakingUserData.equals(user2StakingData.publicKey));
+      assert.equal(checkPendingWithdrawal.amount.toNumber(), 1_000_000_000);
+      assert.ok(checkPendingWithdrawal.unlockTimestamp.toNumber() > Math.floor(Date.now() / 1000) - 60);
+
+      // BadCase: withdraw before the cooldown elapses
+      await assert.rejects(
+        async () => {
+          await withdraw(pendingWithdrawal1);
+        },
+        (err) => {
+          assert.equal(err.code, 310);
+          assert.equal(err.msg, "Unstake cooldown is not elapsed");
+          return true;
+        }
+      );
+
+      // Cancel stakes the pending amount back
+      await stakingProgram.rpc.cancelUnstake(
         {
           accounts: {
-            governance: governanceAccount2.publicKey,
-            payer: payer.publicKey,
-            treasuryOwner: treasuryOwnerPK2,
-            systemProgram: anchor.web3.SystemProgram.programId,
-            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            stakingUserData: user2StakingData.publicKey,
+            pendingWithdrawal: pendingWithdrawal1.publicKey,
+            userTokenWallet: user2TokenWalletPK,
+            userTokenWalletOwner: user2Authority.publicKey,
+            stakingData: stakingAccount.publicKey,
+            allowListEntry: await allowListEntryPK(user2Authority.publicKey),
+            holdingWallet: holdingWalletPK,
+            stakingTokenMetadata: metadataAccount.publicKey,
+            mint: mint.publicKey,
+            mintAuthority: pdaMintAuthority,
+            tokenProgram: TOKEN_PROGRAM_ID,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           },
-          signers: [governanceAccount2]
+          signers: [user2Authority]
+        }
+      );
+      await checkPending(4_500_000_000, 4_500_000_000, 0);
+      assert.equal(await provider.connection.getAccountInfo(pendingWithdrawal1.publicKey), null);
+
+      // Withdraw after the cooldown
+      const userTokenAmount = (await mint.getAccountInfo(user2TokenWalletPK)).amount.toNumber();
+      const pendingWithdrawal2 = anchor.web3.Keypair.generate();
+      await requestUnstake(1_000_000_000, pendingWithdrawal2);
+      sleep(5000);
+      await withdraw(pendingWithdrawal2);
+      await checkPending(3_500_000_000, 3_500_000_000, 0);
+      assert.equal(await provider.connection.getAccountInfo(pendingWithdrawal2.publicKey), null);
+      let userTokenInfo = await mint.getAccountInfo(user2TokenWalletPK);
+      assert.equal(userTokenInfo.amount.toNumber(), userTokenAmount + 1_000_000_000);
+
+      // BadCase: withdrawal is closed
+      await assert.rejects(
+        async () => {
+          await withdraw(pendingWithdrawal2);
+        },
+        (err) => {
+          return true;
         }
       );
 
-      let remainingAccounts = [];
-      let signers = [];
-      for (i = 0; i < 10; i++) {
-        remainingAccounts.push({ pubkey: sponsorsPubkeys[i], isSigner: true, isWritable: false });
-        signers.push(sponsors[i]);
-      }
-
-      // Added the 16th sponsor
-      newSponsor = anchor.web3.Keypair.generate();
-      await govProgram.rpc.addSponsor(
-        newSponsor.publicKey,
+      // User2 stakes the amount back without the cooldown
+      await setCooldown(0, stakingOwner);
+      await stakingProgram.rpc.stake(
+        new anchor.BN(1_000_000_000),
         {
           accounts: {
-            governance: governanceAccount2.publicKey,
+            stakingUserData: user2StakingData.publicKey,
+            userTokenWallet: user2TokenWalletPK,
+            userTokenWalletOwner: user2Authority.publicKey,
+            stakingData: stakingAccount.publicKey,
+            allowListEntry: await allowListEntryPK(user2Authority.publicKey),
+            holdingWallet: holdingWalletPK,
+            feeCollector: holdingWalletPK,
+            stakingTokenMetadata: metadataAccount.publicKey,
+            mint: mint.publicKey,
+            mintAuthority: pdaMintAuthority,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           },
-          remainingAccounts: remainingAccounts,
-          signers: signers,
+          signers: [user2Authority]
         }
       );
-
-      checkSponsors = sponsorsPubkeys;
-      checkSponsors.push(newSponsor.publicKey);
-      checkGovData = await govProgram.account.governance.fetch(governanceAccount2.publicKey);
-      assert.equal(checkGovData.sponsors.length, 16);
-      assert.deepStrictEqual(checkGovData.sponsors, checkSponsors);
+      await checkPending(4_500_000_000, 4_500_000_000, 0);
     });
 
-    let treasuryPK = null;
-    let treasuryStatsPK = null;
-
-    it('Initialize treasury stats test', async () => {
-      treasuryPK = await treasuryMint.createAccount(treasuryOwnerPK);
-
-      let pda = await anchor.web3.PublicKey.findProgramAddress(
+    it('Pause test', async () => {
+      const guardian = anchor.web3.Keypair.generate();
+      const pause = async (level, authority) => {
+        await stakingProgram.rpc.pause(
+          level,
+          {
+            accounts: {
+              stakingData: stakingAccount.publicKey,
+              authority: authority.publicKey,
+            },
+            signers: [authority]
+          }
+        );
+      };
+      const unpause = async (authority) => {
+        await stakingProgram.rpc.unpause(
+          {
+            accounts: {
+              stakingData: stakingAccount.publicKey,
+              authority: authority.publicKey,
+            },
+            signers: [authority]
+          }
+        );
+      };
+      const stake = async (amount) => {
+        await stakingProgram.rpc.stake(
+          new anchor.BN(amount),
+          {
+            accounts: {
+              stakingUserData: user2StakingData.publicKey,
+              userTokenWallet: user2TokenWalletPK,
+              userTokenWalletOwner: user2Authority.publicKey,
+              stakingData: stakingAccount.publicKey,
+              allowListEntry: await allowListEntryPK(user2Authority.publicKey),
+              holdingWallet: holdingWalletPK,
+              feeCollector: holdingWalletPK,
+              stakingTokenMetadata: metadataAccount.publicKey,
+              mint: mint.publicKey,
+              mintAuthority: pdaMintAuthority,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            },
+            signers: [user2Authority]
+          }
+        );
+      };
+      const unstake = async (amount) => {
+        await stakingProgram.rpc.unstake(
+          new anchor.BN(amount),
+          {
+            accounts: {
+              stakingUserData: user2StakingData.publicKey,
+              userTokenWallet: user2TokenWalletPK,
+              userTokenWalletOwner: user2Authority.publicKey,
+              stakingData: stakingAccount.publicKey,
+              holdingWallet: holdingWalletPK,
+              holdingWalletOwner: holdingWalletOwnerPK,
+              penaltyWallet: holdingWalletPK,
+              feeCollector: holdingWalletPK,
+              stakingTokenMetadata: metadataAccount.publicKey,
+              mint: mint.publicKey,
+              mintAuthority: pdaMintAuthority,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            },
+            signers: [user2Authority]
+          }
+        );
+      };
+      const rejectsPaused = async (call) => {
+        await assert.rejects(
+          call,
+          (err) => {
+            assert.equal(err.code, 311);
+            assert.equal(err.msg, "Staking is paused");
+            return true;
+          }
+        );
+      };
+
+      // BadCase: only owner can set the guardian
+      await assert.rejects(
+        async () => {
+          await stakingProgram.rpc.setGuardian(
+            guardian.publicKey,
+            {
+              accounts: {
+                stakingData: stakingAccount.publicKey,
+                stakingOwner: guardian.publicKey,
+              },
+              signers: [guardian]
+            }
+          );
+        },
+        (err) => {
+          assert.equal(err.code, 143);
+          return true;
+        }
+      );
+
+      await stakingProgram.rpc.setGuardian(
+        guardian.publicKey,
+        {
+          accounts: {
+            stakingData: stakingAccount.publicKey,
+            stakingOwner: stakingOwner.publicKey,
+          },
+          signers: [stakingOwner]
+        }
+      );
+      let checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.ok(checkStakingData.guardian.equals(guardian.publicKey));
+
+      // BadCase: neither owner nor guardian
+      await assert.rejects(
+        async () => {
+          await pause({ full: {} }, user2Authority);
+        },
+        (err) => {
+          assert.equal(err.code, 143);
+          return true;
+        }
+      );
+
+      // BadCase: unpaused is not a pause level
+      await assert.rejects(
+        async () => {
+          await pause({ unpaused: {} }, guardian);
+        },
+        (err) => {
+          return true;
+        }
+      );
+
+      // Unstake-only level blocks the deposits only
+      await pause({ unstakeOnly: {} }, guardian);
+      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.ok(checkStakingData.pauseLevel.unstakeOnly);
+      await rejectsPaused(async () => {
+        await stake(1_000_000_000);
+      });
+      await unstake(1_000_000_000);
+
+      // Full level blocks everything
+      await pause({ full: {} }, stakingOwner);
+      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.ok(checkStakingData.pauseLevel.full);
+      await rejectsPaused(async () => {
+        await stake(1_000_000_000);
+      });
+      await rejectsPaused(async () => {
+        await unstake(1_000_000_000);
+      });
+
+      // BadCase: neither owner nor guardian
+      await assert.rejects(
+        async () => {
+          await unpause(user2Authority);
+        },
+        (err) => {
+          assert.equal(err.code, 143);
+          return true;
+        }
+      );
+
+      await unpause(guardian);
+      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.ok(checkStakingData.pauseLevel.unpaused);
+      await stake(1_000_000_000);
+      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.equal(checkStakingData.totalStaked.toNumber(), 4_500_000_000);
+      assert.equal(checkStakingData.totalShares.toNumber(), 4_500_000_000);
+    });
+
+    it('Ownership transfer test', async () => {
+      const newOwner = anchor.web3.Keypair.generate();
+      const proposeOwner = async (stakingData, proposed, owner) => {
+        await stakingProgram.rpc.proposeOwner(
+          proposed,
+          {
+            accounts: {
+              stakingData: stakingData,
+              stakingOwner: owner.publicKey,
+            },
+            signers: [owner]
+          }
+        );
+      };
+      const acceptOwner = async (stakingData, pendingOwner) => {
+        await stakingProgram.rpc.acceptOwner(
+          {
+            accounts: {
+              stakingData: stakingData,
+              pendingOwner: pendingOwner.publicKey,
+            },
+            signers: [pendingOwner]
+          }
+        );
+      };
+      const rejectsConstraint = async (call) => {
+        await assert.rejects(
+          call,
+          (err) => {
+            assert.equal(err.code, 143);
+            return true;
+          }
+        );
+      };
+
+      // BadCase: only owner can propose
+      await rejectsConstraint(async () => {
+        await proposeOwner(stakingAccount.publicKey, newOwner.publicKey, newOwner);
+      });
+
+      // Proposal is cancelled with the default pubkey
+      await proposeOwner(stakingAccount.publicKey, newOwner.publicKey, stakingOwner);
+      let checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.ok(checkStakingData.pendingOwner.equals(newOwner.publicKey));
+      await proposeOwner(stakingAccount.publicKey, new anchor.web3.PublicKey(0), stakingOwner);
+      await rejectsConstraint(async () => {
+        await acceptOwner(stakingAccount.publicKey, newOwner);
+      });
+
+      await proposeOwner(stakingAccount.publicKey, newOwner.publicKey, stakingOwner);
+
+      // BadCase: only pending owner can accept
+      await rejectsConstraint(async () => {
+        await acceptOwner(stakingAccount.publicKey, user1Authority);
+      });
+
+      // Owner is not changed until the proposal is accepted
+      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.ok(checkStakingData.owner.equals(stakingOwner.publicKey));
+
+      await acceptOwner(stakingAccount.publicKey, newOwner);
+      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.ok(checkStakingData.owner.equals(newOwner.publicKey));
+      assert.ok(checkStakingData.pendingOwner.equals(new anchor.web3.PublicKey(0)));
+
+      // BadCase: previous owner lost the owner instructions
+      await rejectsConstraint(async () => {
+        await stakingProgram.rpc.setCooldown(
+          new anchor.BN(0),
+          {
+            accounts: {
+              stakingData: stakingAccount.publicKey,
+              stakingOwner: stakingOwner.publicKey,
+            },
+            signers: [stakingOwner]
+          }
+        );
+      });
+
+      // Ownership is transferred back
+      await proposeOwner(stakingAccount.publicKey, stakingOwner.publicKey, newOwner);
+      await acceptOwner(stakingAccount.publicKey, stakingOwner);
+      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.ok(checkStakingData.owner.equals(stakingOwner.publicKey));
+
+      // Renounce the ownership of a separate staking pool
+      const renouncedPool = anchor.web3.Keypair.generate();
+      let pda = await anchor.web3.PublicKey.findProgramAddress(
+        [stakingProgram.programId.toBuffer(), renouncedPool.publicKey.toBuffer()],
+        stakingProgram.programId
+      );
+      const renouncedHoldingWalletOwnerPK = pda[0];
+      const renouncedHoldingBump = pda[1];
+      const renouncedHoldingWalletPK = await mint.createAccount(renouncedHoldingWalletOwnerPK);
+      pda = await anchor.web3.PublicKey.findProgramAddress(
         [
-          Buffer.from(TREASURY_STATS_PREFIX),
-          govProgram.programId.toBuffer(),
-          governanceAccount.publicKey.toBuffer(),
-          treasuryPK.toBuffer(),
+          stakingProgram.programId.toBuffer(),
+          renouncedPool.publicKey.toBuffer(),
+          metadataAccount.publicKey.toBuffer()
         ],
-        govProgram.programId
+        stakingProgram.programId
+      );
+      await stakingProgram.rpc.initializeStaking(
+        metadataAccount.publicKey,
+        new anchor.BN(currentTimestamp),
+        new anchor.BN(365),
+        new anchor.BN(188),
+        new anchor.BN(cap),
+        renouncedHoldingBump,
+        pda[1],
+        {
+          accounts: {
+            stakingData: renouncedPool.publicKey,
+            stakingOwner: stakingOwner.publicKey,
+            holdingWallet: renouncedHoldingWalletPK,
+            holdingWalletOwner: renouncedHoldingWalletOwnerPK,
+            stakingTokenMetadata: metadataAccount.publicKey,
+            stakingTokenMint: mint.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+          },
+          signers: [renouncedPool, stakingOwner]
+        }
+      );
+      await proposeOwner(renouncedPool.publicKey, newOwner.publicKey, stakingOwner);
+
+      // BadCase: only owner can renounce
+      await rejectsConstraint(async () => {
+        await stakingProgram.rpc.renounceOwner(
+          {
+            accounts: {
+              stakingData: renouncedPool.publicKey,
+              stakingOwner: newOwner.publicKey,
+            },
+            signers: [newOwner]
+          }
+        );
+      });
+
+      await stakingProgram.rpc.renounceOwner(
+        {
+          accounts: {
+            stakingData: renouncedPool.publicKey,
+            stakingOwner: stakingOwner.publicKey,
+          },
+          signers: [stakingOwner]
+        }
+      );
+      checkStakingData = await stakingProgram.account.stakingData.fetch(renouncedPool.publicKey);
+      assert.ok(checkStakingData.owner.equals(new anchor.web3.PublicKey(0)));
+      assert.ok(checkStakingData.pendingOwner.equals(new anchor.web3.PublicKey(0)));
+
+      // BadCase: pending proposal is dropped and the owner instructions are disabled
+      await rejectsConstraint(async () => {
+        await acceptOwner(renouncedPool.publicKey, newOwner);
+      });
+      await rejectsConstraint(async () => {
+        await proposeOwner(renouncedPool.publicKey, stakingOwner.publicKey, stakingOwner);
+      });
+    });
+
+    it('Slash test', async () => {
+      const slasher = anchor.web3.Keypair.generate();
+      const setSlasher = async (newSlasher, owner) => {
+        await stakingProgram.rpc.setSlasher(
+          newSlasher,
+          {
+            accounts: {
+              stakingData: stakingAccount.publicKey,
+              stakingOwner: owner.publicKey,
+            },
+            signers: [owner]
+          }
+        );
+      };
+      const slash = async (slashBps, stakingUserData, slashWallet, authority) => {
+        await stakingProgram.rpc.slash(
+          new anchor.BN(slashBps),
+          1,
+          {
+            accounts: {
+              stakingData: stakingAccount.publicKey,
+              slasher: authority.publicKey,
+              stakingUserData: stakingUserData,
+              holdingWallet: holdingWalletPK,
+              holdingWalletOwner: holdingWalletOwnerPK,
+              slashWallet: slashWallet,
+              stakingTokenMetadata: metadataAccount.publicKey,
+              mint: mint.publicKey,
+              mintAuthority: pdaMintAuthority,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            },
+            signers: [authority]
+          }
+        );
+      };
+      const rejectsSlash = async (call, code) => {
+        await assert.rejects(
+          call,
+          (err) => {
+            assert.equal(err.code, code);
+            return true;
+          }
+        );
+      };
+      const slashWalletPK = await mint.createAccount(payer.publicKey);
+
+      // BadCase: no slasher is set
+      await rejectsSlash(async () => {
+        await slash(1000, user2StakingData.publicKey, slashWalletPK, stakingOwner);
+      }, 143);
+
+      // BadCase: only owner can set the slasher
+      await rejectsSlash(async () => {
+        await setSlasher(slasher.publicKey, slasher);
+      }, 143);
+
+      await setSlasher(slasher.publicKey, stakingOwner);
+      let checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.ok(checkStakingData.slasher.equals(slasher.publicKey));
+
+      // BadCase: not the slasher
+      await rejectsSlash(async () => {
+        await slash(1000, user2StakingData.publicKey, slashWalletPK, stakingOwner);
+      }, 143);
+
+      // BadCase: slash out of range
+      await rejectsSlash(async () => {
+        await slash(0, user2StakingData.publicKey, slashWalletPK, slasher);
+      }, 326);
+      await rejectsSlash(async () => {
+        await slash(10001, user2StakingData.publicKey, slashWalletPK, slasher);
+      }, 326);
+
+      // BadCase: user1 has no shares
+      await rejectsSlash(async () => {
+        await slash(1000, user1StakingData.publicKey, slashWalletPK, slasher);
+      }, 326);
+
+      // Slashed tokens are moved to the slash wallet
+      let holdingAmount = (await mint.getAccountInfo(holdingWalletPK)).amount.toNumber();
+      await slash(1000, user2StakingData.publicKey, slashWalletPK, slasher);
+      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.equal(checkStakingData.totalStaked.toNumber(), 4_050_000_000);
+      assert.equal(checkStakingData.totalShares.toNumber(), 4_050_000_000);
+      let checkUser2Data = await stakingProgram.account.stakingUserData.fetch(user2StakingData.publicKey);
+      assert.equal(checkUser2Data.ownershipShare.toNumber(), 4_050_000_000);
+      assert.equal((await mint.getAccountInfo(slashWalletPK)).amount.toNumber(), 450_000_000);
+      assert.equal((await mint.getAccountInfo(holdingWalletPK)).amount.toNumber(), holdingAmount - 450_000_000);
+
+      // Slashed tokens are burned when the slash wallet is the holding wallet
+      holdingAmount = (await mint.getAccountInfo(holdingWalletPK)).amount.toNumber();
+      const supply = (await mint.getMintInfo()).supply.toNumber();
+      await slash(100, user2StakingData.publicKey, holdingWalletPK, slasher);
+      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.equal(checkStakingData.totalStaked.toNumber(), 4_009_500_000);
+      assert.equal(checkStakingData.totalShares.toNumber(), 4_009_500_000);
+      checkUser2Data = await stakingProgram.account.stakingUserData.fetch(user2StakingData.publicKey);
+      assert.equal(checkUser2Data.ownershipShare.toNumber(), 4_009_500_000);
+      assert.equal((await mint.getAccountInfo(holdingWalletPK)).amount.toNumber(), holdingAmount - 40_500_000);
+      assert.equal((await mint.getMintInfo()).supply.toNumber(), supply - 40_500_000);
+
+      // User2 stakes the slashed amount back
+      await setSlasher(new anchor.web3.PublicKey(0), stakingOwner);
+      await stakingProgram.rpc.stake(
+        new anchor.BN(490_500_000),
+        {
+          accounts: {
+            stakingUserData: user2StakingData.publicKey,
+            userTokenWallet: user2TokenWalletPK,
+            userTokenWalletOwner: user2Authority.publicKey,
+            stakingData: stakingAccount.publicKey,
+            allowListEntry: await allowListEntryPK(user2Authority.publicKey),
+            holdingWallet: holdingWalletPK,
+            feeCollector: holdingWalletPK,
+            stakingTokenMetadata: metadataAccount.publicKey,
+            mint: mint.publicKey,
+            mintAuthority: pdaMintAuthority,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          signers: [user2Authority]
+        }
+      );
+      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.equal(checkStakingData.totalStaked.toNumber(), 4_500_000_000);
+      assert.equal(checkStakingData.totalShares.toNumber(), 4_500_000_000);
+    });
+
+    it('Reward stream test', async () => {
+      const emissionPerSecond = 1_000;
+      const vaultAmount = 10_000;
+      const rewardMint = await Token.createMint(
+        provider.connection,
+        payer,
+        payer.publicKey,
+        null,
+        0,
+        TOKEN_PROGRAM_ID
+      );
+      const rewardStream = anchor.web3.Keypair.generate();
+      let pda = await anchor.web3.PublicKey.findProgramAddress(
+        [stakingProgram.programId.toBuffer(), rewardStream.publicKey.toBuffer()],
+        stakingProgram.programId
+      );
+      const vaultOwnerPK = pda[0];
+      const vaultBump = pda[1];
+      const vaultPK = await rewardMint.createAccount(vaultOwnerPK);
+      await rewardMint.mintTo(vaultPK, payer, [], vaultAmount);
+
+      await stakingProgram.rpc.addRewardStream(
+        new anchor.BN(emissionPerSecond),
+        new anchor.BN(0),
+        new anchor.BN(Math.floor(Date.now() / 1000) + 5),
+        vaultBump,
+        {
+          accounts: {
+            stakingData: stakingAccount.publicKey,
+            stakingOwner: stakingOwner.publicKey,
+            rewardStream: rewardStream.publicKey,
+            sponsor: payer.publicKey,
+            vault: vaultPK,
+            vaultOwner: vaultOwnerPK,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          signers: [stakingOwner, rewardStream]
+        }
+      );
+      let checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.deepStrictEqual(checkStakingData.rewardStreams, [rewardStream.publicKey]);
+      const checkRewardStream = await stakingProgram.account.rewardStream.fetch(rewardStream.publicKey);
+      assert.ok(checkRewardStream.sponsor.equals(payer.publicKey));
+      assert.ok(checkRewardStream.vault.equals(vaultPK));
+
+      // User2 holds all the shares
+      pda = await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from(USER_REWARD_PREFIX),
+          stakingProgram.programId.toBuffer(),
+          rewardStream.publicKey.toBuffer(),
+          user2StakingData.publicKey.toBuffer(),
+        ],
+        stakingProgram.programId
+      );
+      const user2RewardPK = pda[0];
+      await stakingProgram.rpc.initUserReward(
+        pda[1],
+        {
+          accounts: {
+            userReward: user2RewardPK,
+            rewardStream: rewardStream.publicKey,
+            stakingUserData: user2StakingData.publicKey,
+            payer: payer.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+          },
+        }
+      );
+
+      // User3 without shares closes the staking data along with the user reward
+      pda = await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from(USER_REWARD_PREFIX),
+          stakingProgram.programId.toBuffer(),
+          rewardStream.publicKey.toBuffer(),
+          user3StakingData.publicKey.toBuffer(),
+        ],
+        stakingProgram.programId
+      );
+      const user3RewardPK = pda[0];
+      await stakingProgram.rpc.initUserReward(
+        pda[1],
+        {
+          accounts: {
+            userReward: user3RewardPK,
+            rewardStream: rewardStream.publicKey,
+            stakingUserData: user3StakingData.publicKey,
+            payer: payer.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+          },
+        }
+      );
+      const closeStakingUser = async (user, rewardAccounts) => {
+        await stakingProgram.rpc.closeStakingUser(
+          {
+            accounts: {
+              stakingUserData: user.stakingData.publicKey,
+              userTokenWallet: user.tokenWalletPK,
+              userTokenWalletOwner: user.authority.publicKey,
+              stakingData: stakingAccount.publicKey,
+              clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            },
+            remainingAccounts: rewardAccounts.map((pubkey) => ({
+              pubkey: pubkey,
+              isWritable: true,
+              isSigner: false,
+            })),
+            signers: [user.authority]
+          }
+        );
+      };
+      const user2 = {
+        stakingData: user2StakingData,
+        tokenWalletPK: user2TokenWalletPK,
+        authority: user2Authority,
+      };
+      const user3 = {
+        stakingData: user3StakingData,
+        tokenWalletPK: user3TokenWalletPK,
+        authority: user3Authority,
+      };
+
+      // BadCase: user2 still has shares
+      await assert.rejects(
+        async () => {
+          await closeStakingUser(user2, [rewardStream.publicKey, user2RewardPK]);
+        },
+        (err) => {
+          assert.equal(err.code, 322);
+          assert.equal(err.msg, "Staking user still has shares, pending withdrawals or rewards");
+          return true;
+        }
+      );
+
+      // BadCase: reward stream accounts are missed
+      await assert.rejects(
+        async () => {
+          await closeStakingUser(user3, []);
+        },
+        (err) => {
+          assert.equal(err.code, 315);
+          assert.equal(err.msg, "Reward stream accounts don't match the staking pool");
+          return true;
+        }
+      );
+
+      const user3Lamports = await provider.connection.getBalance(user3Authority.publicKey);
+      const rentLamports =
+        (await provider.connection.getBalance(user3StakingData.publicKey)) +
+        (await provider.connection.getBalance(user3RewardPK));
+      await closeStakingUser(user3, [rewardStream.publicKey, user3RewardPK]);
+      assert.equal(await provider.connection.getAccountInfo(user3StakingData.publicKey), null);
+      assert.equal(await provider.connection.getAccountInfo(user3RewardPK), null);
+      assert.ok(await provider.connection.getBalance(user3Authority.publicKey) > user3Lamports + rentLamports - 10_000);
+
+      const sponsorRewardWalletPK = await rewardMint.createAccount(payer.publicKey);
+      const removeRewardStream = async (owner) => {
+        await stakingProgram.rpc.removeRewardStream(
+          {
+            accounts: {
+              stakingData: stakingAccount.publicKey,
+              stakingOwner: owner.publicKey,
+              rewardStream: rewardStream.publicKey,
+              sponsor: payer.publicKey,
+              vault: vaultPK,
+              vaultOwner: vaultOwnerPK,
+              sponsorRewardWallet: sponsorRewardWalletPK,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            },
+            signers: [owner]
+          }
+        );
+      };
+
+      // BadCase: stream still emits
+      await assert.rejects(
+        async () => {
+          await removeRewardStream(stakingOwner);
+        },
+        (err) => {
+          assert.equal(err.code, 314);
+          assert.equal(err.msg, "Invalid reward stream");
+          return true;
+        }
+      );
+
+      sleep(6000);
+
+      // BadCase: only owner can remove the stream
+      await assert.rejects(
+        async () => {
+          await removeRewardStream(user1Authority);
+        },
+        (err) => {
+          assert.equal(err.code, 143);
+          return true;
+        }
+      );
+
+      // Slot is freed and only the rewards not owed to users are returned to the sponsor
+      await removeRewardStream(stakingOwner);
+      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.deepStrictEqual(checkStakingData.rewardStreams, []);
+      const emitted = emissionPerSecond *
+        (checkRewardStream.endTimestamp.toNumber() - checkRewardStream.startTimestamp.toNumber());
+      const removedRewardStream = await stakingProgram.account.rewardStream.fetch(rewardStream.publicKey);
+      assert.equal(removedRewardStream.distributedRewards.toNumber(), emitted);
+      assert.equal(removedRewardStream.claimedRewards.toNumber(), 0);
+      assert.equal((await rewardMint.getAccountInfo(vaultPK)).amount.toNumber(), emitted);
+      assert.equal(
+        (await rewardMint.getAccountInfo(sponsorRewardWalletPK)).amount.toNumber(),
+        vaultAmount - emitted
+      );
+
+      // BadCase: stream is already removed
+      await assert.rejects(
+        async () => {
+          await removeRewardStream(stakingOwner);
+        },
+        (err) => {
+          assert.equal(err.code, 314);
+          assert.equal(err.msg, "Invalid reward stream");
+          return true;
+        }
+      );
+
+      const closeUserReward = async () => {
+        await stakingProgram.rpc.closeUserReward(
+          {
+            accounts: {
+              stakingData: stakingAccount.publicKey,
+              stakingUserData: user2StakingData.publicKey,
+              userTokenWallet: user2TokenWalletPK,
+              userTokenWalletOwner: user2Authority.publicKey,
+              rewardStream: rewardStream.publicKey,
+              userReward: user2RewardPK,
+            },
+            signers: [user2Authority]
+          }
+        );
+      };
+
+      // BadCase: user2 has rewards to claim
+      await assert.rejects(
+        async () => {
+          await closeUserReward();
+        },
+        (err) => {
+          assert.equal(err.code, 322);
+          return true;
+        }
+      );
+
+      // User2 claims all emissions of the removed stream
+      const user2RewardWalletPK = await rewardMint.createAccount(user2Authority.publicKey);
+      await stakingProgram.rpc.claimRewards(
+        {
+          accounts: {
+            stakingData: stakingAccount.publicKey,
+            stakingUserData: user2StakingData.publicKey,
+            userTokenWallet: user2TokenWalletPK,
+            userTokenWalletOwner: user2Authority.publicKey,
+            rewardStream: rewardStream.publicKey,
+            userReward: user2RewardPK,
+            vault: vaultPK,
+            vaultOwner: vaultOwnerPK,
+            userRewardWallet: user2RewardWalletPK,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          signers: [user2Authority]
+        }
+      );
+      const claimed = (await rewardMint.getAccountInfo(user2RewardWalletPK)).amount.toNumber();
+      assert.ok(claimed <= emitted && claimed >= emitted - 1);
+      assert.equal((await rewardMint.getAccountInfo(vaultPK)).amount.toNumber(), emitted - claimed);
+
+      const user2Lamports = await provider.connection.getBalance(user2Authority.publicKey);
+      await closeUserReward();
+      assert.equal(await provider.connection.getAccountInfo(user2RewardPK), null);
+      assert.ok(await provider.connection.getBalance(user2Authority.publicKey) > user2Lamports);
+    });
+
+    it('Reward reserve test', async () => {
+      const setRewardMode = async (rewardMode, stakingData) => {
+        await stakingProgram.rpc.setRewardMode(
+          rewardMode,
+          {
+            accounts: {
+              stakingData: stakingData,
+              stakingOwner: stakingOwner.publicKey,
+            },
+            signers: [stakingOwner]
+          }
+        );
+      };
+      const fundRewardReserve = async (amount, stakingData, holdingWallet) => {
+        await stakingProgram.rpc.fundRewardReserve(
+          new anchor.BN(amount),
+          {
+            accounts: {
+              stakingData: stakingData,
+              holdingWallet: holdingWallet,
+              funderWallet: user3TokenWalletPK,
+              funder: user3Authority.publicKey,
+              tokenProgram: TOKEN_PROGRAM_ID,
+            },
+            signers: [user3Authority]
+          }
+        );
+      };
+      const withdrawRewardReserve = async (amount, owner) => {
+        await stakingProgram.rpc.withdrawRewardReserve(
+          new anchor.BN(amount),
+          {
+            accounts: {
+              stakingData: stakingAccount.publicKey,
+              stakingOwner: owner.publicKey,
+              holdingWallet: holdingWalletPK,
+              holdingWalletOwner: holdingWalletOwnerPK,
+              recipientWallet: user3TokenWalletPK,
+              tokenProgram: TOKEN_PROGRAM_ID,
+            },
+            signers: [owner]
+          }
+        );
+      };
+
+      // BadCase: empty reserve
+      await assert.rejects(
+        async () => {
+          await withdrawRewardReserve(1, stakingOwner);
+        },
+        (err) => {
+          assert.equal(err.code, 302);
+          assert.equal(err.msg, "Invalid amount");
+          return true;
+        }
+      );
+
+      const holdingAmount = (await mint.getAccountInfo(holdingWalletPK)).amount.toNumber();
+      await setRewardMode({ reserve: {} }, stakingAccount.publicKey);
+      await fundRewardReserve(1_000_000_000, stakingAccount.publicKey, holdingWalletPK);
+      let checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.ok(checkStakingData.rewardMode.reserve);
+      assert.equal(checkStakingData.rewardReserve.toNumber(), 1_000_000_000);
+      assert.equal(checkStakingData.reserveExhausted, false);
+      let holdingWalletInfo = await mint.getAccountInfo(holdingWalletPK);
+      assert.equal(holdingWalletInfo.amount.toNumber(), holdingAmount + 1_000_000_000);
+
+      // BadCase: more than the reserve
+      await assert.rejects(
+        async () => {
+          await withdrawRewardReserve(1_000_000_001, stakingOwner);
+        },
+        (err) => {
+          assert.equal(err.code, 302);
+          return true;
+        }
+      );
+
+      // BadCase: only owner can withdraw the reserve
+      await assert.rejects(
+        async () => {
+          await withdrawRewardReserve(1_000_000_000, user3Authority);
+        },
+        (err) => {
+          assert.equal(err.code, 143);
+          return true;
+        }
+      );
+
+      // Unused reserve is withdrawn before switching back to mint mode
+      await withdrawRewardReserve(1_000_000_000, stakingOwner);
+      await setRewardMode({ mint: {} }, stakingAccount.publicKey);
+      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.ok(checkStakingData.rewardMode.mint);
+      assert.equal(checkStakingData.rewardReserve.toNumber(), 0);
+      holdingWalletInfo = await mint.getAccountInfo(holdingWalletPK);
+      assert.equal(holdingWalletInfo.amount.toNumber(), holdingAmount);
+      let userTokenInfo = await mint.getAccountInfo(user3TokenWalletPK);
+      assert.equal(userTokenInfo.amount, ui_amount_to_amount(user3TokenBalance, 9));
+
+      // Reserve pool started a day ago, so the next accrual pays the interest of a day
+      const reservePool = anchor.web3.Keypair.generate();
+      let pda = await anchor.web3.PublicKey.findProgramAddress(
+        [stakingProgram.programId.toBuffer(), reservePool.publicKey.toBuffer()],
+        stakingProgram.programId
+      );
+      const reserveHoldingWalletOwnerPK = pda[0];
+      const reserveHoldingBump = pda[1];
+      const reserveHoldingWalletPK = await mint.createAccount(reserveHoldingWalletOwnerPK);
+      pda = await anchor.web3.PublicKey.findProgramAddress(
+        [
+          stakingProgram.programId.toBuffer(),
+          reservePool.publicKey.toBuffer(),
+          metadataAccount.publicKey.toBuffer()
+        ],
+        stakingProgram.programId
+      );
+      const reserveMintAuthorityPK = pda[0];
+      const reserveMintAuthBump = pda[1];
+      const startingTimestamp = Math.floor(Date.now() / 1000) - SECONDS_PER_DAY + 10;
+      await stakingProgram.rpc.initializeStaking(
+        metadataAccount.publicKey,
+        new anchor.BN(startingTimestamp),
+        new anchor.BN(365),
+        new anchor.BN(188),
+        new anchor.BN(cap),
+        reserveHoldingBump,
+        reserveMintAuthBump,
+        {
+          accounts: {
+            stakingData: reservePool.publicKey,
+            stakingOwner: stakingOwner.publicKey,
+            holdingWallet: reserveHoldingWalletPK,
+            holdingWalletOwner: reserveHoldingWalletOwnerPK,
+            stakingTokenMetadata: metadataAccount.publicKey,
+            stakingTokenMint: mint.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+          },
+          signers: [reservePool, stakingOwner]
+        }
+      );
+      await setRewardMode({ reserve: {} }, reservePool.publicKey);
+      await fundRewardReserve(1, reservePool.publicKey, reserveHoldingWalletPK);
+
+      // User3 stakes to the reserve pool
+      const reserveUserData = anchor.web3.Keypair.generate();
+      pda = await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from(ALLOW_LIST_PREFIX),
+          stakingProgram.programId.toBuffer(),
+          reservePool.publicKey.toBuffer(),
+          user3Authority.publicKey.toBuffer(),
+        ],
+        stakingProgram.programId
+      );
+      const reserveAllowListEntryPK = pda[0];
+      await stakingProgram.rpc.initializeStakingUser(
+        {
+          accounts: {
+            stakingUserData: reserveUserData.publicKey,
+            userTokenWallet: user3TokenWalletPK,
+            userTokenWalletOwner: user3Authority.publicKey,
+            stakingData: reservePool.publicKey,
+            allowListEntry: reserveAllowListEntryPK,
+            stakingTokenMetadata: metadataAccount.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+          },
+          signers: [user3Authority, reserveUserData]
+        }
+      );
+      await stakingProgram.rpc.stake(
+        new anchor.BN(1_000_000_000),
+        {
+          accounts: {
+            stakingUserData: reserveUserData.publicKey,
+            userTokenWallet: user3TokenWalletPK,
+            userTokenWalletOwner: user3Authority.publicKey,
+            stakingData: reservePool.publicKey,
+            allowListEntry: reserveAllowListEntryPK,
+            holdingWallet: reserveHoldingWalletPK,
+            feeCollector: reserveHoldingWalletPK,
+            stakingTokenMetadata: metadataAccount.publicKey,
+            mint: mint.publicKey,
+            mintAuthority: reserveMintAuthorityPK,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          signers: [user3Authority]
+        }
+      );
+
+      sleep(15000);
+
+      // Interest of the day is more than the reserve
+      await stakingProgram.rpc.accrueInterest(
+        {
+          accounts: {
+            stakingData: reservePool.publicKey,
+            holdingWallet: reserveHoldingWalletPK,
+            stakingTokenMetadata: metadataAccount.publicKey,
+            mint: mint.publicKey,
+            mintAuthority: reserveMintAuthorityPK,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          }
+        }
+      );
+      checkStakingData = await stakingProgram.account.stakingData.fetch(reservePool.publicKey);
+      assert.equal(checkStakingData.reserveExhausted, true);
+      assert.equal(checkStakingData.rewardReserve.toNumber(), 0);
+      assert.equal(checkStakingData.totalStaked.toNumber(), 1_000_000_001);
+      assert.equal(checkStakingData.lastInterestAccruedTimestamp.toNumber(), startingTimestamp + SECONDS_PER_DAY);
+
+      // Funding resumes the accrual
+      await fundRewardReserve(1_000_000, reservePool.publicKey, reserveHoldingWalletPK);
+      checkStakingData = await stakingProgram.account.stakingData.fetch(reservePool.publicKey);
+      assert.equal(checkStakingData.reserveExhausted, false);
+      assert.equal(checkStakingData.rewardReserve.toNumber(), 1_000_000);
+    });
+
+    it('Accrue interest instruction call test', async () => {
+      await stakingProgram.rpc.accrueInterest(
+        {
+          accounts: {
+            stakingData: stakingAccount.publicKey,
+            holdingWallet: holdingWalletPK,
+            stakingTokenMetadata: metadataAccount.publicKey,
+            mint: mint.publicKey,
+            mintAuthority: pdaMintAuthority,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          }
+        }
+      );
+    });
+  });
+
+  // Governance tests
+  const govUser1Auth = anchor.web3.Keypair.generate();
+  const govUser2Auth = anchor.web3.Keypair.generate();
+
+  let govUser1TokenWallet = null;
+  let govUser2TokenWallet = null;
+
+  let govUser1TokenBalance = 50.0;
+  let govUser2TokenBalance = 50.0;
+  let treasuryMint = null;
+
+  let treasuryMint2 = null;
+  let govUser1TokenWallet2 = null;
+  let govUser2TokenWallet2 = null;
+
+  describe('Governance tests', () => {
+    it('Prepairing users to funding treasury', async () => {
+      treasuryMetadata = anchor.web3.Keypair.generate();
+
+      // Create gov token
+      treasuryMint = await Token.createMint(
+        provider.connection,
+        payer,
+        mintAuthority.publicKey,
+        null,
+        0,
+        TOKEN_PROGRAM_ID
+      );
+
+      // Create another token for gov
+      treasuryMint2 = await Token.createMint(
+        provider.connection,
+        payer,
+        mintAuthority.publicKey,
+        null,
+        0,
+        TOKEN_PROGRAM_ID
+      );
+
+      govUser1TokenWallet = await treasuryMint.createAccount(govUser1Auth.publicKey);
+      await treasuryMint.mintTo(
+        govUser1TokenWallet,
+        mintAuthority,
+        [],
+        ui_amount_to_amount(govUser1TokenBalance, 9)
+      );
+
+      govUser2TokenWallet = await treasuryMint.createAccount(govUser2Auth.publicKey);
+      await treasuryMint.mintTo(
+        govUser2TokenWallet,
+        mintAuthority,
+        [],
+        ui_amount_to_amount(govUser2TokenBalance, 9)
+      );
+
+      govUser1TokenWallet2 = await treasuryMint2.createAccount(govUser1Auth.publicKey);
+      await treasuryMint2.mintTo(
+        govUser1TokenWallet2,
+        mintAuthority,
+        [],
+        ui_amount_to_amount(govUser1TokenBalance, 9)
+      );
+
+      govUser2TokenWallet2 = await treasuryMint2.createAccount(govUser2Auth.publicKey);
+      await treasuryMint2.mintTo(
+        govUser2TokenWallet2,
+        mintAuthority,
+        [],
+        ui_amount_to_amount(govUser2TokenBalance, 9)
+      );
+    });
+
+    const govProgram = anchor.workspace.Governance;
+    const testLockerProgram = anchor.workspace.TestLocker;
+    let governanceAccount = null;
+    let treasuryOwnerPK = null;
+    let lockerAuthorityPK = null;
+    let lockerBump = null;
+    let testLockerAuthorityPK = null;
+    let testLockerBump = null;
+    let sponsors = null;
+
+    const approvalFixedPeriodInSeconds = 15;
+    const minApprovalPercent = 51;
+    const minStakeToPropose = 1_000_000_000;
+    const minVoteParticipationPercent = 50;
+
+    const maxProposalPaymentPercent = 50;
+    const paymentInPeriodLimitPercent = 50;
+    const paymentPeriodSec = 15;
+    const executionDelaySeconds = 0;
+
+    it('Initialize governance', async () => {
+      governanceAccount = anchor.web3.Keypair.generate();
+
+      let pda = await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from(TREASURY_PREFIX),
+          govProgram.programId.toBuffer(),
+          governanceAccount.publicKey.toBuffer()
+        ],
+        govProgram.programId
+      );
+      treasuryOwnerPK = pda[0];
+      let treasuryOwnerBump = pda[1];
+
+      // BadCase: not enough sponsors
+      sponsors = [
+        user1Authority.publicKey,
+        user2Authority.publicKey,
+      ];
+      await assert.rejects(
+        async () => {
+          await govProgram.rpc.initializeGovernance(
+            stakingAccount.publicKey,
+            new anchor.BN(approvalFixedPeriodInSeconds),
+            minApprovalPercent,
+            new anchor.BN(minStakeToPropose),
+            minVoteParticipationPercent,
+            new anchor.BN(paymentPeriodSec),
+            new anchor.BN(executionDelaySeconds),
+            treasuryOwnerBump,
+            sponsors,
+            {
+              accounts: {
+                governance: governanceAccount.publicKey,
+                payer: payer.publicKey,
+                treasuryOwner: treasuryOwnerPK,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+                clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+              },
+              signers: [governanceAccount]
+            }
+          );
+        },
+        (err) => {
+          assert.equal(err.code, 308);
+          assert.equal(err.msg, "Not enough sponsors");
+          return true;
+        }
+      );
+
+      // BadCase: too much sponsors (17)
+      sponsors = [
+        user1Authority.publicKey, user2Authority.publicKey, user1Authority.publicKey,
+        user2Authority.publicKey, user1Authority.publicKey, user2Authority.publicKey,
+        user1Authority.publicKey, user2Authority.publicKey, user1Authority.publicKey,
+        user2Authority.publicKey, user1Authority.publicKey, user2Authority.publicKey,
+        user1Authority.publicKey, user2Authority.publicKey, user1Authority.publicKey,
+        user2Authority.publicKey, user2Authority.publicKey,
+      ];
+      await assert.rejects(
+        async () => {
+          await govProgram.rpc.initializeGovernance(
+            stakingAccount.publicKey,
+            new anchor.BN(approvalFixedPeriodInSeconds),
+            minApprovalPercent,
+            new anchor.BN(minStakeToPropose),
+            minVoteParticipationPercent,
+            new anchor.BN(paymentPeriodSec),
+            new anchor.BN(executionDelaySeconds),
+            treasuryOwnerBump,
+            sponsors,
+            {
+              accounts: {
+                governance: governanceAccount.publicKey,
+                payer: payer.publicKey,
+                treasuryOwner: treasuryOwnerPK,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+                clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+              },
+              signers: [governanceAccount]
+            }
+          );
+        },
+        (err) => {
+          assert.equal(err.code, 309);
+          assert.equal(err.msg, "Sponsors limit exceeded");
+          return true;
+        }
+      );
+
+      sponsors = [
+        user1Authority.publicKey,
+        user2Authority.publicKey,
+        user3Authority.publicKey,
+      ];
+      await govProgram.rpc.initializeGovernance(
+        stakingAccount.publicKey,
+        new anchor.BN(approvalFixedPeriodInSeconds),
+        minApprovalPercent,
+        new anchor.BN(minStakeToPropose),
+        minVoteParticipationPercent,
+        new anchor.BN(paymentPeriodSec),
+        new anchor.BN(executionDelaySeconds),
+        treasuryOwnerBump,
+        sponsors,
+        {
+          accounts: {
+            governance: governanceAccount.publicKey,
+            payer: payer.publicKey,
+            treasuryOwner: treasuryOwnerPK,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          signers: [governanceAccount]
+        }
+      );
+
+      // check data
+      let checkGovData = await govProgram.account.governance.fetch(governanceAccount.publicKey);
+      assert.ok(checkGovData.stakingData.equals(stakingAccount.publicKey));
+      assert.equal(checkGovData.approvalFixedPeriodInSeconds, approvalFixedPeriodInSeconds);
+      assert.equal(checkGovData.minApprovalPercent, minApprovalPercent);
+      assert.equal(checkGovData.treasuryOwnerBump, treasuryOwnerBump);
+      assert.equal(checkGovData.minStakeToPropose, minStakeToPropose);
+      assert.equal(checkGovData.minVoteParticipationPercent, minVoteParticipationPercent);
+      assert.deepStrictEqual(checkGovData.sponsors, sponsors);
+      assert.equal(checkGovData.paymentPeriodSec, paymentPeriodSec);
+      assert.equal(checkGovData.executionDelaySeconds, executionDelaySeconds);
+
+      // Authorize governance to lock the votes
+      pda = await anchor.web3.PublicKey.findProgramAddress(
+        [Buffer.from(LOCKER_AUTHORITY_PREFIX)],
+        govProgram.programId
+      );
+      lockerAuthorityPK = pda[0];
+      lockerBump = pda[1];
+      pda = await anchor.web3.PublicKey.findProgramAddress(
+        [Buffer.from(LOCKER_AUTHORITY_PREFIX)],
+        testLockerProgram.programId
+      );
+      testLockerAuthorityPK = pda[0];
+      testLockerBump = pda[1];
+
+      // BadCase: more lockers than allowed
+      await assert.rejects(
+        async () => {
+          await stakingProgram.rpc.setAuthorizedLockers(
+            [...Array(5)].map(() => anchor.web3.Keypair.generate().publicKey),
+            {
+              accounts: {
+                stakingData: stakingAccount.publicKey,
+                stakingOwner: stakingOwner.publicKey,
+              },
+              signers: [stakingOwner]
+            }
+          );
+        },
+        (err) => {
+          assert.equal(err.code, 328);
+          assert.equal(err.msg, "Too many authorized lockers");
+          return true;
+        }
+      );
+
+      // Authorize governance and the test locker program
+      await stakingProgram.rpc.setAuthorizedLockers(
+        [govProgram.programId, testLockerProgram.programId],
+        {
+          accounts: {
+            stakingData: stakingAccount.publicKey,
+            stakingOwner: stakingOwner.publicKey,
+          },
+          signers: [stakingOwner]
+        }
+      );
+      const checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.deepStrictEqual(
+        checkStakingData.authorizedLockers,
+        [govProgram.programId, testLockerProgram.programId]
+      );
+    });
+
+    it('Changing sponsors list test', async () => {
+      let newSponsor1 = anchor.web3.Keypair.generate();
+      let newSponsor2 = anchor.web3.Keypair.generate();
+
+      // BadCase: not enough signers
+      await assert.rejects(
+        async () => {
+          await govProgram.rpc.addSponsor(
+            newSponsor1.publicKey,
+            {
+              accounts: {
+                governance: governanceAccount.publicKey,
+              },
+              remainingAccounts: [
+                {
+                  pubkey: user1Authority.publicKey,
+                  isSigner: true,
+                  isWritable: false,
+                },
+              ],
+              signers: [user1Authority],
+            }
+          );
+        },
+        (err) => {
+          assert.equal(err.code, 311);
+          assert.equal(err.msg, "Not enough signers to change sponsors list");
+          return true;
+        }
+      );
+
+      // BadCase: duplicates in signers list
+      await assert.rejects(
+        async () => {
+          await govProgram.rpc.addSponsor(
+            newSponsor1.publicKey,
+            {
+              accounts: {
+                governance: governanceAccount.publicKey,
+              },
+              remainingAccounts: [
+                {
+                  pubkey: user1Authority.publicKey,
+                  isSigner: true,
+                  isWritable: false,
+                },
+                {
+                  pubkey: user1Authority.publicKey,
+                  isSigner: true,
+                  isWritable: false,
+                },
+              ],
+              signers: [user1Authority, user1Authority],
+            }
+          );
+        },
+        (err) => {
+          assert.equal(err.code, 311);
+          assert.equal(err.msg, "Not enough signers to change sponsors list");
+          return true;
+        }
+      );
+
+      await govProgram.rpc.addSponsor(
+        newSponsor1.publicKey,
+        {
+          accounts: {
+            governance: governanceAccount.publicKey,
+          },
+          remainingAccounts: [
+            {
+              pubkey: user1Authority.publicKey,
+              isSigner: true,
+              isWritable: false,
+            },
+            {
+              pubkey: user2Authority.publicKey,
+              isSigner: true,
+              isWritable: false,
+            },
+          ],
+          signers: [user1Authority, user2Authority],
+        }
+      );
+
+      sponsors = [user1Authority.publicKey, user2Authority.publicKey, user3Authority.publicKey, newSponsor1.publicKey];
+      checkGovData = await govProgram.account.governance.fetch(governanceAccount.publicKey);
+      assert.equal(checkGovData.sponsors.length, 4);
+      assert.deepStrictEqual(checkGovData.sponsors, sponsors);
+
+      // BadCase: sponsor already in list
+      await assert.rejects(
+        async () => {
+          await govProgram.rpc.addSponsor(
+            newSponsor1.publicKey,
+            {
+              accounts: {
+                governance: governanceAccount.publicKey,
+              },
+              remainingAccounts: [
+                {
+                  pubkey: user1Authority.publicKey,
+                  isSigner: true,
+                  isWritable: false,
+                },
+                {
+                  pubkey: user2Authority.publicKey,
+                  isSigner: true,
+                  isWritable: false,
+                },
+                {
+                  pubkey: newSponsor1.publicKey,
+                  isSigner: true,
+                  isWritable: false,
+                },
+              ],
+              signers: [user1Authority, user2Authority, newSponsor1],
+            }
+          );
+        },
+        (err) => {
+          assert.equal(err.code, 312);
+          assert.equal(err.msg, "Sponsor is already in list");
+          return true;
+        }
+      );
+
+      await govProgram.rpc.addSponsor(
+        newSponsor2.publicKey,
+        {
+          accounts: {
+            governance: governanceAccount.publicKey,
+          },
+          remainingAccounts: [
+            {
+              pubkey: user1Authority.publicKey,
+              isSigner: true,
+              isWritable: false,
+            },
+            {
+              pubkey: user2Authority.publicKey,
+              isSigner: true,
+              isWritable: false,
+            },
+            {
+              pubkey: newSponsor1.publicKey,
+              isSigner: true,
+              isWritable: false,
+            },
+          ],
+          signers: [user1Authority, user2Authority, newSponsor1],
+        }
+      );
+
+      sponsors = [user1Authority.publicKey, user2Authority.publicKey, user3Authority.publicKey, newSponsor1.publicKey, newSponsor2.publicKey];
+      checkGovData = await govProgram.account.governance.fetch(governanceAccount.publicKey);
+      assert.equal(checkGovData.sponsors.length, 5);
+      assert.deepStrictEqual(checkGovData.sponsors, sponsors);
+
+      // BadCase: There is such sponsor in list
+      await assert.rejects(
+        async () => {
+          let fakeSponsor = anchor.web3.Keypair.generate();
+          await govProgram.rpc.removeSponsor(
+            fakeSponsor.publicKey,
+            {
+              accounts: {
+                governance: governanceAccount.publicKey,
+              },
+              remainingAccounts: [
+                {
+                  pubkey: user1Authority.publicKey,
+                  isSigner: true,
+                  isWritable: false,
+                },
+                {
+                  pubkey: user2Authority.publicKey,
+                  isSigner: true,
+                  isWritable: false,
+                },
+                {
+                  pubkey: newSponsor1.publicKey,
+                  isSigner: true,
+                  isWritable: false,
+                },
+              ],
+              signers: [user1Authority, user2Authority, newSponsor1],
+            }
+          );
+        },
+        (err) => {
+          assert.equal(err.code, 313);
+          assert.equal(err.msg, "Sponsor is already not in list");
+          return true;
+        }
+      );
+
+      // Remove newSponsor2 from list
+      await govProgram.rpc.removeSponsor(
+        newSponsor2.publicKey,
+        {
+          accounts: {
+            governance: governanceAccount.publicKey,
+          },
+          remainingAccounts: [
+            {
+              pubkey: user1Authority.publicKey,
+              isSigner: true,
+              isWritable: false,
+            },
+            {
+              pubkey: user2Authority.publicKey,
+              isSigner: true,
+              isWritable: false,
+            },
+            {
+              pubkey: newSponsor1.publicKey,
+              isSigner: true,
+              isWritable: false,
+            },
+          ],
+          signers: [user1Authority, user2Authority, newSponsor1],
+        }
+      );
+
+      sponsors = [user1Authority.publicKey, user2Authority.publicKey, user3Authority.publicKey, newSponsor1.publicKey];
+      checkGovData = await govProgram.account.governance.fetch(governanceAccount.publicKey);
+      assert.equal(checkGovData.sponsors.length, 4);
+      assert.deepStrictEqual(checkGovData.sponsors, sponsors);
+
+      // BadCase: not enough signers
+      await assert.rejects(
+        async () => {
+          await govProgram.rpc.removeSponsor(
+            newSponsor1.publicKey,
+            {
+              accounts: {
+                governance: governanceAccount.publicKey,
+              },
+              remainingAccounts: [
+                {
+                  pubkey: user1Authority.publicKey,
+                  isSigner: true,
+                  isWritable: false,
+                },
+                {
+                  pubkey: user2Authority.publicKey,
+                  isSigner: true,
+                  isWritable: false,
+                },
+              ],
+              signers: [user1Authority, user2Authority],
+            }
+          );
+        },
+        (err) => {
+          assert.equal(err.code, 311);
+          assert.equal(err.msg, "Not enough signers to change sponsors list");
+          return true;
+        }
+      );
+
+      // BadCase: not enough signers (0)
+      await assert.rejects(
+        async () => {
+          await govProgram.rpc.removeSponsor(
+            newSponsor1.publicKey,
+            {
+              accounts: {
+                governance: governanceAccount.publicKey,
+              },
+              remainingAccounts: [],
+              signers: [],
+            }
+          );
+        },
+        (err) => {
+          assert.equal(err.code, 311);
+          assert.equal(err.msg, "Not enough signers to change sponsors list");
+          return true;
+        }
+      );
+
+      // BadCase: duplicate signers
+      await assert.rejects(
+        async () => {
+          await govProgram.rpc.removeSponsor(
+            newSponsor1.publicKey,
+            {
+              accounts: {
+                governance: governanceAccount.publicKey,
+              },
+              remainingAccounts: [
+                {
+                  pubkey: user1Authority.publicKey,
+                  isSigner: true,
+                  isWritable: false,
+                },
+                {
+                  pubkey: user2Authority.publicKey,
+                  isSigner: true,
+                  isWritable: false,
+                },
+                {
+                  pubkey: user2Authority.publicKey,
+                  isSigner: true,
+                  isWritable: false,
+                },
+              ],
+              signers: [user1Authority, user2Authority, user2Authority],
+            }
+          );
+        },
+        (err) => {
+          assert.equal(err.code, 311);
+          assert.equal(err.msg, "Not enough signers to change sponsors list");
+          return true;
+        }
+      );
+
+      // Remove newSponsor1 from list
+      await govProgram.rpc.removeSponsor(
+        newSponsor1.publicKey,
+        {
+          accounts: {
+            governance: governanceAccount.publicKey,
+          },
+          remainingAccounts: [
+            {
+              pubkey: user1Authority.publicKey,
+              isSigner: true,
+              isWritable: false,
+            },
+            {
+              pubkey: user2Authority.publicKey,
+              isSigner: true,
+              isWritable: false,
+            },
+            {
+              pubkey: user3Authority.publicKey,
+              isSigner: true,
+              isWritable: false,
+            },
+          ],
+          signers: [user1Authority, user2Authority, user3Authority],
+        }
+      );
+
+      sponsors = [user1Authority.publicKey, user2Authority.publicKey, user3Authority.publicKey];
+      checkGovData = await govProgram.account.governance.fetch(governanceAccount.publicKey);
+      assert.equal(checkGovData.sponsors.length, 3);
+      assert.deepStrictEqual(checkGovData.sponsors, sponsors);
+
+      // BadCase: less than 3 sponsors
+      await assert.rejects(
+        async () => {
+          await govProgram.rpc.removeSponsor(
+            user3Authority.publicKey,
+            {
+              accounts: {
+                governance: governanceAccount.publicKey,
+              },
+              remainingAccounts: [
+                {
+                  pubkey: user1Authority.publicKey,
+                  isSigner: true,
+                  isWritable: false,
+                },
+                {
+                  pubkey: user2Authority.publicKey,
+                  isSigner: true,
+                  isWritable: false,
+                },
+              ],
+              signers: [user1Authority, user2Authority],
+            }
+          );
+        },
+        (err) => {
+          assert.equal(err.code, 309);
+          assert.equal(err.msg, "Sponsors limit exceeded");
+          return true;
+        }
+      );
+    });
+
+    it('Sponsors limit list test', async () => {
+      let governanceAccount2 = anchor.web3.Keypair.generate();
+      let pda = await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from(TREASURY_PREFIX),
+          govProgram.programId.toBuffer(),
+          governanceAccount2.publicKey.toBuffer()
+        ],
+        govProgram.programId
+      );
+      treasuryOwnerPK2 = pda[0];
+      treasuryOwnerBump2 = pda[1];
+      treasuryPK2 = await treasuryMint.createAccount(treasuryOwnerPK2);
+
+      sponsors = [
+        anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(),
+        anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(),
+        anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(),
+        anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(),
+        anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(),
+      ];
+      sponsorsPubkeys = [];
+      for (i = 0; i < sponsors.length; i++) {
+        sponsorsPubkeys.push(sponsors[i].publicKey);
+      }
+      await govProgram.rpc.initializeGovernance(
+        stakingAccount.publicKey,
+        new anchor.BN(approvalFixedPeriodInSeconds),
+        minApprovalPercent,
+        new anchor.BN(minStakeToPropose),
+        minVoteParticipationPercent,
+        new anchor.BN(paymentPeriodSec),
+        new anchor.BN(executionDelaySeconds),
+        treasuryOwnerBump2,
+        sponsorsPubkeys,
+        {
+          accounts: {
+            governance: governanceAccount2.publicKey,
+            payer: payer.publicKey,
+            treasuryOwner: treasuryOwnerPK2,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          signers: [governanceAccount2]
+        }
+      );
+
+      let remainingAccounts = [];
+      let signers = [];
+      for (i = 0; i < 10; i++) {
+        remainingAccounts.push({ pubkey: sponsorsPubkeys[i], isSigner: true, isWritable: false });
+        signers.push(sponsors[i]);
+      }
+
+      // Added the 16th sponsor
+      newSponsor = anchor.web3.Keypair.generate();
+      await govProgram.rpc.addSponsor(
+        newSponsor.publicKey,
+        {
+          accounts: {
+            governance: governanceAccount2.publicKey,
+          },
+          remainingAccounts: remainingAccounts,
+          signers: signers,
+        }
+      );
+
+      checkSponsors = sponsorsPubkeys;
+      checkSponsors.push(newSponsor.publicKey);
+      checkGovData = await govProgram.account.governance.fetch(governanceAccount2.publicKey);
+      assert.equal(checkGovData.sponsors.length, 16);
+      assert.deepStrictEqual(checkGovData.sponsors, checkSponsors);
+    });
+
+    let treasuryPK = null;
+    let treasuryStatsPK = null;
+
+    it('Initialize treasury stats test', async () => {
+      treasuryPK = await treasuryMint.createAccount(treasuryOwnerPK);
+
+      let pda = await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from(TREASURY_STATS_PREFIX),
+          govProgram.programId.toBuffer(),
+          governanceAccount.publicKey.toBuffer(),
+          treasuryPK.toBuffer(),
+        ],
+        govProgram.programId
+      );
+      treasuryStatsPK = pda[0];
+      let treasuryStatsPKBump = pda[1];
+
+      await govProgram.rpc.initializeTreasuryStats(
+        maxProposalPaymentPercent,
+        paymentInPeriodLimitPercent,
+        treasuryStatsPKBump,
+        {
+          accounts: {
+            governance: governanceAccount.publicKey,
+            treasuryStats: treasuryStatsPK,
+            treasury: treasuryPK,
+            treasuryOwner: treasuryOwnerPK,
+            payer: payer.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+          }
+        }
+      )
+
+      let checkTreasuryStatsData = await govProgram.account.treasuryStats.fetch(treasuryStatsPK);
+      assert.ok(checkTreasuryStatsData.treasury.equals(treasuryPK));
+      assert.equal(checkTreasuryStatsData.maxProposalPaymentPercent, maxProposalPaymentPercent);
+      assert.equal(checkTreasuryStatsData.paymentAmountInPeriodLimitPercent, paymentInPeriodLimitPercent);
+      assert.equal(checkTreasuryStatsData.paymentAmountInPeriod, 0);
+      assert.equal(checkTreasuryStatsData.highestBalance, 0);
+    });
+
+    let govUser1FundAmount = 14_000_000_000;
+
+    it('Fund treasury test', async () => {
+      let fundAmount = 1_000_000_001;
+      await govProgram.rpc.fundTreasury(
+        new anchor.BN(fundAmount),
+        {
+          accounts: {
+            governance: governanceAccount.publicKey,
+            userTokenWallet: govUser1TokenWallet,
+            userTokenWalletOwner: govUser1Auth.publicKey,
+            treasury: treasuryPK,
+            treasuryMint: treasuryMint.publicKey,
+            treasuryStats: treasuryStatsPK,
+            treasuryOwner: treasuryOwnerPK,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          signers: [govUser1Auth]
+        }
+      );
+
+      // Check data
+      let checkTreasury = await treasuryMint.getAccountInfo(treasuryPK);
+      assert.equal(checkTreasury.amount, fundAmount);
+      let checkTreasuryStatsData = await govProgram.account.treasuryStats.fetch(treasuryStatsPK);
+      assert.equal(checkTreasuryStatsData.highestBalance.toNumber(), fundAmount);
+
+      // Check highest balance updating
+      await govProgram.rpc.fundTreasury(
+        new anchor.BN(govUser1FundAmount),
+        {
+          accounts: {
+            governance: governanceAccount.publicKey,
+            userTokenWallet: govUser1TokenWallet,
+            userTokenWalletOwner: govUser1Auth.publicKey,
+            treasury: treasuryPK,
+            treasuryMint: treasuryMint.publicKey,
+            treasuryStats: treasuryStatsPK,
+            treasuryOwner: treasuryOwnerPK,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          signers: [govUser1Auth]
+        }
+      );
+
+      // Check data
+      checkTreasury = await treasuryMint.getAccountInfo(treasuryPK);
+      assert.equal(checkTreasury.amount, govUser1FundAmount + fundAmount);
+      checkTreasuryStatsData = await govProgram.account.treasuryStats.fetch(treasuryStatsPK);
+      assert.equal(checkTreasuryStatsData.highestBalance.toNumber(), govUser1FundAmount + fundAmount);
+      govUser1FundAmount = govUser1FundAmount + fundAmount;
+
+      // TODO: funding another treasury
+      // TODO: BadCase: treasury from another SPL Token??
+    });
+
+    let proposal1PK = null;
+    let proposal1IpfsHash = 'zb2rhe143L6sgu2Nba4TZgFMdPidGMA6hmWhK9wLUoVGWYsR7';
+    let proposal1Amount = 1_500_000_000;
+
+    let proposal2PK = null;
+    let proposal2IpfsHash = '11223344556677889900112233445566';
+    let proposal2Amount = 2_500_000_000;
+
+    let proposal3PK = null;
+    let proposal3IpfsHash = '11223344556611223344556677889900';
+    let proposal3Amount = 1_000_000_000;
+
+    it('Make proposal test', async () => {
+      let pda = await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from(PROPOSAL_PREFIX),
+          govProgram.programId.toBuffer(),
+          governanceAccount.publicKey.toBuffer(),
+          Buffer.from(proposal1IpfsHash.slice(0, 32)),
+        ],
+        govProgram.programId
+      );
+      proposal1PK = pda[0];
+      let proposal1Bump = pda[1];
+
+      pda = await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from(PROPOSAL_PREFIX),
+          govProgram.programId.toBuffer(),
+          governanceAccount.publicKey.toBuffer(),
+          Buffer.from(proposal2IpfsHash.slice(0, 32)),
+        ],
+        govProgram.programId
+      );
+      proposal2PK = pda[0];
+      let proposal2Bump = pda[1];
+
+      pda = await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from(PROPOSAL_PREFIX),
+          govProgram.programId.toBuffer(),
+          governanceAccount.publicKey.toBuffer(),
+          Buffer.from(proposal3IpfsHash.slice(0, 32)),
+        ],
+        govProgram.programId
+      );
+      proposal3PK = pda[0];
+      let proposal3Bump = pda[1];
+
+      // User1 stake additional tokens to make proposal
+      let user1AdditionalAmount = 500_000_000;
+      await stakingProgram.rpc.stake(
+        new anchor.BN(user1AdditionalAmount),
+        {
+          accounts: {
+            stakingUserData: user1StakingData.publicKey,
+            userTokenWallet: user1TokenWalletPK,
+            userTokenWalletOwner: user1Authority.publicKey,
+            stakingData: stakingAccount.publicKey,
+            allowListEntry: await allowListEntryPK(user1Authority.publicKey),
+            holdingWallet: holdingWalletPK,
+            feeCollector: holdingWalletPK,
+            stakingTokenMetadata: metadataAccount.publicKey,
+            mint: mint.publicKey,
+            mintAuthority: pdaMintAuthority,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          signers: [user1Authority]
+        }
+      );
+
+      // BadCase: min stake amount check
+      await assert.rejects(
+        async () => {
+          await govProgram.rpc.makeProposal(
+            new anchor.BN(proposal1Amount),
+            proposal1Bump,
+            proposal1IpfsHash,
+            {
+              accounts: {
+                governance: governanceAccount.publicKey,
+                treasury: treasuryPK,
+                treasuryStats: treasuryStatsPK,
+                stakingUserData: user1StakingData.publicKey,
+                userTokenWallet: user1TokenWalletPK,
+                userTokenWalletOwner: user1Authority.publicKey,
+                proposal: proposal1PK,
+                recipient: govUser2TokenWallet,
+                payer: payer.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+                clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+              },
+              signers: [user1Authority]
+            }
+          );
+        },
+        (err) => {
+          assert.equal(err.code, 305);
+          assert.equal(err.msg, "There is not enough staked tokens to make proposal");
+          return true;
+        }
+      );
+
+      // BadCase: too big payment amount (more than treasury amount)
+      await assert.rejects(
+        async () => {
+          let amount = govUser1FundAmount + 1;
+          await govProgram.rpc.makeProposal(
+            new anchor.BN(amount),
+            proposal1Bump,
+            proposal1IpfsHash,
+            {
+              accounts: {
+                governance: governanceAccount.publicKey,
+                treasury: treasuryPK,
+                treasuryStats: treasuryStatsPK,
+                stakingUserData: user2StakingData.publicKey,
+                userTokenWallet: user2TokenWalletPK,
+                userTokenWalletOwner: user2Authority.publicKey,
+                proposal: proposal1PK,
+                recipient: govUser1TokenWallet,
+                payer: payer.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+                clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+              },
+              signers: [user2Authority]
+            }
+          );
+        },
+        (err) => {
+          assert.equal(err.code, 302);
+          assert.equal(err.msg, "Governance treasury insufficient funds");
+          return true;
+        }
+      );
+
+      // BadCase: too big payment amount (max payment amount limit)
+      await assert.rejects(
+        async () => {
+          treasuryStatsData = await govProgram.account.treasuryStats.fetch(treasuryStatsPK);
+          let maxProposalPaymentAmount = treasuryStatsData.highestBalance * (maxProposalPaymentPercent / 100.0);
+          let amount = maxProposalPaymentAmount + 1;
+          await govProgram.rpc.makeProposal(
+            new anchor.BN(amount),
+            proposal1Bump,
+            proposal1IpfsHash,
+            {
+              accounts: {
+                governance: governanceAccount.publicKey,
+                treasury: treasuryPK,
+                treasuryStats: treasuryStatsPK,
+                stakingUserData: user2StakingData.publicKey,
+                userTokenWallet: user2TokenWalletPK,
+                userTokenWalletOwner: user2Authority.publicKey,
+                proposal: proposal1PK,
+                recipient: govUser1TokenWallet,
+                payer: payer.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+                clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+              },
+              signers: [user2Authority]
+            }
+          );
+        },
+        (err) => {
+          assert.equal(err.code, 306);
+          assert.equal(err.msg, "Max proposal payment amount limit exceeded");
+          return true;
+        }
+      );
+
+      // User1 stake additional tokens for voting before the proposals snapshot
+      await stakingProgram.rpc.stake(
+        new anchor.BN(5_000_000_000),
+        {
+          accounts: {
+            stakingUserData: user1StakingData.publicKey,
+            userTokenWallet: user1TokenWalletPK,
+            userTokenWalletOwner: user1Authority.publicKey,
+            stakingData: stakingAccount.publicKey,
+            allowListEntry: await allowListEntryPK(user1Authority.publicKey),
+            holdingWallet: holdingWalletPK,
+            feeCollector: holdingWalletPK,
+            stakingTokenMetadata: metadataAccount.publicKey,
+            mint: mint.publicKey,
+            mintAuthority: pdaMintAuthority,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          signers: [user1Authority]
+        }
+      );
+
+      // User4 stake additional tokens for voting before the proposals snapshot
+      await stakingProgram.rpc.stake(
+        new anchor.BN(10_000_000_000),
+        {
+          accounts: {
+            stakingUserData: user4StakingData.publicKey,
+            userTokenWallet: user4TokenWalletPK,
+            userTokenWalletOwner: user4Authority.publicKey,
+            stakingData: stakingAccount.publicKey,
+            allowListEntry: await allowListEntryPK(user4Authority.publicKey),
+            holdingWallet: holdingWalletPK,
+            feeCollector: holdingWalletPK,
+            stakingTokenMetadata: metadataAccount.publicKey,
+            mint: mint.publicKey,
+            mintAuthority: pdaMintAuthority,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          signers: [user4Authority]
+        }
+      );
+
+      // staking user2 making proposal
+      await govProgram.rpc.makeProposal(
+        new anchor.BN(proposal1Amount),
+        proposal1Bump,
+        proposal1IpfsHash,
+        {
+          accounts: {
+            governance: governanceAccount.publicKey,
+            treasury: treasuryPK,
+            treasuryStats: treasuryStatsPK,
+            stakingUserData: user2StakingData.publicKey,
+            userTokenWallet: user2TokenWalletPK,
+            userTokenWalletOwner: user2Authority.publicKey,
+            proposal: proposal1PK,
+            recipient: govUser1TokenWallet,
+            payer: payer.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          signers: [user2Authority]
+        }
+      );
+
+      // Check data
+      treasuryStatsData = await govProgram.account.treasuryStats.fetch(treasuryStatsPK);
+      assert.equal(treasuryStatsData.paymentAmountInPeriod.toNumber(), proposal1Amount);
+      let checkProposalData = await govProgram.account.proposal.fetch(proposal1PK);
+      assert.ok(checkProposalData.governance.equals(governanceAccount.publicKey));
+      assert.ok(checkProposalData.author.equals(user2Authority.publicKey));
+      assert.ok(checkProposalData.recipient.equals(govUser1TokenWallet));
+      assert.equal(checkProposalData.paymentAmount, proposal1Amount);
+      assert.equal(checkProposalData.ipfsHash, proposal1IpfsHash);
+      assert.equal(checkProposalData.prosWeight, 0);
+      assert.equal(checkProposalData.consWeight, 0);
+      assert.ok(checkProposalData.state.voting);
+      assert.equal(checkProposalData.isSponsored, false);
+      assert.ok(checkProposalData.snapshotSlot.toNumber() > 0);
+      assert.equal(checkProposalData.snapshotTimestamp.toNumber(), checkProposalData.startingTimestamp.toNumber());
+      assert.equal(
+        checkProposalData.votingEndsAt.toNumber(),
+        checkProposalData.startingTimestamp.toNumber() + approvalFixedPeriodInSeconds
+      );
+
+      // Staking user2 making another proposal (proposal2)
+      await govProgram.rpc.makeProposal(
+        new anchor.BN(proposal2Amount),
+        proposal2Bump,
+        proposal2IpfsHash,
+        {
+          accounts: {
+            governance: governanceAccount.publicKey,
+            treasury: treasuryPK,
+            treasuryStats: treasuryStatsPK,
+            stakingUserData: user2StakingData.publicKey,
+            userTokenWallet: user2TokenWalletPK,
+            userTokenWalletOwner: user2Authority.publicKey,
+            proposal: proposal2PK,
+            recipient: govUser1TokenWallet,
+            payer: payer.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          signers: [user2Authority]
+        }
+      );
+
+      // Check data
+      treasuryStatsData = await govProgram.account.treasuryStats.fetch(treasuryStatsPK);
+      assert.equal(treasuryStatsData.paymentAmountInPeriod.toNumber(), proposal1Amount + proposal2Amount);
+      checkProposalData = await govProgram.account.proposal.fetch(proposal2PK);
+      assert.ok(checkProposalData.governance.equals(governanceAccount.publicKey));
+      assert.ok(checkProposalData.recipient.equals(govUser1TokenWallet));
+      assert.equal(checkProposalData.paymentAmount, proposal2Amount);
+      assert.equal(checkProposalData.ipfsHash, proposal2IpfsHash);
+      assert.equal(checkProposalData.prosWeight, 0);
+      assert.equal(checkProposalData.consWeight, 0);
+      assert.ok(checkProposalData.state.voting);
+      assert.equal(checkProposalData.isSponsored, false);
+
+      // BadCase: payment limit in period exceeded
+      await assert.rejects(
+        async () => {
+          treasuryStatsData = await govProgram.account.treasuryStats.fetch(treasuryStatsPK);
+          let paymentInPeriodLimit = treasuryStatsData.highestBalance * (paymentInPeriodLimitPercent / 100.0);
+          let amount = paymentInPeriodLimit - (proposal1Amount + proposal2Amount) + 1;
+          await govProgram.rpc.makeProposal(
+            new anchor.BN(amount),
+            proposal3Bump,
+            proposal3IpfsHash,
+            {
+              accounts: {
+                governance: governanceAccount.publicKey,
+                treasury: treasuryPK,
+                treasuryStats: treasuryStatsPK,
+                stakingUserData: user2StakingData.publicKey,
+                userTokenWallet: user2TokenWalletPK,
+                userTokenWalletOwner: user2Authority.publicKey,
+                proposal: proposal3PK,
+                recipient: govUser1TokenWallet,
+                payer: payer.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+                clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+              },
+              signers: [user2Authority]
+            }
+          );
+        },
+        (err) => {
+          assert.equal(err.code, 310);
+          assert.equal(err.msg, "This proposal exceeds the maximum amount that can be paid this period");
+          return true;
+        }
       );
-      treasuryStatsPK = pda[0];
-      let treasuryStatsPKBump = pda[1];
 
-      await govProgram.rpc.initializeTreasuryStats(
-        maxProposalPaymentPercent,
-        paymentInPeriodLimitPercent,
-        treasuryStatsPKBump,
+      // Staking user2 making another proposal (proposal3)
+      await govProgram.rpc.makeProposal(
+        new anchor.BN(proposal3Amount),
+        proposal3Bump,
+        proposal3IpfsHash,
         {
           accounts: {
             governance: governanceAccount.publicKey,
-            treasuryStats: treasuryStatsPK,
             treasury: treasuryPK,
-            treasuryOwner: treasuryOwnerPK,
+            treasuryStats: treasuryStatsPK,
+            stakingUserData: user2StakingData.publicKey,
+            userTokenWallet: user2TokenWalletPK,
+            userTokenWalletOwner: user2Authority.publicKey,
+            proposal: proposal3PK,
+            recipient: govUser1TokenWallet,
             payer: payer.publicKey,
             systemProgram: anchor.web3.SystemProgram.programId,
             rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-          }
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          signers: [user2Authority]
         }
-      )
+      );
 
-      let checkTreasuryStatsData = await govProgram.account.treasuryStats.fetch(treasuryStatsPK);
-      assert.ok(checkTreasuryStatsData.treasury.equals(treasuryPK));
-      assert.equal(checkTreasuryStatsData.maxProposalPaymentPercent, maxProposalPaymentPercent);
-      assert.equal(checkTreasuryStatsData.paymentAmountInPeriodLimitPercent, paymentInPeriodLimitPercent);
-      assert.equal(checkTreasuryStatsData.paymentAmountInPeriod, 0);
-      assert.equal(checkTreasuryStatsData.highestBalance, 0);
+      // Check data
+      treasuryStatsData = await govProgram.account.treasuryStats.fetch(treasuryStatsPK);
+      assert.equal(treasuryStatsData.paymentAmountInPeriod.toNumber(), proposal1Amount + proposal2Amount + proposal3Amount);
+      checkProposalData = await govProgram.account.proposal.fetch(proposal3PK);
+      assert.ok(checkProposalData.governance.equals(governanceAccount.publicKey));
+      assert.ok(checkProposalData.recipient.equals(govUser1TokenWallet));
+      assert.equal(checkProposalData.paymentAmount, proposal3Amount);
+      assert.equal(checkProposalData.ipfsHash, proposal3IpfsHash);
+      assert.equal(checkProposalData.prosWeight, 0);
+      assert.equal(checkProposalData.consWeight, 0);
+      assert.ok(checkProposalData.state.voting);
+      assert.equal(checkProposalData.isSponsored, false);
     });
 
-    let govUser1FundAmount = 14_000_000_000;
+    it('Approve proposal test', async () => {
+      // Additional tokens for voting are staked before the proposals snapshot
+      let user1StakeAmount = 5_000_000_000 + 500_000_000;
 
-    it('Fund treasury test', async () => {
-      let fundAmount = 1_000_000_001;
-      await govProgram.rpc.fundTreasury(
-        new anchor.BN(fundAmount),
+      let pda = await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from(VOTE_MARKER_PREFIX),
+          governanceAccount.publicKey.toBuffer(),
+          proposal1PK.toBuffer(),
+          user1StakingData.publicKey.toBuffer(),
+        ],
+        govProgram.programId
+      );
+      let voteMarkerPK = pda[0];
+      let voteMarkerBump = pda[1];
+
+      // User1 vote for proposal1
+      await govProgram.rpc.approveProposal(
+        true,
+        voteMarkerBump,
+        lockerBump,
         {
           accounts: {
             governance: governanceAccount.publicKey,
-            userTokenWallet: govUser1TokenWallet,
-            userTokenWalletOwner: govUser1Auth.publicKey,
-            treasury: treasuryPK,
-            treasuryMint: treasuryMint.publicKey,
-            treasuryStats: treasuryStatsPK,
-            treasuryOwner: treasuryOwnerPK,
-            tokenProgram: TOKEN_PROGRAM_ID,
+            stakingUserData: user1StakingData.publicKey,
+            userTokenWallet: user1TokenWalletPK,
+            userTokenWalletOwner: user1Authority.publicKey,
+            stakingData: stakingAccount.publicKey,
+            allowListEntry: await allowListEntryPK(user1Authority.publicKey),
+            proposal: proposal1PK,
+            voteMarker: voteMarkerPK,
+            lockerAuthority: lockerAuthorityPK,
+            governanceProgram: govProgram.programId,
+            payer: payer.publicKey,
+            stakingProgram: stakingProgram.programId,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           },
-          signers: [govUser1Auth]
+          signers: [user1Authority],
         }
       );
 
-      // Check data
-      let checkTreasury = await treasuryMint.getAccountInfo(treasuryPK);
-      assert.equal(checkTreasury.amount, fundAmount);
-      let checkTreasuryStatsData = await govProgram.account.treasuryStats.fetch(treasuryStatsPK);
-      assert.equal(checkTreasuryStatsData.highestBalance.toNumber(), fundAmount);
+      // BadCase: user1 vote for proposal1 again
+      await assert.rejects(
+        async () => {
+          await govProgram.rpc.approveProposal(
+            true,
+            voteMarkerBump,
+            lockerBump,
+            {
+              accounts: {
+                governance: governanceAccount.publicKey,
+                stakingUserData: user1StakingData.publicKey,
+                userTokenWallet: user1TokenWalletPK,
+                userTokenWalletOwner: user1Authority.publicKey,
+                stakingData: stakingAccount.publicKey,
+                allowListEntry: await allowListEntryPK(user1Authority.publicKey),
+                proposal: proposal1PK,
+                voteMarker: voteMarkerPK,
+                lockerAuthority: lockerAuthorityPK,
+                governanceProgram: govProgram.programId,
+                payer: payer.publicKey,
+                stakingProgram: stakingProgram.programId,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+              },
+              signers: [user1Authority],
+            }
+          );
+        },
+        (err) => {
+          return true;
+        }
+      );
 
-      // Check highest balance updating
-      await govProgram.rpc.fundTreasury(
-        new anchor.BN(govUser1FundAmount),
-        {
-          accounts: {
-            governance: governanceAccount.publicKey,
-            userTokenWallet: govUser1TokenWallet,
-            userTokenWalletOwner: govUser1Auth.publicKey,
-            treasury: treasuryPK,
-            treasuryMint: treasuryMint.publicKey,
-            treasuryStats: treasuryStatsPK,
-            treasuryOwner: treasuryOwnerPK,
-            tokenProgram: TOKEN_PROGRAM_ID,
-          },
-          signers: [govUser1Auth]
+      let checkProposalData = await govProgram.account.proposal.fetch(proposal1PK);
+      assert.ok(checkProposalData.state.voting);
+      assert.equal(checkProposalData.prosWeight, user1StakeAmount);
+      assert.equal(checkProposalData.consWeight, 0);
+
+      // BadCase: author can't cancel the proposal after votes
+      await assert.rejects(
+        async () => {
+          await govProgram.rpc.cancelProposal(
+            {
+              accounts: {
+                proposal: proposal1PK,
+                author: user2Authority.publicKey,
+              },
+              signers: [user2Authority],
+            }
+          );
+        },
+        (err) => {
+          assert.equal(err.code, 326);
+          assert.equal(err.msg, "Proposal already has votes");
+          return true;
         }
       );
+      let checkUser1StakingData = await stakingProgram.account.stakingUserData.fetch(user1StakingData.publicKey);
+      assert.equal(checkUser1StakingData.locks.length, 1);
+      assert.equal(checkUser1StakingData.locks[0].amount, user1StakeAmount);
+      assert.ok(checkUser1StakingData.locks[0].locker.equals(proposal1PK));
+      let lockedUntil = checkUser1StakingData.locks[0].until;
 
-      // Check data
-      checkTreasury = await treasuryMint.getAccountInfo(treasuryPK);
-      assert.equal(checkTreasury.amount, govUser1FundAmount + fundAmount);
-      checkTreasuryStatsData = await govProgram.account.treasuryStats.fetch(treasuryStatsPK);
-      assert.equal(checkTreasuryStatsData.highestBalance.toNumber(), govUser1FundAmount + fundAmount);
-      govUser1FundAmount = govUser1FundAmount + fundAmount;
+      // BadCase: User1 tries to unlock his amount without the locker program
+      await assert.rejects(
+        async () => {
+          await stakingProgram.rpc.lockAmount(
+            new anchor.BN(lockedUntil),
+            new anchor.BN(user1StakeAmount - 100),
+            proposal1PK,
+            lockerBump,
+            {
+              accounts: {
+                stakingData: stakingAccount.publicKey,
+                stakingUserData: user1StakingData.publicKey,
+                userTokenWallet: user1TokenWalletPK,
+                userTokenWalletOwner: user1Authority.publicKey,
+                lockerProgram: govProgram.programId,
+                lockerAuthority: user1Authority.publicKey,
+                clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+              },
+              signers: [user1Authority],
+            }
+          );
+        },
+        (err) => {
+          assert.equal(err.msg, "Unauthorized locker");
+          return true;
+        }
+      );
 
-      // TODO: funding another treasury
-      // TODO: BadCase: treasury from another SPL Token??
-    });
+      // Locked amount not changed
+      checkUser1StakingData = await stakingProgram.account.stakingUserData.fetch(user1StakingData.publicKey);
+      assert.equal(checkUser1StakingData.locks.length, 1);
+      assert.equal(checkUser1StakingData.locks[0].until.toNumber(), lockedUntil);
+      assert.equal(checkUser1StakingData.locks[0].amount.toNumber(), user1StakeAmount);
+
+      const testLock = async (until, amount) => {
+        await testLockerProgram.rpc.lock(
+          new anchor.BN(until),
+          new anchor.BN(amount),
+          proposal1PK,
+          testLockerBump,
+          {
+            accounts: {
+              stakingData: stakingAccount.publicKey,
+              stakingUserData: user1StakingData.publicKey,
+              userTokenWallet: user1TokenWalletPK,
+              userTokenWalletOwner: user1Authority.publicKey,
+              lockerAuthority: testLockerAuthorityPK,
+              lockerProgram: testLockerProgram.programId,
+              stakingProgram: stakingProgram.programId,
+              clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            },
+            signers: [user1Authority],
+          }
+        );
+      };
+      const testRelease = async (locker) => {
+        await testLockerProgram.rpc.release(
+          locker,
+          testLockerBump,
+          {
+            accounts: {
+              stakingData: stakingAccount.publicKey,
+              stakingUserData: user1StakingData.publicKey,
+              lockerAuthority: testLockerAuthorityPK,
+              lockerProgram: testLockerProgram.programId,
+              stakingProgram: stakingProgram.programId,
+            },
+          }
+        );
+      };
 
-    let proposal1PK = null;
-    let proposal1IpfsHash = 'zb2rhe143L6sgu2Nba4TZgFMdPidGMA6hmWhK9wLUoVGWYsR7';
-    let proposal1Amount = 1_500_000_000;
+      // BadCase: Unlock by time
+      await assert.rejects(
+        async () => {
+          await testLock(currentTimestamp, user1StakeAmount);
+        },
+        (err) => {
+          assert.equal(err.code, 304);
+          assert.equal(err.msg, "Trying to unlock amount");
+          return true;
+        }
+      );
 
-    let proposal2PK = null;
-    let proposal2IpfsHash = '11223344556677889900112233445566';
-    let proposal2Amount = 2_500_000_000;
+      // Lock of the test locker is kept apart from the governance lock
+      await testLock(lockedUntil, user1StakeAmount);
+      checkUser1StakingData = await stakingProgram.account.stakingUserData.fetch(user1StakingData.publicKey);
+      assert.equal(checkUser1StakingData.locks.length, 2);
+      assert.ok(checkUser1StakingData.locks[1].lockerProgram.equals(testLockerProgram.programId));
+      assert.ok(checkUser1StakingData.locks[1].locker.equals(proposal1PK));
+      assert.equal(checkUser1StakingData.locks[1].amount.toNumber(), user1StakeAmount);
 
-    let proposal3PK = null;
-    let proposal3IpfsHash = '11223344556611223344556677889900';
-    let proposal3Amount = 1_000_000_000;
+      // Unlock amount: smaller amount doesn't change the lock
+      await testLock(lockedUntil, user1StakeAmount - 100);
+      checkUser1StakingData = await stakingProgram.account.stakingUserData.fetch(user1StakingData.publicKey);
+      assert.equal(checkUser1StakingData.locks.length, 2);
+      assert.equal(checkUser1StakingData.locks[1].until.toNumber(), lockedUntil);
+      assert.equal(checkUser1StakingData.locks[1].amount.toNumber(), user1StakeAmount);
 
-    it('Make proposal test', async () => {
-      let pda = await anchor.web3.PublicKey.findProgramAddress(
+      // BadCase: User1 tries to release the lock without the locker program
+      await assert.rejects(
+        async () => {
+          await stakingProgram.rpc.releaseLock(
+            proposal1PK,
+            testLockerBump,
+            {
+              accounts: {
+                stakingData: stakingAccount.publicKey,
+                stakingUserData: user1StakingData.publicKey,
+                lockerProgram: testLockerProgram.programId,
+                lockerAuthority: user1Authority.publicKey,
+              },
+              signers: [user1Authority],
+            }
+          );
+        },
+        (err) => {
+          assert.equal(err.code, 325);
+          assert.equal(err.msg, "Unauthorized locker");
+          return true;
+        }
+      );
+
+      // Release the test locker lock
+      await testRelease(proposal1PK);
+      checkUser1StakingData = await stakingProgram.account.stakingUserData.fetch(user1StakingData.publicKey);
+      assert.equal(checkUser1StakingData.locks.length, 1);
+      assert.ok(checkUser1StakingData.locks[0].lockerProgram.equals(govProgram.programId));
+
+      // BadCase: test locker can't release the governance lock
+      await assert.rejects(
+        async () => {
+          await testRelease(proposal1PK);
+        },
+        (err) => {
+          assert.equal(err.code, 304);
+          return true;
+        }
+      );
+      checkUser1StakingData = await stakingProgram.account.stakingUserData.fetch(user1StakingData.publicKey);
+      assert.equal(checkUser1StakingData.locks.length, 1);
+      assert.equal(checkUser1StakingData.locks[0].amount.toNumber(), user1StakeAmount);
+
+      // User2 vote for proposal1
+      pda = await anchor.web3.PublicKey.findProgramAddress(
         [
-          Buffer.from(PROPOSAL_PREFIX),
-          govProgram.programId.toBuffer(),
+          Buffer.from(VOTE_MARKER_PREFIX),
           governanceAccount.publicKey.toBuffer(),
-          Buffer.from(proposal1IpfsHash.slice(0, 32)),
+          proposal1PK.toBuffer(),
+          user2StakingData.publicKey.toBuffer(),
         ],
         govProgram.programId
       );
-      proposal1PK = pda[0];
-      let proposal1Bump = pda[1];
+      voteMarkerPK = pda[0];
+      voteMarkerBump = pda[1];
+
+      await govProgram.rpc.approveProposal(
+        false,
+        voteMarkerBump,
+        lockerBump,
+        {
+          accounts: {
+            governance: governanceAccount.publicKey,
+            stakingUserData: user2StakingData.publicKey,
+            userTokenWallet: user2TokenWalletPK,
+            userTokenWalletOwner: user2Authority.publicKey,
+            stakingData: stakingAccount.publicKey,
+            proposal: proposal1PK,
+            voteMarker: voteMarkerPK,
+            lockerAuthority: lockerAuthorityPK,
+            governanceProgram: govProgram.programId,
+            payer: payer.publicKey,
+            stakingProgram: stakingProgram.programId,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          signers: [user2Authority]
+        }
+      );
+
+      checkProposalData = await govProgram.account.proposal.fetch(proposal1PK);
+      let checkUser2StakingData = await stakingProgram.account.stakingUserData.fetch(user2StakingData.publicKey);
+      assert.ok(checkProposalData.state.voting);
+      assert.equal(checkProposalData.prosWeight, user1StakeAmount);
+      assert.deepStrictEqual(checkProposalData.consWeight, checkUser2StakingData.locks[0].amount);
+      assert.deepStrictEqual(checkUser2StakingData.locks[0].amount, checkUser2StakingData.ownershipShare);
 
+      // User2 vote for proposal2
       pda = await anchor.web3.PublicKey.findProgramAddress(
         [
-          Buffer.from(PROPOSAL_PREFIX),
-          govProgram.programId.toBuffer(),
+          Buffer.from(VOTE_MARKER_PREFIX),
           governanceAccount.publicKey.toBuffer(),
-          Buffer.from(proposal2IpfsHash.slice(0, 32)),
+          proposal2PK.toBuffer(),
+          user2StakingData.publicKey.toBuffer(),
         ],
         govProgram.programId
       );
-      proposal2PK = pda[0];
-      let proposal2Bump = pda[1];
+      voteMarkerPK = pda[0];
+      voteMarkerBump = pda[1];
+
+      await govProgram.rpc.approveProposal(
+        true,
+        voteMarkerBump,
+        lockerBump,
+        {
+          accounts: {
+            governance: governanceAccount.publicKey,
+            stakingUserData: user2StakingData.publicKey,
+            userTokenWallet: user2TokenWalletPK,
+            userTokenWalletOwner: user2Authority.publicKey,
+            stakingData: stakingAccount.publicKey,
+            proposal: proposal2PK,
+            voteMarker: voteMarkerPK,
+            lockerAuthority: lockerAuthorityPK,
+            governanceProgram: govProgram.programId,
+            payer: payer.publicKey,
+            stakingProgram: stakingProgram.programId,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          signers: [user2Authority]
+        }
+      );
+
+      checkProposalData = await govProgram.account.proposal.fetch(proposal2PK);
+      checkUser2StakingData = await stakingProgram.account.stakingUserData.fetch(user2StakingData.publicKey);
+      assert.ok(checkProposalData.state.voting);
+      // Shares are already locked by the proposal1 vote, so there is no new lock entry
+      assert.equal(checkUser2StakingData.locks.length, 1);
+      assert.ok(checkUser2StakingData.locks[0].locker.equals(proposal1PK));
+      assert.deepStrictEqual(checkProposalData.prosWeight, checkUser2StakingData.ownershipShare);
+      assert.equal(checkProposalData.consWeight.toNumber(), 0);
 
+      // User4 vote for proposal3 (not sponsor)
       pda = await anchor.web3.PublicKey.findProgramAddress(
         [
-          Buffer.from(PROPOSAL_PREFIX),
-          govProgram.programId.toBuffer(),
+          Buffer.from(VOTE_MARKER_PREFIX),
           governanceAccount.publicKey.toBuffer(),
-          Buffer.from(proposal3IpfsHash.slice(0, 32)),
+          proposal3PK.toBuffer(),
+          user4StakingData.publicKey.toBuffer(),
         ],
         govProgram.programId
       );
-      proposal3PK = pda[0];
-      let proposal3Bump = pda[1];
+      voteMarkerPK = pda[0];
+      voteMarkerBump = pda[1];
 
-      // User1 stake additional tokens to make proposal
-      let user1AdditionalAmount = 500_000_000;
-      await stakingProgram.rpc.stake(
-        new anchor.BN(user1AdditionalAmount),
+      await govProgram.rpc.approveProposal(
+        true,
+        voteMarkerBump,
+        lockerBump,
         {
           accounts: {
-            stakingUserData: user1StakingData.publicKey,
-            userTokenWallet: user1TokenWalletPK,
-            userTokenWalletOwner: user1Authority.publicKey,
+            governance: governanceAccount.publicKey,
+            stakingUserData: user4StakingData.publicKey,
+            userTokenWallet: user4TokenWalletPK,
+            userTokenWalletOwner: user4Authority.publicKey,
             stakingData: stakingAccount.publicKey,
-            holdingWallet: holdingWalletPK,
-            stakingTokenMetadata: metadataAccount.publicKey,
-            mint: mint.publicKey,
-            mintAuthority: pdaMintAuthority,
-            tokenProgram: TOKEN_PROGRAM_ID,
+            proposal: proposal3PK,
+            voteMarker: voteMarkerPK,
+            lockerAuthority: lockerAuthorityPK,
+            governanceProgram: govProgram.programId,
+            payer: payer.publicKey,
+            stakingProgram: stakingProgram.programId,
+            systemProgram: anchor.web3.SystemProgram.programId,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           },
-          signers: [user1Authority]
+          signers: [user4Authority],
         }
       );
+    });
 
-      // BadCase: min stake amount check
+    it('Finalize still in vote proposal test', async () => {
+      // BadCase: the vote in progres
       await assert.rejects(
         async () => {
-          await govProgram.rpc.makeProposal(
-            new anchor.BN(proposal1Amount),
-            proposal1Bump,
-            proposal1IpfsHash,
+          await govProgram.rpc.finalizeProposal(
             {
               accounts: {
                 governance: governanceAccount.publicKey,
-                treasury: treasuryPK,
-                treasuryStats: treasuryStatsPK,
-                stakingUserData: user1StakingData.publicKey,
-                userTokenWallet: user1TokenWalletPK,
-                userTokenWalletOwner: user1Authority.publicKey,
+                stakingData: stakingAccount.publicKey,
                 proposal: proposal1PK,
-                recipient: govUser2TokenWallet,
-                payer: payer.publicKey,
-                systemProgram: anchor.web3.SystemProgram.programId,
-                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
                 clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
               },
-              signers: [user1Authority]
             }
           );
         },
         (err) => {
-          assert.equal(err.code, 305);
-          assert.equal(err.msg, "There is not enough staked tokens to make proposal");
+          assert.equal(err.code, 0x130);
+          assert.equal(err.msg, "The vote is in progress");
           return true;
         }
       );
+    });
 
-      // BadCase: too big payment amount (more than treasury amount)
+    it('Finalize proposal test', async () => {
+      let sleep_ms = approvalFixedPeriodInSeconds * 1000;
+      console.log("sleep for ms: ", sleep_ms);
+      sleep(sleep_ms);
+
+      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      console.log("Total staked: ", checkStakingData.totalStaked.toNumber());
+      checkGovData = await govProgram.account.governance.fetch(governanceAccount.publicKey);
+      console.log("minVoteParticipationPercent: ", checkGovData.minVoteParticipationPercent);
+      console.log("minApprovalPercent: ", checkGovData.minApprovalPercent);
+
+      checkProposalData = await govProgram.account.proposal.fetch(proposal1PK);
+      console.log("Proposal 1:")
+      console.log("prosWeight: ", checkProposalData.prosWeight.toNumber());
+      console.log("consWeight: ", checkProposalData.consWeight.toNumber());
+      console.log("isSponsored: ", checkProposalData.isSponsored);
+
+      checkProposalData = await govProgram.account.proposal.fetch(proposal2PK);
+      console.log("Proposal 2:")
+      console.log("prosWeight: ", checkProposalData.prosWeight.toNumber());
+      console.log("consWeight: ", checkProposalData.consWeight.toNumber());
+      console.log("isSponsored: ", checkProposalData.isSponsored);
+
+      checkProposalData = await govProgram.account.proposal.fetch(proposal3PK);
+      console.log("Proposal 3:")
+      console.log("prosWeight: ", checkProposalData.prosWeight.toNumber());
+      console.log("consWeight: ", checkProposalData.consWeight.toNumber());
+      console.log("isSponsored: ", checkProposalData.isSponsored);
+
+      checkRecipient = await treasuryMint.getAccountInfo(checkProposalData.recipient);
+      let recipient1BalanceBefore = checkRecipient.amount.toNumber();
+
+      await govProgram.rpc.finalizeProposal(
+        {
+          accounts: {
+            governance: governanceAccount.publicKey,
+            stakingData: stakingAccount.publicKey,
+            proposal: proposal1PK,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+        }
+      );
+
+      // BadCase: proposal already closed
       await assert.rejects(
         async () => {
-          let amount = govUser1FundAmount + 1;
-          await govProgram.rpc.makeProposal(
-            new anchor.BN(amount),
-            proposal1Bump,
-            proposal1IpfsHash,
+          await govProgram.rpc.finalizeProposal(
             {
               accounts: {
                 governance: governanceAccount.publicKey,
-                treasury: treasuryPK,
-                treasuryStats: treasuryStatsPK,
-                stakingUserData: user2StakingData.publicKey,
-                userTokenWallet: user2TokenWalletPK,
-                userTokenWalletOwner: user2Authority.publicKey,
+                stakingData: stakingAccount.publicKey,
                 proposal: proposal1PK,
-                recipient: govUser1TokenWallet,
-                payer: payer.publicKey,
-                systemProgram: anchor.web3.SystemProgram.programId,
-                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
                 clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
               },
-              signers: [user2Authority]
             }
           );
         },
         (err) => {
-          assert.equal(err.code, 302);
-          assert.equal(err.msg, "Governance treasury insufficient funds");
+          assert.equal(err.code, 303);
+          assert.equal(err.msg, "Proposal is already closed");
           return true;
         }
       );
 
-      // BadCase: too big payment amount (max payment amount limit)
+      // Approved proposal is queued, nothing is paid on finalizing
+      checkProposalData = await govProgram.account.proposal.fetch(proposal1PK);
+      assert.ok(checkProposalData.state.succeeded);
+      checkTreasury = await treasuryMint.getAccountInfo(treasuryPK);
+      assert.equal(checkTreasury.amount, govUser1FundAmount);
+
+      // Execution delay is 0, so the proposal can be executed right away
+      await govProgram.rpc.executeProposal(
+        {
+          accounts: {
+            governance: governanceAccount.publicKey,
+            proposal: proposal1PK,
+            treasury: treasuryPK,
+            treasuryOwner: treasuryOwnerPK,
+            treasuryMint: treasuryMint.publicKey,
+            recipient: govUser1TokenWallet,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+        }
+      );
+
+      // Check executed proposal results
+      checkProposalData = await govProgram.account.proposal.fetch(proposal1PK);
+      assert.ok(checkProposalData.state.executed);
+      checkTreasury = await treasuryMint.getAccountInfo(treasuryPK);
+      assert.equal(checkTreasury.amount, govUser1FundAmount - checkProposalData.paymentAmount);
+      checkRecipient = await treasuryMint.getAccountInfo(checkProposalData.recipient);
+      assert.equal(checkRecipient.amount.toNumber(), recipient1BalanceBefore + checkProposalData.paymentAmount.toNumber());
+
+      // BadCase: proposal already executed
       await assert.rejects(
         async () => {
-          treasuryStatsData = await govProgram.account.treasuryStats.fetch(treasuryStatsPK);
-          let maxProposalPaymentAmount = treasuryStatsData.highestBalance * (maxProposalPaymentPercent / 100.0);
-          let amount = maxProposalPaymentAmount + 1;
-          await govProgram.rpc.makeProposal(
-            new anchor.BN(amount),
-            proposal1Bump,
-            proposal1IpfsHash,
+          await govProgram.rpc.executeProposal(
             {
               accounts: {
                 governance: governanceAccount.publicKey,
-                treasury: treasuryPK,
-                treasuryStats: treasuryStatsPK,
-                stakingUserData: user2StakingData.publicKey,
-                userTokenWallet: user2TokenWalletPK,
-                userTokenWalletOwner: user2Authority.publicKey,
                 proposal: proposal1PK,
+                treasury: treasuryPK,
+                treasuryOwner: treasuryOwnerPK,
+                treasuryMint: treasuryMint.publicKey,
                 recipient: govUser1TokenWallet,
-                payer: payer.publicKey,
-                systemProgram: anchor.web3.SystemProgram.programId,
-                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+                tokenProgram: TOKEN_PROGRAM_ID,
                 clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
               },
-              signers: [user2Authority]
             }
           );
         },
         (err) => {
-          assert.equal(err.code, 306);
-          assert.equal(err.msg, "Max proposal payment amount limit exceeded");
+          assert.equal(err.code, 319);
+          assert.equal(err.msg, "Proposal is already executed");
           return true;
         }
       );
 
-      // staking user2 making proposal
-      await govProgram.rpc.makeProposal(
-        new anchor.BN(proposal1Amount),
-        proposal1Bump,
-        proposal1IpfsHash,
-        {
-          accounts: {
-            governance: governanceAccount.publicKey,
-            treasury: treasuryPK,
-            treasuryStats: treasuryStatsPK,
-            stakingUserData: user2StakingData.publicKey,
-            userTokenWallet: user2TokenWalletPK,
-            userTokenWalletOwner: user2Authority.publicKey,
-            proposal: proposal1PK,
-            recipient: govUser1TokenWallet,
-            payer: payer.publicKey,
-            systemProgram: anchor.web3.SystemProgram.programId,
-            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
-          },
-          signers: [user2Authority]
+      // BadCase: payment proposal has no config to apply
+      await assert.rejects(
+        async () => {
+          await govProgram.rpc.applyConfigProposal(
+            {
+              accounts: {
+                governance: governanceAccount.publicKey,
+                proposal: proposal1PK,
+                clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+              },
+            }
+          );
+        },
+        (err) => {
+          assert.equal(err.code, 320);
+          assert.equal(err.msg, "Proposal has no config to apply");
+          return true;
         }
       );
 
-      // Check data
-      treasuryStatsData = await govProgram.account.treasuryStats.fetch(treasuryStatsPK);
-      assert.equal(treasuryStatsData.paymentAmountInPeriod.toNumber(), proposal1Amount);
-      let checkProposalData = await govProgram.account.proposal.fetch(proposal1PK);
-      assert.ok(checkProposalData.governance.equals(governanceAccount.publicKey));
-      assert.ok(checkProposalData.recipient.equals(govUser1TokenWallet));
-      assert.equal(checkProposalData.paymentAmount, proposal1Amount);
-      assert.equal(checkProposalData.ipfsHash, proposal1IpfsHash);
-      assert.equal(checkProposalData.prosWeight, 0);
-      assert.equal(checkProposalData.consWeight, 0);
-      assert.equal(checkProposalData.isClosed, false);
-      assert.equal(checkProposalData.isSponsored, false);
+      let treasuryBalanceBefore = checkTreasury.amount;
 
-      // Staking user2 making another proposal (proposal2)
-      await govProgram.rpc.makeProposal(
-        new anchor.BN(proposal2Amount),
-        proposal2Bump,
-        proposal2IpfsHash,
+      // Proposal 2 min participation percent not reached
+      checkRecipient = await treasuryMint.getAccountInfo(checkProposalData.recipient);
+      let recipient2BalanceBefore = checkRecipient.amount.toNumber();
+      await govProgram.rpc.finalizeProposal(
         {
           accounts: {
             governance: governanceAccount.publicKey,
-            treasury: treasuryPK,
-            treasuryStats: treasuryStatsPK,
-            stakingUserData: user2StakingData.publicKey,
-            userTokenWallet: user2TokenWalletPK,
-            userTokenWalletOwner: user2Authority.publicKey,
+            stakingData: stakingAccount.publicKey,
             proposal: proposal2PK,
-            recipient: govUser1TokenWallet,
-            payer: payer.publicKey,
-            systemProgram: anchor.web3.SystemProgram.programId,
-            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           },
-          signers: [user2Authority]
         }
       );
 
-      // Check data
-      treasuryStatsData = await govProgram.account.treasuryStats.fetch(treasuryStatsPK);
-      assert.equal(treasuryStatsData.paymentAmountInPeriod.toNumber(), proposal1Amount + proposal2Amount);
+      // Check not approved proposal results
       checkProposalData = await govProgram.account.proposal.fetch(proposal2PK);
-      assert.ok(checkProposalData.governance.equals(governanceAccount.publicKey));
-      assert.ok(checkProposalData.recipient.equals(govUser1TokenWallet));
-      assert.equal(checkProposalData.paymentAmount, proposal2Amount);
-      assert.equal(checkProposalData.ipfsHash, proposal2IpfsHash);
-      assert.equal(checkProposalData.prosWeight, 0);
-      assert.equal(checkProposalData.consWeight, 0);
-      assert.equal(checkProposalData.isClosed, false);
-      assert.equal(checkProposalData.isSponsored, false);
+      assert.ok(checkProposalData.state.defeated);
+      assert.ok(checkProposalData.defeatReason.quorum);
+      checkTreasury = await treasuryMint.getAccountInfo(treasuryPK);
+      assert.deepStrictEqual(checkTreasury.amount, treasuryBalanceBefore);
+      checkRecipient = await treasuryMint.getAccountInfo(checkProposalData.recipient);
+      assert.equal(checkRecipient.amount.toNumber(), recipient2BalanceBefore);
 
-      // BadCase: payment limit in period exceeded
+      // BadCase: not approved proposal is not queued and can't be cancelled
       await assert.rejects(
         async () => {
-          treasuryStatsData = await govProgram.account.treasuryStats.fetch(treasuryStatsPK);
-          let paymentInPeriodLimit = treasuryStatsData.highestBalance * (paymentInPeriodLimitPercent / 100.0);
-          let amount = paymentInPeriodLimit - (proposal1Amount + proposal2Amount) + 1;
-          await govProgram.rpc.makeProposal(
-            new anchor.BN(amount),
-            proposal3Bump,
-            proposal3IpfsHash,
+          await govProgram.rpc.cancelQueuedProposal(
             {
               accounts: {
                 governance: governanceAccount.publicKey,
-                treasury: treasuryPK,
-                treasuryStats: treasuryStatsPK,
-                stakingUserData: user2StakingData.publicKey,
-                userTokenWallet: user2TokenWalletPK,
-                userTokenWalletOwner: user2Authority.publicKey,
-                proposal: proposal3PK,
-                recipient: govUser1TokenWallet,
-                payer: payer.publicKey,
-                systemProgram: anchor.web3.SystemProgram.programId,
-                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-                clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+                proposal: proposal2PK,
               },
-              signers: [user2Authority]
             }
           );
         },
         (err) => {
-          assert.equal(err.code, 310);
-          assert.equal(err.msg, "This proposal exceeds the maximum amount that can be paid this period");
+          assert.equal(err.code, 318);
+          assert.equal(err.msg, "Proposal is not approved");
           return true;
         }
       );
 
-      // Staking user2 making another proposal (proposal3)
-      await govProgram.rpc.makeProposal(
-        new anchor.BN(proposal3Amount),
-        proposal3Bump,
-        proposal3IpfsHash,
+      // Finalize the proposal 3 (it is not sponsored)
+      checkRecipient = await treasuryMint.getAccountInfo(checkProposalData.recipient);
+      recipient2BalanceBefore = checkRecipient.amount.toNumber();
+      await govProgram.rpc.finalizeProposal(
         {
           accounts: {
             governance: governanceAccount.publicKey,
-            treasury: treasuryPK,
-            treasuryStats: treasuryStatsPK,
-            stakingUserData: user2StakingData.publicKey,
-            userTokenWallet: user2TokenWalletPK,
-            userTokenWalletOwner: user2Authority.publicKey,
+            stakingData: stakingAccount.publicKey,
             proposal: proposal3PK,
-            recipient: govUser1TokenWallet,
-            payer: payer.publicKey,
-            systemProgram: anchor.web3.SystemProgram.programId,
-            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           },
-          signers: [user2Authority]
         }
       );
 
-      // Check data
-      treasuryStatsData = await govProgram.account.treasuryStats.fetch(treasuryStatsPK);
-      assert.equal(treasuryStatsData.paymentAmountInPeriod.toNumber(), proposal1Amount + proposal2Amount + proposal3Amount);
+      // Check not approved proposal results
       checkProposalData = await govProgram.account.proposal.fetch(proposal3PK);
-      assert.ok(checkProposalData.governance.equals(governanceAccount.publicKey));
-      assert.ok(checkProposalData.recipient.equals(govUser1TokenWallet));
-      assert.equal(checkProposalData.paymentAmount, proposal3Amount);
-      assert.equal(checkProposalData.ipfsHash, proposal3IpfsHash);
-      assert.equal(checkProposalData.prosWeight, 0);
-      assert.equal(checkProposalData.consWeight, 0);
-      assert.equal(checkProposalData.isClosed, false);
-      assert.equal(checkProposalData.isSponsored, false);
+      assert.ok(checkProposalData.state.defeated);
+      assert.ok(checkProposalData.defeatReason.notSponsored);
+      checkTreasury = await treasuryMint.getAccountInfo(treasuryPK);
+      assert.deepStrictEqual(checkTreasury.amount, treasuryBalanceBefore);
+      checkRecipient = await treasuryMint.getAccountInfo(checkProposalData.recipient);
+      assert.equal(checkRecipient.amount.toNumber(), recipient2BalanceBefore);
     });
 
-    it('Approve proposal test', async () => {
-      // User1 stake additional tokens for voting
-      let user1AdditionalAmount = 5_000_000_000;
-      let user1StakeAmount = user1AdditionalAmount + 500_000_000;
+    it('Unlock amounts test', async () => {
       await stakingProgram.rpc.stake(
-        new anchor.BN(user1AdditionalAmount),
+        new anchor.BN(100_000_000),
         {
           accounts: {
             stakingUserData: user1StakingData.publicKey,
             userTokenWallet: user1TokenWalletPK,
             userTokenWalletOwner: user1Authority.publicKey,
             stakingData: stakingAccount.publicKey,
+            allowListEntry: await allowListEntryPK(user1Authority.publicKey),
             holdingWallet: holdingWalletPK,
+            feeCollector: holdingWalletPK,
             stakingTokenMetadata: metadataAccount.publicKey,
             mint: mint.publicKey,
             mintAuthority: pdaMintAuthority,
@@ -1762,17 +4242,44 @@ describe('functional tests', () => {
         }
       );
 
-      // User4 stake additional tokens for voting
-      let user4StakeAmount = 10_000_000_000
+      checkStakingUserData = await stakingProgram.account.stakingUserData.fetch(user1StakingData.publicKey);
+      assert.equal(checkStakingUserData.locks.length, 0);
+
+      await stakingProgram.rpc.stake(
+        new anchor.BN(100_000_000),
+        {
+          accounts: {
+            stakingUserData: user2StakingData.publicKey,
+            userTokenWallet: user2TokenWalletPK,
+            userTokenWalletOwner: user2Authority.publicKey,
+            stakingData: stakingAccount.publicKey,
+            allowListEntry: await allowListEntryPK(user2Authority.publicKey),
+            holdingWallet: holdingWalletPK,
+            feeCollector: holdingWalletPK,
+            stakingTokenMetadata: metadataAccount.publicKey,
+            mint: mint.publicKey,
+            mintAuthority: pdaMintAuthority,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          signers: [user2Authority]
+        }
+      );
+
+      checkStakingUserData = await stakingProgram.account.stakingUserData.fetch(user2StakingData.publicKey);
+      assert.equal(checkStakingUserData.locks.length, 0);
+
       await stakingProgram.rpc.stake(
-        new anchor.BN(user4StakeAmount),
+        new anchor.BN(100_000_000),
         {
           accounts: {
             stakingUserData: user4StakingData.publicKey,
             userTokenWallet: user4TokenWalletPK,
             userTokenWalletOwner: user4Authority.publicKey,
             stakingData: stakingAccount.publicKey,
+            allowListEntry: await allowListEntryPK(user4Authority.publicKey),
             holdingWallet: holdingWalletPK,
+            feeCollector: holdingWalletPK,
             stakingTokenMetadata: metadataAccount.publicKey,
             mint: mint.publicKey,
             mintAuthority: pdaMintAuthority,
@@ -1783,329 +4290,555 @@ describe('functional tests', () => {
         }
       );
 
-      let pda = await anchor.web3.PublicKey.findProgramAddress(
+      checkStakingUserData = await stakingProgram.account.stakingUserData.fetch(user4StakingData.publicKey);
+      assert.equal(checkStakingUserData.locks.length, 0);
+    });
+
+    it('Reset payment amount in period test', async () => {
+      let proposal4IpfsHash = "00223344556611223344556677889911"
+      pda = await anchor.web3.PublicKey.findProgramAddress(
         [
-          Buffer.from(VOTE_MARKER_PREFIX),
+          Buffer.from(PROPOSAL_PREFIX),
+          govProgram.programId.toBuffer(),
           governanceAccount.publicKey.toBuffer(),
-          proposal1PK.toBuffer(),
-          user1StakingData.publicKey.toBuffer(),
+          Buffer.from(proposal4IpfsHash.slice(0, 32)),
         ],
         govProgram.programId
       );
-      let voteMarkerPK = pda[0];
-      let voteMarkerBump = pda[1];
+      let proposal4PK = pda[0];
+      let proposal4Bump = pda[1];
 
-      // User1 vote for proposal1
-      await govProgram.rpc.approveProposal(
-        true,
-        voteMarkerBump,
+      checkGovData = await govProgram.account.governance.fetch(governanceAccount.publicKey);
+      let oldTimestamp = checkGovData.paymentPeriodStart.toNumber();
+
+      let amount = 5_000_000_000;
+      await govProgram.rpc.makeProposal(
+        new anchor.BN(amount),
+        proposal4Bump,
+        proposal4IpfsHash,
         {
           accounts: {
             governance: governanceAccount.publicKey,
-            stakingUserData: user1StakingData.publicKey,
-            userTokenWallet: user1TokenWalletPK,
-            userTokenWalletOwner: user1Authority.publicKey,
-            stakingData: stakingAccount.publicKey,
-            proposal: proposal1PK,
-            voteMarker: voteMarkerPK,
+            treasury: treasuryPK,
+            treasuryStats: treasuryStatsPK,
+            stakingUserData: user2StakingData.publicKey,
+            userTokenWallet: user2TokenWalletPK,
+            userTokenWalletOwner: user2Authority.publicKey,
+            proposal: proposal4PK,
+            recipient: govUser1TokenWallet,
             payer: payer.publicKey,
-            stakingProgram: stakingProgram.programId,
             systemProgram: anchor.web3.SystemProgram.programId,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           },
-          signers: [user1Authority],
+          signers: [user2Authority]
         }
       );
 
-      // BadCase: user1 vote for proposal1 again
+      checkGovData = await govProgram.account.governance.fetch(governanceAccount.publicKey);
+      assert.ok(checkGovData.paymentPeriodStart.toNumber() > oldTimestamp);
+      treasuryStatsData = await govProgram.account.treasuryStats.fetch(treasuryStatsPK);
+      assert.equal(treasuryStatsData.paymentAmountInPeriod.toNumber(), amount);
+    });
+
+    it('Approve with shares below snapshot test', async () => {
+      let proposal4IpfsHash = "00223344556611223344556677889911"
+      let proposal4PK = (await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from(PROPOSAL_PREFIX),
+          govProgram.programId.toBuffer(),
+          governanceAccount.publicKey.toBuffer(),
+          Buffer.from(proposal4IpfsHash.slice(0, 32)),
+        ],
+        govProgram.programId
+      ))[0];
+
+      // User4 unstakes after the proposal4 snapshot
+      await stakingProgram.rpc.unstake(
+        new anchor.BN(100_000_000),
+        {
+          accounts: {
+            stakingUserData: user4StakingData.publicKey,
+            userTokenWallet: user4TokenWalletPK,
+            userTokenWalletOwner: user4Authority.publicKey,
+            stakingData: stakingAccount.publicKey,
+            holdingWallet: holdingWalletPK,
+            holdingWalletOwner: holdingWalletOwnerPK,
+            penaltyWallet: holdingWalletPK,
+            feeCollector: holdingWalletPK,
+            stakingTokenMetadata: metadataAccount.publicKey,
+            mint: mint.publicKey,
+            mintAuthority: pdaMintAuthority,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          signers: [user4Authority]
+        }
+      );
+
+      // BadCase: user4 shares are below the proposal4 snapshot
+      let pda = await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from(VOTE_MARKER_PREFIX),
+          governanceAccount.publicKey.toBuffer(),
+          proposal4PK.toBuffer(),
+          user4StakingData.publicKey.toBuffer(),
+        ],
+        govProgram.programId
+      );
+      let voteMarkerPK = pda[0];
+      let voteMarkerBump = pda[1];
       await assert.rejects(
         async () => {
           await govProgram.rpc.approveProposal(
-            true,
+            false,
             voteMarkerBump,
+            lockerBump,
             {
               accounts: {
                 governance: governanceAccount.publicKey,
-                stakingUserData: user1StakingData.publicKey,
-                userTokenWallet: user1TokenWalletPK,
-                userTokenWalletOwner: user1Authority.publicKey,
+                stakingUserData: user4StakingData.publicKey,
+                userTokenWallet: user4TokenWalletPK,
+                userTokenWalletOwner: user4Authority.publicKey,
                 stakingData: stakingAccount.publicKey,
-                proposal: proposal1PK,
+                proposal: proposal4PK,
                 voteMarker: voteMarkerPK,
+                lockerAuthority: lockerAuthorityPK,
+                governanceProgram: govProgram.programId,
                 payer: payer.publicKey,
                 stakingProgram: stakingProgram.programId,
                 systemProgram: anchor.web3.SystemProgram.programId,
                 clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
               },
-              signers: [user1Authority],
+              signers: [user4Authority],
             }
           );
         },
         (err) => {
+          assert.equal(err.code, 327);
+          assert.equal(err.msg, "Staked shares are below the snapshot shares");
+          return true;
+        }
+      );
+    });
+
+    it('Executable proposal test', async () => {
+      let executableIpfsHash = "00334455667711223344556677889911";
+      let pda = await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from(PROPOSAL_PREFIX),
+          govProgram.programId.toBuffer(),
+          governanceAccount.publicKey.toBuffer(),
+          Buffer.from(executableIpfsHash.slice(0, 32)),
+        ],
+        govProgram.programId
+      );
+      let executablePK = pda[0];
+      let executableBump = pda[1];
+
+      const toProposalInstruction = (instruction) => {
+        return {
+          programId: instruction.programId,
+          accounts: instruction.keys.map((meta) => {
+            return { pubkey: meta.pubkey, isSigner: meta.isSigner, isWritable: meta.isWritable };
+          }),
+          data: instruction.data,
+        };
+      };
+      const makeExecutableProposal = async (instructions) => {
+        await govProgram.rpc.makeExecutableProposal(
+          executableBump,
+          executableIpfsHash,
+          instructions,
+          {
+            accounts: {
+              governance: governanceAccount.publicKey,
+              treasury: treasuryPK,
+              treasuryStats: treasuryStatsPK,
+              treasuryOwner: treasuryOwnerPK,
+              stakingUserData: user2StakingData.publicKey,
+              userTokenWallet: user2TokenWalletPK,
+              userTokenWalletOwner: user2Authority.publicKey,
+              proposal: executablePK,
+              payer: payer.publicKey,
+              systemProgram: anchor.web3.SystemProgram.programId,
+              rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+              clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            },
+            signers: [user2Authority]
+          }
+        );
+      };
+
+      // BadCase: treasury owner can't approve the treasury tokens
+      await assert.rejects(
+        async () => {
+          await makeExecutableProposal([toProposalInstruction(
+            Token.createApproveInstruction(
+              TOKEN_PROGRAM_ID,
+              treasuryPK,
+              govUser2TokenWallet,
+              treasuryOwnerPK,
+              [],
+              1_000_000_000,
+            )
+          )]);
+        },
+        (err) => {
+          assert.equal(err.code, 316);
+          assert.equal(err.msg, "Invalid proposal instructions");
+          return true;
+        }
+      );
+
+      // BadCase: treasury owner can't change the treasury authority
+      await assert.rejects(
+        async () => {
+          await makeExecutableProposal([toProposalInstruction(
+            Token.createSetAuthorityInstruction(
+              TOKEN_PROGRAM_ID,
+              treasuryPK,
+              govUser2Auth.publicKey,
+              'AccountOwner',
+              treasuryOwnerPK,
+              [],
+            )
+          )]);
+        },
+        (err) => {
+          assert.equal(err.code, 316);
+          assert.equal(err.msg, "Invalid proposal instructions");
           return true;
         }
       );
 
-      let checkProposalData = await govProgram.account.proposal.fetch(proposal1PK);
-      assert.equal(checkProposalData.isClosed, false);
-      assert.equal(checkProposalData.prosWeight, user1StakeAmount);
-      assert.equal(checkProposalData.consWeight, 0);
-      let checkUser1StakingData = await stakingProgram.account.stakingUserData.fetch(user1StakingData.publicKey);
-      assert.equal(checkUser1StakingData.lockedAmount, user1StakeAmount);
-      let lockedUntil = checkUser1StakingData.lockedUntil;
-
-      // BadCase: User1 tries to unlock his amount by time
+      // BadCase: treasury can't be used by other programs
       await assert.rejects(
         async () => {
-          await stakingProgram.rpc.lockAmount(
-            new anchor.BN(currentTimestamp),
-            new anchor.BN(user1StakeAmount),
-            {
-              accounts: {
-                stakingData: stakingAccount.publicKey,
-                stakingUserData: user1StakingData.publicKey,
-                userTokenWallet: user1TokenWalletPK,
-                userTokenWalletOwner: user1Authority.publicKey,
-                clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
-              },
-              signers: [user1Authority],
-            }
-          );
+          await makeExecutableProposal([{
+            programId: stakingProgram.programId,
+            accounts: [
+              { pubkey: treasuryPK, isSigner: false, isWritable: true },
+              { pubkey: treasuryOwnerPK, isSigner: true, isWritable: false },
+            ],
+            data: Buffer.from([]),
+          }]);
         },
         (err) => {
-          assert.equal(err.code, 0x130);
-          assert.equal(err.msg, "Trying to unlock amount");
+          assert.equal(err.code, 316);
+          assert.equal(err.msg, "Invalid proposal instructions");
           return true;
         }
       );
 
-      // User1 tries to unlock his amount
-      await stakingProgram.rpc.lockAmount(
-        new anchor.BN(lockedUntil),
-        new anchor.BN(user1StakeAmount - 100),
-        {
-          accounts: {
-            stakingData: stakingAccount.publicKey,
-            stakingUserData: user1StakingData.publicKey,
-            userTokenWallet: user1TokenWalletPK,
-            userTokenWalletOwner: user1Authority.publicKey,
-            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
-          },
-          signers: [user1Authority],
+      // BadCase: transfers are limited by max proposal payment amount
+      treasuryStatsData = await govProgram.account.treasuryStats.fetch(treasuryStatsPK);
+      let maxProposalPaymentAmount = treasuryStatsData.highestBalance * (maxProposalPaymentPercent / 100.0);
+      await assert.rejects(
+        async () => {
+          await makeExecutableProposal([toProposalInstruction(
+            Token.createTransferInstruction(
+              TOKEN_PROGRAM_ID,
+              treasuryPK,
+              govUser2TokenWallet,
+              treasuryOwnerPK,
+              [],
+              maxProposalPaymentAmount + 1,
+            )
+          )]);
+        },
+        (err) => {
+          assert.equal(err.code, 306);
+          assert.equal(err.msg, "Max proposal payment amount limit exceeded");
+          return true;
         }
       );
 
-      // Locked amount not changed
-      checkUser1StakingData = await stakingProgram.account.stakingUserData.fetch(user1StakingData.publicKey);
-      assert.equal(checkUser1StakingData.lockedUntil.toNumber(), lockedUntil);
-      assert.equal(checkUser1StakingData.lockedAmount.toNumber(), user1StakeAmount);
-
-      // User2 vote for proposal1
-      pda = await anchor.web3.PublicKey.findProgramAddress(
-        [
-          Buffer.from(VOTE_MARKER_PREFIX),
-          governanceAccount.publicKey.toBuffer(),
-          proposal1PK.toBuffer(),
-          user2StakingData.publicKey.toBuffer(),
-        ],
-        govProgram.programId
+      // Make proposal with two treasury transfers
+      let transferAmount = 400_000_000;
+      let transferInstruction = toProposalInstruction(
+        Token.createTransferInstruction(
+          TOKEN_PROGRAM_ID,
+          treasuryPK,
+          govUser2TokenWallet,
+          treasuryOwnerPK,
+          [],
+          transferAmount,
+        )
+      );
+      checkGovData = await govProgram.account.governance.fetch(governanceAccount.publicKey);
+      let periodStartBefore = checkGovData.paymentPeriodStart.toNumber();
+      let paymentInPeriodBefore = treasuryStatsData.paymentAmountInPeriod.toNumber();
+      await makeExecutableProposal([transferInstruction, transferInstruction]);
+
+      // Transfers are counted in the treasury stats as the payment amount
+      checkProposalData = await govProgram.account.proposal.fetch(executablePK);
+      assert.ok(checkProposalData.kind.executable);
+      assert.ok(checkProposalData.recipient.equals(treasuryPK));
+      assert.equal(checkProposalData.paymentAmount.toNumber(), 2 * transferAmount);
+      assert.equal(checkProposalData.instructions.length, 2);
+      checkGovData = await govProgram.account.governance.fetch(governanceAccount.publicKey);
+      if (checkGovData.paymentPeriodStart.toNumber() != periodStartBefore) {
+        paymentInPeriodBefore = 0;
+      }
+      treasuryStatsData = await govProgram.account.treasuryStats.fetch(treasuryStatsPK);
+      assert.equal(
+        treasuryStatsData.paymentAmountInPeriod.toNumber(),
+        paymentInPeriodBefore + 2 * transferAmount
       );
-      voteMarkerPK = pda[0];
-      voteMarkerBump = pda[1];
 
-      await govProgram.rpc.approveProposal(
-        false,
-        voteMarkerBump,
+      // User1 (sponsor) and user4 vote for the proposal
+      const approve = async (stakingUserData, tokenWallet, authority) => {
+        let pda = await anchor.web3.PublicKey.findProgramAddress(
+          [
+            Buffer.from(VOTE_MARKER_PREFIX),
+            governanceAccount.publicKey.toBuffer(),
+            executablePK.toBuffer(),
+            stakingUserData.toBuffer(),
+          ],
+          govProgram.programId
+        );
+        await govProgram.rpc.approveProposal(
+          true,
+          pda[1],
+          lockerBump,
+          {
+            accounts: {
+              governance: governanceAccount.publicKey,
+              stakingUserData: stakingUserData,
+              userTokenWallet: tokenWallet,
+              userTokenWalletOwner: authority.publicKey,
+              stakingData: stakingAccount.publicKey,
+              proposal: executablePK,
+              voteMarker: pda[0],
+              lockerAuthority: lockerAuthorityPK,
+              governanceProgram: govProgram.programId,
+              payer: payer.publicKey,
+              stakingProgram: stakingProgram.programId,
+              systemProgram: anchor.web3.SystemProgram.programId,
+              clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            },
+            signers: [authority],
+          }
+        );
+      };
+      await approve(user1StakingData.publicKey, user1TokenWalletPK, user1Authority);
+      await approve(user4StakingData.publicKey, user4TokenWalletPK, user4Authority);
+
+      let sleep_ms = approvalFixedPeriodInSeconds * 1000;
+      console.log("sleep for ms: ", sleep_ms);
+      sleep(sleep_ms);
+
+      await govProgram.rpc.finalizeProposal(
         {
           accounts: {
             governance: governanceAccount.publicKey,
-            stakingUserData: user2StakingData.publicKey,
-            userTokenWallet: user2TokenWalletPK,
-            userTokenWalletOwner: user2Authority.publicKey,
             stakingData: stakingAccount.publicKey,
-            proposal: proposal1PK,
-            voteMarker: voteMarkerPK,
-            payer: payer.publicKey,
-            stakingProgram: stakingProgram.programId,
-            systemProgram: anchor.web3.SystemProgram.programId,
+            proposal: executablePK,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           },
-          signers: [user2Authority]
         }
       );
+      checkProposalData = await govProgram.account.proposal.fetch(executablePK);
+      assert.ok(checkProposalData.state.succeeded);
 
-      checkProposalData = await govProgram.account.proposal.fetch(proposal1PK);
-      let checkUser2StakingData = await stakingProgram.account.stakingUserData.fetch(user2StakingData.publicKey);
-      assert.equal(checkProposalData.isClosed, false);
-      assert.equal(checkProposalData.prosWeight, user1StakeAmount);
-      assert.deepStrictEqual(checkProposalData.consWeight, checkUser2StakingData.lockedAmount);
-      assert.deepStrictEqual(checkUser2StakingData.lockedAmount, checkUser2StakingData.ownershipShare);
-
-      // User2 vote for proposal2
-      pda = await anchor.web3.PublicKey.findProgramAddress(
-        [
-          Buffer.from(VOTE_MARKER_PREFIX),
-          governanceAccount.publicKey.toBuffer(),
-          proposal2PK.toBuffer(),
-          user2StakingData.publicKey.toBuffer(),
-        ],
-        govProgram.programId
-      );
-      voteMarkerPK = pda[0];
-      voteMarkerBump = pda[1];
+      let treasuryBefore = (await treasuryMint.getAccountInfo(treasuryPK)).amount.toNumber();
+      let recipientBefore = (await treasuryMint.getAccountInfo(govUser2TokenWallet)).amount.toNumber();
 
-      await govProgram.rpc.approveProposal(
-        true,
-        voteMarkerBump,
+      // Instructions are invoked with the treasury owner signature
+      await govProgram.rpc.executeProposal(
         {
           accounts: {
             governance: governanceAccount.publicKey,
-            stakingUserData: user2StakingData.publicKey,
-            userTokenWallet: user2TokenWalletPK,
-            userTokenWalletOwner: user2Authority.publicKey,
-            stakingData: stakingAccount.publicKey,
-            proposal: proposal2PK,
-            voteMarker: voteMarkerPK,
-            payer: payer.publicKey,
-            stakingProgram: stakingProgram.programId,
-            systemProgram: anchor.web3.SystemProgram.programId,
+            proposal: executablePK,
+            treasury: treasuryPK,
+            treasuryOwner: treasuryOwnerPK,
+            treasuryMint: treasuryMint.publicKey,
+            recipient: treasuryPK,
+            tokenProgram: TOKEN_PROGRAM_ID,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           },
-          signers: [user2Authority]
+          remainingAccounts: [
+            { pubkey: treasuryPK, isSigner: false, isWritable: true },
+            { pubkey: govUser2TokenWallet, isSigner: false, isWritable: true },
+            { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+          ],
         }
       );
 
-      checkProposalData = await govProgram.account.proposal.fetch(proposal2PK);
-      checkUser2StakingData = await stakingProgram.account.stakingUserData.fetch(user2StakingData.publicKey);
-      assert.equal(checkProposalData.isClosed, false);
-      assert.deepStrictEqual(checkProposalData.prosWeight, checkUser2StakingData.lockedAmount);
-      assert.equal(checkProposalData.consWeight.toNumber(), 0);
-      assert.deepStrictEqual(checkUser2StakingData.lockedAmount, checkUser2StakingData.ownershipShare);
+      checkProposalData = await govProgram.account.proposal.fetch(executablePK);
+      assert.ok(checkProposalData.state.executed);
+      checkTreasury = await treasuryMint.getAccountInfo(treasuryPK);
+      assert.equal(checkTreasury.amount.toNumber(), treasuryBefore - 2 * transferAmount);
+      checkRecipient = await treasuryMint.getAccountInfo(govUser2TokenWallet);
+      assert.equal(checkRecipient.amount.toNumber(), recipientBefore + 2 * transferAmount);
+    });
 
-      // User4 vote for proposal3 (not sponsor)
-      pda = await anchor.web3.PublicKey.findProgramAddress(
+    it('Config proposal test', async () => {
+      let configIpfsHash = "00445566778811223344556677889911";
+      let pda = await anchor.web3.PublicKey.findProgramAddress(
         [
-          Buffer.from(VOTE_MARKER_PREFIX),
+          Buffer.from(PROPOSAL_PREFIX),
+          govProgram.programId.toBuffer(),
           governanceAccount.publicKey.toBuffer(),
-          proposal3PK.toBuffer(),
-          user4StakingData.publicKey.toBuffer(),
+          Buffer.from(configIpfsHash.slice(0, 32)),
         ],
         govProgram.programId
       );
-      voteMarkerPK = pda[0];
-      voteMarkerBump = pda[1];
+      let configProposalPK = pda[0];
+      let configProposalBump = pda[1];
+
+      let newConfig = {
+        approvalFixedPeriodInSeconds: new anchor.BN(approvalFixedPeriodInSeconds + 5),
+        minApprovalPercent: 60,
+        minStakeToPropose: new anchor.BN(2 * minStakeToPropose),
+        minVoteParticipationPercent: 40,
+        paymentPeriodSec: new anchor.BN(2 * paymentPeriodSec),
+        executionDelaySeconds: new anchor.BN(60),
+      };
+      const makeConfigProposal = async (config) => {
+        await govProgram.rpc.makeConfigProposal(
+          configProposalBump,
+          configIpfsHash,
+          config,
+          {
+            accounts: {
+              governance: governanceAccount.publicKey,
+              treasury: treasuryPK,
+              treasuryOwner: treasuryOwnerPK,
+              stakingUserData: user2StakingData.publicKey,
+              userTokenWallet: user2TokenWalletPK,
+              userTokenWalletOwner: user2Authority.publicKey,
+              proposal: configProposalPK,
+              payer: payer.publicKey,
+              systemProgram: anchor.web3.SystemProgram.programId,
+              rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+              clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            },
+            signers: [user2Authority]
+          }
+        );
+      };
+      const applyConfigProposal = async () => {
+        await govProgram.rpc.applyConfigProposal(
+          {
+            accounts: {
+              governance: governanceAccount.publicKey,
+              proposal: configProposalPK,
+              clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            },
+          }
+        );
+      };
 
-      await govProgram.rpc.approveProposal(
-        true,
-        voteMarkerBump,
-        {
-          accounts: {
-            governance: governanceAccount.publicKey,
-            stakingUserData: user4StakingData.publicKey,
-            userTokenWallet: user4TokenWalletPK,
-            userTokenWalletOwner: user4Authority.publicKey,
-            stakingData: stakingAccount.publicKey,
-            proposal: proposal3PK,
-            voteMarker: voteMarkerPK,
-            payer: payer.publicKey,
-            stakingProgram: stakingProgram.programId,
-            systemProgram: anchor.web3.SystemProgram.programId,
-            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
-          },
-          signers: [user4Authority],
+      // BadCase: min approval percent is below the config bound
+      await assert.rejects(
+        async () => {
+          await makeConfigProposal({ ...newConfig, minApprovalPercent: 40 });
+        },
+        (err) => {
+          assert.equal(err.code, 321);
+          assert.equal(err.msg, "Governance config is out of bounds");
+          return true;
         }
       );
-    });
 
-    it('Finalize still in vote proposal test', async () => {
-      // BadCase: the vote in progres
+      // BadCase: execution delay is above the config bound
       await assert.rejects(
         async () => {
-          await govProgram.rpc.finalizeProposal(
-            {
-              accounts: {
-                governance: governanceAccount.publicKey,
-                stakingData: stakingAccount.publicKey,
-                proposal: proposal1PK,
-                treasury: treasuryPK,
-                treasuryOwner: treasuryOwnerPK,
-                treasuryMint: treasuryMint.publicKey,
-                recipient: govUser1TokenWallet,
-                tokenProgram: TOKEN_PROGRAM_ID,
-                clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
-              },
-            }
-          );
+          await makeConfigProposal({ ...newConfig, executionDelaySeconds: new anchor.BN(31 * 24 * 60 * 60) });
         },
         (err) => {
-          assert.equal(err.code, 0x130);
-          assert.equal(err.msg, "The vote is in progress");
+          assert.equal(err.code, 321);
+          assert.equal(err.msg, "Governance config is out of bounds");
           return true;
         }
       );
-    });
-
-    it('Finalize proposal test', async () => {
-      let sleep_ms = approvalFixedPeriodInSeconds * 1000;
-      console.log("sleep for ms: ", sleep_ms);
-      sleep(sleep_ms);
-
-      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
-      console.log("Total staked: ", checkStakingData.totalStaked.toNumber());
-      checkGovData = await govProgram.account.governance.fetch(governanceAccount.publicKey);
-      console.log("minVoteParticipationPercent: ", checkGovData.minVoteParticipationPercent);
-      console.log("minApprovalPercent: ", checkGovData.minApprovalPercent);
 
-      checkProposalData = await govProgram.account.proposal.fetch(proposal1PK);
-      console.log("Proposal 1:")
-      console.log("prosWeight: ", checkProposalData.prosWeight.toNumber());
-      console.log("consWeight: ", checkProposalData.consWeight.toNumber());
-      console.log("isSponsored: ", checkProposalData.isSponsored);
+      await makeConfigProposal(newConfig);
+      checkProposalData = await govProgram.account.proposal.fetch(configProposalPK);
+      assert.ok(checkProposalData.kind.configChange);
+      assert.equal(checkProposalData.paymentAmount.toNumber(), 0);
 
-      checkProposalData = await govProgram.account.proposal.fetch(proposal2PK);
-      console.log("Proposal 2:")
-      console.log("prosWeight: ", checkProposalData.prosWeight.toNumber());
-      console.log("consWeight: ", checkProposalData.consWeight.toNumber());
-      console.log("isSponsored: ", checkProposalData.isSponsored);
+      // BadCase: config can't be applied during the vote
+      await assert.rejects(
+        async () => {
+          await applyConfigProposal();
+        },
+        (err) => {
+          assert.equal(err.code, 318);
+          assert.equal(err.msg, "Proposal is not approved");
+          return true;
+        }
+      );
 
-      checkProposalData = await govProgram.account.proposal.fetch(proposal3PK);
-      console.log("Proposal 3:")
-      console.log("prosWeight: ", checkProposalData.prosWeight.toNumber());
-      console.log("consWeight: ", checkProposalData.consWeight.toNumber());
-      console.log("isSponsored: ", checkProposalData.isSponsored);
+      // User1 (sponsor) and user4 vote for the proposal
+      const approve = async (stakingUserData, tokenWallet, authority) => {
+        let pda = await anchor.web3.PublicKey.findProgramAddress(
+          [
+            Buffer.from(VOTE_MARKER_PREFIX),
+            governanceAccount.publicKey.toBuffer(),
+            configProposalPK.toBuffer(),
+            stakingUserData.toBuffer(),
+          ],
+          govProgram.programId
+        );
+        await govProgram.rpc.approveProposal(
+          true,
+          pda[1],
+          lockerBump,
+          {
+            accounts: {
+              governance: governanceAccount.publicKey,
+              stakingUserData: stakingUserData,
+              userTokenWallet: tokenWallet,
+              userTokenWalletOwner: authority.publicKey,
+              stakingData: stakingAccount.publicKey,
+              proposal: configProposalPK,
+              voteMarker: pda[0],
+              lockerAuthority: lockerAuthorityPK,
+              governanceProgram: govProgram.programId,
+              payer: payer.publicKey,
+              stakingProgram: stakingProgram.programId,
+              systemProgram: anchor.web3.SystemProgram.programId,
+              clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            },
+            signers: [authority],
+          }
+        );
+      };
+      await approve(user1StakingData.publicKey, user1TokenWalletPK, user1Authority);
+      await approve(user4StakingData.publicKey, user4TokenWalletPK, user4Authority);
 
-      checkRecipient = await treasuryMint.getAccountInfo(checkProposalData.recipient);
-      let recipient1BalanceBefore = checkRecipient.amount.toNumber();
+      let sleep_ms = approvalFixedPeriodInSeconds * 1000;
+      console.log("sleep for ms: ", sleep_ms);
+      sleep(sleep_ms);
 
       await govProgram.rpc.finalizeProposal(
         {
           accounts: {
             governance: governanceAccount.publicKey,
             stakingData: stakingAccount.publicKey,
-            proposal: proposal1PK,
-            treasury: treasuryPK,
-            treasuryOwner: treasuryOwnerPK,
-            treasuryMint: treasuryMint.publicKey,
-            recipient: govUser1TokenWallet,
-            tokenProgram: TOKEN_PROGRAM_ID,
+            proposal: configProposalPK,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           },
         }
       );
+      checkProposalData = await govProgram.account.proposal.fetch(configProposalPK);
+      assert.ok(checkProposalData.state.succeeded);
 
-      // BadCase: proposal already closed
+      // BadCase: config change proposal can't be executed
       await assert.rejects(
         async () => {
-          await govProgram.rpc.finalizeProposal(
+          await govProgram.rpc.executeProposal(
             {
               accounts: {
                 governance: governanceAccount.publicKey,
-                stakingData: stakingAccount.publicKey,
-                proposal: proposal1PK,
+                proposal: configProposalPK,
                 treasury: treasuryPK,
                 treasuryOwner: treasuryOwnerPK,
                 treasuryMint: treasuryMint.publicKey,
-                recipient: govUser1TokenWallet,
+                recipient: treasuryPK,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
               },
@@ -2113,193 +4846,472 @@ describe('functional tests', () => {
           );
         },
         (err) => {
-          assert.equal(err.code, 303);
-          assert.equal(err.msg, "Proposal is already closed");
+          assert.equal(err.code, 317);
+          assert.equal(err.msg, "Config change proposal must be applied");
           return true;
         }
       );
 
-      // Check approved proposal results
-      checkProposalData = await govProgram.account.proposal.fetch(proposal1PK);
-      assert.equal(checkProposalData.isClosed, true);
-      checkTreasury = await treasuryMint.getAccountInfo(treasuryPK);
-      assert.equal(checkTreasury.amount, govUser1FundAmount - checkProposalData.paymentAmount);
-      checkRecipient = await treasuryMint.getAccountInfo(checkProposalData.recipient);
-      assert.equal(checkRecipient.amount.toNumber(), recipient1BalanceBefore + checkProposalData.paymentAmount.toNumber());
+      // Execution delay of the current config is 0, the eta is already reached
+      assert.ok(checkProposalData.eta.toNumber() >= checkProposalData.votingEndsAt.toNumber());
+      await applyConfigProposal();
 
-      let treasuryBalanceBefore = checkTreasury.amount;
+      checkProposalData = await govProgram.account.proposal.fetch(configProposalPK);
+      assert.ok(checkProposalData.state.executed);
+      checkGovData = await govProgram.account.governance.fetch(governanceAccount.publicKey);
+      assert.equal(
+        checkGovData.approvalFixedPeriodInSeconds.toNumber(),
+        newConfig.approvalFixedPeriodInSeconds.toNumber()
+      );
+      assert.equal(checkGovData.minApprovalPercent, newConfig.minApprovalPercent);
+      assert.equal(checkGovData.minStakeToPropose.toNumber(), newConfig.minStakeToPropose.toNumber());
+      assert.equal(checkGovData.minVoteParticipationPercent, newConfig.minVoteParticipationPercent);
+      assert.equal(checkGovData.paymentPeriodSec.toNumber(), newConfig.paymentPeriodSec.toNumber());
+      assert.equal(checkGovData.executionDelaySeconds.toNumber(), newConfig.executionDelaySeconds.toNumber());
 
-      // Proposal 2 min participation percent not reached
-      checkRecipient = await treasuryMint.getAccountInfo(checkProposalData.recipient);
-      let recipient2BalanceBefore = checkRecipient.amount.toNumber();
-      await govProgram.rpc.finalizeProposal(
-        {
-          accounts: {
-            governance: governanceAccount.publicKey,
-            stakingData: stakingAccount.publicKey,
-            proposal: proposal2PK,
-            treasury: treasuryPK,
-            treasuryOwner: treasuryOwnerPK,
-            treasuryMint: treasuryMint.publicKey,
-            recipient: govUser1TokenWallet,
-            tokenProgram: TOKEN_PROGRAM_ID,
-            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
-          },
+      // BadCase: config is applied only once
+      await assert.rejects(
+        async () => {
+          await applyConfigProposal();
+        },
+        (err) => {
+          assert.equal(err.code, 319);
+          assert.equal(err.msg, "Proposal is already executed");
+          return true;
         }
       );
+    });
+  });
 
-      // Check not approved proposal results
-      checkProposalData = await govProgram.account.proposal.fetch(proposal2PK);
-      assert.equal(checkProposalData.isClosed, true);
-      checkTreasury = await treasuryMint.getAccountInfo(treasuryPK);
-      assert.deepStrictEqual(checkTreasury.amount, treasuryBalanceBefore);
-      checkRecipient = await treasuryMint.getAccountInfo(checkProposalData.recipient);
-      assert.equal(checkRecipient.amount.toNumber(), recipient2BalanceBefore);
+  // Fees and penalties change the staked value the governance tests rely on, so they go last
+  describe('Staking fees and interest tests', () => {
+    let feeCollectorPK = null;
+    let penaltyWalletPK = null;
 
-      // Finalize the proposal 3 (it is not sponsored)
-      checkRecipient = await treasuryMint.getAccountInfo(checkProposalData.recipient);
-      recipient2BalanceBefore = checkRecipient.amount.toNumber();
-      await govProgram.rpc.finalizeProposal(
+    const user1 = () => ({stakingData: user1StakingData, tokenWallet: user1TokenWalletPK, authority: user1Authority});
+    const user3 = () => ({stakingData: user3StakingData, tokenWallet: user3TokenWalletPK, authority: user3Authority});
+
+    const stake = async (user, amount, feeCollector) => {
+      await stakingProgram.rpc.stake(
+        new anchor.BN(amount),
         {
           accounts: {
-            governance: governanceAccount.publicKey,
+            stakingUserData: user.stakingData.publicKey,
+            userTokenWallet: user.tokenWallet,
+            userTokenWalletOwner: user.authority.publicKey,
             stakingData: stakingAccount.publicKey,
-            proposal: proposal3PK,
-            treasury: treasuryPK,
-            treasuryOwner: treasuryOwnerPK,
-            treasuryMint: treasuryMint.publicKey,
-            recipient: govUser1TokenWallet,
+            allowListEntry: await allowListEntryPK(user.authority.publicKey),
+            holdingWallet: holdingWalletPK,
+            feeCollector: feeCollector,
+            stakingTokenMetadata: metadataAccount.publicKey,
+            mint: mint.publicKey,
+            mintAuthority: pdaMintAuthority,
             tokenProgram: TOKEN_PROGRAM_ID,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           },
+          signers: [user.authority]
         }
       );
-
-      // Check not approved proposal results
-      checkProposalData = await govProgram.account.proposal.fetch(proposal3PK);
-      assert.equal(checkProposalData.isClosed, true);
-      checkTreasury = await treasuryMint.getAccountInfo(treasuryPK);
-      assert.deepStrictEqual(checkTreasury.amount, treasuryBalanceBefore);
-      checkRecipient = await treasuryMint.getAccountInfo(checkProposalData.recipient);
-      assert.equal(checkRecipient.amount.toNumber(), recipient2BalanceBefore);
-    });
-
-    it('Unlock amounts test', async () => {
-      await stakingProgram.rpc.stake(
-        new anchor.BN(100_000_000),
+    };
+    const unstake = async (user, amount, feeCollector, penaltyWallet) => {
+      await stakingProgram.rpc.unstake(
+        new anchor.BN(amount),
         {
           accounts: {
-            stakingUserData: user1StakingData.publicKey,
-            userTokenWallet: user1TokenWalletPK,
-            userTokenWalletOwner: user1Authority.publicKey,
+            stakingUserData: user.stakingData.publicKey,
+            userTokenWallet: user.tokenWallet,
+            userTokenWalletOwner: user.authority.publicKey,
             stakingData: stakingAccount.publicKey,
             holdingWallet: holdingWalletPK,
+            holdingWalletOwner: holdingWalletOwnerPK,
+            penaltyWallet: penaltyWallet,
+            feeCollector: feeCollector,
             stakingTokenMetadata: metadataAccount.publicKey,
             mint: mint.publicKey,
             mintAuthority: pdaMintAuthority,
             tokenProgram: TOKEN_PROGRAM_ID,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           },
-          signers: [user1Authority]
+          signers: [user.authority]
         }
       );
-
-      checkStakingUserData = await stakingProgram.account.stakingUserData.fetch(user1StakingData.publicKey);
-      assert.equal(checkStakingUserData.lockedAmount.toNumber(), 0);
-      assert.equal(checkStakingUserData.lockedUntil.toNumber(), 0);
-
-      await stakingProgram.rpc.stake(
-        new anchor.BN(100_000_000),
+    };
+    const setFees = async (depositFeeBps, withdrawFeeBps, feeCollector) => {
+      await stakingProgram.rpc.setFees(
+        new anchor.BN(depositFeeBps),
+        new anchor.BN(withdrawFeeBps),
         {
           accounts: {
-            stakingUserData: user2StakingData.publicKey,
-            userTokenWallet: user2TokenWalletPK,
-            userTokenWalletOwner: user2Authority.publicKey,
             stakingData: stakingAccount.publicKey,
+            stakingOwner: stakingOwner.publicKey,
             holdingWallet: holdingWalletPK,
-            stakingTokenMetadata: metadataAccount.publicKey,
-            mint: mint.publicKey,
-            mintAuthority: pdaMintAuthority,
-            tokenProgram: TOKEN_PROGRAM_ID,
-            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            feeCollector: feeCollector,
           },
-          signers: [user2Authority]
+          signers: [stakingOwner]
         }
       );
-
-      checkStakingUserData = await stakingProgram.account.stakingUserData.fetch(user2StakingData.publicKey);
-      assert.equal(checkStakingUserData.lockedAmount.toNumber(), 0);
-      assert.equal(checkStakingUserData.lockedUntil.toNumber(), 0);
-
-      await stakingProgram.rpc.stake(
-        new anchor.BN(100_000_000),
+    };
+    const setEarlyUnstakePenalty = async (penalty, windowSeconds, penaltyWallet) => {
+      await stakingProgram.rpc.setEarlyUnstakePenalty(
+        new anchor.BN(penalty),
+        new anchor.BN(windowSeconds),
         {
           accounts: {
-            stakingUserData: user4StakingData.publicKey,
-            userTokenWallet: user4TokenWalletPK,
-            userTokenWalletOwner: user4Authority.publicKey,
+            stakingData: stakingAccount.publicKey,
+            stakingOwner: stakingOwner.publicKey,
+            holdingWallet: holdingWalletPK,
+            penaltyWallet: penaltyWallet,
+          },
+          signers: [stakingOwner]
+        }
+      );
+    };
+    const setDepositLimits = async (allowListEnabled, userDepositCap, totalDepositCap) => {
+      await stakingProgram.rpc.setDepositLimits(
+        allowListEnabled,
+        new anchor.BN(userDepositCap),
+        new anchor.BN(totalDepositCap),
+        {
+          accounts: {
+            stakingData: stakingAccount.publicKey,
+            stakingOwner: stakingOwner.publicKey,
+          },
+          signers: [stakingOwner]
+        }
+      );
+    };
+    const claimInterest = async (user) => {
+      await stakingProgram.rpc.claimInterest(
+        {
+          accounts: {
+            stakingUserData: user.stakingData.publicKey,
+            userTokenWallet: user.tokenWallet,
+            userTokenWalletOwner: user.authority.publicKey,
             stakingData: stakingAccount.publicKey,
             holdingWallet: holdingWalletPK,
+            holdingWalletOwner: holdingWalletOwnerPK,
             stakingTokenMetadata: metadataAccount.publicKey,
             mint: mint.publicKey,
             mintAuthority: pdaMintAuthority,
             tokenProgram: TOKEN_PROGRAM_ID,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           },
-          signers: [user4Authority]
+          signers: [user.authority]
         }
       );
+    };
+    const tokenBalance = async (wallet) => {
+      return (await mint.getAccountInfo(wallet)).amount.toNumber();
+    };
 
-      checkStakingUserData = await stakingProgram.account.stakingUserData.fetch(user4StakingData.publicKey);
-      assert.equal(checkStakingUserData.lockedAmount.toNumber(), 0);
-      assert.equal(checkStakingUserData.lockedUntil.toNumber(), 0);
+    it('Fees test', async () => {
+      feeCollectorPK = await mint.createAccount(stakingOwner.publicKey);
+
+      // BadCase: fee is higher than maximum (10%)
+      await assert.rejects(
+        async () => {
+          await setFees(1_001, 0, feeCollectorPK);
+        },
+        (err) => {
+          assert.equal(err.code, 318);
+          assert.equal(err.msg, "Invalid fee");
+          return true;
+        }
+      );
+
+      // BadCase: fee in the holding wallet would be counted as staked
+      await assert.rejects(
+        async () => {
+          await setFees(100, 200, holdingWalletPK);
+        },
+        (err) => {
+          assert.equal(err.code, 318);
+          assert.equal(err.msg, "Invalid fee");
+          return true;
+        }
+      );
+
+      await setFees(100, 200, feeCollectorPK);
+      let checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.equal(checkStakingData.depositFeeBps.toNumber(), 100);
+      assert.equal(checkStakingData.withdrawFeeBps.toNumber(), 200);
+      assert.ok(checkStakingData.feeCollector.equals(feeCollectorPK));
+
+      // BadCase: fee goes only to the fee collector of the staking pool
+      await assert.rejects(
+        async () => {
+          await stake(user3(), 1_000_000_000, holdingWalletPK);
+        },
+        (err) => {
+          assert.equal(err.code, 143);
+          return true;
+        }
+      );
+
+      // Deposit fee is skimmed from the stake amount
+      let user3BalanceBefore = await tokenBalance(user3TokenWalletPK);
+      let totalStakedBefore = checkStakingData.totalStaked.toNumber();
+      await stake(user3(), 1_000_000_000, feeCollectorPK);
+      assert.equal(await tokenBalance(user3TokenWalletPK), user3BalanceBefore - 1_000_000_000);
+      assert.equal(await tokenBalance(feeCollectorPK), 10_000_000);
+      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.equal(checkStakingData.totalStaked.toNumber(), totalStakedBefore + 990_000_000);
+
+      // Withdraw fee is taken from the unstaked amount
+      user3BalanceBefore = await tokenBalance(user3TokenWalletPK);
+      await unstake(user3(), 500_000_000, feeCollectorPK, holdingWalletPK);
+      assert.equal(await tokenBalance(user3TokenWalletPK), user3BalanceBefore + 490_000_000);
+      assert.equal(await tokenBalance(feeCollectorPK), 20_000_000);
+      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.equal(checkStakingData.totalStaked.toNumber(), totalStakedBefore + 490_000_000);
+
+      // Fees off, the fee collector is back to the holding wallet
+      await setFees(0, 0, holdingWalletPK);
     });
 
-    it('Reset payment amount in period test', async () => {
-      let proposal4IpfsHash = "00223344556611223344556677889911"
-      pda = await anchor.web3.PublicKey.findProgramAddress(
-        [
-          Buffer.from(PROPOSAL_PREFIX),
-          govProgram.programId.toBuffer(),
-          governanceAccount.publicKey.toBuffer(),
-          Buffer.from(proposal4IpfsHash.slice(0, 32)),
-        ],
-        govProgram.programId
+    it('Early unstake penalty test', async () => {
+      penaltyWalletPK = await mint.createAccount(stakingOwner.publicKey);
+
+      // BadCase: penalty is higher than maximum (50%)
+      await assert.rejects(
+        async () => {
+          await setEarlyUnstakePenalty(5_001, 3600, penaltyWalletPK);
+        },
+        (err) => {
+          assert.equal(err.code, 317);
+          assert.equal(err.msg, "Invalid early unstake penalty");
+          return true;
+        }
       );
-      let proposal4PK = pda[0];
-      let proposal4Bump = pda[1];
 
-      checkGovData = await govProgram.account.governance.fetch(governanceAccount.publicKey);
-      let oldTimestamp = checkGovData.paymentPeriodStart.toNumber();
+      // BadCase: penalty window is longer than 365 days
+      await assert.rejects(
+        async () => {
+          await setEarlyUnstakePenalty(1_000, 366 * SECONDS_PER_DAY, penaltyWalletPK);
+        },
+        (err) => {
+          assert.equal(err.code, 317);
+          assert.equal(err.msg, "Invalid early unstake penalty");
+          return true;
+        }
+      );
 
-      let amount = 5_000_000_000;
-      await govProgram.rpc.makeProposal(
-        new anchor.BN(amount),
-        proposal4Bump,
-        proposal4IpfsHash,
+      // Penalty goes to the external penalty wallet
+      await setEarlyUnstakePenalty(1_000, 3600, penaltyWalletPK);
+      let checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.equal(checkStakingData.earlyUnstakePenalty.toNumber(), 1_000);
+      assert.equal(checkStakingData.penaltyWindowSeconds.toNumber(), 3600);
+      assert.ok(checkStakingData.penaltyWallet.equals(penaltyWalletPK));
+
+      // BadCase: penalty goes only to the penalty wallet of the staking pool
+      await assert.rejects(
+        async () => {
+          await unstake(user3(), 200_000_000, holdingWalletPK, holdingWalletPK);
+        },
+        (err) => {
+          assert.equal(err.code, 143);
+          return true;
+        }
+      );
+
+      let amount = 200_000_000;
+      let user3BalanceBefore = await tokenBalance(user3TokenWalletPK);
+      let totalStakedBefore = checkStakingData.totalStaked.toNumber();
+      await unstake(user3(), amount, holdingWalletPK, penaltyWalletPK);
+      let penalty = await tokenBalance(penaltyWalletPK);
+      // User3 staked in the previous test, the penalty decays from 10% during the window
+      assert.ok(penalty > 0);
+      assert.ok(penalty <= amount / 10);
+      assert.equal(await tokenBalance(user3TokenWalletPK), user3BalanceBefore + amount - penalty);
+      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.equal(checkStakingData.totalStaked.toNumber(), totalStakedBefore - amount);
+
+      // Penalty left in the holding wallet stays staked for the remaining stakers
+      await setEarlyUnstakePenalty(1_000, 3600, holdingWalletPK);
+      let penaltyWalletBefore = await tokenBalance(penaltyWalletPK);
+      user3BalanceBefore = await tokenBalance(user3TokenWalletPK);
+      totalStakedBefore = checkStakingData.totalStaked.toNumber();
+      let totalSharesBefore = checkStakingData.totalShares.toNumber();
+      await unstake(user3(), amount, holdingWalletPK, holdingWalletPK);
+      penalty = user3BalanceBefore + amount - await tokenBalance(user3TokenWalletPK);
+      assert.ok(penalty > 0);
+      assert.ok(penalty <= amount / 10);
+      assert.equal(await tokenBalance(penaltyWalletPK), penaltyWalletBefore);
+      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      assert.equal(checkStakingData.totalStaked.toNumber(), totalStakedBefore - amount + penalty);
+      assert.ok(checkStakingData.totalShares.toNumber() < totalSharesBefore);
+    });
+
+    it('Claim interest test', async () => {
+      // BadCase: user1 interest is compounded
+      await assert.rejects(
+        async () => {
+          await claimInterest(user1());
+        },
+        (err) => {
+          assert.equal(err.code, 316);
+          assert.equal(err.msg, "Wrong interest mode");
+          return true;
+        }
+      );
+
+      // User1 switches to claim mode, the current value of the shares becomes the principal
+      await stakingProgram.rpc.setInterestMode(
+        { claim: {} },
         {
           accounts: {
-            governance: governanceAccount.publicKey,
-            treasury: treasuryPK,
-            treasuryStats: treasuryStatsPK,
-            stakingUserData: user2StakingData.publicKey,
-            userTokenWallet: user2TokenWalletPK,
-            userTokenWalletOwner: user2Authority.publicKey,
-            proposal: proposal4PK,
-            recipient: govUser1TokenWallet,
-            payer: payer.publicKey,
-            systemProgram: anchor.web3.SystemProgram.programId,
-            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            stakingUserData: user1StakingData.publicKey,
+            userTokenWallet: user1TokenWalletPK,
+            userTokenWalletOwner: user1Authority.publicKey,
+            stakingData: stakingAccount.publicKey,
           },
-          signers: [user2Authority]
+          signers: [user1Authority]
+        }
+      );
+      let checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      let checkUser1Data = await stakingProgram.account.stakingUserData.fetch(user1StakingData.publicKey);
+      assert.ok(checkUser1Data.interestMode.claim);
+      let principal = checkUser1Data.principal.toNumber();
+      let user1Value = checkUser1Data.ownershipShare
+        .mul(checkStakingData.totalStaked)
+        .div(checkStakingData.totalShares)
+        .toNumber();
+      assert.ok(Math.abs(principal - user1Value) <= 1);
+
+      // BadCase: nothing is accrued above the principal yet
+      await assert.rejects(
+        async () => {
+          await claimInterest(user1());
+        },
+        (err) => {
+          assert.equal(err.code, 302);
+          assert.equal(err.msg, "Invalid amount");
+          return true;
         }
       );
 
-      checkGovData = await govProgram.account.governance.fetch(governanceAccount.publicKey);
-      assert.ok(checkGovData.paymentPeriodStart.toNumber() > oldTimestamp);
-      treasuryStatsData = await govProgram.account.treasuryStats.fetch(treasuryStatsPK);
-      assert.equal(treasuryStatsData.paymentAmountInPeriod.toNumber(), amount);
+      // Early unstake penalty left in the pool accrues to the user1 shares
+      let user3BalanceBefore = await tokenBalance(user3TokenWalletPK);
+      await stake(user3(), 300_000_000, holdingWalletPK);
+      await unstake(user3(), 300_000_000, holdingWalletPK, holdingWalletPK);
+      let penalty = user3BalanceBefore - await tokenBalance(user3TokenWalletPK);
+      assert.ok(penalty > 0);
+
+      let user1BalanceBefore = await tokenBalance(user1TokenWalletPK);
+      await claimInterest(user1());
+
+      let interest = await tokenBalance(user1TokenWalletPK) - user1BalanceBefore;
+      assert.ok(interest > 0);
+      assert.ok(interest < penalty);
+      checkUser1Data = await stakingProgram.account.stakingUserData.fetch(user1StakingData.publicKey);
+      assert.equal(checkUser1Data.claimedInterest.toNumber(), interest);
+      assert.equal(checkUser1Data.principal.toNumber(), principal);
+      // Value of the rest shares is back to the principal (burned shares are rounded up)
+      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      user1Value = checkUser1Data.ownershipShare
+        .mul(checkStakingData.totalStaked)
+        .div(checkStakingData.totalShares)
+        .toNumber();
+      assert.ok(user1Value <= principal);
+      assert.ok(principal - user1Value <= 2);
+
+      // Penalty off
+      await setEarlyUnstakePenalty(0, 0, holdingWalletPK);
+    });
+
+    it('Allow-list and deposit caps test', async () => {
+      // BadCase: user3 is not in the allow-list
+      await setDepositLimits(true, 0, 0);
+      await assert.rejects(
+        async () => {
+          await stake(user3(), 100_000_000, holdingWalletPK);
+        },
+        (err) => {
+          assert.equal(err.code, 319);
+          assert.equal(err.msg, "Wallet is not in the allow-list");
+          return true;
+        }
+      );
+
+      // BadCase: only owner can add to the allow-list
+      const pda = await anchor.web3.PublicKey.findProgramAddress(
+        [
+          Buffer.from(ALLOW_LIST_PREFIX),
+          stakingProgram.programId.toBuffer(),
+          stakingAccount.publicKey.toBuffer(),
+          user3Authority.publicKey.toBuffer(),
+        ],
+        stakingProgram.programId
+      );
+      const addToAllowList = async (owner) => {
+        await stakingProgram.rpc.addToAllowList(
+          pda[1],
+          {
+            accounts: {
+              allowListEntry: pda[0],
+              stakingData: stakingAccount.publicKey,
+              stakingOwner: owner.publicKey,
+              wallet: user3Authority.publicKey,
+              systemProgram: anchor.web3.SystemProgram.programId,
+              rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            },
+            signers: [owner]
+          }
+        );
+      };
+      await assert.rejects(
+        async () => {
+          await addToAllowList(user3Authority);
+        },
+        (err) => {
+          assert.equal(err.code, 143);
+          return true;
+        }
+      );
+
+      await addToAllowList(stakingOwner);
+      const checkEntry = await stakingProgram.account.allowListEntry.fetch(pda[0]);
+      assert.ok(checkEntry.stakingData.equals(stakingAccount.publicKey));
+      assert.ok(checkEntry.wallet.equals(user3Authority.publicKey));
+
+      await stake(user3(), 100_000_000, holdingWalletPK);
+
+      // BadCase: user3 stake exceeds the user deposit cap
+      let checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      let checkUser3Data = await stakingProgram.account.stakingUserData.fetch(user3StakingData.publicKey);
+      let user3Value = checkUser3Data.ownershipShare
+        .mul(checkStakingData.totalStaked)
+        .div(checkStakingData.totalShares)
+        .toNumber();
+      await setDepositLimits(true, user3Value + 50_000_000, 0);
+      await assert.rejects(
+        async () => {
+          await stake(user3(), 100_000_000, holdingWalletPK);
+        },
+        (err) => {
+          assert.equal(err.code, 320);
+          assert.equal(err.msg, "User deposit cap exceeded");
+          return true;
+        }
+      );
+      await stake(user3(), 40_000_000, holdingWalletPK);
+
+      // BadCase: user3 stake exceeds the total deposit cap
+      checkStakingData = await stakingProgram.account.stakingData.fetch(stakingAccount.publicKey);
+      await setDepositLimits(true, 0, checkStakingData.totalStaked.toNumber() + 50_000_000);
+      await assert.rejects(
+        async () => {
+          await stake(user3(), 100_000_000, holdingWalletPK);
+        },
+        (err) => {
+          assert.equal(err.code, 321);
+          assert.equal(err.msg, "Total deposit cap exceeded");
+          return true;
+        }
+      );
+
+      await setDepositLimits(false, 0, 0);
+      await stake(user3(), 100_000_000, holdingWalletPK);
     });
   });
 });